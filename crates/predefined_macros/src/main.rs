@@ -142,13 +142,10 @@ fn preprocess_with_compiler(
 
     println!("out: {}", out);
 
-    let output = match output {
-        Ok(output) => output,
-        Err(e) => {
-            println!("Error: {}", e);
-            return Err(e);
-        }
-    };
+    if let Err(e) = output {
+        println!("Error: {}", e);
+        return Err(e);
+    }
 
     // Read the preprocessed output.
     let output = std::fs::read_to_string("temp.out")?;
@@ -0,0 +1,79 @@
+//! Times the lexer and the parser over a large, generated translation unit.
+//!
+//! Run with `cargo bench --bench frontend`. Each phase is run a few times
+//! and the fastest run is reported, along with its throughput.
+
+use rcc::Session;
+use std::{
+    fmt::Write,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+const FUNCTIONS: usize = 2_000;
+const RUNS: usize = 10;
+
+/// A translation unit of `FUNCTIONS` small functions, with declarations,
+/// expressions and control flow.
+fn source() -> String {
+    let mut source = String::new();
+    for i in 0..FUNCTIONS {
+        writeln!(
+            source,
+            "int function_{i}(int count, int *values) {{\n    \
+                 int total = {i};\n    \
+                 for (int index = 0; index < count; index++) {{\n        \
+                     if (values[index] > total) {{\n            \
+                         total = total + values[index] * 2;\n        \
+                     }} else {{\n            \
+                         total = total - 1;\n        \
+                     }}\n    \
+                 }}\n    \
+                 return total;\n\
+             }}\n"
+        )
+        .unwrap();
+    }
+    source
+}
+
+/// The fastest of `RUNS` runs of `phase` over `source`. The size of what
+/// `phase` produces is checked, so that it can't be optimized away.
+fn time(source: &str, phase: impl Fn(&mut Session, usize) -> usize) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let mut session = Session::builder().build();
+            let file_id = session.add_source("bench.c", source);
+
+            let start = Instant::now();
+            let size = phase(&mut session, file_id);
+            let elapsed = start.elapsed();
+
+            assert!(size > 0);
+            elapsed
+        })
+        .min()
+        .unwrap()
+}
+
+fn report(name: &str, source: &str, elapsed: Duration) {
+    let megabytes = source.len() as f64 / (1024.0 * 1024.0);
+    println!(
+        "{name:<6} {:>10.2?} {:>8.1} MiB/s",
+        elapsed,
+        megabytes / elapsed.as_secs_f64()
+    );
+}
+
+fn main() {
+    let source = source();
+    println!("{} functions, {} bytes, fastest of {RUNS} runs", FUNCTIONS, source.len());
+
+    let lex = time(&source, |session, file_id| session.lex(file_id).len());
+    report("lex", &source, lex);
+
+    let parse = time(&source, |session, file_id| session.parse(file_id).children().len());
+    report("parse", &source, parse);
+}
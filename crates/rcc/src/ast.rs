@@ -7,9 +7,10 @@ use crate::{
         DiagnosticsEngine,
         FileId,
     },
+    lexer::Span,
 };
-use codespan_reporting::diagnostic::Diagnostic;
-use derive_more::Display;
+use crate::diagnostics::Diagnostic;
+pub use crate::symbol::Symbol;
 use owo_colors::OwoColorize;
 use std::fmt::Display;
 
@@ -57,6 +58,16 @@ pub struct Function {
     pub params:      Vec<Param>,
     pub return_type: Box<DataType>,
     pub body:        Statement,
+    pub attributes:  Vec<Attribute>,
+}
+
+/// A GNU attribute, e.g. `format(printf, 1, 2)` in
+/// `__attribute__((format(printf, 1, 2)))`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attribute {
+    pub name: Symbol,
+    /// The arguments of the attribute, as written in the source.
+    pub args: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -69,6 +80,8 @@ pub struct Param {
 pub enum Expr {
     Literal(Literal),
     Binary { left: Box<Expr>, operator: BinOp, right: Box<Expr> },
+    /// An expression the AST can't express yet, e.g. a call.
+    Unknown,
     // Add other types of expressions as needed
 }
 
@@ -82,8 +95,32 @@ pub enum BinOp {
     // Add other binary operators as needed
 }
 
+/// A statement, and the span of the source it is lowered from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statement {
+    pub kind: StatementKind,
+    pub span: Span,
+}
+
+impl Statement {
+    pub fn new(kind: StatementKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    pub fn is_compound(&self) -> bool {
+        matches!(self.kind, StatementKind::Compound(_))
+    }
+
+    pub fn has_statements(&self) -> bool {
+        match &self.kind {
+            StatementKind::Compound(block) => !block.statements.is_empty(),
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum Statement {
+pub enum StatementKind {
     Expression(Expr),
     If {
         condition:   Expr,
@@ -94,7 +131,7 @@ pub enum Statement {
         condition: Expr,
         body:      Vec<Statement>,
     },
-    Return(Expr),
+    Return(Option<Expr>),
     Compound(Block),
     DoWhile {
         body:      Vec<Statement>,
@@ -106,24 +143,22 @@ pub enum Statement {
         increment:   Option<Expr>,
         body:        Vec<Statement>,
     },
+    Switch {
+        condition: Expr,
+        body:      Vec<Statement>,
+    },
+    /// A `case` label. The statements it labels are the ones after it.
+    Case(Expr),
+    /// A `default` label.
+    Default,
+    /// A label to `goto`, e.g. `retry:` in `retry: x = f();`.
+    Label(Symbol),
+    Goto(Symbol),
     Break,
     Continue,
     Assignment(Assignment),
 }
 
-impl Statement {
-    pub fn is_compound(&self) -> bool {
-        matches!(self, Statement::Compound(_))
-    }
-
-    pub fn has_statements(&self) -> bool {
-        match self {
-            Statement::Compound(block) => !block.statements.is_empty(),
-            _ => false,
-        }
-    }
-}
-
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Block {
     pub statements: Vec<Statement>,
@@ -167,6 +202,9 @@ pub struct Declaration {
     pub specifiers: Vec<DeclarationSpecifier>,
     pub ty:         DataType,
     pub var:        Symbol,
+    pub attributes: Vec<Attribute>,
+    /// The assembler name given with `__asm__("name")`, if any.
+    pub asm_label:  Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -184,6 +222,7 @@ pub enum StorageClass {
     Auto,
     ThreadLocal,
     Typedef,
+    Constexpr,
     // Add other storage classes as needed
 }
 
@@ -196,24 +235,30 @@ pub struct Enum {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Struct {
     pub name:    String,
-    pub members: Vec<Declaration>,
+    /// Whether this is a `union`, whose members all start at its beginning.
+    pub union:   bool,
+    pub members: Vec<Member>,
+    /// The alignment members are capped at, as set by `#pragma pack(N)`, or
+    /// `1` with `__attribute__((packed))`.
+    pub pack:    Option<u64>,
 }
 
+/// A member of a struct or union.
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct Type {
-    pub ty: DataType,
-}
-
-#[derive(Debug, Display, PartialEq, Eq, Clone)]
-#[display(fmt = "{name}")]
-pub struct Symbol {
-    pub name: String,
+pub struct Member {
+    /// The name of the member, or `None` for an unnamed bit-field or an
+    /// anonymous struct or union.
+    pub name:      Option<Symbol>,
+    pub ty:        DataType,
+    /// The width of a bit-field, e.g. `3` in `unsigned flags : 3;`.
+    pub bit_width: Option<u32>,
+    /// The alignment given with `_Alignas`, if any.
+    pub alignment: Option<u64>,
 }
 
-impl From<String> for Symbol {
-    fn from(name: String) -> Self {
-        Self { name }
-    }
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Type {
+    pub ty: DataType,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -222,6 +267,11 @@ pub enum DataType {
     Int,
     // #[strum(serialize = "char")]
     Char,
+    Bool,
+    Short,
+    Long,
+    LongLong,
+    LongDouble,
     // #[strum(serialize = "unknown")]
     Unknown,
     // #[strum(serialize = "float")]
@@ -247,6 +297,11 @@ impl Display for DataType {
         match self {
             DataType::Int => write!(f, "int"),
             DataType::Char => write!(f, "char"),
+            DataType::Bool => write!(f, "_Bool"),
+            DataType::Short => write!(f, "short"),
+            DataType::Long => write!(f, "long"),
+            DataType::LongLong => write!(f, "long long"),
+            DataType::LongDouble => write!(f, "long double"),
             DataType::Unknown => write!(f, "unknown"),
             DataType::Float => write!(f, "float"),
             DataType::Double => write!(f, "double"),
@@ -282,6 +337,7 @@ pub fn reduce_with_diagnostics(
 pub struct Visitor {
     pub(crate) tree:    Tree,
     pub(crate) sink:    AstSink,
+    #[allow(dead_code)]
     pub(crate) file_id: FileId,
 }
 
@@ -300,7 +356,7 @@ impl Visitor {
     /// Lower the CST to an AST and collect diagnostics
     pub fn lower_with_diagnostics(
         mut self,
-        diagnostics_engine: &mut crate::diagnostics::DiagnosticsEngine,
+        _diagnostics_engine: &mut crate::diagnostics::DiagnosticsEngine,
     ) -> AstSink {
         self.visit_translation_unit();
         // self.sink.drain_errors(diagnostics_engine);
@@ -351,13 +407,14 @@ impl Visitor {
         // }
     }
 
+    #[allow(dead_code)]
     fn visit_external_declaration(&self) {
         tracing::trace!(
             "{}",
             format!("  {}  Lowering {}...", "LOWERING".cyan(), "ExternalDeclaration".green())
         );
 
-        let node = self.nth_child(0);
+        let _node = self.nth_child(0);
 
         // println!("{:#?}", node);
 
@@ -380,6 +437,7 @@ impl Visitor {
         }
     }
 
+    #[allow(dead_code)]
     fn nth_child(&self, index: usize) -> Option<&Child> {
         self.tree.nth_child(index)
     }
@@ -0,0 +1,177 @@
+//! Decoding source files.
+//!
+//! Sources are read as bytes. A file starting with a byte order mark is
+//! decoded as the encoding it marks, UTF-8 or UTF-16, and the mark is
+//! dropped; any other file is decoded from the input charset, which is UTF-8
+//! unless set with `-finput-charset=`. Bytes that aren't valid in that
+//! charset don't fail the file: each run of them is replaced with U+FFFD and
+//! reported at the place it was replaced.
+
+use crate::lexer::Span;
+use encoding_rs::{
+    DecoderResult,
+    UTF_16BE,
+    UTF_16LE,
+};
+pub use encoding_rs::{
+    Encoding as Charset,
+    UTF_8,
+};
+
+/// The charset named `label`, e.g. `utf-8`, `latin1` or `UTF-16LE`, ignoring
+/// case. Names are those of the WHATWG Encoding Standard, where `latin1` and
+/// `iso-8859-1` both name `windows-1252`, a superset of ISO 8859-1.
+pub fn charset(label: &str) -> Option<&'static Charset> {
+    Charset::for_label(label.trim().as_bytes())
+}
+
+/// A source file, decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoded {
+    /// The text of the file, without its byte order mark.
+    pub text:      String,
+    /// The charset the file was decoded from.
+    pub charset:   &'static Charset,
+    /// Whether the file started with a byte order mark.
+    pub bom:       bool,
+    /// The runs of bytes that didn't decode, in order.
+    pub malformed: Vec<Malformed>,
+}
+
+/// A run of bytes that didn't decode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Malformed {
+    /// Where the run was replaced in the decoded text, with a U+FFFD for
+    /// each malformed sequence in it.
+    pub span:  Span,
+    /// The bytes of the run.
+    pub bytes: Vec<u8>,
+}
+
+/// Decodes `bytes`, from `input_charset` unless they start with a byte
+/// order mark.
+pub fn decode(bytes: &[u8], input_charset: &'static Charset) -> Decoded {
+    let (charset, bom_len) = Charset::for_bom(bytes).unwrap_or((input_charset, 0));
+    let input = &bytes[bom_len..];
+
+    let mut decoder = charset.new_decoder_without_bom_handling();
+    let mut text = String::with_capacity(input.len());
+    let mut malformed: Vec<Malformed> = Vec::new();
+    // Where the last malformed sequence ended, in `input`.
+    let mut malformed_end = None;
+    let mut read = 0;
+    loop {
+        let (result, len) =
+            decoder.decode_to_string_without_replacement(&input[read..], &mut text, true);
+        read += len;
+
+        match result {
+            DecoderResult::InputEmpty => break,
+            DecoderResult::OutputFull => {
+                let needed = decoder.max_utf8_buffer_length_without_replacement(input.len() - read);
+                text.reserve(needed.unwrap_or(input.len() - read).max(4));
+            }
+            DecoderResult::Malformed(bad, after) => {
+                let end = read - usize::from(after);
+                let start = end - usize::from(bad);
+                let offset = text.len();
+                text.push(char::REPLACEMENT_CHARACTER);
+
+                match malformed.last_mut() {
+                    Some(run) if malformed_end == Some(start) => {
+                        run.span = Span::new(*run.span.start(), text.len());
+                        run.bytes.extend_from_slice(&input[start..end]);
+                    }
+                    _ => malformed.push(Malformed {
+                        span:  Span::new(offset, text.len()),
+                        bytes: input[start..end].to_vec(),
+                    }),
+                }
+                malformed_end = Some(end);
+            }
+        }
+    }
+
+    Decoded { text, charset, bom: bom_len > 0, malformed }
+}
+
+impl Decoded {
+    /// Encodes `text` the way this file was: in the same charset, and with a
+    /// byte order mark if it had one. This is how `rcc fix` writes a fixed
+    /// file back. Returns `None` if `text` has characters the charset can't
+    /// represent.
+    pub fn encode(&self, text: &str) -> Option<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(text.len() + 3);
+
+        // `encoding_rs` decodes UTF-16, but only ever encodes UTF-8.
+        if self.charset == UTF_16LE || self.charset == UTF_16BE {
+            let little_endian = self.charset == UTF_16LE;
+            let units = self.bom.then_some(0xFEFF).into_iter().chain(text.encode_utf16());
+            for unit in units {
+                let unit = if little_endian { unit.to_le_bytes() } else { unit.to_be_bytes() };
+                bytes.extend_from_slice(&unit);
+            }
+            return Some(bytes);
+        }
+
+        if self.bom {
+            bytes.extend_from_slice(b"\xEF\xBB\xBF");
+        }
+        let (encoded, _, unmappable) = self.charset.encode(text);
+        if unmappable {
+            return None;
+        }
+        bytes.extend_from_slice(&encoded);
+
+        Some(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::WINDOWS_1252;
+
+    #[test]
+    fn byte_order_marks_are_dropped() {
+        let utf8 = decode(b"\xEF\xBB\xBFint x;", UTF_8);
+        assert_eq!((utf8.text.as_str(), utf8.charset, utf8.bom), ("int x;", UTF_8, true));
+
+        let utf16 = decode(b"\xFF\xFEi\0n\0t\0 \0\xE9\0;\0", WINDOWS_1252);
+        assert_eq!((utf16.text.as_str(), utf16.charset), ("int é;", UTF_16LE));
+        assert!(utf16.malformed.is_empty());
+
+        let utf16 = decode(b"\xFE\xFF\0i\0n\0t\0;", UTF_8);
+        assert_eq!((utf16.text.as_str(), utf16.charset), ("int;", UTF_16BE));
+    }
+
+    #[test]
+    fn malformed_bytes_are_replaced() {
+        let decoded = decode(b"// caf\xE9\xE9\nint x = \xFF;", UTF_8);
+        assert_eq!(decoded.text, "// caf\u{FFFD}\u{FFFD}\nint x = \u{FFFD};");
+        assert_eq!(decoded.malformed, [
+            Malformed { span: Span::new(6, 12), bytes: vec![0xE9, 0xE9] },
+            Malformed { span: Span::new(21, 24), bytes: vec![0xFF] },
+        ]);
+
+        let latin1 = decode(b"// caf\xE9\n", charset("latin1").unwrap());
+        assert_eq!(latin1.text, "// café\n");
+        assert!(latin1.malformed.is_empty());
+    }
+
+    #[test]
+    fn encodes_as_decoded() {
+        for bytes in [
+            &b"\xEF\xBB\xBFint x;"[..],
+            b"\xFF\xFEi\0n\0t\0 \0\xE9\0;\0",
+            b"\xFE\xFF\0i\0n\0t\0;",
+        ] {
+            let decoded = decode(bytes, UTF_8);
+            assert_eq!(decoded.encode(&decoded.text).as_deref(), Some(bytes));
+        }
+
+        let latin1 = decode(b"// caf\xE9\n", WINDOWS_1252);
+        assert_eq!(latin1.encode("// café;\n").as_deref(), Some(&b"// caf\xE9;\n"[..]));
+        assert_eq!(latin1.encode("// 𝔵"), None);
+    }
+}
@@ -0,0 +1,302 @@
+//! The conditions of `#if` and `#elif`.
+//!
+//! A condition is an integer constant expression. Its `defined` operators
+//! are replaced by `1` or `0` first ([`replace_defined`]), so that their
+//! operands aren't expanded; then the macros in it are expanded by the
+//! caller, and what is left is evaluated ([`evaluate`]). An identifier left
+//! after expansion, keywords included, is `0`.
+//!
+//! Values are `intmax_t`, here `i64`, and arithmetic wraps around. Unsigned
+//! constants aren't told apart from signed ones yet, and character
+//! constants aren't supported, as the lexer doesn't lex them.
+
+use crate::{
+    cst::integer_value,
+    lexer::{
+        Span,
+        Token,
+        TokenKind,
+    },
+    symbol::Symbol,
+};
+use derive_more::Display;
+
+/// An open conditional group, e.g. `#ifdef FOO ... #elifdef BAR ... #endif`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Conditional {
+    /// Whether the lines of the current branch are kept.
+    pub(crate) active:       bool,
+    /// Whether a branch of the group has already been kept, in which case
+    /// every later branch is skipped.
+    pub(crate) taken:        bool,
+    /// Whether the lines around the group are kept. If not, no branch is.
+    pub(crate) outer_active: bool,
+    /// Whether the `#else` of the group has been seen.
+    pub(crate) seen_else:    bool,
+}
+
+#[derive(Debug, Display, Clone, PartialEq, Eq)]
+pub enum ConditionError {
+    #[display(fmt = "expected an expression")]
+    ExpectedExpression,
+    #[display(fmt = "expected `{_0}`")]
+    ExpectedToken(&'static str),
+    #[display(fmt = "unexpected `{_0}`")]
+    UnexpectedToken(Symbol),
+    #[display(fmt = "invalid integer constant `{_0}`")]
+    InvalidConstant(Symbol),
+    #[display(fmt = "`defined` without a macro name")]
+    DefinedWithoutName,
+    #[display(fmt = "division by zero")]
+    DivisionByZero,
+    #[display(fmt = "function-like macro `{_0}` can't be invoked here")]
+    FunctionLikeMacro(Symbol),
+}
+
+/// Replaces each `defined NAME` and `defined ( NAME )` of `tokens` with `1`
+/// if `is_defined(NAME)`, and with `0` otherwise.
+pub fn replace_defined(
+    tokens: &[Token],
+    is_defined: impl Fn(Symbol) -> bool,
+) -> Result<Vec<Token>, (ConditionError, Span)> {
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut at = 0;
+    while let Some(&token) = tokens.get(at) {
+        at += 1;
+        if token.lexeme != "defined" {
+            output.push(token);
+            continue;
+        }
+
+        let parenthesized = tokens.get(at).is_some_and(|paren| paren.kind == TokenKind::LPAREN);
+        at += usize::from(parenthesized);
+        let name = match tokens.get(at) {
+            Some(name) if is_name(name.kind) => *name,
+            _ => return Err((ConditionError::DefinedWithoutName, token.span)),
+        };
+        at += 1;
+        if parenthesized {
+            match tokens.get(at) {
+                Some(paren) if paren.kind == TokenKind::RPAREN => at += 1,
+                _ => return Err((ConditionError::ExpectedToken(")"), name.span)),
+            }
+        }
+
+        let value = if is_defined(name.lexeme) { "1" } else { "0" };
+        output.push(Token {
+            kind: TokenKind::INTEGER_CONSTANT,
+            lexeme: Symbol::intern(value),
+            ..token
+        });
+    }
+
+    Ok(output)
+}
+
+/// Evaluates the condition `tokens`, once `defined` is replaced and macros
+/// are expanded. An error with nothing left to point at is reported at
+/// `end`, e.g. the directive.
+pub fn evaluate(tokens: &[Token], end: Span) -> Result<bool, (ConditionError, Span)> {
+    let mut evaluator = Evaluator { tokens, at: 0, end };
+    let value = evaluator.conditional(true)?;
+    match tokens.get(evaluator.at) {
+        Some(token) => Err((ConditionError::UnexpectedToken(token.lexeme), token.span)),
+        None => Ok(value != 0),
+    }
+}
+
+/// Whether a token of `kind` can name a macro.
+fn is_name(kind: TokenKind) -> bool {
+    kind == TokenKind::IDENTIFIER || kind.is_keyword()
+}
+
+/// The precedence of a binary operator, from `||` up to `*`.
+fn precedence(kind: TokenKind) -> Option<u8> {
+    Some(match kind {
+        TokenKind::DOUBLEPIPE => 0,
+        TokenKind::DOUBLEAMP => 1,
+        TokenKind::PIPE => 2,
+        TokenKind::CARET => 3,
+        TokenKind::AMP => 4,
+        TokenKind::EQEQ | TokenKind::NE => 5,
+        TokenKind::LT | TokenKind::GT | TokenKind::LE | TokenKind::GE => 6,
+        TokenKind::LSHIFT | TokenKind::RSHIFT => 7,
+        TokenKind::PLUS | TokenKind::MINUS => 8,
+        TokenKind::STAR | TokenKind::SLASH | TokenKind::PERCENT => 9,
+        _ => return None,
+    })
+}
+
+/// Evaluates an expression by precedence climbing. The operands which are
+/// not evaluated, such as the `1 / 0` of `0 && 1 / 0`, are parsed as
+/// `live == false`, and can't fail on their values.
+struct Evaluator<'a> {
+    tokens: &'a [Token],
+    at:     usize,
+    end:    Span,
+}
+
+impl Evaluator<'_> {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.at).copied();
+        self.at += usize::from(token.is_some());
+        token
+    }
+
+    fn eat(&mut self, kind: TokenKind) -> bool {
+        let at = self.tokens.get(self.at).is_some_and(|token| token.kind == kind);
+        self.at += usize::from(at);
+        at
+    }
+
+    fn expect(
+        &mut self,
+        kind: TokenKind,
+        spelling: &'static str,
+    ) -> Result<(), (ConditionError, Span)> {
+        if self.eat(kind) {
+            return Ok(());
+        }
+        let span = self.tokens.get(self.at).map_or(self.end, |token| token.span);
+        Err((ConditionError::ExpectedToken(spelling), span))
+    }
+
+    /// `condition ? then : otherwise`, or a binary expression.
+    fn conditional(&mut self, live: bool) -> Result<i64, (ConditionError, Span)> {
+        let condition = self.binary(0, live)?;
+        if !self.eat(TokenKind::QUESTION) {
+            return Ok(condition);
+        }
+
+        let then = self.conditional(live && condition != 0)?;
+        self.expect(TokenKind::COLON, ":")?;
+        let otherwise = self.conditional(live && condition == 0)?;
+        Ok(if condition != 0 { then } else { otherwise })
+    }
+
+    /// A chain of binary operators of at least `min_precedence`.
+    fn binary(&mut self, min_precedence: u8, live: bool) -> Result<i64, (ConditionError, Span)> {
+        let mut left = self.unary(live)?;
+        while let Some(operator) = self.tokens.get(self.at).copied() {
+            let Some(precedence) = precedence(operator.kind).filter(|&p| p >= min_precedence)
+            else {
+                break;
+            };
+            self.at += 1;
+
+            let right_live = match operator.kind {
+                TokenKind::DOUBLEAMP => live && left != 0,
+                TokenKind::DOUBLEPIPE => live && left == 0,
+                _ => live,
+            };
+            let right = self.binary(precedence + 1, right_live)?;
+            left = match operator.kind {
+                TokenKind::SLASH | TokenKind::PERCENT if right == 0 => {
+                    if live {
+                        return Err((ConditionError::DivisionByZero, operator.span));
+                    }
+                    0
+                }
+                TokenKind::SLASH => left.wrapping_div(right),
+                TokenKind::PERCENT => left.wrapping_rem(right),
+                TokenKind::STAR => left.wrapping_mul(right),
+                TokenKind::PLUS => left.wrapping_add(right),
+                TokenKind::MINUS => left.wrapping_sub(right),
+                TokenKind::LSHIFT => left.wrapping_shl(right as u32),
+                TokenKind::RSHIFT => left.wrapping_shr(right as u32),
+                TokenKind::LT => i64::from(left < right),
+                TokenKind::GT => i64::from(left > right),
+                TokenKind::LE => i64::from(left <= right),
+                TokenKind::GE => i64::from(left >= right),
+                TokenKind::EQEQ => i64::from(left == right),
+                TokenKind::NE => i64::from(left != right),
+                TokenKind::AMP => left & right,
+                TokenKind::CARET => left ^ right,
+                TokenKind::PIPE => left | right,
+                TokenKind::DOUBLEAMP => i64::from(left != 0 && right != 0),
+                _ => i64::from(left != 0 || right != 0),
+            };
+        }
+
+        Ok(left)
+    }
+
+    /// A unary operator applied to an operand, or an operand.
+    fn unary(&mut self, live: bool) -> Result<i64, (ConditionError, Span)> {
+        let Some(token) = self.next() else {
+            return Err((ConditionError::ExpectedExpression, self.end));
+        };
+
+        match token.kind {
+            TokenKind::PLUS => self.unary(live),
+            TokenKind::MINUS => Ok(self.unary(live)?.wrapping_neg()),
+            TokenKind::TILDE => Ok(!self.unary(live)?),
+            TokenKind::BANG => Ok(i64::from(self.unary(live)? == 0)),
+            TokenKind::LPAREN => {
+                let value = self.conditional(live)?;
+                self.expect(TokenKind::RPAREN, ")")?;
+                Ok(value)
+            }
+            TokenKind::INTEGER_CONSTANT => integer_value(token.lexeme.as_str())
+                .ok_or((ConditionError::InvalidConstant(token.lexeme), token.span)),
+            kind if is_name(kind) => Ok(0),
+            _ => Err((ConditionError::UnexpectedToken(token.lexeme), token.span)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use logos::Logos;
+    use rstest::rstest;
+
+    fn tokens(text: &str) -> Vec<Token> {
+        let mut lexer = TokenKind::lexer(text);
+        let mut tokens = Vec::new();
+        while let Some(kind) = lexer.next() {
+            let kind = kind.unwrap_or(TokenKind::UNKNOWN);
+            if kind != TokenKind::WHITESPACE {
+                let span = lexer.span();
+                tokens.push(Token::new(kind, lexer.slice(), Span::new(span.start, span.end)));
+            }
+        }
+        tokens
+    }
+
+    fn condition(text: &str) -> Result<bool, ConditionError> {
+        let tokens = replace_defined(&tokens(text), |name| name == "FOO").map_err(|(e, _)| e)?;
+        evaluate(&tokens, Span::new(0, 0)).map_err(|(error, _)| error)
+    }
+
+    #[rstest]
+    #[case::constant("1", true)]
+    #[case::hexadecimal("0x10 == 16", true)]
+    #[case::arithmetic("2 + 3 * 4 == 14", true)]
+    #[case::parentheses("(2 + 3) * 4 == 20", true)]
+    #[case::shifts("1 << 4 >> 2 == 4", true)]
+    #[case::comparisons("1 < 2 && 2 <= 2 && 3 > 2 && 3 >= 3 && 1 != 2", true)]
+    #[case::bitwise("(6 & 3) == 2 && (6 | 1) == 7 && (6 ^ 2) == 4 && ~0 == -1", true)]
+    #[case::negation("!0 && !!5 && -1 < 0", true)]
+    #[case::ternary("0 ? 1 : 2 == 2", true)]
+    #[case::defined("defined FOO && defined(FOO) && !defined BAR", true)]
+    #[case::identifier("BAR", false)]
+    #[case::keyword("int", false)]
+    #[case::short_circuit("0 && 1 / 0", false)]
+    #[case::unevaluated_branch("1 ? 1 : 1 % 0", true)]
+    fn evaluates(#[case] text: &str, #[case] expected: bool) {
+        assert_eq!(condition(text), Ok(expected), "{text}");
+    }
+
+    #[rstest]
+    #[case::empty("", ConditionError::ExpectedExpression)]
+    #[case::dangling_operator("1 +", ConditionError::ExpectedExpression)]
+    #[case::unclosed("(1", ConditionError::ExpectedToken(")"))]
+    #[case::trailing("1 2", ConditionError::UnexpectedToken(Symbol::intern("2")))]
+    #[case::division_by_zero("1 / 0", ConditionError::DivisionByZero)]
+    #[case::defined_without_name("defined", ConditionError::DefinedWithoutName)]
+    #[case::floating("1.5", ConditionError::UnexpectedToken(Symbol::intern("1.5")))]
+    fn rejects(#[case] text: &str, #[case] expected: ConditionError) {
+        assert_eq!(condition(text), Err(expected), "{text}");
+    }
+}
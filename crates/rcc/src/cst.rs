@@ -1,5 +1,5 @@
-#![allow(bad_style, missing_docs, unreachable_pub)]
-use codespan_reporting::diagnostic::Diagnostic;
+#![allow(bad_style, missing_docs, unreachable_pub, non_local_definitions)]
+use crate::diagnostics::Diagnostic;
 use num_derive::{
     FromPrimitive,
     ToPrimitive,
@@ -50,16 +50,16 @@ mod smoke_test_tree_traversal {
 
     #[test]
     fn test_find_token() {
-        let token1 = Token::new(TokenKind::IDENTIFIER, "foo".into(), Span::new(0, 3));
-        let token2 = Token::new(TokenKind::INTEGER_CONSTANT, "42".into(), Span::new(4, 5));
-        let token3 = Token::new(TokenKind::STRING, "\"hello\"".into(), Span::new(6, 12));
+        let token1 = Token::new(TokenKind::IDENTIFIER, "foo", Span::new(0, 3));
+        let token2 = Token::new(TokenKind::INTEGER_CONSTANT, "42", Span::new(4, 5));
+        let token3 = Token::new(TokenKind::STRING, "\"hello\"", Span::new(6, 12));
 
         let tree = Tree {
             kind:     TreeKind::Expression,
             range:    Span::new(0, 12),
             children: vec![
                 Child::Token(token1),
-                Child::Token(token2.clone()),
+                Child::Token(token2),
                 Child::Token(token3),
             ],
             file_id:  0,
@@ -72,9 +72,58 @@ mod smoke_test_tree_traversal {
         // Test finding a token that doesn't exist.
         assert_eq!(tree.find_token(TokenKind::FLOATING_CONSTANT), None);
     }
+    #[test]
+    fn test_from_keyword() {
+        assert_eq!(SyntaxKind::from_keyword("while", Standard::C11), Some(SyntaxKind::WHILE_KW));
+        assert_eq!(SyntaxKind::from_keyword("_Bool", Standard::C11), Some(SyntaxKind::BOOL_KW));
+        assert_eq!(SyntaxKind::from_keyword("whiles", Standard::C11), None);
+
+        // Keywords introduced by C23 are identifiers before it.
+        assert_eq!(SyntaxKind::from_keyword("bool", Standard::C11), None);
+        assert_eq!(SyntaxKind::from_keyword("bool", Standard::C23), Some(SyntaxKind::BOOL_KW));
+        assert_eq!(
+            SyntaxKind::from_keyword("nullptr", Standard::C23),
+            Some(SyntaxKind::NULLPTR_KW)
+        );
+
+        // `typeof` is both a GNU and a C23 keyword, `__typeof__` only a GNU one.
+        assert_eq!(SyntaxKind::from_keyword("typeof", Standard::C11), None);
+        assert_eq!(
+            SyntaxKind::from_keyword("typeof", Standard::Gnu11),
+            Some(SyntaxKind::TYPEOF_KW)
+        );
+        assert_eq!(SyntaxKind::from_keyword("typeof", Standard::C23), Some(SyntaxKind::TYPEOF_KW));
+        assert_eq!(SyntaxKind::from_keyword("__typeof__", Standard::C23), None);
+    }
+
+    #[test]
+    fn test_keywords() {
+        let c11 = SyntaxKind::keywords(Standard::C11).collect::<Vec<_>>();
+        assert!(c11.contains(&"unsigned"));
+        assert!(c11.contains(&"_Bool"));
+        assert!(!c11.contains(&"bool"));
+        assert!(!c11.contains(&"__attribute__"));
+
+        let gnu11 = SyntaxKind::keywords(Standard::Gnu11).collect::<Vec<_>>();
+        assert!(gnu11.contains(&"__attribute__"));
+        assert!(gnu11.contains(&"typeof"));
+
+        for keyword in KEYWORDS {
+            assert!(
+                SyntaxKind::from_keyword(keyword, Standard::Gnu11).is_some() ||
+                    SyntaxKind::from_keyword(keyword, Standard::C23).is_some(),
+                "`{keyword}` is not a keyword"
+            );
+        }
+    }
 }
 
-#[allow(clippy::manual_non_exhaustive, non_snake_case, non_camel_case_types)]
+#[allow(
+    clippy::manual_non_exhaustive,
+    clippy::upper_case_acronyms,
+    non_snake_case,
+    non_camel_case_types
+)]
 #[derive(
     Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, FromPrimitive, ToPrimitive, EnumCount,
 )]
@@ -251,27 +300,91 @@ pub enum SyntaxKind {
     INTEGER_CONSTANT,
     FLOATING_CONSTANT,
     FUNC_NAME_KW,
+    ATTRIBUTE_KW,
+    ASM_KW,
+    EXTENSION_KW,
+    TYPEOF_KW,
+    TRUE_KW,
+    FALSE_KW,
+    NULLPTR_KW,
+    CONSTEXPR_KW,
+    TYPEOF_UNQUAL_KW,
 }
 use self::SyntaxKind::*;
+
+/// The spellings of every keyword, whether or not the selected standard has
+/// it (see [`SyntaxKind::from_keyword`]). Standard spellings come first, so
+/// that they are preferred over GNU ones when suggesting a keyword.
+const KEYWORDS: &[&str] = &[
+    "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else",
+    "enum", "extern", "float", "for", "goto", "if", "inline", "int", "long", "register",
+    "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch", "typedef",
+    "union", "unsigned", "void", "volatile", "while", "_Alignas", "_Alignof", "_Atomic", "_Bool",
+    "_Complex", "_Generic", "_Imaginary", "_Noreturn", "_Static_assert", "_Thread_local",
+    "__func__", "alignas", "alignof", "bool", "static_assert", "thread_local", "true", "false",
+    "nullptr", "constexpr", "typeof", "typeof_unqual", "__const", "__const__", "__inline",
+    "__inline__", "__restrict", "__restrict__", "__signed", "__signed__", "__volatile",
+    "__volatile__", "__alignof", "__alignof__", "__attribute__", "__attribute", "asm", "__asm__",
+    "__asm", "__extension__", "__typeof__", "__typeof",
+];
 impl SyntaxKind {
     pub fn is_keyword(self) -> bool {
         matches!(
             self,
-            AND_KW |
-                ELSE_KW |
-                LOAD_KW |
+            AUTO_KW |
                 BREAK_KW |
-                FOR_KW |
-                NOT_KW |
+                CASE_KW |
+                CHAR_KW |
+                CONST_KW |
                 CONTINUE_KW |
+                DEFAULT_KW |
+                DO_KW |
+                DOUBLE_KW |
+                ELSE_KW |
+                ENUM_KW |
+                EXTERN_KW |
+                FLOAT_KW |
+                FOR_KW |
+                GOTO_KW |
                 IF_KW |
-                OR_KW |
-                DEF_KW |
-                IN_KW |
-                PASS_KW |
-                ELIF_KW |
-                LAMBDA_KW |
-                RETURN_KW
+                INLINE_KW |
+                INT_KW |
+                LONG_KW |
+                REGISTER_KW |
+                RESTRICT_KW |
+                RETURN_KW |
+                SHORT_KW |
+                SIGNED_KW |
+                SIZEOF_KW |
+                STATIC_KW |
+                STRUCT_KW |
+                SWITCH_KW |
+                TYPEDEF_KW |
+                UNION_KW |
+                UNSIGNED_KW |
+                VOID_KW |
+                VOLATILE_KW |
+                WHILE_KW |
+                ALIGNAS_KW |
+                ALIGNOF_KW |
+                ATOMIC_KW |
+                BOOL_KW |
+                COMPLEX_KW |
+                GENERIC_KW |
+                IMAGINARY_KW |
+                NORETURN_KW |
+                STATIC_ASSERT_KW |
+                THREAD_LOCAL_KW |
+                FUNC_NAME_KW |
+                ATTRIBUTE_KW |
+                ASM_KW |
+                EXTENSION_KW |
+                TYPEOF_KW |
+                TRUE_KW |
+                FALSE_KW |
+                NULLPTR_KW |
+                CONSTEXPR_KW |
+                TYPEOF_UNQUAL_KW
         )
     }
     pub fn is_punct(self) -> bool {
@@ -321,32 +434,29 @@ impl SyntaxKind {
     pub fn is_literal(self) -> bool {
         matches!(self, IDENTIFIER | INT | FLOAT | STRING | BYTES)
     }
-    pub fn from_keyword(ident: &str) -> Option<SyntaxKind> {
-        let kw = match ident {
-            "and" => AND_KW,
-            "else" => ELSE_KW,
-            "load" => LOAD_KW,
-            "break" => BREAK_KW,
-            "for" => FOR_KW,
-            "not" => NOT_KW,
-            "continue" => CONTINUE_KW,
-            "if" => IF_KW,
-            "or" => OR_KW,
-            "def" => DEF_KW,
-            "in" => IN_KW,
-            "pass" => PASS_KW,
-            "elif" => ELIF_KW,
-            "lambda" => LAMBDA_KW,
-            "return" => RETURN_KW,
-            _ => return None,
-        };
-        Some(kw)
+    /// The keyword spelled `ident` in `standard`, if any. Whether a word is a
+    /// keyword depends on the standard: `nullptr` is only a keyword in C23,
+    /// and `__attribute__` only with GNU extensions.
+    pub fn from_keyword(ident: &str, standard: Standard) -> Option<SyntaxKind> {
+        let mut lexer = TokenKind::lexer(ident);
+        let kind = lexer.next()?.ok()?;
+
+        let is_keyword = lexer.span() == (0..ident.len()) &&
+            kind.is_keyword() &&
+            kind.is_keyword_in(ident, standard);
+
+        is_keyword.then(|| kind.to_syntax())
     }
-    pub fn from_contextual_keyword(ident: &str) -> Option<SyntaxKind> {
-        let kw = match ident {
-            _ => return None,
-        };
-        Some(kw)
+    /// Every spelling of a keyword in `standard`, e.g. to suggest one in
+    /// place of a misspelled identifier.
+    pub fn keywords(standard: Standard) -> impl Iterator<Item = &'static str> {
+        KEYWORDS
+            .iter()
+            .copied()
+            .filter(move |keyword| Self::from_keyword(keyword, standard).is_some())
+    }
+    pub fn from_contextual_keyword(_ident: &str) -> Option<SyntaxKind> {
+        None
     }
     pub fn from_char(c: char) -> Option<SyntaxKind> {
         let tok = match c {
@@ -381,39 +491,65 @@ impl SyntaxKind {
 use crate::{
     ast::{
         AstSink,
+        Assignment,
+        Attribute,
+        BinOp,
         Block,
         DataType,
         Declaration,
+        DeclarationSpecifier,
+        Enum,
         Expr,
         ExternDecl,
         Function,
         Literal,
+        Member,
         Param,
         Statement,
+        StatementKind,
+        StorageClass,
+        Struct,
         Symbol,
         TranslationUnit,
     },
+    cst::nodes::CstNode,
     diagnostics::{
         self,
         DiagnosticsEngine,
         FileId,
     },
+    flow::{
+        self,
+        FlowError,
+    },
+    layout::{
+        self,
+        PackPragma,
+    },
     lexer::{
         Span,
         Token,
         TokenKind,
     },
+    standard::Standard,
 };
+use getset::{
+    CopyGetters,
+    Getters,
+};
+use logos::Logos;
 use owo_colors::OwoColorize;
 // use smartstring::alias::String;
-use std::{
-    default,
-    fmt::{
+use std::fmt::{
         self,
         Display,
-    },
+    };
+use strum_macros::{
+    Display,
+    EnumIter,
 };
-use strum_macros::Display;
+
+pub mod nodes;
 
 #[macro_export]
 macro_rules! format_to {
@@ -423,7 +559,7 @@ macro_rules! format_to {
     };
 }
 
-#[derive(Debug, Default, Display, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, Default, Display, EnumIter, PartialEq, Eq, Clone, Copy)]
 pub enum TreeKind {
     #[default]
     Unknown,
@@ -519,6 +655,15 @@ pub enum TreeKind {
     Designation,
     DesignatorList,
     Designator,
+    AttributeSpecifier,
+    Attribute,
+    AsmLabel,
+    AsmStatement,
+    AsmOperandList,
+    AsmOperand,
+    TypeofSpecifier,
+    StatementExpression,
+    StandardAttributeSpecifier,
 }
 
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
@@ -561,7 +706,7 @@ impl TreeSink {
         self.syntax_errors.push(error);
     }
 
-    pub fn token(&mut self, kind: TokenKind, lexeme: String, span: Span) {
+    pub fn token(&mut self, kind: TokenKind, lexeme: Symbol, span: Span) {
         let token = Token::new(kind, lexeme, span);
         self.tree.children.push(Child::Token(token));
     }
@@ -577,11 +722,15 @@ impl TreeSink {
     }
 }
 
-#[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[derive(Debug, Default, PartialEq, Eq, Clone, CopyGetters, Getters)]
 pub struct Tree {
+    #[getset(get_copy = "pub")]
     pub(crate) kind:     TreeKind,
+    #[getset(get_copy = "pub")]
     pub(crate) range:    Span,
+    #[getset(get = "pub")]
     pub(crate) children: Vec<Child>,
+    #[getset(get_copy = "pub")]
     pub(crate) file_id:  FileId,
     pub(crate) pos:      usize, // The index into the children vector.
 }
@@ -616,6 +765,7 @@ impl Tree {
     }
 
     // Helper function to find a token of a specific kind.
+    #[allow(dead_code)]
     fn find_token(&self, kind: TokenKind) -> Option<&Token> {
         self.children
             .iter()
@@ -630,6 +780,32 @@ impl Tree {
         self.children.get(n)
     }
 
+    /// Every token of the tree, in source order.
+    pub fn tokens(&self) -> Box<dyn Iterator<Item = &Token> + '_> {
+        Box::new(self.children.iter().flat_map(|child| match child {
+            Child::Token(token) => Box::new(std::iter::once(token)),
+            Child::Tree(tree) => tree.tokens(),
+        }))
+    }
+
+    /// The source text of the tree, rebuilt from its tokens. Tokens which
+    /// are separated by whitespace or comments in the source are separated by
+    /// a single space.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        let mut prev_end = None;
+
+        for token in self.tokens() {
+            if prev_end.is_some_and(|end| end < *token.span.start()) {
+                text.push(' ');
+            }
+            text.push_str(token.lexeme.as_str());
+            prev_end = Some(*token.span.end());
+        }
+
+        text
+    }
+
     pub fn num_functions(&self) -> usize {
         self.children
             .iter()
@@ -738,6 +914,35 @@ impl Tree {
         assert!(buf.ends_with('\n'));
     }
 
+    /// The tree as [`Tree::print`] lays it out, without colors, e.g. for
+    /// snapshots.
+    pub fn dump(&self) -> String {
+        let mut buf = String::new();
+        self.dump_to(&mut buf, 0);
+        buf
+    }
+
+    fn dump_to(&self, buf: &mut String, level: usize) {
+        let indent = "  ".repeat(level);
+        let branch = if level == 0 { "" } else { "└─" };
+        format_to!(buf, "{indent}{branch}{}@{}\n", self.kind, self.range);
+
+        for child in &self.children {
+            match child {
+                Child::Token(token) => {
+                    format_to!(
+                        buf,
+                        "{indent}  \\- {:?}@{} '{}'\n",
+                        token.kind(),
+                        token.span(),
+                        token.lexeme
+                    );
+                }
+                Child::Tree(tree) => tree.dump_to(buf, level + 1),
+            }
+        }
+    }
+
     // Matches certain patterns on parse trees and transforms them into
     // AST nodes. AST nodes are a more minimal representation of thee
     // parse tree that is easier to work with, and is more suitable for
@@ -760,7 +965,7 @@ impl Tree {
                     // Find the child node of ExternDecl that is a FunctionDef.
                     if let Some(function_def) = extern_decl.find_child(TreeKind::FunctionDef) {
                         functions.push(ExternDecl::Function(function_def.transform_function()));
-                    } else if let Some(declaration) = extern_decl.find_child(TreeKind::Declaration)
+                    } else if let Some(_declaration) = extern_decl.find_child(TreeKind::Declaration)
                     {
                         // functions.push(ExternDecl::Declaration(
                         //     declaration.
@@ -768,9 +973,9 @@ impl Tree {
                         // ));
                         // .push(ExternDecl::Declaration(declaration.
                         // transform_declaration()));
-                    } else {
-                        unreachable!("Expected FunctionDef or Declaration node")
                     }
+                    // Otherwise, the `ExternDecl` was left empty by a syntax
+                    // error, which has already been reported.
                 } else {
                     unreachable!("Expected ExternDecl node")
                 }
@@ -782,6 +987,11 @@ impl Tree {
 
     pub fn lower_with_diagnostics(&mut self, diagnostics: &mut DiagnosticsEngine) -> AstSink {
         let mut functions = Vec::<ExternDecl>::new();
+        let pack_pragmas = diagnostics
+            .files
+            .get(self.file_id)
+            .map(|file| layout::pack_pragmas(file.source()))
+            .unwrap_or_default();
 
         for child in &mut self.children {
             if let Child::Tree(extern_decl) = child {
@@ -790,16 +1000,19 @@ impl Tree {
                     // Find the child node of ExternDecl that is a FunctionDef.
                     if let Some(function_def) = extern_decl.find_child(TreeKind::FunctionDef) {
                         functions.push(ExternDecl::Function(
-                            function_def.clone().transform_function_with_diagnostics(diagnostics),
+                            function_def
+                                .clone()
+                                .transform_function_with_diagnostics(diagnostics, &pack_pragmas),
                         ));
                     } else if let Some(declaration) = extern_decl.find_child(TreeKind::Declaration)
                     {
                         functions.push(ExternDecl::Declaration(
-                            declaration.transform_declaration_with_diagnostics(diagnostics),
+                            declaration
+                                .transform_declaration_with_diagnostics(diagnostics, &pack_pragmas),
                         ));
-                    } else {
-                        unreachable!("Expected FunctionDef or Declaration node")
                     }
+                    // Otherwise, the `ExternDecl` was left empty by a syntax
+                    // error, which has already been reported.
                 } else {
                     unreachable!("Expected ExternDecl node")
                 }
@@ -818,20 +1031,20 @@ impl Tree {
     }
 
     pub fn at_token(&self, kind: TokenKind) -> bool {
-        self.nth(0).map_or(false, |child| match child {
+        self.nth(0).is_some_and(|child| match child {
             Child::Token(token) => token.kind() == &kind,
             _ => false,
         })
     }
 
     pub fn at_tree(&self, kind: TreeKind) -> bool {
-        self.nth(0).map_or(false, |child| match child {
+        self.nth(0).is_some_and(|child| match child {
             Child::Tree(tree) => tree.kind == kind,
             _ => false,
         })
     }
 
-    pub fn expect_tree(&mut self, diagnostics: &mut DiagnosticsEngine, kind: TreeKind) {
+    pub fn expect_tree(&mut self, _diagnostics: &mut DiagnosticsEngine, kind: TreeKind) {
         if self.eat_tree(kind) {
             return;
         }
@@ -852,7 +1065,7 @@ impl Tree {
         // );
     }
 
-    pub fn expect_token(&mut self, diagnostics: &mut DiagnosticsEngine, kind: TokenKind) {
+    pub fn expect_token(&mut self, _diagnostics: &mut DiagnosticsEngine, kind: TokenKind) {
         if self.eat_token(kind) {
             return;
         }
@@ -898,32 +1111,11 @@ impl Tree {
     fn transform_function_with_diagnostics(
         &mut self,
         diagnostics: &mut DiagnosticsEngine,
+        pack_pragmas: &[(usize, PackPragma)],
     ) -> Function {
-        let (return_type, params, name) = self.extract_function_signature();
+        let (return_type, params, name) = self.extract_function_signature(pack_pragmas);
         let body = self.extract_function_body();
-
-        // If we have a return type, but no block, then emit an error.
-        // testdata/parse/b.c:56:17: warning: non-void function does not return a value
-        // [-Wreturn-type] int qux(int x) {}
-        //                 ^
-        // 1 warning generated.
-        if return_type != Box::new(DataType::Void) && !body.has_statements() {
-            tracing::warn!(
-                "{}",
-                &format!(
-                    "  {}  Function {}@{} has no statements",
-                    "PARSER".yellow(),
-                    name.green(),
-                    self.range.to_string().black().italic(),
-                )
-            );
-
-            // non-void function does not return a value
-            self.push_error(
-                diagnostics::non_void_function_doesnt_return_value(self.file_id, self.range),
-                diagnostics,
-            );
-        }
+        self.check_old_style_parameters(diagnostics);
 
         tracing::trace!(
             "{}",
@@ -940,11 +1132,35 @@ impl Tree {
             )
         );
 
-        Function { name: Symbol { name }, params, return_type, body }
+        let function = Function {
+            name,
+            params,
+            return_type,
+            body,
+            attributes: self.extract_function_attributes(),
+        };
+        // A missing `return` is reported at the closing brace of the body,
+        // where the function returns without a value.
+        let closing_brace = nodes::FunctionDef::cast(self)
+            .and_then(|function_def| function_def.body())
+            .and_then(|body| body.rbrace_token())
+            .map(|rbrace| rbrace.span);
+        for (error, span) in flow::check_function(&function) {
+            let span = match error {
+                FlowError::MissingReturn(_) => closing_brace.unwrap_or(span),
+                _ => span,
+            };
+            self.push_error(
+                diagnostics::control_flow_diagnostic(self.file_id, span, &error),
+                diagnostics,
+            );
+        }
+
+        function
     }
 
     fn transform_function(&self) -> Function {
-        let (return_type, params, name) = self.extract_function_signature();
+        let (return_type, params, name) = self.extract_function_signature(&[]);
         let body = self.extract_function_body();
 
         // if !body.has_statements() {
@@ -991,161 +1207,53 @@ impl Tree {
             )
         );
 
-        Function { name: Symbol { name }, params, return_type, body }
+        Function {
+            name,
+            params,
+            return_type,
+            body,
+            attributes: self.extract_function_attributes(),
+        }
     }
 
-    fn extract_parameter_info(&self, param: &Tree) -> (DataType, String) {
-        let mut param_type = DataType::Int;
-        let mut param_name = String::new();
-
-        if param.kind == TreeKind::ParameterDeclaration {
-            // println!("param {:#?}", param);
-            if let Child::Tree(declaration_specifiers) = &param.children[0] {
-                if let Child::Tree(type_specifier) = &declaration_specifiers.children[0] {
-                    if type_specifier.kind == TreeKind::TypeSpecifier {
-                        param_type = type_specifier.transform_type();
-                    }
-                }
-            }
-
-            // Start iterating through the children.
-            let mut pointer_count = 0;
-            let mut data_type_idx = 1;
-
-            // println!("param.children.len() {:#?}", param.children.len());
-
-            while data_type_idx < param.children.len() {
-                // println!("param.children[{}] {:#?}", data_type_idx,
-                // param.children[data_type_idx]);
-                if let Child::Tree(param_child) = &param.children[data_type_idx] {
-                    if param_child.kind == TreeKind::Pointer {
-                        pointer_count += 1;
-
-                        // Found a pointer, so go deeper into the tree to check for more pointers.
-                        let mut pointer_child = param_child;
-                        // println!("pointer {:#?}", pointer_child);
-
-                        while pointer_child.children.len() > 1 {
-                            if let Child::Tree(inner_pointer) = &pointer_child.children[1] {
-                                // println!("inner_pointer {:#?}", inner_pointer);
-                                if inner_pointer.kind == TreeKind::Pointer {
-                                    pointer_count += 1;
-                                    pointer_child = inner_pointer;
-                                } else {
-                                    // Handle other cases or report an error if needed.
-                                    // TODO: Add error handling or logging.
-
-                                    tracing::error!(
-                                        "{}",
-                                        &format!(
-                                            "  {}  Unexpected node while lowering {}@{} to {}. \
-                                             Expected {} or {} but found {}",
-                                            "PARSER".yellow(),
-                                            inner_pointer.kind.to_string().green(),
-                                            inner_pointer.range.to_string().black().italic(),
-                                            "ParameterDeclaration".cyan(),
-                                            "Pointer".cyan(),
-                                            "DirectDeclarator".cyan(),
-                                            inner_pointer.kind.to_string().green(),
-                                        )
-                                    );
-                                    break; // Exit the loop on error.
-                                }
-                            } else {
-                                // Handle unexpected nodes or report an error if needed.
-                                // TODO: Add error handling or logging.
-                                break; // Exit the loop on error.
-                            }
-                        }
-                    } else if param_child.kind == TreeKind::DirectDeclarator {
-                        // println!("direct_declarator {param_child:#?}");
-
-                        // We found the direct declarator, so extract the parameter name.
-                        if let Child::Token(token) = &param_child.children[0] {
-                            if token.kind == TokenKind::IDENTIFIER {
-                                param_name = token.lexeme.clone();
-
-                                tracing::trace!(
-                                    "{}",
-                                    &format!(
-                                        "{} Lowering {}@{} to {} {} {}{}{}",
-                                        "PARSER".yellow(),
-                                        token.kind.to_string().blue(),
-                                        token.span().to_string().black().italic(),
-                                        "ParameterName".cyan(),
-                                        "-".red(),
-                                        " ".yellow().on_black(),
-                                        token.lexeme.red().on_black(),
-                                        " ".yellow().on_black(),
-                                    )
-                                );
-                            } else {
-                                // TODO: Error handling
-
-                                tracing::error!(
-                                    "{}",
-                                    &format!(
-                                        "  {}  Unexpected node while lowering {}@{} to {}. \
-                                         Expected {} but found {}",
-                                        "PARSER".yellow(),
-                                        token.kind.to_string().green(),
-                                        token.span.to_string().black().italic(),
-                                        "Token".cyan(),
-                                        "IDENTIFIER".cyan(),
-                                        token.kind.to_string().green(),
-                                    )
-                                );
-                            }
-                        }
-                        break; // Exit the loop since we've found the
-                               // declarator.
-                    } else {
-                        // Handle other cases or report an error if needed.
-                        // TODO: Add error handling or logging.
-
-                        tracing::error!(
-                            "{}",
-                            &format!(
-                                "  {}  Unexpected node while lowering {}@{} to {}. Expected {} or \
-                                 {} but found {}",
-                                "PARSER".yellow(),
-                                param_child.kind.to_string().green(),
-                                param_child.range.to_string().black().italic(),
-                                "ParameterDeclaration".cyan(),
-                                "Pointer".cyan(),
-                                "DirectDeclarator".cyan(),
-                                param_child.kind.to_string().green(),
-                            )
-                        );
-                    }
-                } else {
-                    // Handle unexpected nodes or report an error if needed.
-                    // TODO: Add error handling or logging.
-                }
+    fn extract_parameter_info(
+        &self,
+        param: nodes::ParameterDeclaration<'_>,
+        pack_pragmas: &[(usize, PackPragma)],
+    ) -> (DataType, Symbol) {
+        let param_tree = param.syntax();
+        let mut param_type = lower_type_specifiers(
+            param.declaration_specifiers().into_iter().flat_map(|it| it.type_specifiers()),
+            pack_pragmas,
+        );
+        let mut param_name = Symbol::default();
 
-                data_type_idx += 1;
-            }
+        // Parameters may be abstract (e.g. `int f(int *)`), in which case
+        // there is no name to extract.
+        if let Some(token) = param.direct_declarator().and_then(|it| it.name()) {
+            param_name = token.lexeme;
 
-            // Create the parameter data type based on pointer count.
-            for _ in 0..pointer_count {
-                param_type = DataType::Pointer(Box::new(param_type));
-            }
-        } else {
-            // TODO: Error handling
-            tracing::error!(
+            tracing::trace!(
                 "{}",
                 &format!(
-                    "  {}  Unexpected node while lowering {}@{} to {}. Expected {} but found {}",
+                    "{} Lowering {}@{} to {} {} {}{}{}",
                     "PARSER".yellow(),
-                    param.kind.to_string().green(),
-                    param.range.to_string().black().italic(),
-                    "ParameterDeclaration".cyan(),
-                    "ParameterDeclaration".cyan(),
-                    param.kind.to_string().green(),
+                    token.kind.to_string().blue(),
+                    token.span().to_string().black().italic(),
+                    "ParameterName".cyan(),
+                    "-".red(),
+                    " ".yellow().on_black(),
+                    token.lexeme.red().on_black(),
+                    " ".yellow().on_black(),
                 )
             );
         }
 
+        // Create the parameter data type based on pointer depth.
+        for _ in 0..param.pointer().map_or(0, |pointer| pointer.depth()) {
+            param_type = DataType::Pointer(Box::new(param_type));
+        }
+
         // TODO: update logging here (make param type range
         // accurate)
         tracing::trace!(
@@ -1153,8 +1261,8 @@ impl Tree {
             &format!(
                 "{} Lowering {}@{} to {} {} {}",
                 "PARSER".yellow(),
-                param.kind.to_string().green(),
-                param.range.to_string().black().italic(),
+                param_tree.kind.to_string().green(),
+                param_tree.range.to_string().black().italic(),
                 "DataType".cyan(),
                 "-".red(),
                 param_type.magenta()
@@ -1173,7 +1281,7 @@ impl Tree {
 
     // if let Child::Tree(direct_declarator) = &param.children[1] {
     //     if let Child::Token(token) = &direct_declarator.children[0] {
-    //         param_name = token.lexeme.clone();
+    //         param_name = token.lexeme;
     //     }
     // }
 
@@ -1209,7 +1317,7 @@ impl Tree {
     //     if let Child::Tree(param_child) = &param.children[data_type_idx] {
     //         if param_child.kind == TreeKind::DirectDeclarator {
     //             if let Child::Token(token) = &param_child.children[0] {
-    //                 param_name = token.lexeme.clone();
+    //                 param_name = token.lexeme;
     //             }
     //         }
     //     }
@@ -1234,21 +1342,26 @@ impl Tree {
     // }
     // V1 END
 
-    fn extract_function_signature(&self) -> (Box<DataType>, Vec<Param>, String) {
-        let mut name = String::new();
+    fn extract_function_signature(
+        &self,
+        pack_pragmas: &[(usize, PackPragma)],
+    ) -> (Box<DataType>, Vec<Param>, Symbol) {
+        let mut name = Symbol::default();
         let mut params = vec![];
         let mut return_type = Box::new(DataType::Int);
 
-        let decl_specifiers = &self.children[0];
+        let Some(function_def) = nodes::FunctionDef::cast(self) else {
+            // TODO: Error handling
+            tracing::error!("Expected FunctionDef node while lowering function declaration");
+            return (return_type, params, name);
+        };
 
-        if let Child::Tree(decl_specifiers) = decl_specifiers {
-            if decl_specifiers.kind == TreeKind::DeclarationSpecifiers {
-                if let Child::Tree(type_specifier) = &decl_specifiers.children[0] {
-                    if type_specifier.kind == TreeKind::TypeSpecifier {
-                        return_type = Box::new(type_specifier.transform_type());
-                    }
-                }
-            } else {
+        match function_def.declaration_specifiers() {
+            Some(specifiers) => {
+                return_type =
+                    Box::new(lower_type_specifiers(specifiers.type_specifiers(), pack_pragmas));
+            }
+            None => {
                 // TODO: Error handling
                 tracing::error!(
                     "Expected DeclarationSpecifiers node while lowering function declaration"
@@ -1256,80 +1369,86 @@ impl Tree {
             }
         }
 
-        let declarator = self.get_declarator();
-
-        if let Child::Tree(direct_declarator) = &declarator.children[0] {
-            if direct_declarator.kind == TreeKind::DirectDeclarator {
-                // Get the function name
-                if let Child::Token(token) = &direct_declarator.children[0] {
-                    if token.kind == TokenKind::IDENTIFIER {
-                        tracing::trace!(
-                            "{}",
-                            &format!(
-                                "{} Lowering {}@{} to {} {} {}{}{}",
-                                "PARSER".yellow(),
-                                token.kind.to_string().blue(),
-                                token.span().to_string().black().italic(),
-                                "FunctionName".cyan(),
-                                "-".red(),
-                                " ".yellow().on_black(),
-                                token.lexeme.green().on_black(),
-                                " ".yellow().on_black(),
-                            )
-                        );
-                        name = token.lexeme.clone();
-                    } else {
-                        // TODO: Error handling
-                        tracing::error!(
-                            "Expected Identifier token while lowering function declaration"
-                        );
-                    }
-                }
-
-                // Get the function parameters (if any)
-                if let Child::Tree(parameter_type_list) = &direct_declarator.children[2] {
-                    if parameter_type_list.kind == TreeKind::ParamTypeList {
-                        if let Child::Tree(param_list) = &parameter_type_list.children[0] {
-                            for child in &param_list.children {
-                                match child {
-                                    Child::Token(token) => {
-                                        // Ensure that the token is a comma, if not then error
-                                        if token.kind() != &TokenKind::COMMA {
-                                            // TODO: Error handling
-                                            tracing::error!(
-                                                "{}",
-                                                &format!(
-                                                    "  {}  Unexpected node while lowering {}@{} \
-                                                     to {}. Expected {} but found {}",
-                                                    "PARSER".yellow(),
-                                                    token.kind.to_string().green(),
-                                                    token.span.to_string().black().italic(),
-                                                    "Token".cyan(),
-                                                    "COMMA".cyan(),
-                                                    token.kind.to_string().green(),
-                                                )
-                                            );
-                                        }
-                                    }
-                                    Child::Tree(param) => {
-                                        let (param_type, param_name) =
-                                            self.extract_parameter_info(param);
-                                        params.push(Param {
-                                            name: Symbol::from(param_name),
-                                            ty:   param_type,
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+        // Get the function name
+        match function_def.name() {
+            Some(token) => {
+                tracing::trace!(
+                    "{}",
+                    &format!(
+                        "{} Lowering {}@{} to {} {} {}{}{}",
+                        "PARSER".yellow(),
+                        token.kind.to_string().blue(),
+                        token.span().to_string().black().italic(),
+                        "FunctionName".cyan(),
+                        "-".red(),
+                        " ".yellow().on_black(),
+                        token.lexeme.green().on_black(),
+                        " ".yellow().on_black(),
+                    )
+                );
+                name = token.lexeme;
+            }
+            None => {
+                // TODO: Error handling
+                tracing::error!("Expected Identifier token while lowering function declaration");
             }
         }
 
+        // Get the function parameters (if any)
+        for param in function_def.params() {
+            let (param_type, param_name) = self.extract_parameter_info(param, pack_pragmas);
+            params.push(Param { name: param_name, ty: param_type });
+        }
+
+        // An old-style definition names its parameters in an identifier list
+        // and declares them before its body, e.g. `int f(a, b) int a; char
+        // *b; { ... }`. Parameters which aren't declared are `int`.
+        let declared = old_style_declarations(function_def, pack_pragmas);
+        for identifier in function_def.identifier_list().into_iter().flat_map(|it| it.identifiers())
+        {
+            let ty = declared
+                .iter()
+                .find(|(declarator, _)| declarator.lexeme == identifier.lexeme)
+                .map_or(DataType::Int, |(_, ty)| ty.clone());
+            params.push(Param { name: identifier.lexeme, ty });
+        }
+
         (return_type, params, name)
     }
 
+    /// Checks the declaration list of an old-style definition against its
+    /// identifier list, and warns that the style is obsolescent.
+    fn check_old_style_parameters(&self, diagnostics: &mut DiagnosticsEngine) {
+        let Some(function_def) = nodes::FunctionDef::cast(self) else {
+            return;
+        };
+        let identifiers = function_def
+            .identifier_list()
+            .into_iter()
+            .flat_map(|it| it.identifiers())
+            .collect::<Vec<_>>();
+        if identifiers.is_empty() && function_def.declaration_list().is_none() {
+            return;
+        }
+
+        if let Some(name) = function_def.name() {
+            diagnostics.emit(diagnostics::old_style_function_definition(self.file_id, name));
+        }
+
+        let declared = old_style_declarations(function_def, &[]);
+        for (declarator, _) in &declared {
+            if !identifiers.iter().any(|identifier| identifier.lexeme == declarator.lexeme) {
+                diagnostics
+                    .emit(diagnostics::undeclared_old_style_parameter(self.file_id, declarator));
+            }
+        }
+        for identifier in identifiers {
+            if !declared.iter().any(|(declarator, _)| declarator.lexeme == identifier.lexeme) {
+                diagnostics.emit(diagnostics::implicit_int_parameter(self.file_id, identifier));
+            }
+        }
+    }
+
     // fn extract_function_signature(&self) -> (Box<DataType>, Vec<Param>, String) {
     //     let mut name = String::new();
     //     let mut params = vec![];
@@ -1359,7 +1478,7 @@ impl Tree {
     //             // Get the function name
     //             if let Child::Token(token) = &direct_declarator.children[0] {
     //                 if token.kind == TokenKind::IDENTIFIER {
-    //                     name = token.lexeme.clone();
+    //                     name = token.lexeme;
     //                 } else {
     //                     // TODO: Error handling
     //                     tracing::error!(
@@ -1401,13 +1520,6 @@ impl Tree {
     //     (return_type, params, name)
     // }
 
-    fn get_declarator(&self) -> &Tree {
-        match &self.children[1] {
-            Child::Tree(tree) if tree.kind == TreeKind::Declarator => tree,
-            _ => panic!("Expected Declarator node in function declaration"),
-        }
-    }
-
     // fn extract_parameter_info(&self, param: &Tree) -> (DataType, String) {
     //     let mut param_type = DataType::Int;
     //     let mut param_name = String::new();
@@ -1423,7 +1535,7 @@ impl Tree {
 
     //         if let Child::Tree(direct_declarator) = &param.children[1] {
     //             if let Child::Token(token) = &direct_declarator.children[0] {
-    //                 param_name = token.lexeme.clone();
+    //                 param_name = token.lexeme;
     //             }
     //         }
     //     }
@@ -1431,33 +1543,33 @@ impl Tree {
     //     (param_type, param_name)
     // }
 
+    fn extract_function_attributes(&self) -> Vec<Attribute> {
+        let specifiers = nodes::FunctionDef::cast(self)
+            .and_then(|function_def| function_def.declaration_specifiers());
+
+        lower_attributes(specifiers.into_iter().flat_map(|it| it.attributes()))
+    }
+
     fn extract_function_body(&self) -> Statement {
-        // The function body should be the last child of the FunctionDef node.
-
-        // The last child should be a Tree node with kind CompoundStatement.
-        if let Some(last_child) = self.children.last() {
-            if let Child::Tree(last_tree) = last_child {
-                if last_tree.kind == TreeKind::CompoundStatement {
-                    // println!("last_tree {:#?}", last_tree);
-                    return last_tree.extract_compound_statement();
-                }
+        match nodes::FunctionDef::cast(self).and_then(|function_def| function_def.body()) {
+            Some(body) => body.syntax().extract_compound_statement(),
+            None => {
+                tracing::error!(
+                    "{}",
+                    &format!(
+                        "  {}  Unexpected node while lowering {}@{} to {}. Expected {} but \
+                         found none",
+                        "PARSER".yellow(),
+                        self.kind.to_string().green(),
+                        self.range.to_string().black().italic(),
+                        "FunctionDef".cyan(),
+                        "CompoundStatement".cyan(),
+                    )
+                );
+
+                Statement::new(StatementKind::Compound(Block::from(vec![])), self.range)
             }
-        } else {
-            tracing::error!(
-                "{}",
-                &format!(
-                    "  {}  Unexpected node while lowering {}@{} to {}. Expected {} but found {}",
-                    "PARSER".yellow(),
-                    self.kind.to_string().green(),
-                    self.range.to_string().black().italic(),
-                    "FunctionDef".cyan(),
-                    "CompoundStatement".cyan(),
-                    self.kind.to_string().green(),
-                )
-            );
         }
-
-        Statement::Return(Expr::Literal(Literal::IntegerConstant(0)))
     }
 
     // This function recursively extracts a compound statement.
@@ -1467,39 +1579,18 @@ impl Tree {
         // : '{' '}'
         // | '{'  block_item_list '}'
         // ;
-        let mut statements = Vec::<Statement>::new();
-
-        // println!("extract_compound_statement {:#?}", self);
-
-        // if let Some(
-
-        let mut compound_statement_seen = false;
-
-        // println!("compound_statement children {:#?}", self.children);
-
-        // Expect that the first child of the compound statement is
-        // a left brace token.
-        if let Child::Token(token) = &self.children[0] {
-            if token.kind() != &TokenKind::LBRACE {
-                // TODO: Error handling
-            }
-        }
-
-        for child in &self.children {
-            // Check if the current child is a Statement.
-            if let Child::Tree(child) = child {
-                // Process the block item list, if anyy.
-                if child.kind == TreeKind::BlockItemList {
-                    // let statement = child.extract_block_item_list();
-                    // statements.push(statement);
-                    compound_statement_seen = true;
-                }
-            }
-        }
-
-        // Create and return a compound statement with the extracted statements.
-        // todo!("Implement compound statement extraction")
-        Statement::Compound(Block::from(statements))
+        //
+        // Declarations among the block items aren't lowered yet, and are
+        // left out.
+        let statements = nodes::CompoundStatement::cast(self)
+            .and_then(|it| it.block_item_list())
+            .into_iter()
+            .flat_map(|it| it.block_items())
+            .filter_map(|it| it.statement())
+            .flat_map(lower_statement)
+            .collect::<Vec<_>>();
+
+        Statement::new(StatementKind::Compound(Block::from(statements)), self.range)
     }
 
     // fn transform_function(&self) -> Function {
@@ -1571,7 +1662,7 @@ impl Tree {
     //                             " ".yellow().on_black(),
     //                         )
     //                     );
-    //                     name = token.lexeme.clone();
+    //                     name = token.lexeme;
     //                 } else {
     //                     // TODO: Error handling
     //                     tracing::error!(
@@ -1592,7 +1683,7 @@ impl Tree {
     //                             // Get the parameters
     //                             for child in &param_list.children {
     //                                 let mut param_type = DataType::Int;
-    //                                 let mut param_name = String::new();
+    //                                 let mut param_name = Symbol::default();
 
     //                                 if let Child::Tree(param) = child {
     //                                     if param.kind ==
@@ -1729,47 +1820,543 @@ impl Tree {
         }
     }
 
-    fn transform_type(&self) -> DataType {
-        // Extract the type specifier from the parse tree
-        let type_specifier = &self.children[0];
-
-        if let Child::Token(type_specifier) = type_specifier {
-            // println!("type_specifier {:#?}", type_specifier);
-            // Match the type specifier to determine the data type
-            match type_specifier.kind {
-                TokenKind::CHAR_KW => DataType::Char,
-                TokenKind::INT_KW => DataType::Int,
-                TokenKind::FLOAT_KW => DataType::Float,
-                TokenKind::DOUBLE_KW => DataType::Double,
-                // Add more cases for other data types as needed
-                _ => DataType::Unknown, // Handle unknown or unsupported data types
-            }
-        } else {
-            DataType::Unknown
+    fn transform_storage_class(&self) -> Option<StorageClass> {
+        let keyword = nodes::StorageClassSpecifier::cast(self).and_then(|it| it.keyword());
+
+        match keyword.map(|it| it.kind) {
+            Some(TokenKind::TYPEDEF_KW) => Some(StorageClass::Typedef),
+            Some(TokenKind::EXTERN_KW) => Some(StorageClass::Extern),
+            Some(TokenKind::STATIC_KW) => Some(StorageClass::Static),
+            Some(TokenKind::THREAD_LOCAL_KW) => Some(StorageClass::ThreadLocal),
+            Some(TokenKind::AUTO_KW) => Some(StorageClass::Auto),
+            Some(TokenKind::REGISTER_KW) => Some(StorageClass::Register),
+            Some(TokenKind::CONSTEXPR_KW) => Some(StorageClass::Constexpr),
+            _ => None,
         }
     }
 
     fn transform_declaration_with_diagnostics(
         &self,
-        diagnostics: &mut DiagnosticsEngine,
+        _diagnostics: &mut DiagnosticsEngine,
+        pack_pragmas: &[(usize, PackPragma)],
     ) -> Declaration {
-        // println!("transform_declaration_with_diagnostics {:#?}", self);
-        todo!()
-    }
+        let declaration = nodes::Declaration::cast(self);
+        let specifiers = declaration.and_then(|it| it.declaration_specifiers());
+        // TODO: lower every declarator of e.g. `int x, *y;`, not just the first
+        let init_declarator = declaration
+            .and_then(|it| it.init_declarator_list())
+            .and_then(|it| it.init_declarators().next());
+        let declarator = init_declarator.and_then(|it| it.declarator());
+
+        let base_type = lower_type_specifiers(
+            specifiers.into_iter().flat_map(|it| it.type_specifiers()),
+            pack_pragmas,
+        );
+        let ty = match declarator {
+            Some(declarator) => lower_declarator_type(base_type.clone(), declarator),
+            None => base_type.clone(),
+        };
 
-    fn extract_statement(&self) -> Statement {
-        todo!()
-    }
+        let mut declaration_specifiers = specifiers
+            .into_iter()
+            .flat_map(|it| it.storage_class_specifiers())
+            .filter_map(|it| it.syntax().transform_storage_class())
+            .map(DeclarationSpecifier::StorageClass)
+            .collect::<Vec<_>>();
+        declaration_specifiers.push(DeclarationSpecifier::Type(base_type));
+
+        let name = declarator
+            .and_then(|it| it.direct_declarator())
+            .and_then(|it| it.name())
+            .map_or_else(Symbol::default, |token| token.lexeme);
+
+        let attributes = lower_attributes(
+            specifiers
+                .into_iter()
+                .flat_map(|it| it.attributes())
+                .chain(init_declarator.into_iter().flat_map(|it| it.attributes())),
+        );
+
+        // Adjacent string literals are concatenated, as in `__asm__("" "f")`.
+        let asm_label = init_declarator.and_then(|it| it.asm_label()).map(|asm_label| {
+            asm_label
+                .strings()
+                .filter_map(|string| string.value())
+                .map(|token| token.lexeme.as_str().trim_matches('"'))
+                .collect::<String>()
+        });
 
-    fn extract_block_item_list(&self) -> Statement {
-        todo!()
+        tracing::trace!(
+            "{}",
+            &format!(
+                "{} Lowering {}@{} to {} {} {}{}{}",
+                "PARSER".yellow(),
+                "Declaration".green(),
+                self.range.to_string().black().italic(),
+                "Declaration".cyan(),
+                "-".red(),
+                " ".yellow(),
+                name.green(),
+                " ".yellow(),
+            )
+        );
+
+        Declaration {
+            specifiers: declaration_specifiers,
+            ty,
+            var:        name,
+            attributes,
+            asm_label,
+        }
     }
 
+    #[allow(dead_code)]
     pub(crate) fn is_function(&self) -> bool {
         self.kind == TreeKind::FunctionDef
     }
 }
 
+/// The parameters declared before the body of an old-style definition, with
+/// their types, e.g. `a` as `int` and `b` as `char *` in `int f(a, b) int a;
+/// char *b; { ... }`.
+fn old_style_declarations<'t>(
+    function_def: nodes::FunctionDef<'t>,
+    pack_pragmas: &[(usize, PackPragma)],
+) -> Vec<(&'t Token, DataType)> {
+    function_def
+        .declaration_list()
+        .into_iter()
+        .flat_map(|it| it.declarations())
+        .flat_map(|declaration| {
+            let specifiers = declaration.declaration_specifiers();
+            let base_type = lower_type_specifiers(
+                specifiers.into_iter().flat_map(|it| it.type_specifiers()),
+                pack_pragmas,
+            );
+
+            declaration
+                .init_declarator_list()
+                .into_iter()
+                .flat_map(|it| it.init_declarators())
+                .filter_map(|it| it.declarator())
+                .filter_map(move |declarator| {
+                    let name = declarator.direct_declarator()?.name()?;
+                    Some((name, lower_declarator_type(base_type.clone(), declarator)))
+                })
+        })
+        .collect()
+}
+
+/// Lowers the type specifiers of a declaration, e.g. `unsigned long long`,
+/// to the type they name together. A declaration without any is `int`.
+///
+/// [`DataType`] doesn't tell signed and unsigned types apart, so `signed` and
+/// `unsigned` only matter on their own, where they mean `int`. Typedef names
+/// aren't resolved yet, and are [`DataType::Unknown`].
+fn lower_type_specifiers<'t>(
+    specifiers: impl Iterator<Item = nodes::TypeSpecifier<'t>>,
+    pack_pragmas: &[(usize, PackPragma)],
+) -> DataType {
+    let mut base = None;
+    let mut longs = 0;
+
+    for specifier in specifiers {
+        if let Some(record) = specifier.struct_or_union_specifier() {
+            return DataType::Struct(lower_struct(record, pack_pragmas));
+        }
+        if let Some(enumeration) = specifier.enum_specifier() {
+            return DataType::Enum(lower_enum(enumeration));
+        }
+
+        match specifier.keyword().map(|it| it.kind) {
+            Some(TokenKind::LONG_KW) => longs += 1,
+            Some(TokenKind::SIGNED_KW | TokenKind::UNSIGNED_KW) => {}
+            // A typedef name, `typeof`, `_Atomic(...)` or a complex type.
+            None | Some(TokenKind::COMPLEX_KW | TokenKind::IMAGINARY_KW) => {
+                return DataType::Unknown;
+            }
+            Some(kind) => base = Some(kind),
+        }
+    }
+
+    match (base, longs) {
+        (Some(TokenKind::DOUBLE_KW), 1..) => DataType::LongDouble,
+        (None | Some(TokenKind::INT_KW), 1) => DataType::Long,
+        (None | Some(TokenKind::INT_KW), 2..) => DataType::LongLong,
+        (None | Some(TokenKind::INT_KW), _) => DataType::Int,
+        (Some(TokenKind::VOID_KW), _) => DataType::Void,
+        (Some(TokenKind::CHAR_KW), _) => DataType::Char,
+        (Some(TokenKind::BOOL_KW), _) => DataType::Bool,
+        (Some(TokenKind::SHORT_KW), _) => DataType::Short,
+        (Some(TokenKind::FLOAT_KW), _) => DataType::Float,
+        (Some(TokenKind::DOUBLE_KW), _) => DataType::Double,
+        _ => DataType::Unknown,
+    }
+}
+
+/// The type `declarator` gives `ty`, e.g. an array of 4 pointers to `int`
+/// for `*a[4]` in `int *a[4];`. Parenthesized declarators, as of pointers to
+/// functions, aren't lowered yet.
+fn lower_declarator_type(mut ty: DataType, declarator: nodes::Declarator<'_>) -> DataType {
+    for _ in 0..declarator.pointer().map_or(0, |pointer| pointer.depth()) {
+        ty = DataType::Pointer(Box::new(ty));
+    }
+    let Some(direct_declarator) = declarator.direct_declarator() else {
+        return ty;
+    };
+
+    // The length of each array dimension, e.g. `2` and `3` in `a[2][3]`, or
+    // `None` for an unknown length, as of a flexible array member.
+    let mut lengths = Vec::new();
+    let mut length = None;
+    for child in &direct_declarator.syntax().children {
+        match child {
+            Child::Token(token) if token.kind == TokenKind::LBRACKET => length = None,
+            Child::Token(token) if token.kind == TokenKind::RBRACKET => lengths.push(length),
+            Child::Tree(tree) => {
+                length = constant_value(tree).and_then(|length| usize::try_from(length).ok());
+            }
+            Child::Token(_) => {}
+        }
+    }
+
+    lengths.into_iter().rev().fold(ty, |ty, length| DataType::Array(Box::new(ty), length))
+}
+
+/// Lowers a struct or union and its members. The members are packed if the
+/// struct or union is `__attribute__((packed))`, or if a `#pragma pack` is in
+/// effect where it is defined.
+fn lower_struct(
+    specifier: nodes::StructOrUnionSpecifier<'_>,
+    pack_pragmas: &[(usize, PackPragma)],
+) -> Struct {
+    let union = specifier
+        .struct_or_union()
+        .and_then(|it| it.keyword())
+        .is_some_and(|keyword| keyword.kind == TokenKind::UNION_KW);
+    let members = specifier
+        .struct_declaration_list()
+        .into_iter()
+        .flat_map(|it| it.struct_declarations())
+        .flat_map(|declaration| lower_members(declaration, pack_pragmas))
+        .collect();
+
+    let attributes =
+        lower_attributes(specifier.attribute_specifiers().flat_map(|it| it.attributes()));
+    let packed = attributes
+        .iter()
+        .any(|attribute| matches!(attribute.name.as_str(), "packed" | "__packed__"));
+    let pack = if packed {
+        Some(1)
+    } else {
+        layout::pack_at(pack_pragmas, *specifier.syntax().range.start())
+    };
+
+    Struct {
+        name: specifier.name().map_or_else(String::new, |name| name.lexeme.to_string()),
+        union,
+        members,
+        pack,
+    }
+}
+
+/// Lowers the members declared by a declaration in a struct or union, e.g.
+/// `x` and `y` in `int x, *y;`, or the anonymous struct or union it declares.
+///
+/// Only an `_Alignas` with a constant is lowered, as the alignment of a type
+/// name depends on the target.
+fn lower_members(
+    declaration: nodes::StructDeclaration<'_>,
+    pack_pragmas: &[(usize, PackPragma)],
+) -> Vec<Member> {
+    let specifiers = declaration.specifier_qualifier_list();
+    let ty = lower_type_specifiers(
+        specifiers.into_iter().flat_map(|it| it.type_specifiers()),
+        pack_pragmas,
+    );
+    // The strictest alignment wins, and `_Alignas(0)` has no effect.
+    let alignment = specifiers
+        .into_iter()
+        .flat_map(|it| it.alignment_specifiers())
+        .filter_map(|it| constant_value(it.constant_expression()?.syntax()))
+        .filter_map(|alignment| u64::try_from(alignment).ok())
+        .filter(|alignment| *alignment > 0)
+        .max();
+
+    let Some(declarators) = declaration.struct_declarator_list() else {
+        return match ty {
+            DataType::Struct(ref record) if record.name.is_empty() => {
+                vec![Member { name: None, ty, bit_width: None, alignment }]
+            }
+            _ => Vec::new(),
+        };
+    };
+
+    declarators
+        .struct_declarators()
+        .map(|declarator| {
+            let name = declarator
+                .declarator()
+                .and_then(|it| it.direct_declarator())
+                .and_then(|it| it.name())
+                .map(|name| name.lexeme);
+            let ty = match declarator.declarator() {
+                Some(declarator) => lower_declarator_type(ty.clone(), declarator),
+                None => ty.clone(),
+            };
+            let bit_width = declarator
+                .width()
+                .and_then(|width| constant_value(width.syntax()))
+                .and_then(|width| u32::try_from(width).ok());
+
+            Member { name, ty, bit_width, alignment }
+        })
+        .collect()
+}
+
+fn lower_enum(specifier: nodes::EnumSpecifier<'_>) -> Enum {
+    Enum {
+        name:      specifier.name().map_or_else(Symbol::default, |name| name.lexeme),
+        constants: specifier
+            .enumerator_list()
+            .into_iter()
+            .flat_map(|it| it.enumerators())
+            .filter_map(|it| it.name())
+            .map(|name| name.lexeme)
+            .collect(),
+    }
+}
+
+/// Lowers a statement, e.g. `if (x) return 1;`. A labeled statement lowers
+/// to its label followed by the statement it labels, and an empty statement
+/// lowers to nothing.
+fn lower_statement(statement: nodes::Statement<'_>) -> Vec<Statement> {
+    let span = statement.syntax().range;
+    let kind = match statement.kind() {
+        Some(nodes::StatementKind::LabeledStatement(labeled)) => {
+            let kind = if let Some(label) = labeled.label() {
+                StatementKind::Label(label.lexeme)
+            } else if labeled.case_token().is_some() {
+                let value = labeled.constant_expression();
+                StatementKind::Case(value.map_or(Expr::Unknown, |it| lower_any_expr(it.syntax())))
+            } else {
+                StatementKind::Default
+            };
+            // The span of the label is up to its colon.
+            let end = labeled.colon_token().map_or(*span.end(), |colon| *colon.span.end());
+
+            let mut statements = vec![Statement::new(kind, Span::new(*span.start(), end))];
+            statements.extend(labeled.statement().into_iter().flat_map(lower_statement));
+            return statements;
+        }
+        Some(nodes::StatementKind::CompoundStatement(compound)) => {
+            return vec![compound.syntax().extract_compound_statement()];
+        }
+        Some(nodes::StatementKind::ExpressionStatement(statement)) => {
+            let Some(expression) = statement.expression() else {
+                return Vec::new();
+            };
+            lower_expression_statement(expression)
+        }
+        Some(nodes::StatementKind::SelectionStatement(selection)) => {
+            let condition =
+                selection.condition().map_or(Expr::Unknown, |it| lower_any_expr(it.syntax()));
+            if selection.if_token().is_some() {
+                StatementKind::If {
+                    condition,
+                    then_branch: lower_body(selection.then_branch()),
+                    else_branch: selection.else_branch().map(|it| lower_body(Some(it))),
+                }
+            } else {
+                StatementKind::Switch { condition, body: lower_body(selection.body()) }
+            }
+        }
+        Some(nodes::StatementKind::IterationStatement(iteration)) => {
+            let body = lower_body(iteration.body());
+            if iteration.for_token().is_some() {
+                // Without a declaration, the first expression statement is
+                // the initializer, and the second one the condition.
+                let mut expression_statements = iteration.expression_statements();
+                let initializer = match iteration.declaration() {
+                    Some(_) => None,
+                    None => expression_statements.next(),
+                };
+                let condition = expression_statements.next().and_then(|it| it.expression());
+
+                StatementKind::For {
+                    initializer: initializer
+                        .and_then(|it| it.expression())
+                        .map(|expression| {
+                            let span = expression.syntax().range;
+                            Box::new(Statement::new(lower_expression_statement(expression), span))
+                        }),
+                    condition: condition.map(|it| lower_any_expr(it.syntax())),
+                    // In a `for`, the only expression outside of the
+                    // expression statements is the increment.
+                    increment: iteration.expression().map(|it| lower_any_expr(it.syntax())),
+                    body,
+                }
+            } else {
+                let condition =
+                    iteration.condition().map_or(Expr::Unknown, |it| lower_any_expr(it.syntax()));
+                if iteration.do_token().is_some() {
+                    StatementKind::DoWhile { body, condition }
+                } else {
+                    StatementKind::While { condition, body }
+                }
+            }
+        }
+        Some(nodes::StatementKind::JumpStatement(jump)) => {
+            if jump.goto_token().is_some() {
+                StatementKind::Goto(jump.label().map_or_else(Symbol::default, |it| it.lexeme))
+            } else if jump.continue_token().is_some() {
+                StatementKind::Continue
+            } else if jump.break_token().is_some() {
+                StatementKind::Break
+            } else {
+                StatementKind::Return(jump.expression().map(|it| lower_any_expr(it.syntax())))
+            }
+        }
+        Some(nodes::StatementKind::AsmStatement(_)) => StatementKind::Expression(Expr::Unknown),
+        // A syntax error, which has already been reported.
+        None => return Vec::new(),
+    };
+
+    vec![Statement::new(kind, span)]
+}
+
+/// Lowers the body of an `if`, `switch` or loop, e.g. the block of a `while`.
+/// A block is flattened into the body, which is a list of statements.
+fn lower_body(statement: Option<nodes::Statement<'_>>) -> Vec<Statement> {
+    statement
+        .into_iter()
+        .flat_map(lower_statement)
+        .flat_map(|statement| match statement.kind {
+            StatementKind::Compound(block) => block.statements,
+            _ => vec![statement],
+        })
+        .collect()
+}
+
+/// Lowers the expression of an expression statement, which is an assignment
+/// if it is a plain one, as in `x = 2;`.
+fn lower_expression_statement(expression: nodes::Expression<'_>) -> StatementKind {
+    let assignment = match expression.syntax().children.as_slice() {
+        [Child::Tree(assignment)] => assignment,
+        _ => return StatementKind::Expression(lower_any_expr(expression.syntax())),
+    };
+
+    match assignment.children.as_slice() {
+        [Child::Tree(left), Child::Token(eq), Child::Tree(right)] if eq.kind == TokenKind::EQ => {
+            StatementKind::Assignment(Assignment {
+                left:  Box::new(lower_any_expr(left)),
+                right: Box::new(lower_any_expr(right)),
+            })
+        }
+        _ => StatementKind::Expression(lower_any_expr(assignment)),
+    }
+}
+
+/// Lowers an expression, or to [`Expr::Unknown`] if the AST can't express it
+/// yet.
+fn lower_any_expr(tree: &Tree) -> Expr {
+    lower_expr(tree).unwrap_or(Expr::Unknown)
+}
+
+/// The value of an integer constant expression, e.g. the width of a
+/// bit-field, if it can be computed.
+fn constant_value(tree: &Tree) -> Option<i64> {
+    flow::constant_value(&lower_expr(tree)?)
+}
+
+/// Lowers an expression, or returns `None` if the AST can't express it yet.
+fn lower_expr(tree: &Tree) -> Option<Expr> {
+    if let Some(binary) = nodes::BinaryExpr::cast(tree) {
+        let mut operands = binary.operands();
+        let mut left = lower_expr(operands.next()?.syntax())?;
+        for (op, right) in binary.ops().zip(operands) {
+            let operator = match op.kind {
+                TokenKind::PLUS => BinOp::Add,
+                TokenKind::MINUS => BinOp::Subtract,
+                TokenKind::STAR => BinOp::Multiply,
+                TokenKind::SLASH => BinOp::Divide,
+                TokenKind::PERCENT => BinOp::Modulo,
+                _ => return None,
+            };
+            let right = lower_expr(right.syntax())?;
+            left = Expr::Binary { left: Box::new(left), operator, right: Box::new(right) };
+        }
+
+        return Some(left);
+    }
+
+    if let Some(primary) = nodes::PrimaryExpression::cast(tree) {
+        if let Some(name) = primary.name() {
+            return Some(Expr::Literal(Literal::Identifier(name.lexeme.to_string())));
+        }
+        if let Some(constant) = primary.constant() {
+            let value = constant.value()?;
+            return match value.kind {
+                TokenKind::INTEGER_CONSTANT => integer_value(value.lexeme.as_str())
+                    .map(|value| Expr::Literal(Literal::IntegerConstant(value))),
+                TokenKind::IDENTIFIER => {
+                    Some(Expr::Literal(Literal::Identifier(value.lexeme.to_string())))
+                }
+                _ => None,
+            };
+        }
+        return lower_expr(primary.expression()?.syntax());
+    }
+
+    // Any other level of the precedence chain only wraps a single operand,
+    // unless it has an operator, e.g. of a cast or an assignment.
+    match tree.children.as_slice() {
+        [Child::Tree(operand)] => lower_expr(operand),
+        _ => None,
+    }
+}
+
+/// The value of an integer constant, e.g. `16` for `0x10u`.
+pub(crate) fn integer_value(lexeme: &str) -> Option<i64> {
+    let digits = lexeme.trim_end_matches(['u', 'U', 'l', 'L']).replace('\'', "");
+    let (digits, radix) = if let Some(hex) =
+        digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X"))
+    {
+        (hex, 16)
+    } else if let Some(binary) = digits.strip_prefix("0b").or_else(|| digits.strip_prefix("0B")) {
+        (binary, 2)
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        (&digits[1..], 8)
+    } else {
+        (digits.as_str(), 10)
+    };
+
+    i64::from_str_radix(digits, radix).ok()
+}
+
+/// Lowers GNU and C23 attributes alike. The name of a namespaced C23
+/// attribute keeps its namespace, e.g. `gnu::always_inline`.
+fn lower_attributes<'t>(attributes: impl Iterator<Item = nodes::Attribute<'t>>) -> Vec<Attribute> {
+    attributes
+        .filter_map(|attribute| {
+            let name = match attribute.prefix() {
+                Some(prefix) => {
+                    Symbol::from(format!("{}::{}", prefix.lexeme, attribute.name()?.lexeme))
+                }
+                None => attribute.name()?.lexeme,
+            };
+            let args = attribute
+                .argument_expression_list()
+                .into_iter()
+                .flat_map(|args| args.assignment_expressions())
+                .map(|arg| arg.syntax().text())
+                .collect();
+
+            Some(Attribute { name, args })
+        })
+        .collect()
+}
+
 impl Display for Tree {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut buf = String::new();
@@ -0,0 +1,418 @@
+//! Typed views over the untyped [`Tree`] built by the parser.
+//!
+//! Every non-terminal of `c.ungram` gets a wrapper (e.g. [`FunctionDef`],
+//! [`SelectionStatement`]) with an accessor per field of the rule, so code
+//! walking the CST can ask for `function_def.declarator()` instead of indexing
+//! into `children`. The wrappers themselves live in `generated.rs`, which is
+//! generated from the grammar by the `sourcegen` test; hand-written
+//! conveniences live in this file.
+//!
+//! Accessors never panic: a missing child (e.g. because the parser recovered
+//! from an error) is reported as `None` or as an empty iterator.
+
+#[rustfmt::skip]
+#[allow(clippy::enum_variant_names)]
+mod generated;
+#[cfg(test)]
+mod sourcegen;
+
+pub use generated::*;
+
+use crate::{
+    cst::{
+        Child,
+        Tree,
+        TreeKind,
+    },
+    lexer::{
+        Token,
+        TokenKind,
+    },
+};
+use std::marker::PhantomData;
+
+/// A typed view of a [`Tree`] of a particular [`TreeKind`] (or of one of
+/// several kinds, for enums such as [`Expr`]).
+pub trait CstNode<'t>: Copy {
+    fn can_cast(kind: TreeKind) -> bool;
+
+    fn cast(syntax: &'t Tree) -> Option<Self>;
+
+    fn syntax(&self) -> &'t Tree;
+}
+
+/// An iterator over the children of a [`Tree`] which can be cast to `N`.
+#[derive(Debug, Clone)]
+pub struct CstChildren<'t, N> {
+    inner: std::slice::Iter<'t, Child>,
+    ph:    PhantomData<N>,
+}
+
+impl<'t, N> CstChildren<'t, N> {
+    fn new(parent: &'t Tree) -> Self {
+        Self { inner: parent.children.iter(), ph: PhantomData }
+    }
+}
+
+impl<'t, N: CstNode<'t>> Iterator for CstChildren<'t, N> {
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        self.inner.by_ref().find_map(|child| match child {
+            Child::Tree(tree) => N::cast(tree),
+            Child::Token(_) => None,
+        })
+    }
+}
+
+mod support {
+    use super::{
+        CstChildren,
+        CstNode,
+    };
+    use crate::{
+        cst::{
+            Child,
+            Tree,
+        },
+        lexer::{
+            Token,
+            TokenKind,
+        },
+    };
+
+    pub(super) fn child<'t, N: CstNode<'t>>(parent: &'t Tree) -> Option<N> {
+        children(parent).next()
+    }
+
+    pub(super) fn nth_child<'t, N: CstNode<'t>>(parent: &'t Tree, n: usize) -> Option<N> {
+        children(parent).nth(n)
+    }
+
+    pub(super) fn children<'t, N: CstNode<'t>>(parent: &'t Tree) -> CstChildren<'t, N> {
+        CstChildren::new(parent)
+    }
+
+    pub(super) fn token(parent: &Tree, kind: TokenKind) -> Option<&Token> {
+        token_any(parent, &[kind])
+    }
+
+    pub(super) fn token_any<'t>(parent: &'t Tree, kinds: &[TokenKind]) -> Option<&'t Token> {
+        tokens_any(parent, kinds).next()
+    }
+
+    pub(super) fn tokens_any<'t, 'k>(
+        parent: &'t Tree,
+        kinds: &'k [TokenKind],
+    ) -> impl Iterator<Item = &'t Token> + 'k
+    where
+        't: 'k,
+    {
+        parent.children.iter().filter_map(move |child| match child {
+            Child::Token(token) if kinds.contains(&token.kind) => Some(token),
+            _ => None,
+        })
+    }
+}
+
+impl<'t> FunctionDef<'t> {
+    /// The name of the function, e.g. `main` in `int main(void) { ... }`.
+    pub fn name(&self) -> Option<&'t Token> {
+        self.declarator()?.direct_declarator()?.name()
+    }
+
+    /// The parameters of the function, e.g. `int argc` and `char **argv` in
+    /// `int main(int argc, char **argv) { ... }`.
+    pub fn params(&self) -> impl Iterator<Item = ParameterDeclaration<'t>> {
+        self.declarator()
+            .and_then(|declarator| declarator.direct_declarator())
+            .and_then(|direct_declarator| direct_declarator.param_type_lists().next())
+            .and_then(|param_type_list| param_type_list.param_list())
+            .into_iter()
+            .flat_map(|param_list| param_list.parameter_declarations())
+    }
+
+    /// The parameter names of an old-style definition, e.g. `a, b` in
+    /// `int f(a, b) int a; char *b; { ... }`. Their types are declared by
+    /// the [`DeclarationList`] which follows.
+    pub fn identifier_list(&self) -> Option<IdentifierList<'t>> {
+        self.declarator()?.direct_declarator()?.identifier_lists().next()
+    }
+}
+
+impl<'t> IdentifierList<'t> {
+    /// Every name in the list, e.g. `a` and `b` in `(a, b)`.
+    pub fn identifiers(&self) -> impl Iterator<Item = &'t Token> {
+        support::tokens_any(self.syntax, &[TokenKind::IDENTIFIER])
+    }
+}
+
+impl<'t> DeclarationSpecifiers<'t> {
+    /// `DeclarationSpecifiers` are nested, one specifier per level (e.g.
+    /// `static` and then `int` in `static int x;`). Yields this level and
+    /// every level nested below it.
+    pub fn chain(&self) -> impl Iterator<Item = DeclarationSpecifiers<'t>> {
+        std::iter::successors(Some(*self), |it| it.declaration_specifiers())
+    }
+
+    /// Every type specifier in the chain, e.g. `unsigned` and `long` in
+    /// `static unsigned long x;`.
+    pub fn type_specifiers(&self) -> impl Iterator<Item = TypeSpecifier<'t>> {
+        self.chain().filter_map(|it| it.type_specifier())
+    }
+
+    /// Every storage class specifier in the chain, e.g. `static` in
+    /// `static int x;`.
+    pub fn storage_class_specifiers(&self) -> impl Iterator<Item = StorageClassSpecifier<'t>> {
+        self.chain().filter_map(|it| it.storage_class_specifier())
+    }
+
+    /// Every attribute in the chain, whether GNU or C23, e.g. `noreturn` in
+    /// `__attribute__((noreturn)) void f(void);` or `nodiscard` in
+    /// `[[nodiscard]] int f(void);`.
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute<'t>> {
+        self.chain().flat_map(|it| attributes(it.syntax()))
+    }
+}
+
+impl<'t> SpecifierQualifierList<'t> {
+    /// `SpecifierQualifierList`s are nested like [`DeclarationSpecifiers`].
+    /// Yields this level and every level nested below it.
+    pub fn chain(&self) -> impl Iterator<Item = SpecifierQualifierList<'t>> {
+        std::iter::successors(Some(*self), |it| it.specifier_qualifier_list())
+    }
+
+    /// Every type specifier in the chain, e.g. `unsigned` and `long` in
+    /// `const unsigned long x;`.
+    pub fn type_specifiers(&self) -> impl Iterator<Item = TypeSpecifier<'t>> {
+        self.chain().filter_map(|it| it.type_specifier())
+    }
+
+    /// Every `_Alignas` in the chain.
+    pub fn alignment_specifiers(&self) -> impl Iterator<Item = AlignmentSpecifier<'t>> {
+        self.chain().filter_map(|it| it.alignment_specifier())
+    }
+}
+
+impl<'t> InitDeclarator<'t> {
+    /// Every attribute following the declarator, whether GNU or C23.
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute<'t>> {
+        attributes(self.syntax())
+    }
+}
+
+/// The attributes of every `__attribute__((...))` and `[[...]]` among the
+/// children of `parent`, in source order.
+fn attributes(parent: &Tree) -> impl Iterator<Item = Attribute<'_>> {
+    parent
+        .children
+        .iter()
+        .filter_map(|child| match child {
+            Child::Tree(tree)
+                if matches!(
+                    tree.kind,
+                    TreeKind::AttributeSpecifier | TreeKind::StandardAttributeSpecifier
+                ) =>
+            {
+                Some(support::children(tree))
+            }
+            _ => None,
+        })
+        .flatten()
+}
+
+impl<'t> Attribute<'t> {
+    /// The name of the attribute, e.g. `format` in `format(printf, 1, 2)` or
+    /// `always_inline` in `gnu::always_inline`. Unlike most names, this may
+    /// be a keyword, as in `__const__`.
+    pub fn name(&self) -> Option<&'t Token> {
+        let mut tokens = self.leading_tokens();
+        let first = tokens.next();
+
+        match (tokens.next(), tokens.next(), tokens.next()) {
+            (Some(_), Some(_), Some(name)) => Some(name),
+            _ => first,
+        }
+    }
+
+    /// The namespace of a C23 attribute, e.g. `gnu` in `gnu::always_inline`.
+    pub fn prefix(&self) -> Option<&'t Token> {
+        let mut tokens = self.leading_tokens();
+        let prefix = tokens.next();
+
+        tokens.next().filter(|token| token.kind == TokenKind::COLON).and(prefix)
+    }
+
+    /// The tokens before the arguments, i.e. `name` or `prefix : : name`.
+    fn leading_tokens(&self) -> impl Iterator<Item = &'t Token> {
+        self.syntax()
+            .children
+            .iter()
+            .map_while(|child| match child {
+                Child::Token(token) if token.kind != TokenKind::LPAREN => Some(token),
+                _ => None,
+            })
+    }
+}
+
+impl<'t> Pointer<'t> {
+    /// The number of levels of indirection, e.g. `2` for `**`.
+    pub fn depth(&self) -> usize {
+        std::iter::successors(Some(*self), |it| it.pointer()).count()
+    }
+}
+
+const BINARY_OPS: &[TokenKind] = &[
+    TokenKind::DOUBLEPIPE,
+    TokenKind::DOUBLEAMP,
+    TokenKind::PIPE,
+    TokenKind::CARET,
+    TokenKind::AMP,
+    TokenKind::EQEQ,
+    TokenKind::NE,
+    TokenKind::LT,
+    TokenKind::GT,
+    TokenKind::LE,
+    TokenKind::GE,
+    TokenKind::LSHIFT,
+    TokenKind::RSHIFT,
+    TokenKind::PLUS,
+    TokenKind::MINUS,
+    TokenKind::STAR,
+    TokenKind::SLASH,
+    TokenKind::PERCENT,
+];
+
+impl<'t> BinaryExpr<'t> {
+    /// The first operator of the expression, or `None` if this level of the
+    /// precedence chain only wraps a single operand (e.g. an
+    /// `AdditiveExpression` for `x * y`).
+    ///
+    /// The parser builds left-associative chains flat, so `a + b - c` is one
+    /// `AdditiveExpression` with three operands; see [`BinaryExpr::ops`] and
+    /// [`BinaryExpr::operands`] for those.
+    pub fn op(&self) -> Option<&'t Token> {
+        self.ops().next()
+    }
+
+    pub fn ops(&self) -> impl Iterator<Item = &'t Token> {
+        support::tokens_any(self.syntax(), BINARY_OPS)
+    }
+
+    pub fn operands(&self) -> CstChildren<'t, Expr<'t>> {
+        support::children(self.syntax())
+    }
+
+    pub fn lhs(&self) -> Option<Expr<'t>> {
+        self.operands().next()
+    }
+
+    pub fn rhs(&self) -> Option<Expr<'t>> {
+        self.operands().nth(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions_sorted::assert_eq;
+
+    use super::*;
+    use crate::{
+        diagnostics::DiagnosticsEngine,
+        parser,
+        standard::Standard,
+    };
+
+    fn parse(text: &str) -> Tree {
+        let mut diagnostics = DiagnosticsEngine::new();
+        let file_id = diagnostics.add_file("test.c", text.to_string());
+        parser::parse_with_diagnostics(text, Standard::default(), &mut diagnostics, file_id)
+    }
+
+    fn first_function(tree: &Tree) -> FunctionDef<'_> {
+        let translation_unit = TranslationUnit::cast(tree).unwrap();
+        let extern_decl = translation_unit.extern_decls().next().unwrap();
+        match extern_decl.kind() {
+            Some(ExternDeclKind::FunctionDef(function_def)) => function_def,
+            other => panic!("expected a function definition, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn function_def_accessors() {
+        let tree = parse("static int add(int x, int *y) { return x; }");
+        let function_def = first_function(&tree);
+
+        assert_eq!(function_def.name().unwrap().lexeme, "add");
+        assert!(function_def.declarator().is_some());
+        assert!(function_def.body().is_some());
+        assert_eq!(
+            function_def
+                .declaration_specifiers()
+                .unwrap()
+                .type_specifiers()
+                .map(|it| it.keyword().unwrap().kind)
+                .collect::<Vec<_>>(),
+            vec![TokenKind::INT_KW]
+        );
+
+        let params = function_def
+            .params()
+            .map(|param| {
+                let name = param.direct_declarator().and_then(|it| it.name()).unwrap();
+                (name.lexeme.as_str(), param.pointer().map_or(0, |it| it.depth()))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(params, vec![("x", 0), ("y", 1)]);
+    }
+
+    #[test]
+    fn selection_statement_accessors() {
+        let tree = parse("int f(int x) { if (x) return 1; else return 2; }");
+        let function_def = first_function(&tree);
+
+        let block_item = function_def
+            .body()
+            .and_then(|body| body.block_item_list())
+            .and_then(|list| list.block_items().next())
+            .unwrap();
+        let Some(StatementKind::SelectionStatement(if_stmt)) =
+            block_item.statement().and_then(|it| it.kind())
+        else {
+            panic!("expected a selection statement");
+        };
+
+        assert!(if_stmt.if_token().is_some());
+        assert!(if_stmt.condition().is_some());
+        assert!(if_stmt.then_branch().is_some());
+        assert!(if_stmt.else_branch().is_some());
+        assert_ne!(if_stmt.then_branch(), if_stmt.else_branch());
+    }
+
+    #[test]
+    fn binary_expr_op() {
+        let tree = parse("int f(int a, int b) { return a + b - 1; }");
+
+        fn find(tree: &Tree, kind: TreeKind) -> Option<&Tree> {
+            if tree.kind == kind {
+                return Some(tree);
+            }
+            tree.children.iter().find_map(|child| match child {
+                Child::Tree(tree) => find(tree, kind),
+                Child::Token(_) => None,
+            })
+        }
+
+        let additive = find(&tree, TreeKind::AdditiveExpression).unwrap();
+        let binary = BinaryExpr::cast(additive).unwrap();
+
+        assert_eq!(binary.op().unwrap().kind, TokenKind::PLUS);
+        assert_eq!(
+            binary.ops().map(|op| op.kind).collect::<Vec<_>>(),
+            vec![TokenKind::PLUS, TokenKind::MINUS]
+        );
+        assert_eq!(binary.operands().count(), 3);
+        assert!(matches!(binary.lhs(), Some(Expr::MultiplicativeExpression(_))));
+        assert!(binary.rhs().is_some());
+    }
+}
@@ -0,0 +1,4090 @@
+//! Generated by `cargo test -p rcc sourcegen` from `c.ungram`, do not edit by hand.
+
+use super::{
+    support,
+    CstChildren,
+    CstNode,
+};
+use crate::{
+    cst::{
+        Tree,
+        TreeKind,
+    },
+    lexer::{
+        Token,
+        TokenKind,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TranslationUnit<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> TranslationUnit<'t> {
+    pub fn extern_decls(&self) -> CstChildren<'t, ExternDecl<'t>> {
+        support::children(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for TranslationUnit<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::TranslationUnit
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExternDecl<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> ExternDecl<'t> {
+    pub fn kind(&self) -> Option<ExternDeclKind<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn function_def(&self) -> Option<FunctionDef<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn declaration(&self) -> Option<Declaration<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn static_assert_declaration(&self) -> Option<StaticAssertDeclaration<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for ExternDecl<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::ExternDecl
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternDeclKind<'t> {
+    FunctionDef(FunctionDef<'t>),
+    Declaration(Declaration<'t>),
+    StaticAssertDeclaration(StaticAssertDeclaration<'t>),
+}
+
+impl<'t> CstNode<'t> for ExternDeclKind<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        matches!(
+            kind,
+            TreeKind::FunctionDef
+                | TreeKind::Declaration
+                | TreeKind::StaticAssertDeclaration
+        )
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        let res = match syntax.kind {
+            TreeKind::FunctionDef => ExternDeclKind::FunctionDef(FunctionDef { syntax }),
+            TreeKind::Declaration => ExternDeclKind::Declaration(Declaration { syntax }),
+            TreeKind::StaticAssertDeclaration => ExternDeclKind::StaticAssertDeclaration(StaticAssertDeclaration { syntax }),
+            _ => return None,
+        };
+        Some(res)
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        match self {
+            ExternDeclKind::FunctionDef(it) => it.syntax,
+            ExternDeclKind::Declaration(it) => it.syntax,
+            ExternDeclKind::StaticAssertDeclaration(it) => it.syntax,
+        }
+    }
+}
+
+impl<'t> From<FunctionDef<'t>> for ExternDeclKind<'t> {
+    fn from(node: FunctionDef<'t>) -> ExternDeclKind<'t> {
+        ExternDeclKind::FunctionDef(node)
+    }
+}
+
+impl<'t> From<Declaration<'t>> for ExternDeclKind<'t> {
+    fn from(node: Declaration<'t>) -> ExternDeclKind<'t> {
+        ExternDeclKind::Declaration(node)
+    }
+}
+
+impl<'t> From<StaticAssertDeclaration<'t>> for ExternDeclKind<'t> {
+    fn from(node: StaticAssertDeclaration<'t>) -> ExternDeclKind<'t> {
+        ExternDeclKind::StaticAssertDeclaration(node)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FunctionDef<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> FunctionDef<'t> {
+    pub fn declaration_specifiers(&self) -> Option<DeclarationSpecifiers<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn declarator(&self) -> Option<Declarator<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn declaration_list(&self) -> Option<DeclarationList<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn body(&self) -> Option<CompoundStatement<'t>> {
+        support::nth_child(self.syntax, 0)
+    }
+}
+
+impl<'t> CstNode<'t> for FunctionDef<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::FunctionDef
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Declaration<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> Declaration<'t> {
+    pub fn declaration_specifiers(&self) -> Option<DeclarationSpecifiers<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn init_declarator_list(&self) -> Option<InitDeclaratorList<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn semicolon_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::SEMICOLON)
+    }
+
+    pub fn static_assert_declaration(&self) -> Option<StaticAssertDeclaration<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for Declaration<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::Declaration
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeclarationList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> DeclarationList<'t> {
+    pub fn declarations(&self) -> CstChildren<'t, Declaration<'t>> {
+        support::children(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for DeclarationList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::DeclarationList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaticAssertDeclaration<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> StaticAssertDeclaration<'t> {
+    pub fn static_assert_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::STATIC_ASSERT_KW)
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn constant_expression(&self) -> Option<ConstantExpression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+
+    pub fn message(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::STRING)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+
+    pub fn semicolon_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::SEMICOLON)
+    }
+}
+
+impl<'t> CstNode<'t> for StaticAssertDeclaration<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::StaticAssertDeclaration
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeclarationSpecifiers<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> DeclarationSpecifiers<'t> {
+    pub fn storage_class_specifier(&self) -> Option<StorageClassSpecifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn type_specifier(&self) -> Option<TypeSpecifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn type_qualifier(&self) -> Option<TypeQualifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn function_specifier(&self) -> Option<FunctionSpecifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn alignment_specifier(&self) -> Option<AlignmentSpecifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn attribute_specifier(&self) -> Option<AttributeSpecifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn standard_attribute_specifier(&self) -> Option<StandardAttributeSpecifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn extension_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::EXTENSION_KW)
+    }
+
+    pub fn declaration_specifiers(&self) -> Option<DeclarationSpecifiers<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for DeclarationSpecifiers<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::DeclarationSpecifiers
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageClassSpecifier<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> StorageClassSpecifier<'t> {
+    pub fn keyword(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::TYPEDEF_KW,
+                TokenKind::EXTERN_KW,
+                TokenKind::STATIC_KW,
+                TokenKind::THREAD_LOCAL_KW,
+                TokenKind::AUTO_KW,
+                TokenKind::REGISTER_KW,
+                TokenKind::CONSTEXPR_KW,
+            ],
+        )
+    }
+}
+
+impl<'t> CstNode<'t> for StorageClassSpecifier<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::StorageClassSpecifier
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeSpecifier<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> TypeSpecifier<'t> {
+    pub fn keyword(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::VOID_KW,
+                TokenKind::CHAR_KW,
+                TokenKind::SHORT_KW,
+                TokenKind::INT_KW,
+                TokenKind::LONG_KW,
+                TokenKind::FLOAT_KW,
+                TokenKind::DOUBLE_KW,
+                TokenKind::SIGNED_KW,
+                TokenKind::UNSIGNED_KW,
+                TokenKind::BOOL_KW,
+                TokenKind::COMPLEX_KW,
+                TokenKind::IMAGINARY_KW,
+            ],
+        )
+    }
+
+    pub fn struct_or_union_specifier(&self) -> Option<StructOrUnionSpecifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn enum_specifier(&self) -> Option<EnumSpecifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn atomic_type_specifier(&self) -> Option<AtomicTypeSpecifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn typeof_specifier(&self) -> Option<TypeofSpecifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn typedef_name(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::IDENTIFIER)
+    }
+}
+
+impl<'t> CstNode<'t> for TypeSpecifier<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::TypeSpecifier
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StructOrUnionSpecifier<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> StructOrUnionSpecifier<'t> {
+    pub fn struct_or_union(&self) -> Option<StructOrUnion<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn attribute_specifiers(&self) -> CstChildren<'t, AttributeSpecifier<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn standard_attribute_specifiers(&self) -> CstChildren<'t, StandardAttributeSpecifier<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn name(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::IDENTIFIER)
+    }
+
+    pub fn lbrace_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LBRACE)
+    }
+
+    pub fn struct_declaration_list(&self) -> Option<StructDeclarationList<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn rbrace_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RBRACE)
+    }
+}
+
+impl<'t> CstNode<'t> for StructOrUnionSpecifier<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::StructOrUnionSpecifier
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StructOrUnion<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> StructOrUnion<'t> {
+    pub fn keyword(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::STRUCT_KW,
+                TokenKind::UNION_KW,
+            ],
+        )
+    }
+}
+
+impl<'t> CstNode<'t> for StructOrUnion<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::StructOrUnion
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StructDeclarationList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> StructDeclarationList<'t> {
+    pub fn struct_declarations(&self) -> CstChildren<'t, StructDeclaration<'t>> {
+        support::children(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for StructDeclarationList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::StructDeclarationList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StructDeclaration<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> StructDeclaration<'t> {
+    pub fn specifier_qualifier_list(&self) -> Option<SpecifierQualifierList<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn struct_declarator_list(&self) -> Option<StructDeclaratorList<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn semicolon_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::SEMICOLON)
+    }
+}
+
+impl<'t> CstNode<'t> for StructDeclaration<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::StructDeclaration
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpecifierQualifierList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> SpecifierQualifierList<'t> {
+    pub fn type_specifier(&self) -> Option<TypeSpecifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn type_qualifier(&self) -> Option<TypeQualifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn alignment_specifier(&self) -> Option<AlignmentSpecifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn attribute_specifier(&self) -> Option<AttributeSpecifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn standard_attribute_specifier(&self) -> Option<StandardAttributeSpecifier<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn extension_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::EXTENSION_KW)
+    }
+
+    pub fn specifier_qualifier_list(&self) -> Option<SpecifierQualifierList<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for SpecifierQualifierList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::SpecifierQualifierList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StructDeclaratorList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> StructDeclaratorList<'t> {
+    pub fn struct_declarators(&self) -> CstChildren<'t, StructDeclarator<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+}
+
+impl<'t> CstNode<'t> for StructDeclaratorList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::StructDeclaratorList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StructDeclarator<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> StructDeclarator<'t> {
+    pub fn declarator(&self) -> Option<Declarator<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn colon_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COLON)
+    }
+
+    pub fn width(&self) -> Option<ConstantExpression<'t>> {
+        support::nth_child(self.syntax, 0)
+    }
+
+    pub fn attribute_specifiers(&self) -> CstChildren<'t, AttributeSpecifier<'t>> {
+        support::children(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for StructDeclarator<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::StructDeclarator
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnumSpecifier<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> EnumSpecifier<'t> {
+    pub fn enum_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::ENUM_KW)
+    }
+
+    pub fn name(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::IDENTIFIER)
+    }
+
+    pub fn lbrace_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LBRACE)
+    }
+
+    pub fn enumerator_list(&self) -> Option<EnumeratorList<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn rbrace_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RBRACE)
+    }
+}
+
+impl<'t> CstNode<'t> for EnumSpecifier<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::EnumSpecifier
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnumeratorList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> EnumeratorList<'t> {
+    pub fn enumerators(&self) -> CstChildren<'t, Enumerator<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+}
+
+impl<'t> CstNode<'t> for EnumeratorList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::EnumeratorList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Enumerator<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> Enumerator<'t> {
+    pub fn name(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::IDENTIFIER)
+    }
+
+    pub fn eq_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::EQ)
+    }
+
+    pub fn value(&self) -> Option<ConstantExpression<'t>> {
+        support::nth_child(self.syntax, 0)
+    }
+}
+
+impl<'t> CstNode<'t> for Enumerator<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::Enumerator
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AtomicTypeSpecifier<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> AtomicTypeSpecifier<'t> {
+    pub fn atomic_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::ATOMIC_KW)
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn type_name(&self) -> Option<TypeName<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+}
+
+impl<'t> CstNode<'t> for AtomicTypeSpecifier<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::AtomicTypeSpecifier
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeQualifier<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> TypeQualifier<'t> {
+    pub fn keyword(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::CONST_KW,
+                TokenKind::RESTRICT_KW,
+                TokenKind::VOLATILE_KW,
+                TokenKind::ATOMIC_KW,
+            ],
+        )
+    }
+}
+
+impl<'t> CstNode<'t> for TypeQualifier<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::TypeQualifier
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FunctionSpecifier<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> FunctionSpecifier<'t> {
+    pub fn keyword(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::INLINE_KW,
+                TokenKind::NORETURN_KW,
+            ],
+        )
+    }
+}
+
+impl<'t> CstNode<'t> for FunctionSpecifier<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::FunctionSpecifier
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlignmentSpecifier<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> AlignmentSpecifier<'t> {
+    pub fn alignas_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::ALIGNAS_KW)
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn type_name(&self) -> Option<TypeName<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn constant_expression(&self) -> Option<ConstantExpression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+}
+
+impl<'t> CstNode<'t> for AlignmentSpecifier<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::AlignmentSpecifier
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Declarator<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> Declarator<'t> {
+    pub fn pointer(&self) -> Option<Pointer<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn direct_declarator(&self) -> Option<DirectDeclarator<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for Declarator<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::Declarator
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DirectDeclarator<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> DirectDeclarator<'t> {
+    pub fn name(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::IDENTIFIER)
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn declarator(&self) -> Option<Declarator<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+
+    pub fn lbracket_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LBRACKET)
+    }
+
+    pub fn static_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::STATIC_KW)
+    }
+
+    pub fn type_qualifier_lists(&self) -> CstChildren<'t, TypeQualifierList<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn star_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::STAR)
+    }
+
+    pub fn assignment_expressions(&self) -> CstChildren<'t, AssignmentExpression<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn rbracket_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RBRACKET)
+    }
+
+    pub fn param_type_lists(&self) -> CstChildren<'t, ParamTypeList<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn identifier_lists(&self) -> CstChildren<'t, IdentifierList<'t>> {
+        support::children(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for DirectDeclarator<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::DirectDeclarator
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pointer<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> Pointer<'t> {
+    pub fn star_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::STAR)
+    }
+
+    pub fn type_qualifier_list(&self) -> Option<TypeQualifierList<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn pointer(&self) -> Option<Pointer<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for Pointer<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::Pointer
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeQualifierList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> TypeQualifierList<'t> {
+    pub fn type_qualifiers(&self) -> CstChildren<'t, TypeQualifier<'t>> {
+        support::children(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for TypeQualifierList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::TypeQualifierList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParamTypeList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> ParamTypeList<'t> {
+    pub fn param_list(&self) -> Option<ParamList<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+
+    pub fn ellipsis_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::ELLIPSIS)
+    }
+}
+
+impl<'t> CstNode<'t> for ParamTypeList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::ParamTypeList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParamList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> ParamList<'t> {
+    pub fn parameter_declarations(&self) -> CstChildren<'t, ParameterDeclaration<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+}
+
+impl<'t> CstNode<'t> for ParamList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::ParamList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParameterDeclaration<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> ParameterDeclaration<'t> {
+    pub fn declaration_specifiers(&self) -> Option<DeclarationSpecifiers<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn pointer(&self) -> Option<Pointer<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn direct_declarator(&self) -> Option<DirectDeclarator<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn direct_abstract_declarator(&self) -> Option<DirectAbstractDeclarator<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn attribute_specifiers(&self) -> CstChildren<'t, AttributeSpecifier<'t>> {
+        support::children(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for ParameterDeclaration<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::ParameterDeclaration
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdentifierList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> IdentifierList<'t> {
+    pub fn identifier_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::IDENTIFIER)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+}
+
+impl<'t> CstNode<'t> for IdentifierList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::IdentifierList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeName<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> TypeName<'t> {
+    pub fn specifier_qualifier_list(&self) -> Option<SpecifierQualifierList<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn abstract_declarator(&self) -> Option<AbstractDeclarator<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for TypeName<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::TypeName
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AbstractDeclarator<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> AbstractDeclarator<'t> {
+    pub fn pointer(&self) -> Option<Pointer<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn direct_abstract_declarator(&self) -> Option<DirectAbstractDeclarator<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for AbstractDeclarator<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::AbstractDeclarator
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DirectAbstractDeclarator<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> DirectAbstractDeclarator<'t> {
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn abstract_declarators(&self) -> CstChildren<'t, AbstractDeclarator<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+
+    pub fn lbracket_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LBRACKET)
+    }
+
+    pub fn static_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::STATIC_KW)
+    }
+
+    pub fn type_qualifier_lists(&self) -> CstChildren<'t, TypeQualifierList<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn star_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::STAR)
+    }
+
+    pub fn assignment_expressions(&self) -> CstChildren<'t, AssignmentExpression<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn rbracket_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RBRACKET)
+    }
+
+    pub fn param_type_lists(&self) -> CstChildren<'t, ParamTypeList<'t>> {
+        support::children(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for DirectAbstractDeclarator<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::DirectAbstractDeclarator
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InitDeclaratorList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> InitDeclaratorList<'t> {
+    pub fn init_declarators(&self) -> CstChildren<'t, InitDeclarator<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+}
+
+impl<'t> CstNode<'t> for InitDeclaratorList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::InitDeclaratorList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InitDeclarator<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> InitDeclarator<'t> {
+    pub fn declarator(&self) -> Option<Declarator<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn asm_label(&self) -> Option<AsmLabel<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn attribute_specifiers(&self) -> CstChildren<'t, AttributeSpecifier<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn standard_attribute_specifiers(&self) -> CstChildren<'t, StandardAttributeSpecifier<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn eq_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::EQ)
+    }
+
+    pub fn initializer(&self) -> Option<Initializer<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for InitDeclarator<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::InitDeclarator
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Initializer<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> Initializer<'t> {
+    pub fn assignment_expression(&self) -> Option<AssignmentExpression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn lbrace_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LBRACE)
+    }
+
+    pub fn initializer_list(&self) -> Option<InitializerList<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+
+    pub fn rbrace_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RBRACE)
+    }
+}
+
+impl<'t> CstNode<'t> for Initializer<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::Initializer
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InitializerList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> InitializerList<'t> {
+    pub fn designations(&self) -> CstChildren<'t, Designation<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn initializers(&self) -> CstChildren<'t, Initializer<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+}
+
+impl<'t> CstNode<'t> for InitializerList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::InitializerList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Designation<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> Designation<'t> {
+    pub fn designator_list(&self) -> Option<DesignatorList<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn eq_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::EQ)
+    }
+}
+
+impl<'t> CstNode<'t> for Designation<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::Designation
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DesignatorList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> DesignatorList<'t> {
+    pub fn designators(&self) -> CstChildren<'t, Designator<'t>> {
+        support::children(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for DesignatorList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::DesignatorList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Designator<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> Designator<'t> {
+    pub fn lbracket_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LBRACKET)
+    }
+
+    pub fn constant_expression(&self) -> Option<ConstantExpression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn rbracket_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RBRACKET)
+    }
+
+    pub fn dot_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::DOT)
+    }
+
+    pub fn name(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::IDENTIFIER)
+    }
+}
+
+impl<'t> CstNode<'t> for Designator<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::Designator
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Statement<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> Statement<'t> {
+    pub fn kind(&self) -> Option<StatementKind<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn labeled_statement(&self) -> Option<LabeledStatement<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn compound_statement(&self) -> Option<CompoundStatement<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn expression_statement(&self) -> Option<ExpressionStatement<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn selection_statement(&self) -> Option<SelectionStatement<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn iteration_statement(&self) -> Option<IterationStatement<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn jump_statement(&self) -> Option<JumpStatement<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn asm_statement(&self) -> Option<AsmStatement<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for Statement<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::Statement
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatementKind<'t> {
+    LabeledStatement(LabeledStatement<'t>),
+    CompoundStatement(CompoundStatement<'t>),
+    ExpressionStatement(ExpressionStatement<'t>),
+    SelectionStatement(SelectionStatement<'t>),
+    IterationStatement(IterationStatement<'t>),
+    JumpStatement(JumpStatement<'t>),
+    AsmStatement(AsmStatement<'t>),
+}
+
+impl<'t> CstNode<'t> for StatementKind<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        matches!(
+            kind,
+            TreeKind::LabeledStatement
+                | TreeKind::CompoundStatement
+                | TreeKind::ExpressionStatement
+                | TreeKind::SelectionStatement
+                | TreeKind::IterationStatement
+                | TreeKind::JumpStatement
+                | TreeKind::AsmStatement
+        )
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        let res = match syntax.kind {
+            TreeKind::LabeledStatement => StatementKind::LabeledStatement(LabeledStatement { syntax }),
+            TreeKind::CompoundStatement => StatementKind::CompoundStatement(CompoundStatement { syntax }),
+            TreeKind::ExpressionStatement => StatementKind::ExpressionStatement(ExpressionStatement { syntax }),
+            TreeKind::SelectionStatement => StatementKind::SelectionStatement(SelectionStatement { syntax }),
+            TreeKind::IterationStatement => StatementKind::IterationStatement(IterationStatement { syntax }),
+            TreeKind::JumpStatement => StatementKind::JumpStatement(JumpStatement { syntax }),
+            TreeKind::AsmStatement => StatementKind::AsmStatement(AsmStatement { syntax }),
+            _ => return None,
+        };
+        Some(res)
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        match self {
+            StatementKind::LabeledStatement(it) => it.syntax,
+            StatementKind::CompoundStatement(it) => it.syntax,
+            StatementKind::ExpressionStatement(it) => it.syntax,
+            StatementKind::SelectionStatement(it) => it.syntax,
+            StatementKind::IterationStatement(it) => it.syntax,
+            StatementKind::JumpStatement(it) => it.syntax,
+            StatementKind::AsmStatement(it) => it.syntax,
+        }
+    }
+}
+
+impl<'t> From<LabeledStatement<'t>> for StatementKind<'t> {
+    fn from(node: LabeledStatement<'t>) -> StatementKind<'t> {
+        StatementKind::LabeledStatement(node)
+    }
+}
+
+impl<'t> From<CompoundStatement<'t>> for StatementKind<'t> {
+    fn from(node: CompoundStatement<'t>) -> StatementKind<'t> {
+        StatementKind::CompoundStatement(node)
+    }
+}
+
+impl<'t> From<ExpressionStatement<'t>> for StatementKind<'t> {
+    fn from(node: ExpressionStatement<'t>) -> StatementKind<'t> {
+        StatementKind::ExpressionStatement(node)
+    }
+}
+
+impl<'t> From<SelectionStatement<'t>> for StatementKind<'t> {
+    fn from(node: SelectionStatement<'t>) -> StatementKind<'t> {
+        StatementKind::SelectionStatement(node)
+    }
+}
+
+impl<'t> From<IterationStatement<'t>> for StatementKind<'t> {
+    fn from(node: IterationStatement<'t>) -> StatementKind<'t> {
+        StatementKind::IterationStatement(node)
+    }
+}
+
+impl<'t> From<JumpStatement<'t>> for StatementKind<'t> {
+    fn from(node: JumpStatement<'t>) -> StatementKind<'t> {
+        StatementKind::JumpStatement(node)
+    }
+}
+
+impl<'t> From<AsmStatement<'t>> for StatementKind<'t> {
+    fn from(node: AsmStatement<'t>) -> StatementKind<'t> {
+        StatementKind::AsmStatement(node)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LabeledStatement<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> LabeledStatement<'t> {
+    pub fn label(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::IDENTIFIER)
+    }
+
+    pub fn case_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::CASE_KW)
+    }
+
+    pub fn constant_expression(&self) -> Option<ConstantExpression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn default_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::DEFAULT_KW)
+    }
+
+    pub fn colon_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COLON)
+    }
+
+    pub fn statement(&self) -> Option<Statement<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for LabeledStatement<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::LabeledStatement
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompoundStatement<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> CompoundStatement<'t> {
+    pub fn lbrace_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LBRACE)
+    }
+
+    pub fn block_item_list(&self) -> Option<BlockItemList<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn rbrace_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RBRACE)
+    }
+}
+
+impl<'t> CstNode<'t> for CompoundStatement<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::CompoundStatement
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockItemList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> BlockItemList<'t> {
+    pub fn block_items(&self) -> CstChildren<'t, BlockItem<'t>> {
+        support::children(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for BlockItemList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::BlockItemList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockItem<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> BlockItem<'t> {
+    pub fn kind(&self) -> Option<BlockItemKind<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn declaration(&self) -> Option<Declaration<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn statement(&self) -> Option<Statement<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for BlockItem<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::BlockItem
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockItemKind<'t> {
+    Declaration(Declaration<'t>),
+    Statement(Statement<'t>),
+}
+
+impl<'t> CstNode<'t> for BlockItemKind<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        matches!(
+            kind,
+            TreeKind::Declaration
+                | TreeKind::Statement
+        )
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        let res = match syntax.kind {
+            TreeKind::Declaration => BlockItemKind::Declaration(Declaration { syntax }),
+            TreeKind::Statement => BlockItemKind::Statement(Statement { syntax }),
+            _ => return None,
+        };
+        Some(res)
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        match self {
+            BlockItemKind::Declaration(it) => it.syntax,
+            BlockItemKind::Statement(it) => it.syntax,
+        }
+    }
+}
+
+impl<'t> From<Declaration<'t>> for BlockItemKind<'t> {
+    fn from(node: Declaration<'t>) -> BlockItemKind<'t> {
+        BlockItemKind::Declaration(node)
+    }
+}
+
+impl<'t> From<Statement<'t>> for BlockItemKind<'t> {
+    fn from(node: Statement<'t>) -> BlockItemKind<'t> {
+        BlockItemKind::Statement(node)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpressionStatement<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> ExpressionStatement<'t> {
+    pub fn expression(&self) -> Option<Expression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn semicolon_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::SEMICOLON)
+    }
+}
+
+impl<'t> CstNode<'t> for ExpressionStatement<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::ExpressionStatement
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelectionStatement<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> SelectionStatement<'t> {
+    pub fn if_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::IF_KW)
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn condition(&self) -> Option<Expression<'t>> {
+        support::nth_child(self.syntax, 0)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+
+    pub fn then_branch(&self) -> Option<Statement<'t>> {
+        support::nth_child(self.syntax, 0)
+    }
+
+    pub fn else_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::ELSE_KW)
+    }
+
+    pub fn else_branch(&self) -> Option<Statement<'t>> {
+        support::nth_child(self.syntax, 1)
+    }
+
+    pub fn switch_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::SWITCH_KW)
+    }
+
+    pub fn body(&self) -> Option<Statement<'t>> {
+        support::nth_child(self.syntax, 0)
+    }
+}
+
+impl<'t> CstNode<'t> for SelectionStatement<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::SelectionStatement
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IterationStatement<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> IterationStatement<'t> {
+    pub fn while_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::WHILE_KW)
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn condition(&self) -> Option<Expression<'t>> {
+        support::nth_child(self.syntax, 0)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+
+    pub fn body(&self) -> Option<Statement<'t>> {
+        support::nth_child(self.syntax, 0)
+    }
+
+    pub fn do_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::DO_KW)
+    }
+
+    pub fn semicolon_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::SEMICOLON)
+    }
+
+    pub fn for_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::FOR_KW)
+    }
+
+    pub fn declaration(&self) -> Option<Declaration<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn expression_statements(&self) -> CstChildren<'t, ExpressionStatement<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn expression(&self) -> Option<Expression<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for IterationStatement<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::IterationStatement
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JumpStatement<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> JumpStatement<'t> {
+    pub fn goto_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::GOTO_KW)
+    }
+
+    pub fn label(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::IDENTIFIER)
+    }
+
+    pub fn semicolon_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::SEMICOLON)
+    }
+
+    pub fn continue_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::CONTINUE_KW)
+    }
+
+    pub fn break_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::BREAK_KW)
+    }
+
+    pub fn return_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RETURN_KW)
+    }
+
+    pub fn expression(&self) -> Option<Expression<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for JumpStatement<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::JumpStatement
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expr<'t> {
+    Expression(Expression<'t>),
+    AssignmentExpression(AssignmentExpression<'t>),
+    ConditionalExpression(ConditionalExpression<'t>),
+    ConstantExpression(ConstantExpression<'t>),
+    LogicalOrExpression(LogicalOrExpression<'t>),
+    LogicalAndExpression(LogicalAndExpression<'t>),
+    InclusiveOrExpression(InclusiveOrExpression<'t>),
+    ExclusiveOrExpression(ExclusiveOrExpression<'t>),
+    AndExpression(AndExpression<'t>),
+    EqualityExpression(EqualityExpression<'t>),
+    RelationalExpression(RelationalExpression<'t>),
+    ShiftExpression(ShiftExpression<'t>),
+    AdditiveExpression(AdditiveExpression<'t>),
+    MultiplicativeExpression(MultiplicativeExpression<'t>),
+    CastExpression(CastExpression<'t>),
+    UnaryExpression(UnaryExpression<'t>),
+    PostfixExpression(PostfixExpression<'t>),
+    PrimaryExpression(PrimaryExpression<'t>),
+}
+
+impl<'t> CstNode<'t> for Expr<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        matches!(
+            kind,
+            TreeKind::Expression
+                | TreeKind::AssignmentExpression
+                | TreeKind::ConditionalExpression
+                | TreeKind::ConstantExpression
+                | TreeKind::LogicalOrExpression
+                | TreeKind::LogicalAndExpression
+                | TreeKind::InclusiveOrExpression
+                | TreeKind::ExclusiveOrExpression
+                | TreeKind::AndExpression
+                | TreeKind::EqualityExpression
+                | TreeKind::RelationalExpression
+                | TreeKind::ShiftExpression
+                | TreeKind::AdditiveExpression
+                | TreeKind::MultiplicativeExpression
+                | TreeKind::CastExpression
+                | TreeKind::UnaryExpression
+                | TreeKind::PostfixExpression
+                | TreeKind::PrimaryExpression
+        )
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        let res = match syntax.kind {
+            TreeKind::Expression => Expr::Expression(Expression { syntax }),
+            TreeKind::AssignmentExpression => Expr::AssignmentExpression(AssignmentExpression { syntax }),
+            TreeKind::ConditionalExpression => Expr::ConditionalExpression(ConditionalExpression { syntax }),
+            TreeKind::ConstantExpression => Expr::ConstantExpression(ConstantExpression { syntax }),
+            TreeKind::LogicalOrExpression => Expr::LogicalOrExpression(LogicalOrExpression { syntax }),
+            TreeKind::LogicalAndExpression => Expr::LogicalAndExpression(LogicalAndExpression { syntax }),
+            TreeKind::InclusiveOrExpression => Expr::InclusiveOrExpression(InclusiveOrExpression { syntax }),
+            TreeKind::ExclusiveOrExpression => Expr::ExclusiveOrExpression(ExclusiveOrExpression { syntax }),
+            TreeKind::AndExpression => Expr::AndExpression(AndExpression { syntax }),
+            TreeKind::EqualityExpression => Expr::EqualityExpression(EqualityExpression { syntax }),
+            TreeKind::RelationalExpression => Expr::RelationalExpression(RelationalExpression { syntax }),
+            TreeKind::ShiftExpression => Expr::ShiftExpression(ShiftExpression { syntax }),
+            TreeKind::AdditiveExpression => Expr::AdditiveExpression(AdditiveExpression { syntax }),
+            TreeKind::MultiplicativeExpression => Expr::MultiplicativeExpression(MultiplicativeExpression { syntax }),
+            TreeKind::CastExpression => Expr::CastExpression(CastExpression { syntax }),
+            TreeKind::UnaryExpression => Expr::UnaryExpression(UnaryExpression { syntax }),
+            TreeKind::PostfixExpression => Expr::PostfixExpression(PostfixExpression { syntax }),
+            TreeKind::PrimaryExpression => Expr::PrimaryExpression(PrimaryExpression { syntax }),
+            _ => return None,
+        };
+        Some(res)
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        match self {
+            Expr::Expression(it) => it.syntax,
+            Expr::AssignmentExpression(it) => it.syntax,
+            Expr::ConditionalExpression(it) => it.syntax,
+            Expr::ConstantExpression(it) => it.syntax,
+            Expr::LogicalOrExpression(it) => it.syntax,
+            Expr::LogicalAndExpression(it) => it.syntax,
+            Expr::InclusiveOrExpression(it) => it.syntax,
+            Expr::ExclusiveOrExpression(it) => it.syntax,
+            Expr::AndExpression(it) => it.syntax,
+            Expr::EqualityExpression(it) => it.syntax,
+            Expr::RelationalExpression(it) => it.syntax,
+            Expr::ShiftExpression(it) => it.syntax,
+            Expr::AdditiveExpression(it) => it.syntax,
+            Expr::MultiplicativeExpression(it) => it.syntax,
+            Expr::CastExpression(it) => it.syntax,
+            Expr::UnaryExpression(it) => it.syntax,
+            Expr::PostfixExpression(it) => it.syntax,
+            Expr::PrimaryExpression(it) => it.syntax,
+        }
+    }
+}
+
+impl<'t> From<Expression<'t>> for Expr<'t> {
+    fn from(node: Expression<'t>) -> Expr<'t> {
+        Expr::Expression(node)
+    }
+}
+
+impl<'t> From<AssignmentExpression<'t>> for Expr<'t> {
+    fn from(node: AssignmentExpression<'t>) -> Expr<'t> {
+        Expr::AssignmentExpression(node)
+    }
+}
+
+impl<'t> From<ConditionalExpression<'t>> for Expr<'t> {
+    fn from(node: ConditionalExpression<'t>) -> Expr<'t> {
+        Expr::ConditionalExpression(node)
+    }
+}
+
+impl<'t> From<ConstantExpression<'t>> for Expr<'t> {
+    fn from(node: ConstantExpression<'t>) -> Expr<'t> {
+        Expr::ConstantExpression(node)
+    }
+}
+
+impl<'t> From<LogicalOrExpression<'t>> for Expr<'t> {
+    fn from(node: LogicalOrExpression<'t>) -> Expr<'t> {
+        Expr::LogicalOrExpression(node)
+    }
+}
+
+impl<'t> From<LogicalAndExpression<'t>> for Expr<'t> {
+    fn from(node: LogicalAndExpression<'t>) -> Expr<'t> {
+        Expr::LogicalAndExpression(node)
+    }
+}
+
+impl<'t> From<InclusiveOrExpression<'t>> for Expr<'t> {
+    fn from(node: InclusiveOrExpression<'t>) -> Expr<'t> {
+        Expr::InclusiveOrExpression(node)
+    }
+}
+
+impl<'t> From<ExclusiveOrExpression<'t>> for Expr<'t> {
+    fn from(node: ExclusiveOrExpression<'t>) -> Expr<'t> {
+        Expr::ExclusiveOrExpression(node)
+    }
+}
+
+impl<'t> From<AndExpression<'t>> for Expr<'t> {
+    fn from(node: AndExpression<'t>) -> Expr<'t> {
+        Expr::AndExpression(node)
+    }
+}
+
+impl<'t> From<EqualityExpression<'t>> for Expr<'t> {
+    fn from(node: EqualityExpression<'t>) -> Expr<'t> {
+        Expr::EqualityExpression(node)
+    }
+}
+
+impl<'t> From<RelationalExpression<'t>> for Expr<'t> {
+    fn from(node: RelationalExpression<'t>) -> Expr<'t> {
+        Expr::RelationalExpression(node)
+    }
+}
+
+impl<'t> From<ShiftExpression<'t>> for Expr<'t> {
+    fn from(node: ShiftExpression<'t>) -> Expr<'t> {
+        Expr::ShiftExpression(node)
+    }
+}
+
+impl<'t> From<AdditiveExpression<'t>> for Expr<'t> {
+    fn from(node: AdditiveExpression<'t>) -> Expr<'t> {
+        Expr::AdditiveExpression(node)
+    }
+}
+
+impl<'t> From<MultiplicativeExpression<'t>> for Expr<'t> {
+    fn from(node: MultiplicativeExpression<'t>) -> Expr<'t> {
+        Expr::MultiplicativeExpression(node)
+    }
+}
+
+impl<'t> From<CastExpression<'t>> for Expr<'t> {
+    fn from(node: CastExpression<'t>) -> Expr<'t> {
+        Expr::CastExpression(node)
+    }
+}
+
+impl<'t> From<UnaryExpression<'t>> for Expr<'t> {
+    fn from(node: UnaryExpression<'t>) -> Expr<'t> {
+        Expr::UnaryExpression(node)
+    }
+}
+
+impl<'t> From<PostfixExpression<'t>> for Expr<'t> {
+    fn from(node: PostfixExpression<'t>) -> Expr<'t> {
+        Expr::PostfixExpression(node)
+    }
+}
+
+impl<'t> From<PrimaryExpression<'t>> for Expr<'t> {
+    fn from(node: PrimaryExpression<'t>) -> Expr<'t> {
+        Expr::PrimaryExpression(node)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryExpr<'t> {
+    LogicalOrExpression(LogicalOrExpression<'t>),
+    LogicalAndExpression(LogicalAndExpression<'t>),
+    InclusiveOrExpression(InclusiveOrExpression<'t>),
+    ExclusiveOrExpression(ExclusiveOrExpression<'t>),
+    AndExpression(AndExpression<'t>),
+    EqualityExpression(EqualityExpression<'t>),
+    RelationalExpression(RelationalExpression<'t>),
+    ShiftExpression(ShiftExpression<'t>),
+    AdditiveExpression(AdditiveExpression<'t>),
+    MultiplicativeExpression(MultiplicativeExpression<'t>),
+}
+
+impl<'t> CstNode<'t> for BinaryExpr<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        matches!(
+            kind,
+            TreeKind::LogicalOrExpression
+                | TreeKind::LogicalAndExpression
+                | TreeKind::InclusiveOrExpression
+                | TreeKind::ExclusiveOrExpression
+                | TreeKind::AndExpression
+                | TreeKind::EqualityExpression
+                | TreeKind::RelationalExpression
+                | TreeKind::ShiftExpression
+                | TreeKind::AdditiveExpression
+                | TreeKind::MultiplicativeExpression
+        )
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        let res = match syntax.kind {
+            TreeKind::LogicalOrExpression => BinaryExpr::LogicalOrExpression(LogicalOrExpression { syntax }),
+            TreeKind::LogicalAndExpression => BinaryExpr::LogicalAndExpression(LogicalAndExpression { syntax }),
+            TreeKind::InclusiveOrExpression => BinaryExpr::InclusiveOrExpression(InclusiveOrExpression { syntax }),
+            TreeKind::ExclusiveOrExpression => BinaryExpr::ExclusiveOrExpression(ExclusiveOrExpression { syntax }),
+            TreeKind::AndExpression => BinaryExpr::AndExpression(AndExpression { syntax }),
+            TreeKind::EqualityExpression => BinaryExpr::EqualityExpression(EqualityExpression { syntax }),
+            TreeKind::RelationalExpression => BinaryExpr::RelationalExpression(RelationalExpression { syntax }),
+            TreeKind::ShiftExpression => BinaryExpr::ShiftExpression(ShiftExpression { syntax }),
+            TreeKind::AdditiveExpression => BinaryExpr::AdditiveExpression(AdditiveExpression { syntax }),
+            TreeKind::MultiplicativeExpression => BinaryExpr::MultiplicativeExpression(MultiplicativeExpression { syntax }),
+            _ => return None,
+        };
+        Some(res)
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        match self {
+            BinaryExpr::LogicalOrExpression(it) => it.syntax,
+            BinaryExpr::LogicalAndExpression(it) => it.syntax,
+            BinaryExpr::InclusiveOrExpression(it) => it.syntax,
+            BinaryExpr::ExclusiveOrExpression(it) => it.syntax,
+            BinaryExpr::AndExpression(it) => it.syntax,
+            BinaryExpr::EqualityExpression(it) => it.syntax,
+            BinaryExpr::RelationalExpression(it) => it.syntax,
+            BinaryExpr::ShiftExpression(it) => it.syntax,
+            BinaryExpr::AdditiveExpression(it) => it.syntax,
+            BinaryExpr::MultiplicativeExpression(it) => it.syntax,
+        }
+    }
+}
+
+impl<'t> From<LogicalOrExpression<'t>> for BinaryExpr<'t> {
+    fn from(node: LogicalOrExpression<'t>) -> BinaryExpr<'t> {
+        BinaryExpr::LogicalOrExpression(node)
+    }
+}
+
+impl<'t> From<LogicalAndExpression<'t>> for BinaryExpr<'t> {
+    fn from(node: LogicalAndExpression<'t>) -> BinaryExpr<'t> {
+        BinaryExpr::LogicalAndExpression(node)
+    }
+}
+
+impl<'t> From<InclusiveOrExpression<'t>> for BinaryExpr<'t> {
+    fn from(node: InclusiveOrExpression<'t>) -> BinaryExpr<'t> {
+        BinaryExpr::InclusiveOrExpression(node)
+    }
+}
+
+impl<'t> From<ExclusiveOrExpression<'t>> for BinaryExpr<'t> {
+    fn from(node: ExclusiveOrExpression<'t>) -> BinaryExpr<'t> {
+        BinaryExpr::ExclusiveOrExpression(node)
+    }
+}
+
+impl<'t> From<AndExpression<'t>> for BinaryExpr<'t> {
+    fn from(node: AndExpression<'t>) -> BinaryExpr<'t> {
+        BinaryExpr::AndExpression(node)
+    }
+}
+
+impl<'t> From<EqualityExpression<'t>> for BinaryExpr<'t> {
+    fn from(node: EqualityExpression<'t>) -> BinaryExpr<'t> {
+        BinaryExpr::EqualityExpression(node)
+    }
+}
+
+impl<'t> From<RelationalExpression<'t>> for BinaryExpr<'t> {
+    fn from(node: RelationalExpression<'t>) -> BinaryExpr<'t> {
+        BinaryExpr::RelationalExpression(node)
+    }
+}
+
+impl<'t> From<ShiftExpression<'t>> for BinaryExpr<'t> {
+    fn from(node: ShiftExpression<'t>) -> BinaryExpr<'t> {
+        BinaryExpr::ShiftExpression(node)
+    }
+}
+
+impl<'t> From<AdditiveExpression<'t>> for BinaryExpr<'t> {
+    fn from(node: AdditiveExpression<'t>) -> BinaryExpr<'t> {
+        BinaryExpr::AdditiveExpression(node)
+    }
+}
+
+impl<'t> From<MultiplicativeExpression<'t>> for BinaryExpr<'t> {
+    fn from(node: MultiplicativeExpression<'t>) -> BinaryExpr<'t> {
+        BinaryExpr::MultiplicativeExpression(node)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Expression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> Expression<'t> {
+    pub fn assignment_expressions(&self) -> CstChildren<'t, AssignmentExpression<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+}
+
+impl<'t> CstNode<'t> for Expression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::Expression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssignmentExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> AssignmentExpression<'t> {
+    pub fn conditional_expression(&self) -> Option<ConditionalExpression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn op(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::EQ,
+                TokenKind::STAREQ,
+                TokenKind::SLASHEQ,
+                TokenKind::PERCENTEQ,
+                TokenKind::PLUSEQ,
+                TokenKind::MINUSEQ,
+                TokenKind::LSHIFTEQ,
+                TokenKind::RSHIFTEQ,
+                TokenKind::AMPEQ,
+                TokenKind::CARETEQ,
+                TokenKind::PIPEEQ,
+            ],
+        )
+    }
+
+    pub fn assignment_expression(&self) -> Option<AssignmentExpression<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for AssignmentExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::AssignmentExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConditionalExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> ConditionalExpression<'t> {
+    pub fn condition(&self) -> Option<LogicalOrExpression<'t>> {
+        support::nth_child(self.syntax, 0)
+    }
+
+    pub fn question_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::QUESTION)
+    }
+
+    pub fn then_branch(&self) -> Option<Expression<'t>> {
+        support::nth_child(self.syntax, 0)
+    }
+
+    pub fn colon_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COLON)
+    }
+
+    pub fn else_branch(&self) -> Option<ConditionalExpression<'t>> {
+        support::nth_child(self.syntax, 0)
+    }
+}
+
+impl<'t> CstNode<'t> for ConditionalExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::ConditionalExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConstantExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> ConstantExpression<'t> {
+    pub fn conditional_expression(&self) -> Option<ConditionalExpression<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for ConstantExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::ConstantExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogicalOrExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> LogicalOrExpression<'t> {
+    pub fn logical_and_expressions(&self) -> CstChildren<'t, LogicalAndExpression<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn op(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::DOUBLEPIPE)
+    }
+}
+
+impl<'t> CstNode<'t> for LogicalOrExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::LogicalOrExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogicalAndExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> LogicalAndExpression<'t> {
+    pub fn inclusive_or_expressions(&self) -> CstChildren<'t, InclusiveOrExpression<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn op(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::DOUBLEAMP)
+    }
+}
+
+impl<'t> CstNode<'t> for LogicalAndExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::LogicalAndExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InclusiveOrExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> InclusiveOrExpression<'t> {
+    pub fn exclusive_or_expressions(&self) -> CstChildren<'t, ExclusiveOrExpression<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn op(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::PIPE)
+    }
+}
+
+impl<'t> CstNode<'t> for InclusiveOrExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::InclusiveOrExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExclusiveOrExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> ExclusiveOrExpression<'t> {
+    pub fn and_expressions(&self) -> CstChildren<'t, AndExpression<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn op(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::CARET)
+    }
+}
+
+impl<'t> CstNode<'t> for ExclusiveOrExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::ExclusiveOrExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AndExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> AndExpression<'t> {
+    pub fn equality_expressions(&self) -> CstChildren<'t, EqualityExpression<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn op(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::AMP)
+    }
+}
+
+impl<'t> CstNode<'t> for AndExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::AndExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EqualityExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> EqualityExpression<'t> {
+    pub fn relational_expressions(&self) -> CstChildren<'t, RelationalExpression<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn op(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::EQEQ,
+                TokenKind::NE,
+            ],
+        )
+    }
+}
+
+impl<'t> CstNode<'t> for EqualityExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::EqualityExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelationalExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> RelationalExpression<'t> {
+    pub fn shift_expressions(&self) -> CstChildren<'t, ShiftExpression<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn op(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::LT,
+                TokenKind::GT,
+                TokenKind::LE,
+                TokenKind::GE,
+            ],
+        )
+    }
+}
+
+impl<'t> CstNode<'t> for RelationalExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::RelationalExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShiftExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> ShiftExpression<'t> {
+    pub fn additive_expressions(&self) -> CstChildren<'t, AdditiveExpression<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn op(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::LSHIFT,
+                TokenKind::RSHIFT,
+            ],
+        )
+    }
+}
+
+impl<'t> CstNode<'t> for ShiftExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::ShiftExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdditiveExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> AdditiveExpression<'t> {
+    pub fn multiplicative_expressions(&self) -> CstChildren<'t, MultiplicativeExpression<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn op(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::PLUS,
+                TokenKind::MINUS,
+            ],
+        )
+    }
+}
+
+impl<'t> CstNode<'t> for AdditiveExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::AdditiveExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MultiplicativeExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> MultiplicativeExpression<'t> {
+    pub fn cast_expressions(&self) -> CstChildren<'t, CastExpression<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn op(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::STAR,
+                TokenKind::SLASH,
+                TokenKind::PERCENT,
+            ],
+        )
+    }
+}
+
+impl<'t> CstNode<'t> for MultiplicativeExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::MultiplicativeExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CastExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> CastExpression<'t> {
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn type_name(&self) -> Option<TypeName<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+
+    pub fn cast_expression(&self) -> Option<CastExpression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn unary_expression(&self) -> Option<UnaryExpression<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for CastExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::CastExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnaryExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> UnaryExpression<'t> {
+    pub fn op(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::INC_OP,
+                TokenKind::DEC_OP,
+            ],
+        )
+    }
+
+    pub fn unary_expression(&self) -> Option<UnaryExpression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn unary_operator(&self) -> Option<UnaryOperator<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn cast_expression(&self) -> Option<CastExpression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn extension_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::EXTENSION_KW)
+    }
+
+    pub fn sizeof_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::SIZEOF_KW)
+    }
+
+    pub fn alignof_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::ALIGNOF_KW)
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn type_name(&self) -> Option<TypeName<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+
+    pub fn postfix_expression(&self) -> Option<PostfixExpression<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for UnaryExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::UnaryExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnaryOperator<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> UnaryOperator<'t> {
+    pub fn op(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::AMP,
+                TokenKind::STAR,
+                TokenKind::PLUS,
+                TokenKind::MINUS,
+                TokenKind::TILDE,
+                TokenKind::BANG,
+            ],
+        )
+    }
+}
+
+impl<'t> CstNode<'t> for UnaryOperator<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::UnaryOperator
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PostfixExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> PostfixExpression<'t> {
+    pub fn primary_expression(&self) -> Option<PrimaryExpression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn lbracket_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LBRACKET)
+    }
+
+    pub fn expressions(&self) -> CstChildren<'t, Expression<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn rbracket_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RBRACKET)
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn argument_expression_lists(&self) -> CstChildren<'t, ArgumentExpressionList<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+
+    pub fn dot_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::DOT)
+    }
+
+    pub fn ptr_op_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::PTR_OP)
+    }
+
+    pub fn field(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::IDENTIFIER)
+    }
+
+    pub fn inc_op_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::INC_OP)
+    }
+
+    pub fn dec_op_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::DEC_OP)
+    }
+}
+
+impl<'t> CstNode<'t> for PostfixExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::PostfixExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArgumentExpressionList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> ArgumentExpressionList<'t> {
+    pub fn assignment_expressions(&self) -> CstChildren<'t, AssignmentExpression<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+}
+
+impl<'t> CstNode<'t> for ArgumentExpressionList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::ArgumentExpressionList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrimaryExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> PrimaryExpression<'t> {
+    pub fn name(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::IDENTIFIER)
+    }
+
+    pub fn constant(&self) -> Option<Constant<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn string(&self) -> Option<String<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn expression(&self) -> Option<Expression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+
+    pub fn statement_expression(&self) -> Option<StatementExpression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn generic_selection(&self) -> Option<GenericSelection<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for PrimaryExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::PrimaryExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenericSelection<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> GenericSelection<'t> {
+    pub fn generic_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::GENERIC_KW)
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn assignment_expression(&self) -> Option<AssignmentExpression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+
+    pub fn generic_assoc_list(&self) -> Option<GenericAssocList<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+}
+
+impl<'t> CstNode<'t> for GenericSelection<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::GenericSelection
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenericAssocList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> GenericAssocList<'t> {
+    pub fn generic_associations(&self) -> CstChildren<'t, GenericAssociation<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+}
+
+impl<'t> CstNode<'t> for GenericAssocList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::GenericAssocList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GenericAssociation<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> GenericAssociation<'t> {
+    pub fn type_name(&self) -> Option<TypeName<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn default_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::DEFAULT_KW)
+    }
+
+    pub fn colon_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COLON)
+    }
+
+    pub fn assignment_expression(&self) -> Option<AssignmentExpression<'t>> {
+        support::child(self.syntax)
+    }
+}
+
+impl<'t> CstNode<'t> for GenericAssociation<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::GenericAssociation
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Constant<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> Constant<'t> {
+    pub fn value(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::INTEGER_CONSTANT,
+                TokenKind::FLOATING_CONSTANT,
+                TokenKind::IDENTIFIER,
+                TokenKind::TRUE_KW,
+                TokenKind::FALSE_KW,
+                TokenKind::NULLPTR_KW,
+            ],
+        )
+    }
+}
+
+impl<'t> CstNode<'t> for Constant<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::Constant
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct String<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> String<'t> {
+    pub fn value(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::STRING,
+                TokenKind::FUNC_NAME_KW,
+            ],
+        )
+    }
+}
+
+impl<'t> CstNode<'t> for String<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::String
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttributeSpecifier<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> AttributeSpecifier<'t> {
+    pub fn attribute_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::ATTRIBUTE_KW)
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn attributes(&self) -> CstChildren<'t, Attribute<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+}
+
+impl<'t> CstNode<'t> for AttributeSpecifier<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::AttributeSpecifier
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attribute<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> Attribute<'t> {
+    pub fn identifier_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::IDENTIFIER)
+    }
+
+    pub fn colon_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COLON)
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn argument_expression_list(&self) -> Option<ArgumentExpressionList<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+}
+
+impl<'t> CstNode<'t> for Attribute<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::Attribute
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AsmLabel<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> AsmLabel<'t> {
+    pub fn asm_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::ASM_KW)
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn strings(&self) -> CstChildren<'t, String<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+}
+
+impl<'t> CstNode<'t> for AsmLabel<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::AsmLabel
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AsmStatement<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> AsmStatement<'t> {
+    pub fn asm_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::ASM_KW)
+    }
+
+    pub fn volatile_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::VOLATILE_KW)
+    }
+
+    pub fn inline_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::INLINE_KW)
+    }
+
+    pub fn goto_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::GOTO_KW)
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn strings(&self) -> CstChildren<'t, String<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn asm_operand_lists(&self) -> CstChildren<'t, AsmOperandList<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+
+    pub fn semicolon_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::SEMICOLON)
+    }
+}
+
+impl<'t> CstNode<'t> for AsmStatement<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::AsmStatement
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AsmOperandList<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> AsmOperandList<'t> {
+    pub fn colon_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COLON)
+    }
+
+    pub fn asm_operands(&self) -> CstChildren<'t, AsmOperand<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+}
+
+impl<'t> CstNode<'t> for AsmOperandList<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::AsmOperandList
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AsmOperand<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> AsmOperand<'t> {
+    pub fn lbracket_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LBRACKET)
+    }
+
+    pub fn identifier_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::IDENTIFIER)
+    }
+
+    pub fn rbracket_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RBRACKET)
+    }
+
+    pub fn string(&self) -> Option<String<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn expression(&self) -> Option<Expression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+}
+
+impl<'t> CstNode<'t> for AsmOperand<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::AsmOperand
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeofSpecifier<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> TypeofSpecifier<'t> {
+    pub fn keyword(&self) -> Option<&'t Token> {
+        support::token_any(
+            self.syntax,
+            &[
+                TokenKind::TYPEOF_KW,
+                TokenKind::TYPEOF_UNQUAL_KW,
+            ],
+        )
+    }
+
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn type_name(&self) -> Option<TypeName<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn expression(&self) -> Option<Expression<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+}
+
+impl<'t> CstNode<'t> for TypeofSpecifier<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::TypeofSpecifier
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatementExpression<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> StatementExpression<'t> {
+    pub fn lparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LPAREN)
+    }
+
+    pub fn compound_statement(&self) -> Option<CompoundStatement<'t>> {
+        support::child(self.syntax)
+    }
+
+    pub fn rparen_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RPAREN)
+    }
+}
+
+impl<'t> CstNode<'t> for StatementExpression<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::StatementExpression
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StandardAttributeSpecifier<'t> {
+    pub(crate) syntax: &'t Tree,
+}
+
+impl<'t> StandardAttributeSpecifier<'t> {
+    pub fn lbracket_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::LBRACKET)
+    }
+
+    pub fn attributes(&self) -> CstChildren<'t, Attribute<'t>> {
+        support::children(self.syntax)
+    }
+
+    pub fn comma_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::COMMA)
+    }
+
+    pub fn rbracket_token(&self) -> Option<&'t Token> {
+        support::token(self.syntax, TokenKind::RBRACKET)
+    }
+}
+
+impl<'t> CstNode<'t> for StandardAttributeSpecifier<'t> {
+    fn can_cast(kind: TreeKind) -> bool {
+        kind == TreeKind::StandardAttributeSpecifier
+    }
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {
+        if Self::can_cast(syntax.kind) {
+            Some(Self { syntax })
+        } else {
+            None
+        }
+    }
+
+    fn syntax(&self) -> &'t Tree {
+        self.syntax
+    }
+}
@@ -0,0 +1,693 @@
+//! Generates the typed CST accessors in `generated.rs` from `c.ungram`.
+//!
+//! Run `cargo test -p rcc sourcegen` after editing the grammar. If the
+//! generated file is out of date, the test rewrites it and fails, so CI
+//! catches a stale checkout.
+
+use crate::{
+    cst::TreeKind,
+    lexer::TokenKind,
+};
+use logos::Logos;
+use std::{
+    collections::{
+        BTreeSet,
+        HashMap,
+    },
+    fmt::Write as _,
+    fs,
+    path::PathBuf,
+};
+use strum::IntoEnumIterator;
+
+/// `TreeKind`s that have no rule in `c.ungram`, either because they are
+/// produced by error recovery or because the parser never builds them.
+const NOT_IN_GRAMMAR: &[TreeKind] = &[
+    TreeKind::Unknown,
+    TreeKind::ErrorTree,
+    TreeKind::File,
+    TreeKind::StatementList,
+    TreeKind::Fn,
+    TreeKind::TypeExpr,
+    TreeKind::Param,
+    TreeKind::Block,
+    TreeKind::StmtLet,
+    TreeKind::StmtReturn,
+    TreeKind::StmtExpr,
+    TreeKind::ExprLiteral,
+    TreeKind::ExprName,
+    TreeKind::ExprParen,
+    TreeKind::ExprBinary,
+    TreeKind::ExprCall,
+    TreeKind::ArgList,
+    TreeKind::Arg,
+];
+
+#[test]
+fn sourcegen_cst_nodes() {
+    let grammar = Grammar::parse(&fs::read_to_string(grammar_path()).unwrap());
+    let generated = generate(&grammar);
+
+    let path = generated_path();
+    let current = fs::read_to_string(&path).unwrap_or_default();
+    if current != generated {
+        fs::write(&path, &generated).unwrap();
+        panic!("{} was not up-to-date, updating", path.display());
+    }
+}
+
+#[test]
+fn grammar_covers_tree_kinds() {
+    let grammar = Grammar::parse(&fs::read_to_string(grammar_path()).unwrap());
+    let in_grammar = grammar.rules.iter().map(|rule| rule.name.as_str()).collect::<BTreeSet<_>>();
+
+    let missing = TreeKind::iter()
+        .filter(|kind| !NOT_IN_GRAMMAR.contains(kind))
+        .map(|kind| kind.to_string())
+        .filter(|kind| !in_grammar.contains(kind.as_str()))
+        .collect::<Vec<_>>();
+    assert!(missing.is_empty(), "TreeKinds without a rule in c.ungram: {missing:?}");
+
+    let stale = NOT_IN_GRAMMAR
+        .iter()
+        .map(|kind| kind.to_string())
+        .filter(|kind| in_grammar.contains(kind.as_str()))
+        .collect::<Vec<_>>();
+    assert!(stale.is_empty(), "TreeKinds listed in NOT_IN_GRAMMAR but present in c.ungram: {stale:?}");
+}
+
+fn grammar_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../c.ungram")
+}
+
+fn generated_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/cst/nodes/generated.rs")
+}
+
+// Grammar
+
+#[derive(Debug, Clone)]
+enum Rule {
+    Labeled { label: String, rule: Box<Rule> },
+    Node(String),
+    Token(String),
+    Seq(Vec<Rule>),
+    Alt(Vec<Rule>),
+    Opt(Box<Rule>),
+    Rep(Box<Rule>),
+}
+
+#[derive(Debug)]
+struct NodeRule {
+    name: String,
+    rule: Rule,
+}
+
+#[derive(Debug)]
+struct Grammar {
+    rules: Vec<NodeRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Lexeme {
+    Ident(String),
+    Token(String),
+    Colon,
+    Eq,
+    Pipe,
+    Star,
+    QMark,
+    LParen,
+    RParen,
+    Semi,
+}
+
+impl Grammar {
+    fn parse(text: &str) -> Grammar {
+        let lexemes = lex(text);
+        let mut p = GrammarParser { lexemes, pos: 0 };
+        let mut rules = Vec::new();
+
+        while p.pos < p.lexemes.len() {
+            let name = match p.bump() {
+                Lexeme::Ident(name) => name,
+                other => panic!("expected a rule name, found {other:?}"),
+            };
+            p.expect(Lexeme::Eq);
+            let rule = p.alt();
+            p.expect(Lexeme::Semi);
+            assert!(rules.iter().all(|it: &NodeRule| it.name != name), "duplicate rule {name}");
+            rules.push(NodeRule { name, rule });
+        }
+
+        for rule in &rules {
+            rule.rule.visit(&mut |it| {
+                if let Rule::Node(name) = it {
+                    assert!(
+                        rules.iter().any(|rule| &rule.name == name),
+                        "undefined rule {name} referenced from {}",
+                        rule.name
+                    );
+                }
+            });
+        }
+
+        Grammar { rules }
+    }
+}
+
+fn lex(text: &str) -> Vec<Lexeme> {
+    let mut res = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '\'' => {
+                let mut token = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => token.extend(chars.next()),
+                        Some('\'') => break,
+                        Some(c) => token.push(c),
+                        None => panic!("unterminated token literal"),
+                    }
+                }
+                res.push(Lexeme::Token(token));
+            }
+            ':' => res.push(Lexeme::Colon),
+            '=' => res.push(Lexeme::Eq),
+            '|' => res.push(Lexeme::Pipe),
+            '*' => res.push(Lexeme::Star),
+            '?' => res.push(Lexeme::QMark),
+            '(' => res.push(Lexeme::LParen),
+            ')' => res.push(Lexeme::RParen),
+            ';' => res.push(Lexeme::Semi),
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = String::from(c);
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    ident.push(c);
+                    chars.next();
+                }
+                res.push(Lexeme::Ident(ident));
+            }
+            c => panic!("unexpected character {c:?} in c.ungram"),
+        }
+    }
+
+    res
+}
+
+struct GrammarParser {
+    lexemes: Vec<Lexeme>,
+    pos:     usize,
+}
+
+impl GrammarParser {
+    fn peek(&self, n: usize) -> Option<&Lexeme> {
+        self.lexemes.get(self.pos + n)
+    }
+
+    fn bump(&mut self) -> Lexeme {
+        let lexeme = self.lexemes[self.pos].clone();
+        self.pos += 1;
+        lexeme
+    }
+
+    fn expect(&mut self, lexeme: Lexeme) {
+        let found = self.bump();
+        assert_eq!(found, lexeme, "unexpected lexeme in c.ungram");
+    }
+
+    fn alt(&mut self) -> Rule {
+        if self.peek(0) == Some(&Lexeme::Pipe) {
+            self.bump();
+        }
+
+        let mut alts = vec![self.seq()];
+        while self.peek(0) == Some(&Lexeme::Pipe) {
+            self.bump();
+            alts.push(self.seq());
+        }
+
+        if alts.len() == 1 {
+            alts.pop().unwrap()
+        } else {
+            Rule::Alt(alts)
+        }
+    }
+
+    fn seq(&mut self) -> Rule {
+        let mut seq = Vec::new();
+        while !matches!(self.peek(0), None | Some(Lexeme::Pipe | Lexeme::RParen | Lexeme::Semi)) {
+            seq.push(self.atom());
+        }
+
+        assert!(!seq.is_empty(), "empty sequence in c.ungram");
+        if seq.len() == 1 {
+            seq.pop().unwrap()
+        } else {
+            Rule::Seq(seq)
+        }
+    }
+
+    fn atom(&mut self) -> Rule {
+        if let (Some(Lexeme::Ident(label)), Some(Lexeme::Colon)) = (self.peek(0), self.peek(1)) {
+            let label = label.clone();
+            self.pos += 2;
+            let rule = self.atom();
+            return Rule::Labeled { label, rule: Box::new(rule) };
+        }
+
+        let mut rule = match self.bump() {
+            Lexeme::Ident(name) => Rule::Node(name),
+            Lexeme::Token(token) => Rule::Token(token),
+            Lexeme::LParen => {
+                let rule = self.alt();
+                self.expect(Lexeme::RParen);
+                rule
+            }
+            other => panic!("unexpected {other:?} in c.ungram"),
+        };
+
+        loop {
+            match self.peek(0) {
+                Some(Lexeme::Star) => rule = Rule::Rep(Box::new(rule)),
+                Some(Lexeme::QMark) => rule = Rule::Opt(Box::new(rule)),
+                _ => break,
+            }
+            self.bump();
+        }
+
+        rule
+    }
+}
+
+impl Rule {
+    fn visit(&self, f: &mut dyn FnMut(&Rule)) {
+        f(self);
+        match self {
+            Rule::Labeled { rule, .. } | Rule::Opt(rule) | Rule::Rep(rule) => rule.visit(f),
+            Rule::Seq(rules) | Rule::Alt(rules) => rules.iter().for_each(|rule| rule.visit(f)),
+            Rule::Node(_) | Rule::Token(_) => {}
+        }
+    }
+
+    /// The alternatives of a rule of the form `A | B | C`, if every
+    /// alternative is a bare node.
+    fn choices(&self) -> Option<Vec<&str>> {
+        match self {
+            Rule::Alt(alts) => alts
+                .iter()
+                .map(|alt| match alt {
+                    Rule::Node(name) => Some(name.as_str()),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+}
+
+// Lowering the grammar to accessors
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Field {
+    /// An unlabeled node, accessed by type.
+    Node { ty: String, many: bool },
+    /// A labeled node, accessed by its position among children of that type.
+    Nth { label: String, ty: String, index: usize },
+    Token { name: String, kinds: Vec<TokenKind> },
+}
+
+impl Field {
+    fn method_name(&self) -> String {
+        match self {
+            Field::Node { ty, many: false } => to_snake_case(ty),
+            Field::Node { ty, many: true } => format!("{}s", to_snake_case(ty)),
+            Field::Nth { label, .. } => label.clone(),
+            Field::Token { name, .. } => name.clone(),
+        }
+    }
+}
+
+/// Possible numbers of preceding children of each node type, as `(min, max)`.
+type Counts = HashMap<String, (usize, usize)>;
+
+fn lower_fields(node: &NodeRule) -> Vec<Field> {
+    let mut fields = Vec::new();
+    lower_rule(&node.rule, &mut Counts::new(), &mut fields, &node.name);
+
+    let mut unlabeled = HashMap::new();
+    count_unlabeled(&node.rule, &mut unlabeled);
+    for field in &mut fields {
+        if let Field::Node { ty, many } = field {
+            *many = unlabeled[ty.as_str()] > 1;
+        }
+    }
+
+    let mut res: Vec<Field> = Vec::new();
+    for field in fields {
+        match res.iter().find(|it| it.method_name() == field.method_name()) {
+            Some(existing) => assert_eq!(
+                existing, &field,
+                "conflicting definitions of `{}` in {}",
+                field.method_name(),
+                node.name
+            ),
+            None => res.push(field),
+        }
+    }
+    res
+}
+
+fn lower_rule(rule: &Rule, counts: &mut Counts, fields: &mut Vec<Field>, node: &str) {
+    match rule {
+        Rule::Node(ty) => {
+            fields.push(Field::Node { ty: ty.clone(), many: false });
+            bump_count(counts, ty);
+        }
+        Rule::Token(token) => {
+            let kind = token_kind(token);
+            fields.push(Field::Token { name: token_method_name(kind), kinds: vec![kind] });
+        }
+        Rule::Labeled { label, rule } => {
+            let rule = match &**rule {
+                Rule::Opt(rule) => rule,
+                rule => rule,
+            };
+            match rule {
+                Rule::Node(ty) => {
+                    let (min, max) = counts.get(ty).copied().unwrap_or_default();
+                    assert_eq!(min, max, "position of `{label}` in {node} is ambiguous");
+                    fields.push(Field::Nth { label: label.clone(), ty: ty.clone(), index: min });
+                    bump_count(counts, ty);
+                }
+                Rule::Token(token) => {
+                    fields.push(Field::Token { name: label.clone(), kinds: vec![token_kind(token)] });
+                }
+                Rule::Alt(alts) => {
+                    let kinds = alts
+                        .iter()
+                        .map(|alt| match alt {
+                            Rule::Token(token) => token_kind(token),
+                            _ => panic!("`{label}` in {node} must label tokens only"),
+                        })
+                        .collect();
+                    fields.push(Field::Token { name: label.clone(), kinds });
+                }
+                _ => panic!("unsupported labeled rule `{label}` in {node}"),
+            }
+        }
+        Rule::Seq(rules) => {
+            for rule in rules {
+                lower_rule(rule, counts, fields, node);
+            }
+        }
+        Rule::Alt(alts) => {
+            let before = counts.clone();
+            let mut merged: Option<Counts> = None;
+            for alt in alts {
+                let mut branch = before.clone();
+                lower_rule(alt, &mut branch, fields, node);
+                merged = Some(match merged {
+                    None => branch,
+                    Some(merged) => merge_counts(&merged, &branch),
+                });
+            }
+            *counts = merged.unwrap_or(before);
+        }
+        Rule::Opt(rule) => {
+            let before = counts.clone();
+            lower_rule(rule, counts, fields, node);
+            *counts = merge_counts(&before, counts);
+        }
+        Rule::Rep(rule) => {
+            let before = counts.clone();
+            lower_rule(rule, counts, fields, node);
+            for (ty, (min, max)) in counts.iter_mut() {
+                let (before_min, before_max) = before.get(ty).copied().unwrap_or_default();
+                if *max != before_max {
+                    *min = before_min;
+                    *max = usize::MAX;
+                }
+            }
+        }
+    }
+}
+
+fn bump_count(counts: &mut Counts, ty: &str) {
+    let (min, max) = counts.entry(ty.to_string()).or_default();
+    *min = min.saturating_add(1);
+    *max = max.saturating_add(1);
+}
+
+fn merge_counts(a: &Counts, b: &Counts) -> Counts {
+    let mut res = Counts::new();
+    for ty in a.keys().chain(b.keys()) {
+        let (a_min, a_max) = a.get(ty).copied().unwrap_or_default();
+        let (b_min, b_max) = b.get(ty).copied().unwrap_or_default();
+        res.insert(ty.clone(), (a_min.min(b_min), a_max.max(b_max)));
+    }
+    res
+}
+
+/// The maximum number of unlabeled children of each node type.
+fn count_unlabeled(rule: &Rule, counts: &mut HashMap<String, usize>) {
+    match rule {
+        Rule::Node(ty) => *counts.entry(ty.clone()).or_default() += 1,
+        Rule::Token(_) | Rule::Labeled { .. } => {}
+        Rule::Seq(rules) => rules.iter().for_each(|rule| count_unlabeled(rule, counts)),
+        Rule::Alt(alts) => {
+            let before = counts.clone();
+            for alt in alts {
+                let mut branch = before.clone();
+                count_unlabeled(alt, &mut branch);
+                for (ty, n) in branch {
+                    let entry = counts.entry(ty).or_default();
+                    *entry = (*entry).max(n);
+                }
+            }
+        }
+        Rule::Opt(rule) => count_unlabeled(rule, counts),
+        Rule::Rep(rule) => {
+            let mut inner = HashMap::new();
+            count_unlabeled(rule, &mut inner);
+            for ty in inner.into_keys() {
+                counts.insert(ty, usize::MAX);
+            }
+        }
+    }
+}
+
+/// Maps a token of the grammar to the `TokenKind` produced by the lexer.
+/// Named tokens (`'identifier'`) refer to the variant of the same name;
+/// everything else is run through the lexer itself.
+fn token_kind(token: &str) -> TokenKind {
+    match token {
+        "identifier" => TokenKind::IDENTIFIER,
+        "integer_constant" => TokenKind::INTEGER_CONSTANT,
+        "floating_constant" => TokenKind::FLOATING_CONSTANT,
+        "string" => TokenKind::STRING,
+        _ => {
+            let mut lexer = TokenKind::lexer(token);
+            match (lexer.next(), lexer.next()) {
+                (Some(Ok(kind)), None) if kind != TokenKind::IDENTIFIER => kind,
+                _ => panic!("'{token}' in c.ungram is not a single token"),
+            }
+        }
+    }
+}
+
+fn token_method_name(kind: TokenKind) -> String {
+    let name = format!("{kind:?}").to_lowercase();
+    format!("{}_token", name.strip_suffix("_kw").unwrap_or(&name))
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut res = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            res.push('_');
+        }
+        res.push(c.to_ascii_lowercase());
+    }
+    res
+}
+
+// Code generation
+
+fn generate(grammar: &Grammar) -> String {
+    let tree_kinds = TreeKind::iter().map(|kind| kind.to_string()).collect::<BTreeSet<_>>();
+    let mut buf = String::new();
+
+    buf.push_str(
+        "//! Generated by `cargo test -p rcc sourcegen` from `c.ungram`, do not edit by hand.\n\n",
+    );
+    buf.push_str("use super::{\n    support,\n    CstChildren,\n    CstNode,\n};\n");
+    buf.push_str("use crate::{\n    cst::{\n        Tree,\n        TreeKind,\n    },\n");
+    buf.push_str("    lexer::{\n        Token,\n        TokenKind,\n    },\n};\n");
+
+    for node in &grammar.rules {
+        let is_tree_kind = tree_kinds.contains(&node.name);
+        let choices = node.rule.choices();
+
+        if is_tree_kind {
+            generate_struct(&mut buf, node);
+            if let Some(choices) = choices {
+                generate_enum(&mut buf, &format!("{}Kind", node.name), &choices);
+            }
+        } else {
+            let choices = choices.unwrap_or_else(|| {
+                panic!("{} is not a TreeKind, so it must be an alternation of nodes", node.name)
+            });
+            for choice in &choices {
+                assert!(
+                    tree_kinds.contains(*choice),
+                    "{} cannot contain the abstract node {choice}",
+                    node.name
+                );
+            }
+            generate_enum(&mut buf, &node.name, &choices);
+        }
+    }
+
+    buf
+}
+
+fn generate_struct(buf: &mut String, node: &NodeRule) {
+    let name = &node.name;
+
+    let _ = write!(
+        buf,
+        "
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct {name}<'t> {{
+    pub(crate) syntax: &'t Tree,
+}}
+
+impl<'t> {name}<'t> {{
+"
+    );
+
+    let mut methods = Vec::new();
+    if node.rule.choices().is_some() {
+        methods.push(format!(
+            "    pub fn kind(&self) -> Option<{name}Kind<'t>> {{\n        \
+             support::child(self.syntax)\n    }}\n"
+        ));
+    }
+    for field in lower_fields(node) {
+        let method = field.method_name();
+        methods.push(match field {
+            Field::Node { ty, many: false } => format!(
+                "    pub fn {method}(&self) -> Option<{ty}<'t>> {{\n        \
+                 support::child(self.syntax)\n    }}\n"
+            ),
+            Field::Node { ty, many: true } => format!(
+                "    pub fn {method}(&self) -> CstChildren<'t, {ty}<'t>> {{\n        \
+                 support::children(self.syntax)\n    }}\n"
+            ),
+            Field::Nth { ty, index, .. } => format!(
+                "    pub fn {method}(&self) -> Option<{ty}<'t>> {{\n        \
+                 support::nth_child(self.syntax, {index})\n    }}\n"
+            ),
+            Field::Token { kinds, .. } if kinds.len() == 1 => format!(
+                "    pub fn {method}(&self) -> Option<&'t Token> {{\n        \
+                 support::token(self.syntax, TokenKind::{:?})\n    }}\n",
+                kinds[0]
+            ),
+            Field::Token { kinds, .. } => {
+                let mut kinds_buf = String::new();
+                for kind in kinds {
+                    let _ = writeln!(kinds_buf, "                TokenKind::{kind:?},");
+                }
+                format!(
+                    "    pub fn {method}(&self) -> Option<&'t Token> {{\n        \
+                     support::token_any(\n            self.syntax,\n            &[\n{kinds_buf}            \
+                     ],\n        )\n    }}\n"
+                )
+            }
+        });
+    }
+    buf.push_str(&methods.join("\n"));
+
+    let _ = write!(
+        buf,
+        "}}
+
+impl<'t> CstNode<'t> for {name}<'t> {{
+    fn can_cast(kind: TreeKind) -> bool {{
+        kind == TreeKind::{name}
+    }}
+
+    fn cast(syntax: &'t Tree) -> Option<Self> {{
+        if Self::can_cast(syntax.kind) {{
+            Some(Self {{ syntax }})
+        }} else {{
+            None
+        }}
+    }}
+
+    fn syntax(&self) -> &'t Tree {{
+        self.syntax
+    }}
+}}
+"
+    );
+}
+
+fn generate_enum(buf: &mut String, name: &str, variants: &[&str]) {
+    let _ = write!(buf, "\n#[derive(Debug, Clone, Copy, PartialEq, Eq)]\npub enum {name}<'t> {{\n");
+    for variant in variants {
+        let _ = writeln!(buf, "    {variant}({variant}<'t>),");
+    }
+    buf.push_str("}\n");
+
+    let _ = write!(
+        buf,
+        "\nimpl<'t> CstNode<'t> for {name}<'t> {{\n    fn can_cast(kind: TreeKind) -> bool {{\n        \
+         matches!(\n            kind,\n"
+    );
+    for (i, variant) in variants.iter().enumerate() {
+        let sep = if i == 0 { "            " } else { "                | " };
+        let _ = writeln!(buf, "{sep}TreeKind::{variant}");
+    }
+    buf.push_str("        )\n    }\n\n    fn cast(syntax: &'t Tree) -> Option<Self> {\n");
+    buf.push_str("        let res = match syntax.kind {\n");
+    for variant in variants {
+        let _ = writeln!(
+            buf,
+            "            TreeKind::{variant} => {name}::{variant}({variant} {{ syntax }}),"
+        );
+    }
+    buf.push_str("            _ => return None,\n        };\n        Some(res)\n    }\n\n");
+    buf.push_str("    fn syntax(&self) -> &'t Tree {\n        match self {\n");
+    for variant in variants {
+        let _ = writeln!(buf, "            {name}::{variant}(it) => it.syntax,");
+    }
+    buf.push_str("        }\n    }\n}\n");
+
+    for variant in variants {
+        let _ = write!(
+            buf,
+            "
+impl<'t> From<{variant}<'t>> for {name}<'t> {{
+    fn from(node: {variant}<'t>) -> {name}<'t> {{
+        {name}::{variant}(node)
+    }}
+}}
+"
+        );
+    }
+}
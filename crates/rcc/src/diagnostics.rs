@@ -1,25 +1,62 @@
+mod codes;
+mod files;
+mod format;
+mod suggestions;
+mod warnings;
+
+pub use codes::DiagnosticCode;
+pub use files::{
+    SourceFile,
+    SourceFiles,
+};
+pub use format::DiagnosticFormat;
+pub use suggestions::{
+    apply_suggestions,
+    edit_distance,
+    similar_name,
+    Applicability,
+    Suggestion,
+};
+pub use warnings::{
+    DiagnosticPragma,
+    WarningGroup,
+    WarningLevel,
+    WarningOptions,
+};
+
 use crate::{
+    charset::{
+        self,
+        Charset,
+    },
+    condition::ConditionError,
+    flow::FlowError,
     lexer::{
         Span,
         Token,
         TokenKind,
     },
-    parser::{
-        display,
-        FN_DEF_DECLARATION_SPECIFIERS_FIRST,
+    parser::FN_DEF_DECLARATION_SPECIFIERS_FIRST,
+    source_manager::{
+        Expansion,
+        ExpansionId,
+        SourceManager,
     },
+    symbol::Symbol,
 };
 pub use codespan_reporting::{
     diagnostic::{
-        Diagnostic,
         Label,
+        LabelStyle,
+        Severity,
     },
-    files::SimpleFiles,
     term::{
         self,
         termcolor::{
+            Buffer,
             ColorChoice,
             StandardStream,
+            WriteColor,
         },
     },
 };
@@ -28,17 +65,143 @@ use getset::{
     MutGetters,
     Setters,
 };
-use owo_colors::OwoColorize;
+use std::{
+    collections::HashMap,
+    io::{
+        self,
+        IsTerminal,
+    },
+    mem,
+    ops::{
+        Deref,
+        DerefMut,
+    },
+    path::Path,
+};
 use typed_builder::TypedBuilder;
 
 pub type FileId = usize;
 
+/// How many errors are reported before the rest are dropped, unless set with
+/// `-ferror-limit=N`.
+pub const DEFAULT_ERROR_LIMIT: usize = 20;
+
+/// How many macro expansions a diagnostic shows the backtrace of, unless set
+/// with `-fmacro-backtrace-limit=N`.
+pub const DEFAULT_MACRO_BACKTRACE_LIMIT: usize = 6;
+
+/// A [`codespan_reporting`] diagnostic, along with the edits it suggests to
+/// fix the code it reports.
+///
+/// The builder methods mirror those of `codespan_reporting`, and the fields
+/// of the underlying diagnostic are reachable through `Deref`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic<FileId> {
+    diagnostic:      codespan_reporting::diagnostic::Diagnostic<FileId>,
+    pub suggestions: Vec<Suggestion<FileId>>,
+    /// The macro expansion the code it reports was produced by, if any,
+    /// whose backtrace is shown along with it.
+    pub expansion:   Option<ExpansionId>,
+}
+
+impl<FileId> Diagnostic<FileId> {
+    pub fn new(severity: Severity) -> Self {
+        codespan_reporting::diagnostic::Diagnostic::new(severity).into()
+    }
+
+    pub fn error() -> Self {
+        Self::new(Severity::Error)
+    }
+
+    pub fn warning() -> Self {
+        Self::new(Severity::Warning)
+    }
+
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.diagnostic = self.diagnostic.with_code(code);
+        self
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.diagnostic = self.diagnostic.with_message(message);
+        self
+    }
+
+    pub fn with_labels(mut self, labels: Vec<Label<FileId>>) -> Self {
+        self.diagnostic = self.diagnostic.with_labels(labels);
+        self
+    }
+
+    pub fn with_notes(mut self, notes: Vec<String>) -> Self {
+        self.diagnostic = self.diagnostic.with_notes(notes);
+        self
+    }
+
+    pub fn with_suggestion(mut self, suggestion: Suggestion<FileId>) -> Self {
+        self.suggestions.push(suggestion);
+        self
+    }
+
+    pub fn with_expansion(mut self, expansion: Option<ExpansionId>) -> Self {
+        self.expansion = expansion;
+        self
+    }
+
+    /// The underlying `codespan_reporting` diagnostic, without suggestions.
+    pub fn into_inner(self) -> codespan_reporting::diagnostic::Diagnostic<FileId> {
+        self.diagnostic
+    }
+}
+
+impl<FileId> From<codespan_reporting::diagnostic::Diagnostic<FileId>> for Diagnostic<FileId> {
+    fn from(diagnostic: codespan_reporting::diagnostic::Diagnostic<FileId>) -> Self {
+        Self { diagnostic, suggestions: Vec::new(), expansion: None }
+    }
+}
+
+impl<FileId> Deref for Diagnostic<FileId> {
+    type Target = codespan_reporting::diagnostic::Diagnostic<FileId>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.diagnostic
+    }
+}
+
+impl<FileId> DerefMut for Diagnostic<FileId> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.diagnostic
+    }
+}
+
 #[derive(Debug, Getters, MutGetters, Setters, TypedBuilder)]
 #[getset(get = "pub", get_mut = "pub", set = "pub")]
 pub struct DiagnosticsEngine {
     pub diagnostics:      Vec<Diagnostic<FileId>>,
-    pub files:            SimpleFiles<String, String>,
+    /// The files diagnostics point into, and where they were read from.
+    pub files:            SourceManager,
     pub diagnostic_count: DiagnosticStats,
+    /// Whether the warnings of each group are ignored, reported or promoted
+    /// to errors.
+    #[builder(default)]
+    pub warnings:         WarningOptions,
+    /// The `#pragma GCC diagnostic` directives of each file, by offset.
+    #[builder(default)]
+    pub pragmas:          HashMap<FileId, Vec<(usize, DiagnosticPragma)>>,
+    /// How many errors are reported before the rest are dropped, or `0` for
+    /// no limit.
+    #[builder(default = DEFAULT_ERROR_LIMIT)]
+    pub error_limit:      usize,
+    /// Whether the `gcc` format shows the source line under each
+    /// diagnostic, with a caret under its location.
+    #[builder(default)]
+    pub show_caret:       bool,
+    /// How many macro expansions a diagnostic shows the backtrace of, or
+    /// `0` for no limit.
+    #[builder(default = DEFAULT_MACRO_BACKTRACE_LIMIT)]
+    pub macro_backtrace_limit: usize,
+    /// How many errors have been emitted so far, flushed or not.
+    #[builder(default)]
+    errors_emitted:       usize,
 }
 
 pub enum ErrorKind {
@@ -87,17 +250,112 @@ impl DiagnosticStats {
     }
 }
 
+impl Default for DiagnosticsEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DiagnosticsEngine {
     pub fn new() -> Self {
+        Self::with_warnings(WarningOptions::default())
+    }
+
+    pub fn with_warnings(warnings: WarningOptions) -> Self {
         Self {
             diagnostics:      Vec::new(),
-            files:            SimpleFiles::new(),
+            files:            SourceManager::new(),
             diagnostic_count: DiagnosticStats::new(),
+            warnings,
+            pragmas:          HashMap::new(),
+            error_limit:      DEFAULT_ERROR_LIMIT,
+            show_caret:       false,
+            macro_backtrace_limit: DEFAULT_MACRO_BACKTRACE_LIMIT,
+            errors_emitted:   0,
         }
     }
 
-    pub fn emit(&mut self, diagnostic: Diagnostic<FileId>) {
+    /// Sets how many errors are reported before the rest are dropped, or `0`
+    /// for no limit.
+    pub fn with_error_limit(mut self, error_limit: usize) -> Self {
+        self.error_limit = error_limit;
+        self
+    }
+
+    /// Sets whether the `gcc` format shows the source line under each
+    /// diagnostic, as `-fdiagnostics-show-caret` does.
+    pub fn with_caret(mut self, show_caret: bool) -> Self {
+        self.show_caret = show_caret;
+        self
+    }
+
+    /// Sets how many macro expansions a diagnostic shows the backtrace of,
+    /// or `0` for no limit.
+    pub fn with_macro_backtrace_limit(mut self, macro_backtrace_limit: usize) -> Self {
+        self.macro_backtrace_limit = macro_backtrace_limit;
+        self
+    }
+
+    /// Whether the error limit has been reached, after which nothing more is
+    /// reported.
+    pub fn error_limit_reached(&self) -> bool {
+        self.error_limit > 0 && self.errors_emitted >= self.error_limit
+    }
+
+    /// Queues `diagnostic` to be flushed. A warning is first leveled by its
+    /// group: it may be dropped, or promoted to an error.
+    ///
+    /// A diagnostic with the same code, message and primary spans as a
+    /// pending one is dropped, as is everything once the error limit has
+    /// been reached.
+    pub fn emit(&mut self, mut diagnostic: Diagnostic<FileId>) {
+        if self.error_limit_reached() {
+            return;
+        }
+
+        let group = diagnostic
+            .code
+            .as_deref()
+            .and_then(|code| code.parse::<DiagnosticCode>().ok())
+            .and_then(DiagnosticCode::group);
+
+        if let Some(group) = group {
+            let level = diagnostic
+                .labels
+                .iter()
+                .find(|label| label.style == LabelStyle::Primary)
+                .and_then(|label| {
+                    let pragmas = self.pragmas.get(&label.file_id)?;
+                    warnings::pragma_level(pragmas, group, label.range.start)
+                })
+                .unwrap_or_else(|| self.warnings.level(group));
+
+            match level {
+                WarningLevel::Ignored => return,
+                WarningLevel::Warning => {
+                    diagnostic.severity = Severity::Warning;
+                    diagnostic.message = format!("{} [-W{group}]", diagnostic.message);
+                }
+                WarningLevel::Error => {
+                    diagnostic.severity = Severity::Error;
+                    diagnostic.message = format!("{} [-Werror={group}]", diagnostic.message);
+                }
+            }
+        }
+
+        if self.diagnostics.iter().any(|pending| is_duplicate(pending, &diagnostic)) {
+            return;
+        }
+
+        let is_error = diagnostic.severity >= Severity::Error;
         self.diagnostics.push(diagnostic);
+
+        if is_error {
+            self.errors_emitted += 1;
+            if self.error_limit_reached() {
+                self.diagnostics.push(too_many_errors(self.error_limit));
+            }
+        }
     }
 
     pub fn add_file(
@@ -105,253 +363,681 @@ impl DiagnosticsEngine {
         file_name: impl Into<String>,
         file_contents: impl Into<String>,
     ) -> usize {
-        self.files.add(file_name.into(), file_contents.into())
+        let file_id = self.files.add(file_name, file_contents);
+        self.scan_pragmas(file_id);
+
+        file_id
+    }
+
+    /// Decodes `bytes` from `input_charset`, as [`charset::decode`] does, and
+    /// adds them as a file, reporting the bytes that didn't decode.
+    pub fn add_bytes(
+        &mut self,
+        file_name: impl Into<String>,
+        bytes: &[u8],
+        input_charset: &'static Charset,
+    ) -> usize {
+        let decoded = charset::decode(bytes, input_charset);
+        let file_id = self.add_file(file_name, decoded.text);
+        self.report_malformed(file_id, decoded.charset, decoded.malformed);
+
+        file_id
+    }
+
+    /// Reads `path` through the source manager, so that an overlay of it is
+    /// read rather than the file on disk, and adds it as
+    /// [`DiagnosticsEngine::add_bytes`] does. A file which has already been
+    /// loaded, by this path or any other, isn't read again.
+    pub fn load_file(
+        &mut self,
+        path: impl AsRef<Path>,
+        input_charset: &'static Charset,
+    ) -> io::Result<usize> {
+        let path = path.as_ref();
+        if let Some(file_id) = self.files.file_id(path) {
+            return Ok(file_id);
+        }
+
+        let decoded = charset::decode(&self.files.read(path)?, input_charset);
+        let file_id = self.files.add_path(path, decoded.text);
+        self.scan_pragmas(file_id);
+        self.report_malformed(file_id, decoded.charset, decoded.malformed);
+
+        Ok(file_id)
+    }
+
+    fn scan_pragmas(&mut self, file_id: FileId) {
+        let Ok(file) = self.files.get(file_id) else {
+            return;
+        };
+        let pragmas = warnings::diagnostic_pragmas(file.source());
+        if !pragmas.is_empty() {
+            self.pragmas.insert(file_id, pragmas);
+        }
+    }
+
+    fn report_malformed(
+        &mut self,
+        file_id: FileId,
+        charset: &'static Charset,
+        malformed: Vec<charset::Malformed>,
+    ) {
+        for malformed in malformed {
+            let name = charset.name();
+            self.emit(invalid_source_encoding(file_id, malformed.span, &malformed.bytes, name));
+        }
+    }
+
+    /// Whether any error has been emitted, whether or not it has been
+    /// flushed yet.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostic_count.error_count > 0 ||
+            self.diagnostics.iter().any(|diagnostic| diagnostic.severity >= Severity::Error)
     }
 
     pub fn flush(&mut self) {
-        let mut writer = StandardStream::stderr(ColorChoice::Always);
-        let config = codespan_reporting::term::Config::default();
+        let mut writer = StandardStream::stderr(stderr_color_choice(ColorChoice::Auto));
+        self.flush_to(&mut writer);
+    }
 
-        for diagnostic in self.diagnostics.drain(..) {
-            term::emit(&mut writer, &config, &self.files, &diagnostic)
-                .expect("Could not emit error");
+    /// Renders and drains all pending diagnostics into `writer`, e.g. a
+    /// [`codespan_reporting::term::termcolor::Buffer`] when embedding `rcc`.
+    pub fn flush_to(&mut self, writer: &mut dyn WriteColor) {
+        self.flush_as(DiagnosticFormat::Human, writer);
+    }
+
+    /// Renders and drains all pending diagnostics into `writer` in `format`.
+    ///
+    /// A SARIF log is written as a whole on each call, so diagnostics meant
+    /// for a single log should be flushed at once.
+    ///
+    /// Diagnostics are sorted by file and position first. Those without a
+    /// primary label, such as the note that the error limit was reached,
+    /// come last.
+    pub fn flush_as(&mut self, format: DiagnosticFormat, writer: &mut dyn WriteColor) {
+        let mut diagnostics = mem::take(&mut self.diagnostics)
+            .into_iter()
+            .map(|diagnostic| self.with_macro_backtrace(diagnostic))
+            .collect::<Vec<_>>();
+        diagnostics.sort_by_key(|diagnostic| {
+            let primary = diagnostic.labels.iter().find(|label| label.style == LabelStyle::Primary);
+            primary.map_or((1, 0, 0), |label| (0, label.file_id, label.range.start))
+        });
+
+        for diagnostic in &diagnostics {
+            match diagnostic.severity {
+                Severity::Bug | Severity::Error => self.diagnostic_count.error_count += 1,
+                Severity::Warning => self.diagnostic_count.warning_count += 1,
+                Severity::Note | Severity::Help => self.diagnostic_count.note_count += 1,
+            }
         }
+
+        let result = match format {
+            DiagnosticFormat::Human => {
+                let config = codespan_reporting::term::Config::default();
+                diagnostics.iter().try_for_each(|diagnostic| {
+                    let diagnostic = self.with_suggestion_notes(diagnostic);
+                    term::emit(writer, &config, self.files.files(), &diagnostic).map_err(|error| {
+                        std::io::Error::new(std::io::ErrorKind::Other, error.to_string())
+                    })
+                })
+            }
+            DiagnosticFormat::Json => format::write_json(writer, &self.files, &diagnostics),
+            DiagnosticFormat::Sarif => format::write_sarif(writer, &self.files, &diagnostics),
+            DiagnosticFormat::Gcc => {
+                format::write_gcc(writer, &self.files, &diagnostics, self.show_caret)
+            }
+        };
+        result.expect("Could not emit error");
+    }
+
+    /// The machine-applicable suggestions of the pending diagnostics of
+    /// `file_id`, i.e. the edits `rcc fix` applies.
+    pub fn machine_applicable_suggestions(&self, file_id: FileId) -> Vec<&Suggestion<FileId>> {
+        self.diagnostics
+            .iter()
+            .flat_map(|diagnostic| &diagnostic.suggestions)
+            .filter(|suggestion| {
+                suggestion.file_id == file_id &&
+                    suggestion.applicability == Applicability::MachineApplicable
+            })
+            .collect()
+    }
+
+    /// `diagnostic` with a label on the invocation and the definition of
+    /// each macro its code was expanded from, innermost first, e.g.
+    ///
+    /// ```text
+    /// 1 │ #define MAX(a, b) ((a) > (b) ? (a) : (b)
+    ///   │         --- macro `MAX` defined here
+    /// 2 │ int x = MAX(1, 2);
+    ///   │         --------- in expansion of macro `MAX`
+    /// ```
+    ///
+    /// Past the macro backtrace limit, only the innermost and outermost
+    /// expansions are shown, with a note saying how many were skipped.
+    fn with_macro_backtrace(&self, mut diagnostic: Diagnostic<FileId>) -> Diagnostic<FileId> {
+        let Some(expansion) = diagnostic.expansion else {
+            return diagnostic;
+        };
+
+        let backtrace = self.files.backtrace(expansion).collect::<Vec<_>>();
+        let limit = self.macro_backtrace_limit;
+        let (first, skipped) = if limit > 0 && backtrace.len() > limit {
+            ((limit + 1) / 2, backtrace.len() - limit)
+        } else {
+            (backtrace.len(), 0)
+        };
+
+        for (depth, expansion) in backtrace.iter().enumerate() {
+            if depth >= first && depth < first + skipped {
+                continue;
+            }
+
+            let Expansion { name, file_id, definition, invocation, .. } = expansion;
+            diagnostic.labels.push(
+                Label::secondary(*file_id, *definition.start()..*definition.end())
+                    .with_message(format!("macro `{name}` defined here")),
+            );
+            diagnostic.labels.push(
+                Label::secondary(*file_id, *invocation.start()..*invocation.end())
+                    .with_message(format!("in expansion of macro `{name}`")),
+            );
+        }
+        if skipped > 0 {
+            diagnostic.notes.push(format!(
+                "skipping {skipped} expansions in the backtrace; use \
+                 -fmacro-backtrace-limit=0 to see all"
+            ));
+        }
+
+        diagnostic
+    }
+
+    /// `diagnostic` with a `help:` note per suggestion, previewing the fixed
+    /// line, e.g.
+    ///
+    /// ```text
+    /// = help: insert `;`
+    ///   2 |     return 0;
+    ///     |             +
+    /// ```
+    fn with_suggestion_notes(
+        &self,
+        diagnostic: &Diagnostic<FileId>,
+    ) -> codespan_reporting::diagnostic::Diagnostic<FileId> {
+        let mut notes = Vec::new();
+        for suggestion in &diagnostic.suggestions {
+            let Ok(file) = self.files.get(suggestion.file_id) else {
+                continue;
+            };
+            notes.push(format!(
+                "help: {}\n{}",
+                suggestion.message(file.source()),
+                suggestion.preview(file.source(), file.line_index())
+            ));
+        }
+
+        (**diagnostic).clone().with_notes(notes)
     }
 }
 
-pub(crate) fn unexpected_token_diagnostic(
+/// Resolves [`ColorChoice::Auto`] for diagnostics written to stderr, which
+/// are then only colored if stderr is a terminal. `termcolor` itself checks
+/// `NO_COLOR` and `TERM=dumb`.
+///
+/// Diagnostics themselves are plain text; styling is only applied when
+/// they are rendered.
+pub fn stderr_color_choice(choice: ColorChoice) -> ColorChoice {
+    match choice {
+        ColorChoice::Auto if !io::stderr().is_terminal() => ColorChoice::Never,
+        choice => choice,
+    }
+}
+
+/// The note that the error limit was reached, and that errors after that are
+/// dropped.
+/// Whether `a` and `b` have the same code, message and primary spans.
+fn is_duplicate(a: &Diagnostic<FileId>, b: &Diagnostic<FileId>) -> bool {
+    let primary_spans = |diagnostic: &Diagnostic<FileId>| {
+        diagnostic
+            .labels
+            .iter()
+            .filter(|label| label.style == LabelStyle::Primary)
+            .map(|label| (label.file_id, label.range.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    a.code == b.code &&
+        a.message == b.message &&
+        primary_spans(a) == primary_spans(b) &&
+        a.expansion == b.expansion
+}
+
+fn too_many_errors(error_limit: usize) -> Diagnostic<FileId> {
+    Diagnostic::new(Severity::Note)
+        .with_message(format!("too many errors emitted ({error_limit}), stopping now"))
+        .with_notes(vec!["Use `-ferror-limit=0` to report every error.".to_string()])
+}
+
+pub(crate) fn unsupported_literal_diagnostic(
     file_id: usize,
-    unexpected_token: &Token,
-    expected: &TokenKind,
+    literal: &Token,
+    feature: &str,
+    required: &str,
 ) -> Diagnostic<FileId> {
-    Diagnostic::error()
-        .with_code("E0001")
+    DiagnosticCode::E0007
+        .diagnostic()
         .with_message(format!(
-            "Unexpected token encountered{} {}{}{}{}",
-            ":".black(),
-            "'".cyan(),
-            unexpected_token.lexeme.yellow(),
-            "'".cyan(),
-            ".".black(),
+            "{feature} are not supported by the selected standard: '{}'",
+            literal.lexeme
         ))
-        .with_notes(
-            vec![
-                format!(
-                    "The parser encountered an {}{} {}{}{}",
-                    "unexpected token".red(),
-                    ":".black(),
-                    "'".cyan(),
-                    unexpected_token.lexeme.yellow(),
-                    "'".cyan(),
-                ),
-                format!(
-                    "It maybe useful to check the {}{} {}{}{}",
-                    "token preceding".green(),
-                    ":".black(),
-                    "'".cyan(),
-                    unexpected_token.lexeme.yellow(),
-                    "'".cyan(),
-                ),
-            ]
-            .into_iter()
-            .collect(),
+        .with_labels(vec![Label::primary(file_id, *literal.span.start()..*literal.span.end())
+            .with_message(format!("{feature} used here"))])
+        .with_notes(vec![format!("{feature} require {required}.")])
+        .with_expansion(literal.expansion)
+}
+
+pub(crate) fn trigraph(
+    file_id: usize,
+    trigraph: &str,
+    span: Span,
+    replacement: char,
+    converted: bool,
+) -> Diagnostic<FileId> {
+    let label = Label::primary(file_id, *span.start()..*span.end());
+
+    if converted {
+        DiagnosticCode::W0003
+            .diagnostic()
+            .with_message(format!("trigraph {trigraph} converted to {replacement}"))
+            .with_labels(vec![label.with_message(format!("this is read as '{replacement}'"))])
+    } else {
+        DiagnosticCode::W0003
+            .diagnostic()
+            .with_message(format!("trigraph {trigraph} ignored, use -trigraphs to enable"))
+            .with_labels(vec![label.with_message("this is read as written")])
+    }
+}
+
+pub(crate) fn character_not_allowed_in_identifier(
+    file_id: usize,
+    span: Span,
+    c: char,
+    at_start: bool,
+) -> Diagnostic<FileId> {
+    let position = if at_start { "at the start of" } else { "in" };
+
+    DiagnosticCode::E0011
+        .diagnostic()
+        .with_message(format!(
+            "character <U+{:04X}> not allowed {position} an identifier",
+            u32::from(c)
+        ))
+        .with_labels(vec![Label::primary(file_id, *span.start()..*span.end())
+            .with_message(format!("`{c}` is not allowed here"))])
+}
+
+pub(crate) fn invalid_universal_character_name(
+    file_id: usize,
+    span: Span,
+    ucn: &str,
+    value: u32,
+) -> Diagnostic<FileId> {
+    let note = if (0xD800..=0xDFFF).contains(&value) {
+        format!("U+{value:04X} is a surrogate, which is not a character")
+    } else if value > 0x10FFFF {
+        format!("U+{value:04X} is past the last character, U+10FFFF")
+    } else {
+        "characters below U+00A0, other than `$`, `@` and `` ` ``, are spelled as themselves"
+            .to_string()
+    };
+
+    DiagnosticCode::E0012
+        .diagnostic()
+        .with_message(format!("`{ucn}` is not a valid universal character name"))
+        .with_labels(vec![Label::primary(file_id, *span.start()..*span.end())])
+        .with_notes(vec![note])
+}
+
+pub(crate) fn confusable_identifier(
+    file_id: usize,
+    name: Symbol,
+    span: Span,
+    other: Symbol,
+    other_span: Span,
+) -> Diagnostic<FileId> {
+    // Either identifier may be the one spelled with look-alikes, or both
+    // may be, so the non-ASCII code points of each are listed.
+    let notes = [name, other]
+        .into_iter()
+        .filter_map(|identifier| {
+            let code_points = identifier
+                .as_str()
+                .chars()
+                .filter(|c| !c.is_ascii())
+                .map(|c| format!("U+{:04X}", u32::from(c)))
+                .collect::<Vec<_>>();
+            (!code_points.is_empty())
+                .then(|| format!("`{identifier}` is spelled with {}", code_points.join(", ")))
+        })
+        .collect();
+
+    DiagnosticCode::W0005
+        .diagnostic()
+        .with_message(format!("identifier `{name}` is confusable with `{other}`"))
+        .with_labels(vec![
+            Label::primary(file_id, *span.start()..*span.end()).with_message("this identifier"),
+            Label::secondary(file_id, *other_span.start()..*other_span.end())
+                .with_message("looks like this one"),
+        ])
+        .with_notes(notes)
+}
+
+pub(crate) fn mixed_script_identifier(
+    file_id: usize,
+    name: Symbol,
+    span: Span,
+    scripts: &[String],
+) -> Diagnostic<FileId> {
+    DiagnosticCode::W0006
+        .diagnostic()
+        .with_message(format!("identifier `{name}` mixes scripts: {}", scripts.join(", ")))
+        .with_labels(vec![Label::primary(file_id, *span.start()..*span.end())])
+}
+
+pub(crate) fn wrong_number_of_macro_arguments(
+    file_id: usize,
+    name: &Token,
+    definition: Span,
+    params: usize,
+    variadic: bool,
+    given: usize,
+) -> Diagnostic<FileId> {
+    let plural = |count: usize| if count == 1 { "" } else { "s" };
+    let at_least = if variadic { "at least " } else { "" };
+
+    DiagnosticCode::E0013
+        .diagnostic()
+        .with_message(format!(
+            "macro `{}` requires {at_least}{params} argument{}, but {given} {} given",
+            name.lexeme,
+            plural(params),
+            if given == 1 { "was" } else { "were" },
+        ))
+        .with_labels(vec![
+            Label::primary(file_id, *name.span.start()..*name.span.end())
+                .with_message(format!("{given} argument{} given", plural(given))),
+            Label::secondary(file_id, *definition.start()..*definition.end())
+                .with_message(format!("macro `{}` defined here", name.lexeme)),
+        ])
+        .with_expansion(name.expansion)
+}
+
+pub(crate) fn unterminated_macro_invocation(file_id: usize, name: &Token) -> Diagnostic<FileId> {
+    DiagnosticCode::E0014
+        .diagnostic()
+        .with_message(format!("unterminated invocation of macro `{}`", name.lexeme))
+        .with_labels(vec![Label::primary(file_id, *name.span.start()..*name.span.end())
+            .with_message("expected `)` before the end of the file")])
+        .with_expansion(name.expansion)
+}
+
+pub(crate) fn unsupported_directive(
+    file_id: usize,
+    span: Span,
+    directive: &str,
+) -> Diagnostic<FileId> {
+    DiagnosticCode::E0019
+        .diagnostic()
+        .with_message(format!("`#{directive}` is not supported by the selected standard"))
+        .with_labels(vec![Label::primary(file_id, *span.start()..*span.end())])
+        .with_notes(vec![format!("`#{directive}` requires --std=c23.")])
+}
+
+pub(crate) fn unterminated_conditional(file_id: usize, end: usize) -> Diagnostic<FileId> {
+    DiagnosticCode::E0020
+        .diagnostic()
+        .with_message("unterminated conditional directive")
+        .with_labels(vec![Label::primary(file_id, end..end)
+            .with_message("expected `#endif` before the end of the file")])
+}
+
+pub(crate) fn unmatched_conditional_directive(
+    file_id: usize,
+    span: Span,
+    directive: &str,
+) -> Diagnostic<FileId> {
+    DiagnosticCode::E0021
+        .diagnostic()
+        .with_message(format!("`#{directive}` without `#if`"))
+        .with_labels(vec![Label::primary(file_id, *span.start()..*span.end())])
+}
+
+pub(crate) fn directive_after_else(
+    file_id: usize,
+    span: Span,
+    directive: &str,
+) -> Diagnostic<FileId> {
+    DiagnosticCode::E0022
+        .diagnostic()
+        .with_message(format!("`#{directive}` after `#else`"))
+        .with_labels(vec![Label::primary(file_id, *span.start()..*span.end())])
+}
+
+pub(crate) fn expected_embed_filename(file_id: usize, span: Span) -> Diagnostic<FileId> {
+    DiagnosticCode::E0023
+        .diagnostic()
+        .with_message("expected \"FILENAME\" or <FILENAME> after `#embed`")
+        .with_labels(vec![Label::primary(file_id, *span.start()..*span.end())])
+}
+
+pub(crate) fn embed_file_not_found(
+    file_id: usize,
+    span: Span,
+    filename: &str,
+) -> Diagnostic<FileId> {
+    DiagnosticCode::E0024
+        .diagnostic()
+        .with_message(format!("`{filename}` file not found"))
+        .with_labels(vec![Label::primary(file_id, *span.start()..*span.end())])
+}
+
+/// An `#if` or `#elif` whose condition can't be evaluated, which is then
+/// taken to be false.
+pub(crate) fn invalid_condition(
+    file_id: usize,
+    span: Span,
+    directive: &str,
+    error: &ConditionError,
+) -> Diagnostic<FileId> {
+    DiagnosticCode::E0025
+        .diagnostic()
+        .with_message(format!("invalid `#{directive}` condition: {error}"))
+        .with_labels(vec![Label::primary(file_id, *span.start()..*span.end())])
+        .with_notes(vec!["The condition is taken to be false.".to_string()])
+}
+
+pub(crate) fn invalid_source_encoding(
+    file_id: usize,
+    span: Span,
+    bytes: &[u8],
+    charset: &str,
+) -> Diagnostic<FileId> {
+    let escaped = bytes.iter().map(|byte| format!("\\x{byte:02X}")).collect::<String>();
+    let mut notes = vec![];
+    if charset == "UTF-8" {
+        notes.push(
+            "help: if the file is in another encoding, name it with `-finput-charset=`, e.g. \
+             `-finput-charset=latin1`"
+                .to_string(),
+        );
+    }
+
+    DiagnosticCode::W0004
+        .diagnostic()
+        .with_message(format!("invalid {charset} in the source: `{escaped}`"))
+        .with_labels(vec![Label::primary(file_id, *span.start()..*span.end())
+            .with_message("replaced with U+FFFD")])
+        .with_notes(notes)
+}
+
+pub(crate) fn old_style_parameters_unsupported(
+    file_id: usize,
+    identifier: &Token,
+) -> Diagnostic<FileId> {
+    DiagnosticCode::E0008
+        .diagnostic()
+        .with_message(format!("Identifier lists are not supported in C23: '{}'", identifier.lexeme))
+        .with_labels(vec![Label::primary(
+            file_id,
+            *identifier.span.start()..*identifier.span.end(),
+        )
+        .with_message("expected a parameter declaration")])
+        .with_notes(vec![
+            "C23 removed old-style function definitions.".to_string(),
+            "Declare the type of each parameter, e.g. `int f(int a)`.".to_string(),
+        ])
+        .with_expansion(identifier.expansion)
+}
+
+pub(crate) fn old_style_function_definition(file_id: usize, name: &Token) -> Diagnostic<FileId> {
+    DiagnosticCode::W0001
+        .diagnostic()
+        .with_message(format!(
+            "old-style definition of '{}' is obsolescent",
+            name.lexeme
+        ))
+        .with_labels(vec![Label::primary(file_id, *name.span.start()..*name.span.end())
+            .with_message("parameters are declared after the declarator")])
+        .with_notes(vec![
+            "Declare the type of each parameter in the parameter list, e.g. `int f(int a)`."
+                .to_string(),
+        ])
+        .with_expansion(name.expansion)
+}
+
+pub(crate) fn implicit_int_parameter(file_id: usize, parameter: &Token) -> Diagnostic<FileId> {
+    DiagnosticCode::W0002
+        .diagnostic()
+        .with_message(format!(
+            "type of parameter '{}' defaults to 'int'",
+            parameter.lexeme
+        ))
+        .with_labels(vec![Label::primary(
+            file_id,
+            *parameter.span.start()..*parameter.span.end(),
         )
+        .with_message("parameter is not declared")])
+        .with_expansion(parameter.expansion)
+}
+
+pub(crate) fn undeclared_old_style_parameter(
+    file_id: usize,
+    declarator: &Token,
+) -> Diagnostic<FileId> {
+    DiagnosticCode::E0009
+        .diagnostic()
+        .with_message(format!(
+            "declaration for parameter '{}' but no such parameter",
+            declarator.lexeme
+        ))
+        .with_labels(vec![Label::primary(
+            file_id,
+            *declarator.span.start()..*declarator.span.end(),
+        )
+        .with_message("not in the identifier list")])
+        .with_notes(vec![
+            "Every declaration before the body of an old-style definition must declare one of \
+             its parameters."
+                .to_string(),
+        ])
+        .with_expansion(declarator.expansion)
+}
+
+pub(crate) fn misspelled_keyword(file_id: usize, name: &Token) -> Diagnostic<FileId> {
+    DiagnosticCode::E0010
+        .diagnostic()
+        .with_message(format!("'{}' is not a keyword or a type name", name.lexeme))
+        .with_labels(vec![Label::primary(file_id, *name.span.start()..*name.span.end())
+            .with_message("expected a keyword or a type name")])
+        .with_expansion(name.expansion)
+}
+
+pub(crate) fn unexpected_token_diagnostic(
+    file_id: usize,
+    unexpected_token: &Token,
+    expected: &TokenKind,
+) -> Diagnostic<FileId> {
+    let lexeme = &unexpected_token.lexeme;
+
+    DiagnosticCode::E0001
+        .diagnostic()
+        .with_message(format!("Unexpected token encountered: '{lexeme}'."))
+        .with_notes(vec![
+            format!("The parser encountered an unexpected token: '{lexeme}'"),
+            format!("It maybe useful to check the token preceding: '{lexeme}'"),
+        ])
         .with_labels(vec![
             Label::primary(file_id, *unexpected_token.span.start()..*unexpected_token.span.end())
-                .with_message(format!(
-                    "Unexpected token found here{} {}{}{}",
-                    ":".black(),
-                    "'".cyan(),
-                    unexpected_token.lexeme.yellow(),
-                    "'".cyan(),
-                )),
+                .with_message(format!("Unexpected token found here: '{lexeme}'")),
             Label::secondary(file_id, *unexpected_token.span.start()..*unexpected_token.span.end())
-                .with_message(format!(
-                    "Expected token{} {}{}{}",
-                    ":".black(),
-                    "'".cyan(),
-                    expected.to_string().green(),
-                    "'".cyan(),
-                )),
+                .with_message(format!("Expected token: '{expected}'")),
         ])
+        .with_expansion(unexpected_token.expansion)
 }
 
 pub(crate) fn unknown_token_diagnostic(file_id: usize, unknown_token: &Token) -> Diagnostic<usize> {
-    Diagnostic::error()
-        .with_code("E0000")
-        .with_message(format!(
-            "Unknown token encountered{} {}{}{}",
-            ":".black(),
-            "'".cyan(),
-            unknown_token.lexeme.yellow(),
-            "'".cyan(),
-        ))
-        .with_notes(
-            vec![
-                format!(
-                    "The lexer encountered an {}{} {}{}{}",
-                    "unknown token".red(),
-                    ":".black(),
-                    "'".cyan(),
-                    unknown_token.lexeme,
-                    "'".cyan(),
-                ),
-                format!(
-                    "This may be due to a {} or an {} in the input{}",
-                    "typo".magenta(),
-                    "unsupported character".magenta(),
-                    ".".black()
-                ),
-                format!(
-                    "Please check the input and make sure it contains {} {}{}",
-                    "ONLY".blue(),
-                    "supported tokens".green(),
-                    ".".black()
-                ),
-                format!(
-                    "For more information on {}{} please refer to the {}{}",
-                    "supported tokens".green(),
-                    ",".black(),
-                    "C Language Specification".cyan(),
-                    ".".black()
-                ),
-            ]
-            .into_iter()
-            .collect(),
-        )
+    let lexeme = &unknown_token.lexeme;
+
+    DiagnosticCode::E0000
+        .diagnostic()
+        .with_message(format!("Unknown token encountered: '{lexeme}'"))
+        .with_notes(vec![
+            format!("The lexer encountered an unknown token: '{lexeme}'"),
+            "This may be due to a typo or an unsupported character in the input.".to_string(),
+            "Please check the input and make sure it contains ONLY supported tokens.".to_string(),
+            "For more information on supported tokens, please refer to the C Language \
+             Specification."
+                .to_string(),
+        ])
         .with_labels(vec![
             Label::primary(file_id, *unknown_token.span.start()..*unknown_token.span.end())
-                .with_message(format!(
-                    "Unknown token found here{} {}{}{}",
-                    ":".black(),
-                    "'".cyan(),
-                    unknown_token.lexeme.yellow(),
-                    "'".cyan(),
-                )),
+                .with_message(format!("Unknown token found here: '{lexeme}'")),
             Label::secondary(file_id, *unknown_token.span.start()..*unknown_token.span.end())
-                .with_message(format!(
-                    "Valid tokens should be used exclusively in the input{}",
-                    ".".black()
-                )),
+                .with_message("Valid tokens should be used exclusively in the input."),
         ])
+        .with_expansion(unknown_token.expansion)
 }
 
+#[allow(dead_code)]
 pub(crate) fn expected_statement() -> Diagnostic<FileId> {
     // TODO: Implement this
     todo!()
 }
 
 pub(crate) fn expected_function_or_decl(file_id: usize, final_token: &Token) -> Diagnostic<FileId> {
-    let mut diagnostic = Diagnostic::error().with_code("E0002").with_message(format!(
-        "Expected {} or {}{} Instead found {}{}{}{}",
-        "function".green(),
-        "declaration".green(),
-        ".".black(),
-        "'".cyan(),
-        "EOF".yellow(),
-        "'".cyan(),
-        ".".black(),
-    ));
-
-    // Add notes with tips and examples
-    let mut notes = vec![
-        format!(
-            "You're missing a {} or a {} in your program{}\nHaving either a {} or a {} in your \
-             program is {} to {}{}",
-            "function definition".green(),
-            "variable declaration".green(),
-            ".".black(),
-            "function definition".green(),
-            "variable declaration".green(),
-            " REQUIRED ".on_red(),
-            "successfully compile".green(),
-            ".".black(),
-        ),
-        format!(
-                "{}{}\n\n{}{} {} a {} to your program{}\n\n{}{}\n\n{}\n{}\n{} {}{} {}, {} {}{} \
-                 {}\n    {} x {} y;\n{}\n{}\n{}\n\n",
-                "Tips for fixing this".yellow(),
-                ":".black(),
-                "1".magenta(),
-                ".".black(),
-                "Add".italic(),
-                "function definition".green(),
-                ".".black(),
-                "Example".blue(),
-                ":".black(),
-                "```".black(),
-                "+++++++++++".green(),
-                "int".red(),
-                "add".blue(),
-                "(int".red(),
-                "x".cyan(),
-                "int".red(),
-                "y".cyan(),
-                ")".red(),
-                "{".red(),
-                "return".cyan(),
-                "+".cyan(),
-                "}".red(),
-                "++++++++".green(),
-                "```".black(),
-            ),
-        // TODO: Example 2: Declaration
-        // "Example 2: Declaration".to_string(),
-        // format!("  {} {} {};", "int".cyan(), "x".yellow(), "{}",),
-        // "Ensure you have either a function definition or a declaration to start your
-        // program."     .to_string(),
-    ];
-
-    diagnostic = diagnostic.with_notes(notes);
-
-    // Add a label indicating the expected tokens
-    diagnostic = diagnostic.with_labels(vec![Label::primary(
-        file_id,
-        *final_token.span().start()..*final_token.span().end(),
-    )
-    .with_message(format!(
-        "Expected {}{}{}{}",
-        "function".green(),
-        " or ".black(),
-        "declaration".green(),
-        ".".black(),
-    ))]);
-
-    diagnostic
-
-    // Diagnostic::error()
-    //     .with_code("E0002")
-    //     .with_message(format!(
-    //         "Expected {} or {}{} Instead found {}{}{}{}",
-    //         "function".green(),
-    //         "declaration".green(),
-    //         ".".black(),
-    //         "'".cyan(),
-    //         "EOF".yellow(),
-    //         "'".cyan(),
-    //         ".".black(),
-    //     ))
-    //     .with_notes(
-    //         vec![
-    //             // TODO: Add a note about adding either a function or a
-    // declaration             // with some examples
-    //         ]
-    //         .into_iter()
-    //         .collect(),
-    //     )
-    //     .with_labels(vec![Label::primary(
-    //         file_id,
-    //         *final_token.span().start()..*final_token.span().end(),
-    //     )
-    //     .with_message(format!(
-    //         "Expected {}{}{}{}",
-    //         "function".green(),
-    //         " or ".black(),
-    //         "declaration".green(),
-    //         ".".black(),
-    //     ))])
+    DiagnosticCode::E0002
+        .diagnostic()
+        .with_message("Expected function or declaration. Instead found 'EOF'.")
+        .with_notes(vec![
+            "You're missing a function definition or a variable declaration in your program.\n\
+             Having either a function definition or a variable declaration in your program is \
+             REQUIRED to successfully compile."
+                .to_string(),
+            "Tips for fixing this:\n\n1. Add a function definition to your program.\n\n\
+             Example:\n\n```\nint add(int x, int y) {\n    return x + y;\n}\n```\n\n"
+                .to_string(),
+        ])
+        .with_labels(vec![Label::primary(
+            file_id,
+            *final_token.span().start()..*final_token.span().end(),
+        )
+        .with_message("Expected function or declaration.")])
+        .with_expansion(final_token.expansion)
 }
 
+#[allow(dead_code)]
 pub(crate) fn expected_declaration_specifier(
     file_id: usize,
     unexpected_token: &Token,
@@ -359,20 +1045,20 @@ pub(crate) fn expected_declaration_specifier(
 ) -> Diagnostic<usize> {
     let expected_specifiers_str = expected_specifiers
         .iter()
-        .map(|kind| format!("'{}'", kind.to_string().green()))
+        .map(|kind| format!("'{kind}'"))
         .collect::<Vec<String>>()
         .join(", ");
 
-    let mut diagnostic = Diagnostic::error()
-        .with_code("E0004")
-        .with_message(format!(
-            "type specifier missing, defaults to 'int'; ISO C99 and later do not support implicit \
-             int [-Wimplicit-int]"
-        ))
+    let mut diagnostic = DiagnosticCode::E0004
+        .diagnostic()
+        .with_message(
+            "type specifier missing, defaults to 'int'; ISO C99 and later do not support \
+             implicit int",
+        )
         .with_notes(vec![
             format!(
                 "The token '{}' is not a valid declaration specifier.",
-                unexpected_token.lexeme.yellow()
+                unexpected_token.lexeme
             ),
             format!("Valid declaration specifiers include: {}", expected_specifiers_str),
             "Declaration specifiers determine the type of a declaration.".to_string(),
@@ -384,7 +1070,7 @@ pub(crate) fn expected_declaration_specifier(
             file_id,
             *unexpected_token.span.start()..*unexpected_token.span.end(),
         )
-        .with_message(format!("Unexpected token '{}'", unexpected_token.lexeme.yellow()))]);
+        .with_message(format!("Unexpected token '{}'", unexpected_token.lexeme))]);
 
     // Add a label indicating the defaulting to 'int' behavior
     diagnostic = diagnostic.with_labels(vec![Label::secondary(
@@ -393,162 +1079,164 @@ pub(crate) fn expected_declaration_specifier(
     )
     .with_message("Type defaults to 'int'")]);
 
-    diagnostic
-}
-
-// pub(crate) fn type_specifier_missing(
-//     file_id: FileId,
-//     unexpected_token: &Token,
-// ) -> Diagnostic<FileId> {
-//     Diagnostic::error()
-//         .with_code("E0005")
-//         .with_message(format!(
-//             "type specifier missing, defaults to 'int'; ISO C99 and later do
-// not support implicit \              int [-Wimplicit-int]"
-//         ))
-//         .with_labels(vec![Label::primary(
-//             file_id,
-//             *unexpected_token.span.start()..*unexpected_token.span.end(),
-//         )
-//         .with_message("Type specifier missing, defaults to 'int'")])
-//         .with_notes(vec![
-//             format!(
-//                 "The token '{}' is not a valid declaration specifier.",
-//                 unexpected_token.lexeme.yellow()
-//             ),
-//             "Valid declaration specifiers include: 'void', 'char', 'short',
-// 'int', 'long', \              'float', 'double', 'signed', 'unsigned',
-// 'struct', 'union', 'enum', 'IDENTIFIER'"                 .to_string(),
-//             "Declaration specifiers determine the type of a
-// declaration.".to_string(),             "Ensure you use one of the valid
-// declaration specifiers when declaring a function or \              variable."
-//                 .to_string(),
-//         ])
-// }
+    diagnostic.with_expansion(unexpected_token.expansion)
+}
 
 pub(crate) fn type_specifier_missing(
     file_id: FileId,
     unexpected_token: &Token,
 ) -> Diagnostic<FileId> {
-    let mut diagnostic = Diagnostic::error().with_code("E0005").with_message(format!(
-        "{} {}{} defaults to {}{}{}{} {} and {} {} support {} {}{}{}",
-        "Type specifier".cyan(),
-        "missing".yellow().italic(),
-        ",".black(),
-        "'".green(),
-        "int".magenta(),
-        "'".green(),
-        ";".black(),
-        "ISO C99".blue(),
-        "later".blue(),
-        "DO NOT".red(),
-        "implicit int".magenta(),
-        "[".black(),
-        "-Wimplicit-int".red(),
-        "]".black(),
-    ));
-
-    // Add labels with semantic highlighting
-    diagnostic = diagnostic.with_labels(vec![Label::primary(
-        file_id,
-        *unexpected_token.span.start()..*unexpected_token.span.end(),
-    )
-    .with_message(format!(
-        // "Type specifier {}{} defaults to {}{}{}{}",
-        "{} {}{} defaults to {}{}{}{}",
-        "Type specifier".cyan(),
-        "missing".yellow().italic(),
-        ",".black(),
-        "'".green(),
-        "int".magenta(),
-        "'".green(),
-        ".".black(),
-    ))]);
-
-    // Add notes with semantic highlighting
-    let notes = vec![
-        format!(
-            "The token {}{}{} is {} a valid {}{}\n\n{}{}\n\n{}\n\n",
-            "'".cyan(),
-            unexpected_token.lexeme.yellow(),
-            "'".cyan(),
-            "NOT".red(),
-            "type specifier".cyan(),
-            ".".black(),
-            "Valid type specifiers include",
-            ":".black(),
-            display(FN_DEF_DECLARATION_SPECIFIERS_FIRST),
-        ),
-        format!(
-            "{} determine the {} of a {} {}e{}g{} {}{} {};{}{} and are {} when {} a {} {} {}{}",
-            "Type specifiers".cyan(),
-            "type".magenta(),
-            "declaration".green(),
-            "(".black(),
-            ".".black(),
-            ".".black(),
-            "`".yellow(),
-            "int".red(),
-            "x".cyan(),
-            "`".yellow(),
-            ")".black(),
-            " REQUIRED ".on_red(),
-            "declaring".green(),
-            "function".green(),
-            "or".black(),
-            "variable".green(),
-            ".".black(),
-        ),
-        format!(
-            "Ensure you use one of the {} {} when {} a {} {} {}{}\n\n",
-            "valid".green(),
-            "type specifiers".cyan(),
-            "declaring".green(),
-            "function".green(),
-            "or".black(),
-            "variable".green(),
-            ".".black(),
-        ),
-        format!(
-            "For more information on {}{} please refer to the {}{}",
-            "type specifiers".cyan(),
-            ",".black(),
-            "C Language Specification".cyan(),
-            ".".black(),
-        ),
-        format!(
-            "You can also use {}{} {}{} {}{} to get more information about {}{}",
-            "`".yellow(),
-            "rcc".green(),
-            "--".black(),
-            "explain".yellow(),
-            "E0005".red(),
-            "`".yellow(),
-            "type specifiers".cyan(),
-            ".".black(),
-        ),
-    ];
-
-    diagnostic = diagnostic.with_notes(notes);
-
-    diagnostic
+    let valid_specifiers = FN_DEF_DECLARATION_SPECIFIERS_FIRST
+        .iter()
+        .map(|kind| format!("'{kind}'"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    DiagnosticCode::E0005
+        .diagnostic()
+        .with_message(
+            "Type specifier missing, defaults to 'int'; ISO C99 and later DO NOT support implicit \
+             int",
+        )
+        .with_labels(vec![Label::primary(
+            file_id,
+            *unexpected_token.span.start()..*unexpected_token.span.end(),
+        )
+        .with_message("Type specifier missing, defaults to 'int'.")])
+        .with_notes(vec![
+            format!(
+                "The token '{}' is NOT a valid type specifier.\n\nValid type specifiers \
+                 include:\n\n{valid_specifiers}\n\n",
+                unexpected_token.lexeme
+            ),
+            "Type specifiers determine the type of a declaration (e.g. `int x;`) and are \
+             REQUIRED when declaring a function or variable."
+                .to_string(),
+            "Ensure you use one of the valid type specifiers when declaring a function or \
+             variable.\n\n"
+                .to_string(),
+            "For more information on type specifiers, please refer to the C Language \
+             Specification."
+                .to_string(),
+            "You can also use `rcc --explain E0005` to get more information about type \
+             specifiers."
+                .to_string(),
+        ])
+        .with_expansion(unexpected_token.expansion)
 }
 
+/// A non-`void` function reaching the closing brace at `range`.
 pub(crate) fn non_void_function_doesnt_return_value(
     file_id: usize,
     range: Span,
+    error: &FlowError,
 ) -> Diagnostic<FileId> {
-    Diagnostic::error()
-        .with_code("E0006")
-        .with_message(format!(
-            "non-void function does not return a value in all control paths [-Wreturn-type]"
-        ))
+    DiagnosticCode::W0009
+        .diagnostic()
+        .with_message(error.to_string())
+        .with_labels(vec![Label::primary(file_id, *range.start()..*range.end())
+            .with_message("control reaches the end of the function without a `return`")])
+        .with_notes(vec!["Ensure you return a value in all control paths.".to_string()])
+}
+
+/// The diagnostic for a problem found by the control-flow checks of a
+/// function, at the statement at `range`.
+pub(crate) fn control_flow_diagnostic(
+    file_id: usize,
+    range: Span,
+    error: &FlowError,
+) -> Diagnostic<FileId> {
+    match error {
+        FlowError::MissingReturn(_) => {
+            non_void_function_doesnt_return_value(file_id, range, error)
+        }
+        FlowError::BreakOutsideLoop | FlowError::ContinueOutsideLoop => {
+            jump_outside_loop(file_id, range, error)
+        }
+        FlowError::CaseOutsideSwitch | FlowError::DefaultOutsideSwitch => {
+            switch_label_outside_switch(file_id, range, error)
+        }
+        FlowError::DuplicateCase(_) | FlowError::DuplicateDefault => {
+            duplicate_switch_label(file_id, range, error)
+        }
+        FlowError::UndefinedLabel(_) => undefined_label(file_id, range, error),
+        FlowError::UnusedLabel(_) => unused_label(file_id, range, error),
+        FlowError::UnreachableCode => unreachable_code(file_id, range, error),
+    }
+}
+
+pub(crate) fn jump_outside_loop(
+    file_id: usize,
+    range: Span,
+    error: &FlowError,
+) -> Diagnostic<FileId> {
+    DiagnosticCode::E0015
+        .diagnostic()
+        .with_message(error.to_string())
+        .with_labels(vec![Label::primary(file_id, *range.start()..*range.end())
+            .with_message(match error {
+                FlowError::ContinueOutsideLoop => "not inside a loop",
+                _ => "not inside a loop or switch",
+            })])
+}
+
+pub(crate) fn switch_label_outside_switch(
+    file_id: usize,
+    range: Span,
+    error: &FlowError,
+) -> Diagnostic<FileId> {
+    DiagnosticCode::E0016
+        .diagnostic()
+        .with_message(error.to_string())
+        .with_labels(vec![Label::primary(file_id, *range.start()..*range.end())
+            .with_message("not inside a switch")])
+}
+
+pub(crate) fn duplicate_switch_label(
+    file_id: usize,
+    range: Span,
+    error: &FlowError,
+) -> Diagnostic<FileId> {
+    DiagnosticCode::E0017
+        .diagnostic()
+        .with_message(error.to_string())
+        .with_labels(vec![Label::primary(file_id, *range.start()..*range.end())
+            .with_message("already used in this switch")])
+}
+
+pub(crate) fn undefined_label(
+    file_id: usize,
+    range: Span,
+    error: &FlowError,
+) -> Diagnostic<FileId> {
+    DiagnosticCode::E0018
+        .diagnostic()
+        .with_message(error.to_string())
+        .with_labels(vec![Label::primary(file_id, *range.start()..*range.end())
+            .with_message("labels are only visible in the function defining them")])
+}
+
+pub(crate) fn unused_label(file_id: usize, range: Span, error: &FlowError) -> Diagnostic<FileId> {
+    DiagnosticCode::W0007
+        .diagnostic()
+        .with_message(error.to_string())
+        .with_labels(vec![Label::primary(file_id, *range.start()..*range.end())
+            .with_message("no `goto` jumps here")])
+}
+
+pub(crate) fn unreachable_code(
+    file_id: usize,
+    range: Span,
+    error: &FlowError,
+) -> Diagnostic<FileId> {
+    DiagnosticCode::W0008
+        .diagnostic()
+        .with_message(error.to_string())
         .with_labels(vec![Label::primary(file_id, *range.start()..*range.end())
-            .with_message("non-void function does not return a value in all control paths")])
+            .with_message("never executed")])
         .with_notes(vec![
-            "A function with a non-void return type must return a value in all control paths."
+            "every path to it leaves with a `return`, `break`, `continue` or `goto` first"
                 .to_string(),
-            "Ensure you return a value in all control paths.".to_string(),
         ])
 }
 
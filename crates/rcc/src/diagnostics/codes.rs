@@ -0,0 +1,413 @@
+//! The registry of diagnostic codes, e.g. `E0005`.
+//!
+//! Every diagnostic `rcc` emits with a code gets it from [`DiagnosticCode`],
+//! along with its severity. The long-form explanation of each code lives in
+//! `codes/<code>.md` and is printed by `rcc --explain <code>`.
+//!
+//! Codes are never reused: once a diagnostic is removed, its code stays
+//! retired (as `E0003` and `E0006` are). A retired code whose diagnostic
+//! was replaced keeps an explanation pointing at its successor, see
+//! [`DiagnosticCode::retired`].
+
+use super::{
+    Diagnostic,
+    FileId,
+    Severity,
+    WarningGroup,
+};
+use strum_macros::{
+    Display,
+    EnumIter,
+    EnumString,
+};
+
+#[derive(Debug, Display, EnumIter, EnumString, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticCode {
+    E0000,
+    E0001,
+    E0002,
+    E0004,
+    E0005,
+    E0007,
+    E0008,
+    E0009,
+    E0010,
+    E0011,
+    E0012,
+    E0013,
+    E0014,
+    E0015,
+    E0016,
+    E0017,
+    E0018,
+    E0019,
+    E0020,
+    E0021,
+    E0022,
+    E0023,
+    E0024,
+    E0025,
+    W0001,
+    W0002,
+    W0003,
+    W0004,
+    W0005,
+    W0006,
+    W0007,
+    W0008,
+    W0009,
+}
+
+impl DiagnosticCode {
+    /// The severity of every diagnostic with this code.
+    pub fn severity(self) -> Severity {
+        match self {
+            DiagnosticCode::W0001 |
+            DiagnosticCode::W0002 |
+            DiagnosticCode::W0003 |
+            DiagnosticCode::W0004 |
+            DiagnosticCode::W0005 |
+            DiagnosticCode::W0006 |
+            DiagnosticCode::W0007 |
+            DiagnosticCode::W0008 |
+            DiagnosticCode::W0009 => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+
+    /// The group controlling a warning, e.g. `-Wimplicit-int`. Errors
+    /// belong to no group.
+    pub fn group(self) -> Option<WarningGroup> {
+        match self {
+            DiagnosticCode::W0001 => Some(WarningGroup::OldStyleDefinition),
+            DiagnosticCode::W0002 => Some(WarningGroup::ImplicitInt),
+            DiagnosticCode::W0003 => Some(WarningGroup::Trigraphs),
+            DiagnosticCode::W0004 => Some(WarningGroup::InvalidSourceEncoding),
+            DiagnosticCode::W0005 => Some(WarningGroup::ConfusableIdentifiers),
+            DiagnosticCode::W0006 => Some(WarningGroup::MixedScriptIdentifiers),
+            DiagnosticCode::W0007 => Some(WarningGroup::Unused),
+            DiagnosticCode::W0008 => Some(WarningGroup::UnreachableCode),
+            DiagnosticCode::W0009 => Some(WarningGroup::ReturnType),
+            _ => None,
+        }
+    }
+
+    /// A one-line summary, e.g. `type specifier missing`.
+    pub fn title(self) -> &'static str {
+        match self {
+            DiagnosticCode::E0000 => "unknown token",
+            DiagnosticCode::E0001 => "unexpected token",
+            DiagnosticCode::E0002 => "expected a function or declaration",
+            DiagnosticCode::E0004 => "expected a declaration specifier",
+            DiagnosticCode::E0005 => "type specifier missing",
+            DiagnosticCode::E0007 => "literal not supported by the selected standard",
+            DiagnosticCode::E0008 => "identifier lists are not supported in C23",
+            DiagnosticCode::E0009 => "declaration for a parameter not in the identifier list",
+            DiagnosticCode::E0010 => "unknown name in place of a keyword or type name",
+            DiagnosticCode::E0011 => "character not allowed in an identifier",
+            DiagnosticCode::E0012 => "invalid universal character name",
+            DiagnosticCode::E0013 => "macro invoked with the wrong number of arguments",
+            DiagnosticCode::E0014 => "unterminated macro invocation",
+            DiagnosticCode::E0015 => "`break` or `continue` outside of a loop",
+            DiagnosticCode::E0016 => "`case` or `default` label outside of a switch",
+            DiagnosticCode::E0017 => "label repeated in a switch",
+            DiagnosticCode::E0018 => "`goto` to an undefined label",
+            DiagnosticCode::E0019 => "directive not supported by the selected standard",
+            DiagnosticCode::E0020 => "unterminated conditional directive",
+            DiagnosticCode::E0021 => "conditional directive without `#if`",
+            DiagnosticCode::E0022 => "conditional directive after `#else`",
+            DiagnosticCode::E0023 => "malformed `#embed`",
+            DiagnosticCode::E0024 => "`#embed` file not found",
+            DiagnosticCode::E0025 => "invalid `#if` condition",
+            DiagnosticCode::W0001 => "old-style function definition",
+            DiagnosticCode::W0002 => "type of parameter defaults to `int`",
+            DiagnosticCode::W0003 => "trigraph in the source",
+            DiagnosticCode::W0004 => "source isn't valid in the input charset",
+            DiagnosticCode::W0005 => "identifier confusable with another",
+            DiagnosticCode::W0006 => "identifier mixing scripts",
+            DiagnosticCode::W0007 => "label defined but not used",
+            DiagnosticCode::W0008 => "unreachable code",
+            DiagnosticCode::W0009 => "non-void function does not return a value",
+        }
+    }
+
+    /// The long-form explanation, in Markdown, with examples.
+    pub fn explanation(self) -> &'static str {
+        match self {
+            DiagnosticCode::E0000 => include_str!("codes/E0000.md"),
+            DiagnosticCode::E0001 => include_str!("codes/E0001.md"),
+            DiagnosticCode::E0002 => include_str!("codes/E0002.md"),
+            DiagnosticCode::E0004 => include_str!("codes/E0004.md"),
+            DiagnosticCode::E0005 => include_str!("codes/E0005.md"),
+            DiagnosticCode::E0007 => include_str!("codes/E0007.md"),
+            DiagnosticCode::E0008 => include_str!("codes/E0008.md"),
+            DiagnosticCode::E0009 => include_str!("codes/E0009.md"),
+            DiagnosticCode::E0010 => include_str!("codes/E0010.md"),
+            DiagnosticCode::E0011 => include_str!("codes/E0011.md"),
+            DiagnosticCode::E0012 => include_str!("codes/E0012.md"),
+            DiagnosticCode::E0013 => include_str!("codes/E0013.md"),
+            DiagnosticCode::E0014 => include_str!("codes/E0014.md"),
+            DiagnosticCode::E0015 => include_str!("codes/E0015.md"),
+            DiagnosticCode::E0016 => include_str!("codes/E0016.md"),
+            DiagnosticCode::E0017 => include_str!("codes/E0017.md"),
+            DiagnosticCode::E0018 => include_str!("codes/E0018.md"),
+            DiagnosticCode::E0019 => include_str!("codes/E0019.md"),
+            DiagnosticCode::E0020 => include_str!("codes/E0020.md"),
+            DiagnosticCode::E0021 => include_str!("codes/E0021.md"),
+            DiagnosticCode::E0022 => include_str!("codes/E0022.md"),
+            DiagnosticCode::E0023 => include_str!("codes/E0023.md"),
+            DiagnosticCode::E0024 => include_str!("codes/E0024.md"),
+            DiagnosticCode::E0025 => include_str!("codes/E0025.md"),
+            DiagnosticCode::W0001 => include_str!("codes/W0001.md"),
+            DiagnosticCode::W0002 => include_str!("codes/W0002.md"),
+            DiagnosticCode::W0003 => include_str!("codes/W0003.md"),
+            DiagnosticCode::W0004 => include_str!("codes/W0004.md"),
+            DiagnosticCode::W0005 => include_str!("codes/W0005.md"),
+            DiagnosticCode::W0006 => include_str!("codes/W0006.md"),
+            DiagnosticCode::W0007 => include_str!("codes/W0007.md"),
+            DiagnosticCode::W0008 => include_str!("codes/W0008.md"),
+            DiagnosticCode::W0009 => include_str!("codes/W0009.md"),
+        }
+    }
+
+    /// The explanation of a retired `code`, which no diagnostic uses any
+    /// more but `rcc --explain` still knows, e.g. `E0006`.
+    pub fn retired(code: &str) -> Option<&'static str> {
+        match code {
+            "E0006" => Some(include_str!("codes/E0006.md")),
+            _ => None,
+        }
+    }
+
+    /// An empty diagnostic with this code and its severity, to be filled in
+    /// with a message, labels and notes.
+    pub(crate) fn diagnostic(self) -> Diagnostic<FileId> {
+        Diagnostic::new(self.severity()).with_code(self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        condition::ConditionError,
+        diagnostics,
+        flow::FlowError,
+        lexer::{
+            Span,
+            Token,
+            TokenKind,
+        },
+    };
+    use std::collections::HashMap;
+    use strum::IntoEnumIterator;
+
+    /// A diagnostic of every constructor, by name.
+    fn constructed() -> Vec<(&'static str, Diagnostic<FileId>)> {
+        let token = Token::new(TokenKind::IDENTIFIER, "x".to_string(), Span::new(0, 1));
+        vec![
+            ("unknown_token_diagnostic", diagnostics::unknown_token_diagnostic(0, &token)),
+            (
+                "unexpected_token_diagnostic",
+                diagnostics::unexpected_token_diagnostic(0, &token, &TokenKind::SEMICOLON),
+            ),
+            ("expected_function_or_decl", diagnostics::expected_function_or_decl(0, &token)),
+            (
+                "expected_declaration_specifier",
+                diagnostics::expected_declaration_specifier(0, &token, &[TokenKind::INT_KW]),
+            ),
+            ("type_specifier_missing", diagnostics::type_specifier_missing(0, &token)),
+            (
+                "non_void_function_doesnt_return_value",
+                diagnostics::non_void_function_doesnt_return_value(
+                    0,
+                    Span::new(0, 1),
+                    &FlowError::MissingReturn("f".into()),
+                ),
+            ),
+            (
+                "unsupported_directive",
+                diagnostics::unsupported_directive(0, Span::new(0, 9), "elifdef"),
+            ),
+            ("unterminated_conditional", diagnostics::unterminated_conditional(0, 9)),
+            (
+                "unmatched_conditional_directive",
+                diagnostics::unmatched_conditional_directive(0, Span::new(0, 6), "endif"),
+            ),
+            ("directive_after_else", diagnostics::directive_after_else(0, Span::new(0, 5), "else")),
+            ("expected_embed_filename", diagnostics::expected_embed_filename(0, Span::new(0, 6))),
+            (
+                "embed_file_not_found",
+                diagnostics::embed_file_not_found(0, Span::new(0, 15), "data.bin"),
+            ),
+            (
+                "invalid_condition",
+                diagnostics::invalid_condition(
+                    0,
+                    Span::new(0, 3),
+                    "if",
+                    &ConditionError::ExpectedExpression,
+                ),
+            ),
+            (
+                "jump_outside_loop",
+                diagnostics::jump_outside_loop(0, Span::new(0, 1), &FlowError::BreakOutsideLoop),
+            ),
+            (
+                "switch_label_outside_switch",
+                diagnostics::switch_label_outside_switch(
+                    0,
+                    Span::new(0, 1),
+                    &FlowError::CaseOutsideSwitch,
+                ),
+            ),
+            (
+                "duplicate_switch_label",
+                diagnostics::duplicate_switch_label(
+                    0,
+                    Span::new(0, 1),
+                    &FlowError::DuplicateCase(1),
+                ),
+            ),
+            (
+                "undefined_label",
+                diagnostics::undefined_label(
+                    0,
+                    Span::new(0, 1),
+                    &FlowError::UndefinedLabel("out".into()),
+                ),
+            ),
+            (
+                "unused_label",
+                diagnostics::unused_label(
+                    0,
+                    Span::new(0, 1),
+                    &FlowError::UnusedLabel("out".into()),
+                ),
+            ),
+            (
+                "unreachable_code",
+                diagnostics::unreachable_code(0, Span::new(0, 1), &FlowError::UnreachableCode),
+            ),
+            (
+                "unsupported_literal_diagnostic",
+                diagnostics::unsupported_literal_diagnostic(0, &token, "Literals", "--std=c23"),
+            ),
+            (
+                "old_style_parameters_unsupported",
+                diagnostics::old_style_parameters_unsupported(0, &token),
+            ),
+            (
+                "undeclared_old_style_parameter",
+                diagnostics::undeclared_old_style_parameter(0, &token),
+            ),
+            (
+                "old_style_function_definition",
+                diagnostics::old_style_function_definition(0, &token),
+            ),
+            ("implicit_int_parameter", diagnostics::implicit_int_parameter(0, &token)),
+            ("misspelled_keyword", diagnostics::misspelled_keyword(0, &token)),
+            ("trigraph", diagnostics::trigraph(0, "??=", Span::new(0, 3), '#', true)),
+            (
+                "character_not_allowed_in_identifier",
+                diagnostics::character_not_allowed_in_identifier(0, Span::new(0, 2), '£', false),
+            ),
+            (
+                "invalid_universal_character_name",
+                diagnostics::invalid_universal_character_name(0, Span::new(0, 6), "\\u0041", 0x41),
+            ),
+            (
+                "wrong_number_of_macro_arguments",
+                diagnostics::wrong_number_of_macro_arguments(
+                    0,
+                    &token,
+                    Span::new(8, 9),
+                    2,
+                    false,
+                    1,
+                ),
+            ),
+            (
+                "unterminated_macro_invocation",
+                diagnostics::unterminated_macro_invocation(0, &token),
+            ),
+            (
+                "confusable_identifier",
+                diagnostics::confusable_identifier(
+                    0,
+                    "pаypal".into(),
+                    Span::new(0, 7),
+                    "paypal".into(),
+                    Span::new(8, 14),
+                ),
+            ),
+            (
+                "mixed_script_identifier",
+                diagnostics::mixed_script_identifier(
+                    0,
+                    "pаypal".into(),
+                    Span::new(0, 7),
+                    &["Latin".to_string(), "Cyrillic".to_string()],
+                ),
+            ),
+            (
+                "invalid_source_encoding",
+                diagnostics::invalid_source_encoding(0, Span::new(0, 3), &[0xE9], "UTF-8"),
+            ),
+        ]
+    }
+
+    #[test]
+    fn constructors_have_unique_codes() {
+        let mut constructors_by_code = HashMap::new();
+        for (constructor, diagnostic) in constructed() {
+            let code = diagnostic.code.as_deref().unwrap_or_else(|| panic!("{constructor}"));
+            let code = code.parse::<DiagnosticCode>().unwrap_or_else(|_| {
+                panic!("`{constructor}` uses the unregistered code {code}")
+            });
+
+            assert_eq!(diagnostic.severity, code.severity(), "{constructor}");
+            if let Some(other) = constructors_by_code.insert(code, constructor) {
+                panic!("`{constructor}` and `{other}` share the code {code}");
+            }
+        }
+
+        let unused = DiagnosticCode::iter()
+            .filter(|code| !constructors_by_code.contains_key(code))
+            .collect::<Vec<_>>();
+        assert!(unused.is_empty(), "no constructor uses {unused:?}");
+    }
+
+    #[test]
+    fn constructors_are_plain_text() {
+        for (constructor, diagnostic) in constructed() {
+            let texts = std::iter::once(&diagnostic.message)
+                .chain(&diagnostic.notes)
+                .chain(diagnostic.labels.iter().map(|label| &label.message));
+            for text in texts {
+                assert!(!text.contains('\x1b'), "`{constructor}` styles {text:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn codes_are_explained() {
+        for code in DiagnosticCode::iter() {
+            assert_eq!(code.to_string().parse::<DiagnosticCode>(), Ok(code));
+            assert!(!code.title().is_empty(), "{code}");
+            assert!(code.explanation().contains("```c"), "{code} has no example");
+            assert_eq!(
+                code.group().is_some(),
+                code.severity() == Severity::Warning,
+                "{code} is a warning without a group or an error with one"
+            );
+        }
+    }
+
+    #[test]
+    fn retired_codes_are_explained() {
+        assert!("E0006".parse::<DiagnosticCode>().is_err());
+        assert!(DiagnosticCode::retired("E0006").unwrap().contains("W0009"));
+        assert_eq!(DiagnosticCode::retired("E0005"), None);
+    }
+}
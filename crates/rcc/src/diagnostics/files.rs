@@ -0,0 +1,121 @@
+//! The files diagnostics point into.
+//!
+//! [`SourceFiles`] takes the place of `codespan_reporting`'s `SimpleFiles`,
+//! keeping a [`LineIndex`] with each file, so that every output format gets
+//! its lines and columns from the same place, without rescanning the file.
+
+use super::FileId;
+use crate::line_index::{
+    Encoding,
+    LineIndex,
+};
+use codespan_reporting::files::{
+    Error,
+    Files,
+    Location,
+};
+use std::ops::Range;
+
+/// A file added to a [`DiagnosticsEngine`](super::DiagnosticsEngine).
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    name:       String,
+    source:     String,
+    line_index: LineIndex,
+}
+
+impl SourceFile {
+    pub fn new(name: impl Into<String>, source: impl Into<String>) -> Self {
+        let source = source.into();
+        let line_index = LineIndex::new(&source);
+        Self { name: name.into(), source, line_index }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn line_index(&self) -> &LineIndex {
+        &self.line_index
+    }
+}
+
+/// The files added to a [`DiagnosticsEngine`](super::DiagnosticsEngine),
+/// by [`FileId`].
+#[derive(Debug, Clone, Default)]
+pub struct SourceFiles {
+    files: Vec<SourceFile>,
+}
+
+impl SourceFiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file, returning its id.
+    pub fn add(&mut self, name: impl Into<String>, source: impl Into<String>) -> FileId {
+        self.files.push(SourceFile::new(name, source));
+        self.files.len() - 1
+    }
+
+    pub fn get(&self, file_id: FileId) -> Result<&SourceFile, Error> {
+        self.files.get(file_id).ok_or(Error::FileMissing)
+    }
+
+    /// The 1-based line and column of `byte_index` in `file_id`, with the
+    /// column counting `encoding`.
+    pub fn location_in(
+        &self,
+        file_id: FileId,
+        byte_index: usize,
+        encoding: Encoding,
+    ) -> Result<Location, Error> {
+        let file = self.get(file_id)?;
+        if byte_index > file.source.len() {
+            return Err(Error::IndexTooLarge { given: byte_index, max: file.source.len() });
+        }
+
+        let position = file.line_index.position(byte_index, encoding);
+        Ok(Location { line_number: position.line + 1, column_number: position.col + 1 })
+    }
+}
+
+impl<'a> Files<'a> for SourceFiles {
+    type FileId = FileId;
+    type Name = &'a str;
+    type Source = &'a str;
+
+    fn name(&'a self, file_id: FileId) -> Result<&'a str, Error> {
+        Ok(self.get(file_id)?.name())
+    }
+
+    fn source(&'a self, file_id: FileId) -> Result<&'a str, Error> {
+        Ok(self.get(file_id)?.source())
+    }
+
+    fn line_index(&'a self, file_id: FileId, byte_index: usize) -> Result<usize, Error> {
+        let file = self.get(file_id)?;
+        if byte_index > file.source.len() {
+            return Err(Error::IndexTooLarge { given: byte_index, max: file.source.len() });
+        }
+
+        Ok(file.line_index.line_col(byte_index).line)
+    }
+
+    fn line_range(&'a self, file_id: FileId, line_index: usize) -> Result<Range<usize>, Error> {
+        let file = self.get(file_id)?;
+        file.line_index.line_range(line_index).ok_or(Error::LineTooLarge {
+            given: line_index,
+            max:   file.line_index.line_count() - 1,
+        })
+    }
+
+    /// Columns count characters, as `codespan_reporting` does.
+    fn location(&'a self, file_id: FileId, byte_index: usize) -> Result<Location, Error> {
+        self.location_in(file_id, byte_index, Encoding::Utf32)
+    }
+}
@@ -0,0 +1,409 @@
+//! Renderers for the formats selected with `--diagnostic-format=`.
+//!
+//! `human` is `codespan_reporting`'s rich format; the others are meant for
+//! tools: `json` writes one object per diagnostic and per line, `sarif` a
+//! single SARIF 2.1.0 log, and `gcc` one `file:line:col: severity: message`
+//! line per diagnostic and per note, as editors and CI log parsers expect.
+//!
+//! Columns are 1-based and count characters, except in `sarif`, where they
+//! count UTF-16 code units, as its `columnKind` says.
+
+use super::{
+    Diagnostic,
+    DiagnosticCode,
+    FileId,
+    Label,
+    Severity,
+    SourceFiles,
+    Suggestion,
+};
+use crate::line_index::Encoding;
+use codespan_reporting::{
+    diagnostic::LabelStyle,
+    files::{
+        Files,
+        Location,
+    },
+};
+use serde_json::json;
+use std::{
+    io::{
+        self,
+        Write,
+    },
+    ops::Range,
+};
+use strum::IntoEnumIterator;
+use strum_macros::{
+    Display,
+    EnumString,
+};
+
+#[derive(Debug, Default, Display, EnumString, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticFormat {
+    /// Rich, multi-line diagnostics with source excerpts.
+    #[default]
+    #[strum(serialize = "human")]
+    Human,
+    /// One JSON object per diagnostic and per line.
+    #[strum(serialize = "json")]
+    Json,
+    /// A SARIF 2.1.0 log, for code-scanning tools.
+    #[strum(serialize = "sarif")]
+    Sarif,
+    /// One `file:line:col: severity: message [code]` line per diagnostic,
+    /// followed by its `note:` lines.
+    #[strum(serialize = "gcc")]
+    Gcc,
+}
+
+pub(super) fn write_json(
+    writer: &mut dyn Write,
+    files: &SourceFiles,
+    diagnostics: &[Diagnostic<FileId>],
+) -> io::Result<()> {
+    for diagnostic in diagnostics {
+        let labels = diagnostic
+            .labels
+            .iter()
+            .map(|label| {
+                let (start, end) = locations(files, label, Encoding::Utf32);
+                json!({
+                    "style": match label.style {
+                        LabelStyle::Primary => "primary",
+                        LabelStyle::Secondary => "secondary",
+                    },
+                    "file": file_name(files, label.file_id),
+                    "byte_start": label.range.start,
+                    "byte_end": label.range.end,
+                    "line_start": start.line_number,
+                    "column_start": start.column_number,
+                    "line_end": end.line_number,
+                    "column_end": end.column_number,
+                    "message": label.message,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let suggestions = diagnostic
+            .suggestions
+            .iter()
+            .map(|suggestion| {
+                json!({
+                    "message": suggestion_message(files, suggestion),
+                    "file": file_name(files, suggestion.file_id),
+                    "byte_start": suggestion.range.start,
+                    "byte_end": suggestion.range.end,
+                    "replacement": suggestion.replacement,
+                    "applicability": suggestion.applicability.to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let object = json!({
+            "code": diagnostic.code,
+            "severity": severity_name(diagnostic.severity),
+            "message": diagnostic.message,
+            "labels": labels,
+            "notes": diagnostic.notes,
+            "suggestions": suggestions,
+        });
+        writeln!(writer, "{object}")?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn write_sarif(
+    writer: &mut dyn Write,
+    files: &SourceFiles,
+    diagnostics: &[Diagnostic<FileId>],
+) -> io::Result<()> {
+    let rules = DiagnosticCode::iter()
+        .map(|code| {
+            json!({
+                "id": code.to_string(),
+                "shortDescription": { "text": code.title() },
+                "fullDescription": { "text": code.explanation() },
+                "defaultConfiguration": { "level": sarif_level(code.severity()) },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let results = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let location = |label: &Label<FileId>| {
+                let (start, end) = locations(files, label, Encoding::Utf16);
+                let (char_offset, char_length) = char_range(files, label.file_id, &label.range);
+                json!({
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": file_name(files, label.file_id) },
+                        "region": {
+                            "startLine": start.line_number,
+                            "startColumn": start.column_number,
+                            "endLine": end.line_number,
+                            "endColumn": end.column_number,
+                            "charOffset": char_offset,
+                            "charLength": char_length,
+                        },
+                    },
+                    "message": { "text": label.message },
+                })
+            };
+            let (primary, secondary): (Vec<_>, Vec<_>) =
+                diagnostic.labels.iter().partition(|label| label.style == LabelStyle::Primary);
+
+            // SARIF has no notion of notes, so they are appended to the
+            // message, one per line.
+            let text = std::iter::once(diagnostic.message.as_str())
+                .chain(diagnostic.notes.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let fixes = diagnostic
+                .suggestions
+                .iter()
+                .map(|suggestion| {
+                    let (char_offset, char_length) =
+                        char_range(files, suggestion.file_id, &suggestion.range);
+                    json!({
+                        "description": { "text": suggestion_message(files, suggestion) },
+                        "artifactChanges": [{
+                            "artifactLocation": { "uri": file_name(files, suggestion.file_id) },
+                            "replacements": [{
+                                "deletedRegion": {
+                                    "charOffset": char_offset,
+                                    "charLength": char_length,
+                                },
+                                "insertedContent": { "text": suggestion.replacement },
+                            }],
+                        }],
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let mut result = json!({
+                "level": sarif_level(diagnostic.severity),
+                "message": { "text": text },
+                "locations": primary.into_iter().map(location).collect::<Vec<_>>(),
+                "relatedLocations": secondary.into_iter().map(location).collect::<Vec<_>>(),
+                "fixes": fixes,
+            });
+            if let Some(code) = &diagnostic.code {
+                result["ruleId"] = json!(code);
+            }
+
+            result
+        })
+        .collect::<Vec<_>>();
+
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "rcc",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "columnKind": "utf16CodeUnits",
+            "results": results,
+        }],
+    });
+    writeln!(writer, "{log:#}")
+}
+
+/// Writes each diagnostic as a `file:line:col: severity: message [code]`
+/// line, followed by a `note:` line per secondary label, note and
+/// suggestion. With `show_caret`, each line is followed by the source line
+/// it points into, with a caret under the location, as GCC's
+/// `-fdiagnostics-show-caret` does.
+pub(super) fn write_gcc(
+    writer: &mut dyn Write,
+    files: &SourceFiles,
+    diagnostics: &[Diagnostic<FileId>],
+    show_caret: bool,
+) -> io::Result<()> {
+    for diagnostic in diagnostics {
+        let primary = diagnostic.labels.iter().find(|label| label.style == LabelStyle::Primary);
+        let location = primary.map(|label| (label.file_id, label.range.clone()));
+
+        let message = match &diagnostic.code {
+            Some(code) => format!("{} [{code}]", diagnostic.message),
+            None => diagnostic.message.clone(),
+        };
+        write_gcc_line(writer, files, location.clone(), diagnostic.severity, &message, show_caret)?;
+
+        for label in &diagnostic.labels {
+            if label.style == LabelStyle::Secondary && !label.message.is_empty() {
+                let location = Some((label.file_id, label.range.clone()));
+                // An excerpt the diagnostic has just shown isn't repeated.
+                let show_caret = show_caret &&
+                    primary.map_or(true, |primary| primary.range != label.range);
+                let message = &label.message;
+                write_gcc_line(writer, files, location, Severity::Note, message, show_caret)?;
+            }
+        }
+        // Notes have no location of their own, so they point at the
+        // diagnostic, without repeating its excerpt.
+        for line in diagnostic.notes.iter().flat_map(|note| note.lines()) {
+            write_gcc_line(writer, files, location.clone(), Severity::Note, line, false)?;
+        }
+        for suggestion in &diagnostic.suggestions {
+            let location = Some((suggestion.file_id, suggestion.range.clone()));
+            let message = suggestion_message(files, suggestion);
+            write_gcc_line(writer, files, location, Severity::Note, &message, false)?;
+            if show_caret {
+                let fix_it = suggestion.replacement.lines().next().filter(|line| !line.is_empty());
+                write_caret(writer, files, suggestion.file_id, suggestion.range.clone(), fix_it)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a single `file:line:col: severity: message` line, or `rcc:
+/// severity: message` without a location.
+fn write_gcc_line(
+    writer: &mut dyn Write,
+    files: &SourceFiles,
+    location: Option<(FileId, Range<usize>)>,
+    severity: Severity,
+    message: &str,
+    show_caret: bool,
+) -> io::Result<()> {
+    let Some((file_id, range)) = location else {
+        return writeln!(writer, "rcc: {}: {message}", severity_name(severity));
+    };
+
+    let start = location_at(files, file_id, range.start, Encoding::Utf32);
+    writeln!(
+        writer,
+        "{}:{}:{}: {}: {message}",
+        file_name(files, file_id),
+        start.line_number,
+        start.column_number,
+        severity_name(severity)
+    )?;
+
+    if show_caret {
+        write_caret(writer, files, file_id, range, None)?;
+    }
+
+    Ok(())
+}
+
+/// Writes the line `range` starts on, with a `^` under its start and a `~`
+/// under the rest of it on that line, and the `fix_it` text replacing it, if
+/// any, under that:
+///
+/// ```text
+///     1 | int x = 1
+///       |          ^
+///       |          ;
+/// ```
+fn write_caret(
+    writer: &mut dyn Write,
+    files: &SourceFiles,
+    file_id: FileId,
+    range: Range<usize>,
+    fix_it: Option<&str>,
+) -> io::Result<()> {
+    let Ok(file) = files.get(file_id) else {
+        return Ok(());
+    };
+    if range.start > file.source().len() {
+        return Ok(());
+    }
+    let line_index = file.line_index().line_col(range.start).line;
+    let Some(line_range) = file.line_index().line_range(line_index) else {
+        return Ok(());
+    };
+
+    let line = file.source()[line_range.clone()].trim_end_matches(['\n', '\r']);
+    let start = range.start - line_range.start;
+    let end = range.end.clamp(range.start, line_range.start + line.len()) - line_range.start;
+    // Tabs are kept, so that the caret lines up however wide they are shown.
+    let padding = line[..start]
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect::<String>();
+    let tildes = line[start..end].chars().count().saturating_sub(1);
+
+    writeln!(writer, "{:>5} | {line}", line_index + 1)?;
+    writeln!(writer, "{:>5} | {padding}^{}", "", "~".repeat(tildes))?;
+    if let Some(fix_it) = fix_it {
+        writeln!(writer, "{:>5} | {padding}{fix_it}", "")?;
+    }
+
+    Ok(())
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug => "bug",
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+        Severity::Help => "help",
+    }
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug | Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note | Severity::Help => "note",
+    }
+}
+
+fn file_name(files: &SourceFiles, file_id: FileId) -> &str {
+    files.name(file_id).unwrap_or("<unknown>")
+}
+
+fn suggestion_message(files: &SourceFiles, suggestion: &Suggestion<FileId>) -> String {
+    files
+        .get(suggestion.file_id)
+        .map_or_else(|_| String::new(), |file| suggestion.message(file.source()))
+}
+
+/// The 1-based line and column of the start and end of `label`, with
+/// columns counting `encoding`.
+fn locations(
+    files: &SourceFiles,
+    label: &Label<FileId>,
+    encoding: Encoding,
+) -> (Location, Location) {
+    (
+        location_at(files, label.file_id, label.range.start, encoding),
+        location_at(files, label.file_id, label.range.end, encoding),
+    )
+}
+
+/// The 1-based line and column of `byte_index` in `file_id`, with the
+/// column counting `encoding`.
+fn location_at(
+    files: &SourceFiles,
+    file_id: FileId,
+    byte_index: usize,
+    encoding: Encoding,
+) -> Location {
+    files
+        .location_in(file_id, byte_index, encoding)
+        .unwrap_or(Location { line_number: 1, column_number: 1 })
+}
+
+/// `range` of `file_id`, as a SARIF `charOffset` and `charLength`, which
+/// count UTF-16 code units.
+fn char_range(files: &SourceFiles, file_id: FileId, range: &Range<usize>) -> (usize, usize) {
+    let Ok(file) = files.get(file_id) else {
+        return (range.start, range.len());
+    };
+    let start = file.line_index().offset_in(range.start, Encoding::Utf16);
+    let end = file.line_index().offset_in(range.end, Encoding::Utf16);
+    (start, end - start)
+}
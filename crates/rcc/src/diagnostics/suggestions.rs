@@ -0,0 +1,251 @@
+//! Fix-its: the edits a diagnostic suggests, e.g. inserting a missing `;`.
+//!
+//! A [`Suggestion`] replaces a byte range of a file with new text. Its
+//! [`Applicability`] tells whether it can be applied without a human looking
+//! at it, which is what `rcc fix` relies on.
+
+use crate::line_index::LineIndex;
+use std::ops::Range;
+use strum_macros::Display;
+
+/// How confident `rcc` is that a [`Suggestion`] is what was meant.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Applicability {
+    /// The suggestion is definitely what was meant, and is applied by
+    /// `rcc fix`.
+    #[strum(serialize = "machine-applicable")]
+    MachineApplicable,
+    /// The suggestion may be what was meant, but should be reviewed.
+    #[strum(serialize = "maybe-incorrect")]
+    MaybeIncorrect,
+    /// The suggestion contains placeholders, e.g. `/* type */`, to be filled
+    /// in by hand.
+    #[strum(serialize = "has-placeholders")]
+    HasPlaceholders,
+}
+
+/// An edit suggested by a diagnostic: replace `range` of `file_id` with
+/// `replacement`. An empty range is an insertion, an empty replacement a
+/// removal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion<FileId> {
+    pub file_id:       FileId,
+    pub range:         Range<usize>,
+    pub replacement:   String,
+    pub applicability: Applicability,
+    /// What the suggestion is shown as, e.g. ``did you mean `return`?``,
+    /// instead of a description of the edit.
+    pub message:       Option<String>,
+}
+
+impl<FileId> Suggestion<FileId> {
+    pub fn new(
+        file_id: FileId,
+        range: Range<usize>,
+        replacement: impl Into<String>,
+        applicability: Applicability,
+    ) -> Self {
+        Self { file_id, range, replacement: replacement.into(), applicability, message: None }
+    }
+
+    /// Suggests inserting `text` at byte `offset`.
+    pub fn insert(
+        file_id: FileId,
+        offset: usize,
+        text: impl Into<String>,
+        applicability: Applicability,
+    ) -> Self {
+        Self::new(file_id, offset..offset, text, applicability)
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// What the suggestion is shown as: its message, if any, or else what it
+    /// does, e.g. ``insert `int` ``.
+    pub fn message(&self, source: &str) -> String {
+        let replacement = self.replacement.trim();
+        if let Some(message) = &self.message {
+            message.clone()
+        } else if self.range.is_empty() {
+            format!("insert `{replacement}`")
+        } else if replacement.is_empty() {
+            format!("remove `{}`", &source[self.range.clone()])
+        } else {
+            format!("replace `{}` with `{replacement}`", &source[self.range.clone()])
+        }
+    }
+
+    /// The first line the suggestion touches, as it reads once applied, with
+    /// the edit marked underneath (`+` for inserted text, `~` for replaced
+    /// text, `-` for removed text):
+    ///
+    /// ```text
+    /// 1 | int main(void) {
+    ///   | +++
+    /// ```
+    pub fn preview(&self, source: &str, line_index: &LineIndex) -> String {
+        let line = line_index.line_col(self.range.start).line;
+        let line_start = line_index.line_range(line).map_or(0, |range| range.start);
+        let line_end =
+            source[self.range.end..].find('\n').map_or(source.len(), |index| self.range.end + index);
+        let line_number = line + 1;
+
+        let before = &source[line_start..self.range.start];
+        let replacement = self.replacement.split('\n').next().unwrap_or_default();
+        let after = &source[self.range.end..line_end];
+
+        let (marker, width) = if self.range.is_empty() {
+            ('+', replacement.trim_end().chars().count())
+        } else if replacement.is_empty() {
+            ('-', 1)
+        } else {
+            ('~', replacement.chars().count())
+        };
+        let gutter = " ".repeat(line_number.to_string().len());
+
+        format!(
+            "{line_number} | {before}{replacement}{after}\n{gutter} | {}{}",
+            " ".repeat(before.chars().count()),
+            marker.to_string().repeat(width.max(1))
+        )
+    }
+}
+
+/// Applies `suggestions` to `source`. Suggestions overlapping one applied
+/// before them are skipped; the number of suggestions applied is returned
+/// along with the new source.
+pub fn apply_suggestions<'a, FileId: 'a>(
+    source: &str,
+    suggestions: impl IntoIterator<Item = &'a Suggestion<FileId>>,
+) -> (String, usize) {
+    let mut suggestions = suggestions.into_iter().collect::<Vec<_>>();
+    suggestions.sort_by_key(|suggestion| (suggestion.range.start, suggestion.range.end));
+
+    let mut fixed = String::with_capacity(source.len());
+    let mut offset = 0;
+    let mut applied = 0;
+    // Two insertions at the same offset conflict too.
+    let mut last_insertion = None;
+    for suggestion in suggestions {
+        if suggestion.range.start < offset || last_insertion == Some(suggestion.range.start) {
+            continue;
+        }
+        if suggestion.range.is_empty() {
+            last_insertion = Some(suggestion.range.start);
+        }
+
+        fixed.push_str(&source[offset..suggestion.range.start]);
+        fixed.push_str(&suggestion.replacement);
+        offset = suggestion.range.end;
+        applied += 1;
+    }
+    fixed.push_str(&source[offset..]);
+
+    (fixed, applied)
+}
+
+/// The number of single-character insertions, removals, substitutions and
+/// transpositions of adjacent characters turning `a` into `b`.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    // `rows[i][j]` is the distance between the first `i` characters of `a`
+    // and the first `j` characters of `b`.
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + substitution);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+/// The candidate closest to `name`, if any is close enough to be what was
+/// meant: within an edit per three characters. Ties go to the first
+/// candidate.
+pub fn similar_name<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let threshold = name.chars().count().max(3) / 3;
+
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn previews() {
+        let source = "main(void) {\n    return 0\n}\n";
+        let line_index = LineIndex::new(source);
+        let int = Suggestion::insert(0, 0, "int ", Applicability::MachineApplicable);
+        assert_eq!(int.message(source), "insert `int`");
+        assert_eq!(int.preview(source, &line_index), "1 | int main(void) {\n  | +++");
+
+        let semicolon = Suggestion::insert(0, 25, ";", Applicability::MachineApplicable);
+        assert_eq!(semicolon.message(source), "insert `;`");
+        assert_eq!(semicolon.preview(source, &line_index), "2 |     return 0;\n  |             +");
+
+        let rename = Suggestion::new(0, 0..4, "f", Applicability::MaybeIncorrect);
+        assert_eq!(rename.message(source), "replace `main` with `f`");
+        assert_eq!(rename.preview(source, &line_index), "1 | f(void) {\n  | ~");
+    }
+
+    #[test]
+    fn similar_names() {
+        assert_eq!(edit_distance("return", "return"), 0);
+        assert_eq!(edit_distance("retrun", "return"), 1);
+        assert_eq!(edit_distance("unsinged", "unsigned"), 1);
+        assert_eq!(edit_distance("whle", "while"), 1);
+        assert_eq!(edit_distance("", "int"), 3);
+
+        let keywords = ["int", "return", "unsigned", "signed", "struct", "while"];
+        assert_eq!(similar_name("retrun", keywords), Some("return"));
+        assert_eq!(similar_name("unsinged", keywords), Some("unsigned"));
+        assert_eq!(similar_name("stuct", keywords), Some("struct"));
+        assert_eq!(similar_name("count", keywords), None);
+        assert_eq!(similar_name("x", keywords), None);
+    }
+
+    #[test]
+    fn applies_suggestions_in_order() {
+        let source = "main(void) {\n    return 0\n}\n";
+        let suggestions = [
+            Suggestion::insert(0, 25, ";", Applicability::MachineApplicable),
+            Suggestion::insert(0, 0, "int ", Applicability::MachineApplicable),
+            // Overlaps the insertion of `int`, so is skipped.
+            Suggestion::insert(0, 0, "long ", Applicability::MachineApplicable),
+        ];
+
+        let (fixed, applied) = apply_suggestions(source, &suggestions);
+        assert_eq!(fixed, "int main(void) {\n    return 0;\n}\n");
+        assert_eq!(applied, 2);
+    }
+}
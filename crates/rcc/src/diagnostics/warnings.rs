@@ -0,0 +1,299 @@
+//! Warning groups, as controlled by `-W` flags and `#pragma GCC diagnostic`.
+//!
+//! Every warning belongs to a [`WarningGroup`] (see
+//! [`DiagnosticCode::group`](super::DiagnosticCode::group)). The flags of a
+//! session, collected in [`WarningOptions`], decide whether the warnings of
+//! a group are ignored, reported or promoted to errors; `#pragma GCC
+//! diagnostic` then overrides that decision for the rest of a file, or until
+//! the matching `pop`.
+
+use std::collections::HashMap;
+use strum_macros::{
+    Display,
+    EnumIter,
+    EnumString,
+};
+
+#[derive(Debug, Display, EnumIter, EnumString, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarningGroup {
+    /// Declarations whose type defaults to `int`.
+    #[strum(serialize = "implicit-int")]
+    ImplicitInt,
+    /// Old-style (K&R) function definitions.
+    #[strum(serialize = "old-style-definition")]
+    OldStyleDefinition,
+    /// Unused variables, parameters, functions and labels.
+    #[strum(serialize = "unused")]
+    Unused,
+    /// Trigraphs, whether or not `-trigraphs` replaces them.
+    #[strum(serialize = "trigraphs")]
+    Trigraphs,
+    /// Bytes which aren't valid in the input charset.
+    #[strum(serialize = "invalid-source-encoding")]
+    InvalidSourceEncoding,
+    /// Identifiers which look like other identifiers.
+    #[strum(serialize = "confusable-identifiers")]
+    ConfusableIdentifiers,
+    /// Identifiers mixing characters of several scripts.
+    #[strum(serialize = "mixed-script-identifiers")]
+    MixedScriptIdentifiers,
+    /// Statements which can never run, e.g. after a `return`.
+    #[strum(serialize = "unreachable-code")]
+    UnreachableCode,
+    /// Non-`void` functions whose end can be reached without a `return`.
+    #[strum(serialize = "return-type")]
+    ReturnType,
+}
+
+impl WarningGroup {
+    fn enabled_by_default(self) -> bool {
+        matches!(
+            self,
+            WarningGroup::ImplicitInt |
+                WarningGroup::OldStyleDefinition |
+                WarningGroup::Trigraphs |
+                WarningGroup::InvalidSourceEncoding |
+                WarningGroup::ConfusableIdentifiers |
+                WarningGroup::MixedScriptIdentifiers |
+                WarningGroup::ReturnType
+        )
+    }
+
+    /// Whether the group is enabled by `-Wall`.
+    fn in_all(self) -> bool {
+        matches!(
+            self,
+            WarningGroup::ImplicitInt |
+                WarningGroup::Unused |
+                WarningGroup::Trigraphs |
+                WarningGroup::ConfusableIdentifiers |
+                WarningGroup::MixedScriptIdentifiers |
+                WarningGroup::ReturnType
+        )
+    }
+
+    /// Whether the group is enabled by `-Wextra`.
+    fn in_extra(self) -> bool {
+        matches!(self, WarningGroup::Unused | WarningGroup::UnreachableCode)
+    }
+}
+
+/// What becomes of the warnings of a group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningLevel {
+    Ignored,
+    Warning,
+    Error,
+}
+
+/// The warning flags of a session, e.g. `-Wall -Werror=implicit-int
+/// -Wno-unused`. Later flags override earlier ones.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WarningOptions {
+    all:        bool,
+    extra:      bool,
+    all_errors: bool,
+    /// Groups explicitly enabled (`-Wx`) or disabled (`-Wno-x`).
+    enabled:    HashMap<WarningGroup, bool>,
+    /// Groups explicitly promoted to errors (`-Werror=x`) or not
+    /// (`-Wno-error=x`).
+    errors:     HashMap<WarningGroup, bool>,
+}
+
+impl WarningOptions {
+    /// Applies a `-W` flag, e.g. `-Wall`, `-Werror=shadow` or
+    /// `-Wno-unused`.
+    pub fn apply_flag(&mut self, flag: &str) -> Result<(), String> {
+        let Some(name) = flag.strip_prefix("-W") else {
+            return Err(format!("`{flag}` is not a warning flag"));
+        };
+        let group = |name: &str| {
+            name.parse::<WarningGroup>().map_err(|_| format!("unknown warning group `{name}`"))
+        };
+
+        match name {
+            "all" => self.all = true,
+            "extra" => self.extra = true,
+            "error" => self.all_errors = true,
+            "no-error" => self.all_errors = false,
+            _ => {
+                if let Some(name) = name.strip_prefix("error=") {
+                    let group = group(name)?;
+                    self.enabled.insert(group, true);
+                    self.errors.insert(group, true);
+                } else if let Some(name) = name.strip_prefix("no-error=") {
+                    self.errors.insert(group(name)?, false);
+                } else if let Some(name) = name.strip_prefix("no-") {
+                    self.enabled.insert(group(name)?, false);
+                } else {
+                    self.enabled.insert(group(name)?, true);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The level of the warnings of `group`, before any pragma.
+    pub fn level(&self, group: WarningGroup) -> WarningLevel {
+        let enabled = self.enabled.get(&group).copied().unwrap_or_else(|| {
+            group.enabled_by_default() ||
+                (self.all && group.in_all()) ||
+                (self.extra && group.in_extra())
+        });
+
+        if !enabled {
+            WarningLevel::Ignored
+        } else if self.errors.get(&group).copied().unwrap_or(self.all_errors) {
+            WarningLevel::Error
+        } else {
+            WarningLevel::Warning
+        }
+    }
+}
+
+/// A `#pragma GCC diagnostic` (or `#pragma clang diagnostic`) directive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticPragma {
+    Push,
+    Pop,
+    Set(WarningGroup, WarningLevel),
+}
+
+/// Every diagnostic pragma of `text`, with the byte offset it takes effect
+/// from. Pragmas naming unknown groups are ignored, as other compilers'
+/// groups often are.
+pub(crate) fn diagnostic_pragmas(text: &str) -> Vec<(usize, DiagnosticPragma)> {
+    let mut pragmas = Vec::new();
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+
+        let Some(directive) = line.trim_start().strip_prefix('#') else {
+            continue;
+        };
+        let words = directive.split_whitespace().collect::<Vec<_>>();
+        let pragma = match words[..] {
+            ["pragma", "GCC" | "clang", "diagnostic", "push"] => DiagnosticPragma::Push,
+            ["pragma", "GCC" | "clang", "diagnostic", "pop"] => DiagnosticPragma::Pop,
+            ["pragma", "GCC" | "clang", "diagnostic", level, flag] => {
+                let level = match level {
+                    "ignored" => WarningLevel::Ignored,
+                    "warning" => WarningLevel::Warning,
+                    "error" => WarningLevel::Error,
+                    _ => continue,
+                };
+                let group = flag.trim_matches('"').strip_prefix("-W").map(str::parse);
+                let Some(Ok(group)) = group else {
+                    continue;
+                };
+
+                DiagnosticPragma::Set(group, level)
+            }
+            _ => continue,
+        };
+
+        pragmas.push((start, pragma));
+    }
+
+    pragmas
+}
+
+/// The level `pragmas` give to `group` at `offset`, if any.
+pub(crate) fn pragma_level(
+    pragmas: &[(usize, DiagnosticPragma)],
+    group: WarningGroup,
+    offset: usize,
+) -> Option<WarningLevel> {
+    let mut stack = vec![None];
+
+    for (_, pragma) in pragmas.iter().take_while(|(start, _)| *start <= offset) {
+        match pragma {
+            DiagnosticPragma::Push => stack.push(*stack.last().unwrap()),
+            // An unmatched `pop` is ignored.
+            DiagnosticPragma::Pop if stack.len() > 1 => {
+                stack.pop();
+            }
+            DiagnosticPragma::Pop => {}
+            DiagnosticPragma::Set(set, level) if *set == group => {
+                *stack.last_mut().unwrap() = Some(*level);
+            }
+            DiagnosticPragma::Set(..) => {}
+        }
+    }
+
+    stack.pop().flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(flags: &[&str]) -> WarningOptions {
+        let mut options = WarningOptions::default();
+        for flag in flags {
+            options.apply_flag(flag).unwrap();
+        }
+        options
+    }
+
+    #[test]
+    fn flags() {
+        let default = options(&[]);
+        assert_eq!(default.level(WarningGroup::ImplicitInt), WarningLevel::Warning);
+        assert_eq!(default.level(WarningGroup::Unused), WarningLevel::Ignored);
+
+        assert_eq!(options(&["-Wall"]).level(WarningGroup::Unused), WarningLevel::Warning);
+        assert_eq!(
+            options(&["-Wall"]).level(WarningGroup::UnreachableCode),
+            WarningLevel::Ignored
+        );
+        assert_eq!(
+            options(&["-Wextra"]).level(WarningGroup::UnreachableCode),
+            WarningLevel::Warning
+        );
+        assert_eq!(
+            options(&["-Wunreachable-code"]).level(WarningGroup::UnreachableCode),
+            WarningLevel::Warning
+        );
+        assert_eq!(
+            options(&["-Wall", "-Wno-unused"]).level(WarningGroup::Unused),
+            WarningLevel::Ignored
+        );
+
+        let errors = options(&["-Werror", "-Wno-error=old-style-definition"]);
+        assert_eq!(errors.level(WarningGroup::ImplicitInt), WarningLevel::Error);
+        assert_eq!(errors.level(WarningGroup::OldStyleDefinition), WarningLevel::Warning);
+        assert_eq!(
+            options(&["-Werror=unreachable-code"]).level(WarningGroup::UnreachableCode),
+            WarningLevel::Error
+        );
+
+        assert!(WarningOptions::default().apply_flag("-Wbogus").is_err());
+    }
+
+    #[test]
+    fn pragmas() {
+        let text = "int a;\n\
+                    #pragma GCC diagnostic push\n\
+                    #pragma GCC diagnostic ignored \"-Wimplicit-int\"\n\
+                    int b;\n\
+                    #pragma GCC diagnostic pop\n\
+                    int c;\n";
+        let pragmas = diagnostic_pragmas(text);
+        assert_eq!(pragmas.len(), 3);
+
+        let at = |needle| {
+            pragma_level(&pragmas, WarningGroup::ImplicitInt, text.find(needle).unwrap())
+        };
+        assert_eq!(at("int a"), None);
+        assert_eq!(at("int b"), Some(WarningLevel::Ignored));
+        assert_eq!(at("int c"), None);
+        assert_eq!(
+            pragma_level(&pragmas, WarningGroup::Unused, text.find("int b").unwrap()),
+            None
+        );
+    }
+}
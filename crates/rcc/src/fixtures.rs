@@ -0,0 +1,538 @@
+//! The snapshot test harness behind `rcc test`, run over `testdata`.
+//!
+//! Every `.c` file under the root is a source fixture. It is parsed and
+//! lowered, and compared against snapshots next to it:
+//!
+//! - `<name>.cst`, the dump of its concrete syntax tree, and
+//! - `<name>.stderr`, its rendered diagnostics, absent if there are none.
+//!
+//! A directory with an `in.c` and an `expected.c` is a preprocessor fixture
+//! instead: `in.c` is preprocessed and compared against `expected.c`. The
+//! other files of the directory, e.g. headers, are left alone.
+//!
+//! Fixtures can be annotated with the diagnostics they should produce, on
+//! the line they are expected on, or on a line below with a `^` per line
+//! up. A fixture with annotations must annotate every error and warning it
+//! produces:
+//!
+//! ```c
+//! int x = 1 2; //~ ERROR E0001
+//! int main(void) {
+//!     break;
+//! //~^ ERROR E0015
+//! }
+//! ```
+//!
+//! A fixture is configured with comments on lines of their own:
+//!
+//! - `// compile-flags: --std=gnu11 -Wall` sets the `--std=`, `--target=`,
+//!   `-trigraphs`, `-finput-charset=`, `-W`, `-ferror-limit=` and
+//!   `-fmacro-backtrace-limit=` flags it is compiled with, and
+//! - `// known-bug: <reason>` marks it as failing for now. It is still run,
+//!   but it is an error for it to pass, and its snapshots are never blessed.
+//!
+//! With `--bless`, out-of-date snapshots are rewritten instead of failing.
+
+use crate::{
+    charset,
+    diagnostics::{
+        Buffer,
+        Diagnostic,
+        FileId,
+        LabelStyle,
+        Severity,
+        WarningOptions,
+        DEFAULT_ERROR_LIMIT,
+        DEFAULT_MACRO_BACKTRACE_LIMIT,
+    },
+    preprocessor::Preprocessor,
+    target::Target,
+    Options,
+    Session,
+    Standard,
+};
+use codespan_reporting::files::Files;
+use std::{
+    fs,
+    io,
+    panic::{
+        self,
+        AssertUnwindSafe,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+use walkdir::WalkDir;
+
+/// How a fixture fared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    /// Its snapshots were out of date, and have been rewritten.
+    Blessed,
+    /// It fails, as its `known-bug` directive says it does.
+    KnownBug,
+    /// It failed, for each of the given reasons.
+    Failed(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureResult {
+    /// The path of the fixture, relative to the root.
+    pub name:    String,
+    pub outcome: Outcome,
+}
+
+/// Runs every fixture under `root`, in path order. With `bless`, snapshots
+/// which are out of date are rewritten.
+pub fn run(root: &Path, bless: bool) -> io::Result<Vec<FixtureResult>> {
+    let mut results = Vec::new();
+
+    for entry in WalkDir::new(root).sort_by_file_name() {
+        let entry = entry.map_err(io::Error::from)?;
+        let path = entry.path();
+        let name = path
+            .strip_prefix(root)
+            .unwrap_or(path)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let outcome = if entry.file_type().is_dir() {
+            if !path.join("in.c").is_file() || !path.join("expected.c").is_file() {
+                continue;
+            }
+            check_preprocessed(path, bless)?
+        } else {
+            // The files of preprocessor fixtures, including those without
+            // an `expected.c`, are inputs rather than fixtures.
+            let in_preprocessor_fixture =
+                path.parent().is_some_and(|parent| parent.join("in.c").is_file());
+            if path.extension().map_or(true, |extension| extension != "c") ||
+                in_preprocessor_fixture
+            {
+                continue;
+            }
+            check_source(path, bless)?
+        };
+
+        results.push(FixtureResult { name, outcome });
+    }
+
+    Ok(results)
+}
+
+/// Checks a directory with an `in.c` and an `expected.c`.
+fn check_preprocessed(dir: &Path, bless: bool) -> io::Result<Outcome> {
+    let text = fs::read_to_string(dir.join("in.c"))?;
+    let directives = Directives::parse(&text);
+    let options = match directives.options() {
+        Ok(options) => options,
+        Err(error) => return Ok(Outcome::Failed(vec![error])),
+    };
+
+    let preprocessed = catch_panic(|| {
+        let mut preprocessor = Preprocessor::new(0, text)
+            .with_standard(*options.standard())
+            .with_target(options.target());
+        preprocessor
+            .preprocess()
+            .map(|()| preprocessor.into_output().into_transformed_text())
+            .map_err(|diagnostic| format!("failed to preprocess: {}", diagnostic.message))
+    });
+
+    match preprocessed.and_then(|preprocessed| preprocessed) {
+        Ok(actual) => {
+            let snapshot = Snapshot { path: dir.join("expected.c"), actual, optional: false };
+            finish(&directives, Vec::new(), &[snapshot], bless)
+        }
+        Err(failure) => finish(&directives, vec![failure], &[], bless),
+    }
+}
+
+/// Checks a `.c` file.
+fn check_source(path: &Path, bless: bool) -> io::Result<Outcome> {
+    let bytes = fs::read(path)?;
+    // Directives and annotations are ASCII, so they can be read before the
+    // input charset is known.
+    let text = String::from_utf8_lossy(&bytes).into_owned();
+    // Diagnostics name the file alone, so that snapshots don't depend on
+    // the root the fixtures are run from.
+    let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+    let directives = Directives::parse(&text);
+    let options = match directives.options() {
+        Ok(options) => options,
+        Err(error) => return Ok(Outcome::Failed(vec![error])),
+    };
+
+    let compiled = catch_panic(|| {
+        // The tree is dumped from a session of its own, so that the
+        // diagnostics of parsing aren't reported twice.
+        let mut session = Session::builder().options(options.clone()).build();
+        let file_id = session.add_bytes(name.as_str(), &bytes);
+        let cst = session.parse(file_id).dump();
+
+        let mut session = Session::builder().options(options).build();
+        let file_id = session.add_bytes(name.as_str(), &bytes);
+        session.lower(file_id);
+
+        let failures = match annotations(&text) {
+            Ok(annotations) => check_annotations(&session, file_id, annotations),
+            Err(error) => vec![error],
+        };
+
+        let mut buffer = Buffer::no_color();
+        session.diagnostics_mut().flush_to(&mut buffer);
+        let stderr = String::from_utf8_lossy(buffer.as_slice()).into_owned();
+
+        (cst, stderr, failures)
+    });
+
+    match compiled {
+        Ok((cst, stderr, failures)) => {
+            let snapshots = [
+                Snapshot { path: path.with_extension("cst"), actual: cst, optional: false },
+                Snapshot { path: path.with_extension("stderr"), actual: stderr, optional: true },
+            ];
+            finish(&directives, failures, &snapshots, bless)
+        }
+        Err(failure) => finish(&directives, vec![failure], &[], bless),
+    }
+}
+
+/// Runs `compile`, turning a panic into a failure of the fixture rather than
+/// of the whole run.
+fn catch_panic<T>(compile: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(compile)).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        format!("panicked: {message}")
+    })
+}
+
+/// Compares `snapshots` to their files, or blesses them, and decides the
+/// outcome of a fixture which otherwise failed with `failures`.
+fn finish(
+    directives: &Directives,
+    mut failures: Vec<String>,
+    snapshots: &[Snapshot],
+    bless: bool,
+) -> io::Result<Outcome> {
+    let mut differences = Vec::new();
+    let mut outdated = Vec::new();
+    for snapshot in snapshots {
+        if let Some(difference) = snapshot.difference()? {
+            differences.push(difference);
+            outdated.push(snapshot);
+        }
+    }
+
+    if directives.known_bug.is_some() {
+        return Ok(if failures.is_empty() && differences.is_empty() {
+            Outcome::Failed(vec!["passes, but is marked as a `known-bug`".to_string()])
+        } else {
+            Outcome::KnownBug
+        });
+    }
+
+    if bless && !outdated.is_empty() {
+        for snapshot in outdated {
+            snapshot.bless()?;
+        }
+        // Blessing can't fix what doesn't match the annotations.
+        return Ok(if failures.is_empty() { Outcome::Blessed } else { Outcome::Failed(failures) });
+    }
+
+    failures.extend(differences);
+    Ok(if failures.is_empty() { Outcome::Passed } else { Outcome::Failed(failures) })
+}
+
+/// The expected contents of a snapshot file.
+struct Snapshot {
+    path:     PathBuf,
+    actual:   String,
+    /// Whether a missing file stands for an empty snapshot.
+    optional: bool,
+}
+
+impl Snapshot {
+    /// Where the file differs from the snapshot, if it does.
+    fn difference(&self) -> io::Result<Option<String>> {
+        let file_name = self.path.file_name().unwrap_or_default().to_string_lossy();
+        let expected = match fs::read_to_string(&self.path) {
+            Ok(expected) => expected,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                if !self.optional {
+                    return Ok(Some(format!("`{file_name}` is missing")));
+                }
+                String::new()
+            }
+            Err(error) => return Err(error),
+        };
+        if expected == self.actual {
+            return Ok(None);
+        }
+
+        let expected_lines = expected.lines().collect::<Vec<_>>();
+        let actual_lines = self.actual.lines().collect::<Vec<_>>();
+        let line = (0..expected_lines.len().max(actual_lines.len()))
+            .find(|&index| expected_lines.get(index) != actual_lines.get(index))
+            .unwrap_or(expected_lines.len());
+        let show = |line: Option<&&str>| {
+            line.map_or_else(|| "<end of file>".to_string(), |line| format!("{line:?}"))
+        };
+
+        Ok(Some(format!(
+            "`{file_name}` differs from line {}:\n  expected: {}\n  actual:   {}",
+            line + 1,
+            show(expected_lines.get(line)),
+            show(actual_lines.get(line)),
+        )))
+    }
+
+    fn bless(&self) -> io::Result<()> {
+        if self.optional && self.actual.is_empty() {
+            match fs::remove_file(&self.path) {
+                Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+                _ => Ok(()),
+            }
+        } else {
+            fs::write(&self.path, &self.actual)
+        }
+    }
+}
+
+/// The `// compile-flags:` and `// known-bug:` comments of a fixture.
+#[derive(Debug, Default)]
+struct Directives {
+    flags:     Vec<String>,
+    known_bug: Option<String>,
+}
+
+impl Directives {
+    fn parse(text: &str) -> Self {
+        let mut directives = Self::default();
+        for line in text.lines().map(str::trim) {
+            if let Some(flags) = line.strip_prefix("// compile-flags:") {
+                directives.flags.extend(flags.split_whitespace().map(str::to_string));
+            } else if let Some(reason) = line.strip_prefix("// known-bug:") {
+                directives.known_bug = Some(reason.trim().to_string());
+            }
+        }
+
+        directives
+    }
+
+    fn options(&self) -> Result<Options, String> {
+        let mut standard = Standard::default();
+        let mut target = Target::default();
+        let mut trigraphs = false;
+        let mut input_charset = charset::UTF_8;
+        let mut warnings = WarningOptions::default();
+        let mut error_limit = DEFAULT_ERROR_LIMIT;
+        let mut macro_backtrace_limit = DEFAULT_MACRO_BACKTRACE_LIMIT;
+
+        for flag in &self.flags {
+            if let Some(name) = flag.strip_prefix("--std=") {
+                standard = name.parse().map_err(|_| format!("unknown standard `{name}`"))?;
+            } else if let Some(triple) = flag.strip_prefix("--target=") {
+                target = triple.parse()?;
+            } else if flag == "-trigraphs" {
+                trigraphs = true;
+            } else if let Some(name) = flag.strip_prefix("-finput-charset=") {
+                input_charset =
+                    charset::charset(name).ok_or(format!("unknown input charset `{name}`"))?;
+            } else if let Some(limit) = flag.strip_prefix("-ferror-limit=") {
+                error_limit = limit.parse().map_err(|_| format!("invalid error limit `{limit}`"))?;
+            } else if let Some(limit) = flag.strip_prefix("-fmacro-backtrace-limit=") {
+                macro_backtrace_limit = limit
+                    .parse()
+                    .map_err(|_| format!("invalid macro backtrace limit `{limit}`"))?;
+            } else if flag.starts_with("-W") {
+                warnings.apply_flag(flag)?;
+            } else {
+                return Err(format!("unsupported flag `{flag}` in `compile-flags`"));
+            }
+        }
+
+        Ok(Options::builder()
+            .standard(standard)
+            .target(target)
+            .trigraphs(trigraphs)
+            .input_charset(input_charset)
+            .warnings(warnings)
+            .error_limit(error_limit)
+            .macro_backtrace_limit(macro_backtrace_limit)
+            .build())
+    }
+}
+
+/// A `//~ ERROR E0001` annotation: a diagnostic expected on `line`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Annotation {
+    line:     usize,
+    severity: Severity,
+    code:     Option<String>,
+}
+
+fn annotations(text: &str) -> Result<Vec<Annotation>, String> {
+    let mut annotations = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let Some(start) = line.find("//~") else {
+            continue;
+        };
+        let annotation = &line[start + 3..];
+        let up = annotation.len() - annotation.trim_start_matches('^').len();
+        let mut words = annotation[up..].split_whitespace();
+
+        let severity = match words.next() {
+            Some("ERROR") => Severity::Error,
+            Some("WARNING") => Severity::Warning,
+            Some("NOTE") => Severity::Note,
+            Some("HELP") => Severity::Help,
+            kind => {
+                return Err(format!(
+                    "line {}: expected `ERROR`, `WARNING`, `NOTE` or `HELP` after `//~`, found \
+                     `{}`",
+                    index + 1,
+                    kind.unwrap_or_default()
+                ))
+            }
+        };
+        if up > index {
+            return Err(format!("line {}: `//~` points above the first line", index + 1));
+        }
+
+        annotations.push(Annotation {
+            line: index + 1 - up,
+            severity,
+            code: words.next().map(str::to_string),
+        });
+    }
+
+    Ok(annotations)
+}
+
+/// Matches the pending diagnostics of `session` against `annotations`,
+/// returning what doesn't match.
+fn check_annotations(
+    session: &Session,
+    file_id: FileId,
+    annotations: Vec<Annotation>,
+) -> Vec<String> {
+    if annotations.is_empty() {
+        return Vec::new();
+    }
+
+    let files = &session.diagnostics().files;
+    let mut unmatched = session
+        .diagnostics()
+        .diagnostics
+        .iter()
+        .filter_map(|diagnostic| {
+            let label = diagnostic
+                .labels
+                .iter()
+                .find(|label| label.style == LabelStyle::Primary && label.file_id == file_id)?;
+            let line = files.location(file_id, label.range.start).ok()?.line_number;
+            Some((line, diagnostic))
+        })
+        .collect::<Vec<_>>();
+
+    let mut failures = Vec::new();
+    for annotation in annotations {
+        let found = unmatched.iter().position(|(line, diagnostic)| {
+            *line == annotation.line &&
+                severity_name(diagnostic.severity) == severity_name(annotation.severity) &&
+                annotation.code.as_ref().map_or(true, |code| diagnostic.code.as_ref() == Some(code))
+        });
+        match found {
+            Some(index) => {
+                unmatched.remove(index);
+            }
+            None => failures.push(format!(
+                "line {}: expected {} {}, found none",
+                annotation.line,
+                severity_name(annotation.severity),
+                annotation.code.as_deref().unwrap_or_default()
+            )),
+        }
+    }
+
+    for (line, diagnostic) in unmatched {
+        if diagnostic.severity >= Severity::Warning {
+            failures.push(format!("line {line}: unexpected {}", describe(diagnostic)));
+        }
+    }
+
+    failures
+}
+
+fn describe(diagnostic: &Diagnostic<FileId>) -> String {
+    match &diagnostic.code {
+        Some(code) => {
+            format!("{} {code}: {}", severity_name(diagnostic.severity), diagnostic.message)
+        }
+        None => format!("{}: {}", severity_name(diagnostic.severity), diagnostic.message),
+    }
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug | Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+        Severity::Help => "help",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_annotations() {
+        let text = "int x = 1 2; //~ ERROR E0001\n\
+                    int twice(n) int n; { return n; }\n\
+                    //~^ WARNING\n\
+                    //~^^^ NOTE E0000 and a comment\n";
+        let annotation = |line, severity, code: Option<&str>| Annotation {
+            line,
+            severity,
+            code: code.map(str::to_string),
+        };
+
+        assert_eq!(
+            annotations(text),
+            Ok(vec![
+                annotation(1, Severity::Error, Some("E0001")),
+                annotation(2, Severity::Warning, None),
+                annotation(1, Severity::Note, Some("E0000")),
+            ])
+        );
+        assert!(annotations("//~ ERROR").is_ok());
+        assert!(annotations("//~^ ERROR").is_err());
+        assert!(annotations("int x; //~ OOPS").is_err());
+    }
+
+    #[test]
+    fn parses_directives() {
+        let directives = Directives::parse(
+            "// compile-flags: --std=gnu11 -Wall\n\
+             // known-bug: not yet\n\
+             int x;\n",
+        );
+        assert_eq!(directives.flags, ["--std=gnu11", "-Wall"]);
+        assert_eq!(directives.known_bug.as_deref(), Some("not yet"));
+        assert!(directives.options().is_ok());
+
+        assert!(Directives::parse("// compile-flags: -O2").options().is_err());
+    }
+}
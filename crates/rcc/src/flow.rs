@@ -0,0 +1,503 @@
+//! Control-flow checks on the bodies of functions.
+//!
+//! A body is walked once, in order, keeping track of whether the statement
+//! at hand can be reached, and of the loops and switches around it. That is
+//! enough to find:
+//!
+//! - non-`void` functions whose end can be reached without a `return`,
+//! - `break` and `continue` outside of the statements they jump out of,
+//! - `case` and `default` labels outside of a `switch`, and repeated ones,
+//! - `goto`s to labels which aren't defined, and labels no `goto` names, and
+//! - statements after a `return`, `break`, `continue` or `goto` which can
+//!   never run.
+//!
+//! As with GCC, conditions aren't evaluated, except that a loop whose
+//! condition is a nonzero constant (or is missing, in a `for`) can only be
+//! left with a `break`. Every label is assumed to be reached, whether or not
+//! anything jumps to it. `main` never misses a `return`, as reaching its end
+//! returns 0 in C99 and later.
+
+use crate::{
+    ast::{
+        BinOp,
+        DataType,
+        Expr,
+        Function,
+        Literal,
+        Statement,
+        StatementKind,
+        Symbol,
+    },
+    lexer::Span,
+};
+use derive_more::Display;
+use std::{
+    collections::HashSet,
+    mem,
+};
+
+/// A problem with the control flow of a function.
+#[derive(Debug, Display, Clone, PartialEq, Eq)]
+pub enum FlowError {
+    #[display(fmt = "non-void function `{_0}` does not return a value in all control paths")]
+    MissingReturn(Symbol),
+    #[display(fmt = "`break` statement not in a loop or switch statement")]
+    BreakOutsideLoop,
+    #[display(fmt = "`continue` statement not in a loop statement")]
+    ContinueOutsideLoop,
+    #[display(fmt = "`case` label not in a switch statement")]
+    CaseOutsideSwitch,
+    #[display(fmt = "`default` label not in a switch statement")]
+    DefaultOutsideSwitch,
+    #[display(fmt = "duplicate case value `{_0}`")]
+    DuplicateCase(i64),
+    #[display(fmt = "multiple default labels in one switch")]
+    DuplicateDefault,
+    #[display(fmt = "use of undeclared label `{_0}`")]
+    UndefinedLabel(Symbol),
+    #[display(fmt = "label `{_0}` defined but not used")]
+    UnusedLabel(Symbol),
+    #[display(fmt = "code will never be executed")]
+    UnreachableCode,
+}
+
+/// Every problem with the control flow of `function`, with the span of the
+/// statement it is found at, in the order of the statements. They are
+/// followed by a missing `return`, at the span of the body, and then by the
+/// problems with its labels, at the first `goto` to an undefined label and
+/// at each unused label.
+pub fn check_function(function: &Function) -> Vec<(FlowError, Span)> {
+    let mut checker = Checker::default();
+    let end_reachable = checker.statement(&function.body, true);
+
+    let mut errors = checker.errors;
+    if end_reachable && *function.return_type != DataType::Void && function.name != "main" {
+        errors.push((FlowError::MissingReturn(function.name), function.body.span));
+    }
+
+    let defined = |name| checker.labels.iter().any(|&(label, _)| label == name);
+    let mut undefined = HashSet::new();
+    for &(name, span) in &checker.gotos {
+        if !defined(name) && undefined.insert(name) {
+            errors.push((FlowError::UndefinedLabel(name), span));
+        }
+    }
+    for &(name, span) in &checker.labels {
+        if !checker.gotos.iter().any(|&(goto, _)| goto == name) {
+            errors.push((FlowError::UnusedLabel(name), span));
+        }
+    }
+
+    errors
+}
+
+/// The value of `expr`, if it is an integer constant expression.
+pub fn constant_value(expr: &Expr) -> Option<i64> {
+    match expr {
+        Expr::Literal(Literal::IntegerConstant(value)) => Some(*value),
+        Expr::Literal(_) | Expr::Unknown => None,
+        Expr::Binary { left, operator, right } => {
+            let (left, right) = (constant_value(left)?, constant_value(right)?);
+            match operator {
+                BinOp::Add => left.checked_add(right),
+                BinOp::Subtract => left.checked_sub(right),
+                BinOp::Multiply => left.checked_mul(right),
+                BinOp::Divide => left.checked_div(right),
+                BinOp::Modulo => left.checked_rem(right),
+            }
+        }
+    }
+}
+
+/// A loop or switch around the statement being checked.
+#[derive(Debug)]
+enum Scope {
+    Loop {
+        /// Whether a reachable `break` leaves the loop.
+        broken:    bool,
+        /// Whether a reachable `continue` skips to the condition.
+        continued: bool,
+    },
+    Switch {
+        broken:  bool,
+        /// The values of the constant `case` labels seen so far.
+        values:  HashSet<i64>,
+        default: bool,
+    },
+}
+
+#[derive(Debug, Default)]
+struct Checker {
+    scopes: Vec<Scope>,
+    /// The labels defined, in order.
+    labels: Vec<(Symbol, Span)>,
+    /// The labels named by `goto`s, in order.
+    gotos:  Vec<(Symbol, Span)>,
+    errors: Vec<(FlowError, Span)>,
+}
+
+impl Checker {
+    /// Checks `statement`, which can be reached if `reachable` is, and
+    /// returns whether the statement after it can be reached.
+    fn statement(&mut self, statement: &Statement, reachable: bool) -> bool {
+        match &statement.kind {
+            StatementKind::Expression(_) | StatementKind::Assignment(_) => reachable,
+            StatementKind::Return(_) => false,
+            StatementKind::Goto(name) => {
+                self.gotos.push((*name, statement.span));
+                false
+            }
+            StatementKind::Compound(block) => self.block(&block.statements, reachable),
+            StatementKind::If { then_branch, else_branch, .. } => {
+                let then_reachable = self.block(then_branch, reachable);
+                let else_reachable = match else_branch {
+                    Some(else_branch) => self.block(else_branch, reachable),
+                    None => reachable,
+                };
+                then_reachable || else_reachable
+            }
+            StatementKind::While { condition, body } => {
+                let (_, broken, _) = self.loop_body(body, reachable);
+                (reachable && !always_true(condition)) || broken
+            }
+            StatementKind::DoWhile { body, condition } => {
+                let (end_reachable, broken, continued) = self.loop_body(body, reachable);
+                ((end_reachable || continued) && !always_true(condition)) || broken
+            }
+            StatementKind::For { initializer, condition, body, .. } => {
+                let reachable = match initializer {
+                    Some(initializer) => self.statement(initializer, reachable),
+                    None => reachable,
+                };
+                let (_, broken, _) = self.loop_body(body, reachable);
+                (reachable && !condition.as_ref().map_or(true, always_true)) || broken
+            }
+            StatementKind::Switch { body, .. } => {
+                self.scopes.push(Scope::Switch {
+                    broken:  false,
+                    values:  HashSet::new(),
+                    default: false,
+                });
+                // Only the labels of the body can be jumped to.
+                let end_reachable = self.block(body, false);
+                let Some(Scope::Switch { broken, default, .. }) = self.scopes.pop() else {
+                    unreachable!("switch scope popped by its body")
+                };
+                end_reachable || broken || (reachable && !default)
+            }
+            StatementKind::Case(value) => {
+                let value = constant_value(value);
+                let error = match self.switch() {
+                    Some(Scope::Switch { values, .. }) => {
+                        value.filter(|value| !values.insert(*value)).map(FlowError::DuplicateCase)
+                    }
+                    _ => Some(FlowError::CaseOutsideSwitch),
+                };
+                self.errors.extend(error.map(|error| (error, statement.span)));
+                true
+            }
+            StatementKind::Default => {
+                let error = match self.switch() {
+                    Some(Scope::Switch { default, .. }) => {
+                        mem::replace(default, true).then_some(FlowError::DuplicateDefault)
+                    }
+                    _ => Some(FlowError::DefaultOutsideSwitch),
+                };
+                self.errors.extend(error.map(|error| (error, statement.span)));
+                true
+            }
+            StatementKind::Label(name) => {
+                self.labels.push((*name, statement.span));
+                true
+            }
+            StatementKind::Break => {
+                match self.scopes.last_mut() {
+                    Some(Scope::Loop { broken, .. } | Scope::Switch { broken, .. }) => {
+                        *broken |= reachable;
+                    }
+                    None => self.errors.push((FlowError::BreakOutsideLoop, statement.span)),
+                }
+                false
+            }
+            StatementKind::Continue => {
+                let innermost_loop =
+                    self.scopes.iter_mut().rev().find(|scope| matches!(scope, Scope::Loop { .. }));
+                match innermost_loop {
+                    Some(Scope::Loop { continued, .. }) => *continued |= reachable,
+                    _ => self.errors.push((FlowError::ContinueOutsideLoop, statement.span)),
+                }
+                false
+            }
+        }
+    }
+
+    /// Checks `statements` in order, the first of which can be reached if
+    /// `reachable` is, and returns whether their end can be reached.
+    ///
+    /// The first statement which can't be reached is reported, unless it is
+    /// a `break` (as in `return 0; break;` in a switch), or the block can't
+    /// be reached at all, in which case the statement before it was.
+    fn block(&mut self, statements: &[Statement], mut reachable: bool) -> bool {
+        let mut reported = !reachable;
+        for statement in statements {
+            if matches!(
+                statement.kind,
+                StatementKind::Case(_) | StatementKind::Default | StatementKind::Label(_)
+            ) {
+                reachable = true;
+                reported = false;
+            } else if !reachable && !reported && statement.kind != StatementKind::Break {
+                self.errors.push((FlowError::UnreachableCode, statement.span));
+                reported = true;
+            }
+
+            reachable = self.statement(statement, reachable);
+        }
+
+        reachable
+    }
+
+    /// Checks the body of a loop, and returns whether its end can be
+    /// reached, and whether a `break` or a `continue` can be.
+    fn loop_body(&mut self, body: &[Statement], reachable: bool) -> (bool, bool, bool) {
+        self.scopes.push(Scope::Loop { broken: false, continued: false });
+        let end_reachable = self.block(body, reachable);
+        let Some(Scope::Loop { broken, continued }) = self.scopes.pop() else {
+            unreachable!("loop scope popped by its body")
+        };
+
+        (end_reachable, broken, continued)
+    }
+
+    /// The innermost switch around the statement being checked, which may
+    /// be outside of loops.
+    fn switch(&mut self) -> Option<&mut Scope> {
+        self.scopes.iter_mut().rev().find(|scope| matches!(scope, Scope::Switch { .. }))
+    }
+}
+
+/// Whether `condition` is a nonzero constant, as in `while (1)`.
+fn always_true(condition: &Expr) -> bool {
+    constant_value(condition).is_some_and(|value| value != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Block;
+    use rstest::rstest;
+
+    fn stmt(kind: StatementKind) -> Statement {
+        Statement::new(kind, Span::default())
+    }
+
+    fn int(value: i64) -> Expr {
+        Expr::Literal(Literal::IntegerConstant(value))
+    }
+
+    fn var(name: &str) -> Expr {
+        Expr::Literal(Literal::Identifier(name.to_string()))
+    }
+
+    fn add(left: Expr, right: Expr) -> Expr {
+        Expr::Binary { left: Box::new(left), operator: BinOp::Add, right: Box::new(right) }
+    }
+
+    /// `f();`, standing in for any statement which falls through.
+    fn call() -> Statement {
+        stmt(StatementKind::Expression(var("f")))
+    }
+
+    fn ret() -> Statement {
+        stmt(StatementKind::Return(Some(int(0))))
+    }
+
+    fn brk() -> Statement {
+        stmt(StatementKind::Break)
+    }
+
+    fn cont() -> Statement {
+        stmt(StatementKind::Continue)
+    }
+
+    fn case(value: Expr) -> Statement {
+        stmt(StatementKind::Case(value))
+    }
+
+    fn default() -> Statement {
+        stmt(StatementKind::Default)
+    }
+
+    fn label(name: &str) -> Statement {
+        stmt(StatementKind::Label(name.into()))
+    }
+
+    fn goto(name: &str) -> Statement {
+        stmt(StatementKind::Goto(name.into()))
+    }
+
+    fn block(statements: Vec<Statement>) -> Statement {
+        stmt(StatementKind::Compound(Block::from(statements)))
+    }
+
+    fn if_else(then_branch: Vec<Statement>, else_branch: Option<Vec<Statement>>) -> Statement {
+        stmt(StatementKind::If { condition: var("x"), then_branch, else_branch })
+    }
+
+    fn while_loop(condition: Expr, body: Vec<Statement>) -> Statement {
+        stmt(StatementKind::While { condition, body })
+    }
+
+    fn do_while(body: Vec<Statement>) -> Statement {
+        stmt(StatementKind::DoWhile { body, condition: var("x") })
+    }
+
+    fn switch(body: Vec<Statement>) -> Statement {
+        stmt(StatementKind::Switch { condition: var("x"), body })
+    }
+
+    fn function(name: &str, return_type: DataType, statements: Vec<Statement>) -> Function {
+        Function {
+            name:        name.into(),
+            params:      vec![],
+            return_type: Box::new(return_type),
+            body:        block(statements),
+            attributes:  vec![],
+        }
+    }
+
+    fn check(return_type: DataType, statements: Vec<Statement>) -> Vec<FlowError> {
+        check_function(&function("f", return_type, statements))
+            .into_iter()
+            .map(|(error, _)| error)
+            .collect()
+    }
+
+    #[rstest]
+    #[case::empty(vec![], false)]
+    #[case::returns(vec![call(), ret()], true)]
+    #[case::returns_in_a_block(vec![block(vec![ret()])], true)]
+    #[case::returns_in_both_branches(vec![if_else(vec![ret()], Some(vec![ret()]))], true)]
+    #[case::returns_in_one_branch(vec![if_else(vec![ret()], Some(vec![call()]))], false)]
+    #[case::returns_in_if_without_else(vec![if_else(vec![ret()], None)], false)]
+    #[case::infinite_while(vec![while_loop(int(1), vec![call()])], true)]
+    #[case::infinite_while_with_break(vec![while_loop(int(1), vec![brk()])], false)]
+    #[case::infinite_for(
+        vec![stmt(StatementKind::For {
+            initializer: None,
+            condition:   None,
+            increment:   None,
+            body:        vec![call()],
+        })],
+        true
+    )]
+    #[case::conditional_while(vec![while_loop(var("x"), vec![ret()])], false)]
+    #[case::do_while_returning(vec![do_while(vec![ret()])], true)]
+    #[case::do_while_continuing(vec![do_while(vec![if_else(vec![cont()], None), ret()])], false)]
+    #[case::switch_without_default(vec![switch(vec![case(int(1)), ret()])], false)]
+    #[case::switch_returning_everywhere(
+        vec![switch(vec![case(int(1)), ret(), default(), ret()])],
+        true
+    )]
+    #[case::switch_with_break(
+        vec![switch(vec![default(), brk()]), ret()],
+        true
+    )]
+    #[case::goto_backwards(vec![label("again"), call(), goto("again")], true)]
+    fn missing_return(#[case] statements: Vec<Statement>, #[case] returns: bool) {
+        let expected = if returns { vec![] } else { vec![FlowError::MissingReturn("f".into())] };
+        assert_eq!(check(DataType::Int, statements.clone()), expected);
+        assert_eq!(check(DataType::Void, statements), []);
+    }
+
+    #[rstest]
+    #[case::break_outside(vec![brk()], &[FlowError::BreakOutsideLoop])]
+    #[case::continue_outside(vec![cont()], &[FlowError::ContinueOutsideLoop])]
+    #[case::break_in_if(vec![if_else(vec![brk()], None)], &[FlowError::BreakOutsideLoop])]
+    #[case::break_in_switch(vec![switch(vec![default(), brk()])], &[])]
+    #[case::continue_in_switch(
+        vec![switch(vec![default(), cont()])],
+        &[FlowError::ContinueOutsideLoop]
+    )]
+    #[case::continue_in_switch_in_loop(
+        vec![while_loop(var("x"), vec![switch(vec![default(), cont()])])],
+        &[]
+    )]
+    #[case::case_outside(vec![case(int(1)), call()], &[FlowError::CaseOutsideSwitch])]
+    #[case::default_outside(vec![default(), call()], &[FlowError::DefaultOutsideSwitch])]
+    #[case::case_in_loop_in_switch(
+        vec![switch(vec![while_loop(var("x"), vec![case(int(1)), call()])])],
+        &[]
+    )]
+    #[case::duplicate_case(
+        vec![switch(vec![
+            case(int(1)),
+            case(int(2)),
+            case(add(int(0), int(1))),
+            case(var("y")),
+            case(var("y")),
+            call(),
+        ])],
+        &[FlowError::DuplicateCase(1)]
+    )]
+    #[case::same_case_in_nested_switches(
+        vec![switch(vec![case(int(1)), switch(vec![case(int(1)), call()])])],
+        &[]
+    )]
+    #[case::duplicate_default(
+        vec![switch(vec![default(), call(), default(), call()])],
+        &[FlowError::DuplicateDefault]
+    )]
+    #[case::undefined_label(
+        vec![if_else(vec![goto("out")], None), goto("out")],
+        &[FlowError::UndefinedLabel("out".into())]
+    )]
+    #[case::unused_label(
+        vec![label("out"), call()],
+        &[FlowError::UnusedLabel("out".into())]
+    )]
+    #[case::used_label(vec![goto("out"), label("out"), call()], &[])]
+    #[case::unreachable_after_return(
+        vec![ret(), call(), call()],
+        &[FlowError::UnreachableCode]
+    )]
+    #[case::unreachable_after_break(
+        vec![while_loop(var("x"), vec![brk(), call()])],
+        &[FlowError::UnreachableCode]
+    )]
+    #[case::unreachable_after_infinite_loop(
+        vec![while_loop(int(1), vec![call()]), call()],
+        &[FlowError::UnreachableCode]
+    )]
+    #[case::unreachable_in_both_blocks(
+        vec![
+            if_else(vec![ret(), call()], None),
+            ret(),
+            block(vec![call(), call()]),
+        ],
+        &[FlowError::UnreachableCode, FlowError::UnreachableCode]
+    )]
+    #[case::reachable_through_label(vec![goto("out"), label("out"), call()], &[])]
+    #[case::break_after_return(
+        vec![switch(vec![case(int(1)), ret(), brk()])],
+        &[]
+    )]
+    #[case::before_the_first_case(vec![switch(vec![call(), default(), call()])], &[])]
+    fn control_flow(#[case] statements: Vec<Statement>, #[case] expected: &[FlowError]) {
+        assert_eq!(check(DataType::Void, statements), expected);
+    }
+
+    #[test]
+    fn constant_values() {
+        assert_eq!(constant_value(&add(int(2), int(3))), Some(5));
+        assert_eq!(
+            constant_value(&Expr::Binary {
+                left:     Box::new(int(1)),
+                operator: BinOp::Divide,
+                right:    Box::new(int(0)),
+            }),
+            None
+        );
+        assert_eq!(constant_value(&add(int(1), var("x"))), None);
+    }
+}
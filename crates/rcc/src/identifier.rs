@@ -0,0 +1,304 @@
+//! Identifiers beyond ASCII.
+//!
+//! Besides letters, digits and `_`, identifiers may be spelled with other
+//! Unicode characters, written as themselves or as universal character names
+//! (`\u00E9`, `\U0001D4B5`). Which characters are allowed depends on the
+//! standard: C23 allows those which are `XID_Start` or `XID_Continue`, as
+//! Unicode Standard Annex #31 recommends, while C11 allows the ranges listed
+//! in its Annex D, some of them everywhere but at the start.
+//!
+//! The name of an identifier is its spelling with universal character names
+//! replaced, normalized to NFC, so that `café`, `caf\u00E9` and `cafe\u0301`
+//! all name the same thing.
+//!
+//! Identifiers can also look alike without being the same, such as `paypal`
+//! spelled with a Latin and with a Cyrillic `а`. Following Unicode Technical
+//! Standard #39, identifiers whose characters look like those of another
+//! identifier, and identifiers mixing scripts, are reported.
+
+use crate::{
+    lexer::Span,
+    standard::Standard,
+    symbol::Symbol,
+};
+use std::{
+    cmp::Ordering,
+    collections::{
+        hash_map::Entry,
+        HashMap,
+        HashSet,
+    },
+    ops::Range,
+};
+use unicode_normalization::UnicodeNormalization;
+use unicode_security::{
+    mixed_script::AugmentedScriptSet,
+    skeleton,
+    MixedScript,
+};
+
+/// The characters C11 allows in identifiers, from its Annex D.1.
+const C11_ALLOWED: &[(u32, u32)] = &[
+    (0x00A8, 0x00A8),
+    (0x00AA, 0x00AA),
+    (0x00AD, 0x00AD),
+    (0x00AF, 0x00AF),
+    (0x00B2, 0x00B5),
+    (0x00B7, 0x00BA),
+    (0x00BC, 0x00BE),
+    (0x00C0, 0x00D6),
+    (0x00D8, 0x00F6),
+    (0x00F8, 0x00FF),
+    (0x0100, 0x167F),
+    (0x1681, 0x180D),
+    (0x180F, 0x1FFF),
+    (0x200B, 0x200D),
+    (0x202A, 0x202E),
+    (0x203F, 0x2040),
+    (0x2054, 0x2054),
+    (0x2060, 0x206F),
+    (0x2070, 0x218F),
+    (0x2460, 0x24FF),
+    (0x2776, 0x2793),
+    (0x2C00, 0x2DFF),
+    (0x2E80, 0x2FFF),
+    (0x3004, 0x3007),
+    (0x3021, 0x302F),
+    (0x3031, 0x303F),
+    (0x3040, 0xD7FF),
+    (0xF900, 0xFD3D),
+    (0xFD40, 0xFDCF),
+    (0xFDF0, 0xFE44),
+    (0xFE47, 0xFFFD),
+    (0x10000, 0x1FFFD),
+    (0x20000, 0x2FFFD),
+    (0x30000, 0x3FFFD),
+    (0x40000, 0x4FFFD),
+    (0x50000, 0x5FFFD),
+    (0x60000, 0x6FFFD),
+    (0x70000, 0x7FFFD),
+    (0x80000, 0x8FFFD),
+    (0x90000, 0x9FFFD),
+    (0xA0000, 0xAFFFD),
+    (0xB0000, 0xBFFFD),
+    (0xC0000, 0xCFFFD),
+    (0xD0000, 0xDFFFD),
+    (0xE0000, 0xEFFFD),
+];
+
+/// The characters C11 doesn't allow at the start of an identifier, from its
+/// Annex D.2: combining marks.
+const C11_NOT_INITIAL: &[(u32, u32)] =
+    &[(0x0300, 0x036F), (0x1DC0, 0x1DFF), (0x20D0, 0x20FF), (0xFE20, 0xFE2F)];
+
+fn in_ranges(c: char, ranges: &[(u32, u32)]) -> bool {
+    let c = u32::from(c);
+    ranges
+        .binary_search_by(|&(first, last)| {
+            if last < c {
+                Ordering::Less
+            } else if first > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Whether `c` may start an identifier in `standard`.
+pub fn is_identifier_start(c: char, standard: Standard) -> bool {
+    if c.is_ascii() {
+        c.is_ascii_alphabetic() || c == '_'
+    } else if standard.c23() {
+        unicode_ident::is_xid_start(c)
+    } else {
+        in_ranges(c, C11_ALLOWED) && !in_ranges(c, C11_NOT_INITIAL)
+    }
+}
+
+/// Whether `c` may appear in an identifier, past its start, in `standard`.
+pub fn is_identifier_continue(c: char, standard: Standard) -> bool {
+    if c.is_ascii() {
+        c.is_ascii_alphanumeric() || c == '_'
+    } else if standard.c23() {
+        unicode_ident::is_xid_continue(c)
+    } else {
+        in_ranges(c, C11_ALLOWED)
+    }
+}
+
+/// The character a universal character name with the value `value` names,
+/// if it may name one: not one below U+00A0 but `$`, `@` and `` ` ``, which
+/// are spelled as themselves, nor a surrogate.
+fn universal_character(value: u32) -> Option<char> {
+    if value < 0xA0 && !matches!(value, 0x24 | 0x40 | 0x60) {
+        return None;
+    }
+
+    char::from_u32(value)
+}
+
+/// Something wrong with the spelling of an identifier, by its byte range in
+/// the spelling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Problem {
+    /// A character which isn't allowed where it is.
+    NotAllowed { range: Range<usize>, c: char, at_start: bool },
+    /// A universal character name which doesn't name a character it may.
+    InvalidUcn { range: Range<usize>, value: u32 },
+}
+
+/// The name of the identifier spelled `spelling`, and what is wrong with its
+/// spelling, if anything. Characters which aren't allowed are kept in the
+/// name, while invalid universal character names are dropped.
+pub(crate) fn decode(spelling: &str, standard: Standard) -> (Symbol, Vec<Problem>) {
+    if spelling.is_ascii() && !spelling.contains('\\') {
+        return (Symbol::intern(spelling), Vec::new());
+    }
+
+    let mut name = String::with_capacity(spelling.len());
+    let mut problems = Vec::new();
+    let mut at = 0;
+    while let Some(rest) = spelling.get(at..).filter(|rest| !rest.is_empty()) {
+        // The lexer only matches complete universal character names.
+        let (c, len) = if let Some(ucn) = rest.strip_prefix('\\') {
+            let len = if ucn.starts_with('u') { 6 } else { 10 };
+            let value = u32::from_str_radix(&rest[2..len], 16).unwrap_or(u32::MAX);
+            match universal_character(value) {
+                Some(c) => (c, len),
+                None => {
+                    problems.push(Problem::InvalidUcn { range: at..at + len, value });
+                    at += len;
+                    continue;
+                }
+            }
+        } else {
+            let c = rest.chars().next().unwrap_or_default();
+            (c, c.len_utf8())
+        };
+
+        let at_start = at == 0;
+        let allowed = if at_start {
+            is_identifier_start(c, standard)
+        } else {
+            is_identifier_continue(c, standard)
+        };
+        if !allowed {
+            problems.push(Problem::NotAllowed { range: at..at + len, c, at_start });
+        }
+
+        name.push(c);
+        at += len;
+    }
+
+    (Symbol::intern(&name.nfc().collect::<String>()), problems)
+}
+
+/// An identifier which may be mistaken for something it isn't.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Lookalike {
+    /// `name` looks like `other`, an earlier identifier, without being it.
+    Confusable { name: Symbol, span: Span, other: Symbol, other_span: Span },
+    /// `name` mixes characters of several scripts, such as Latin and
+    /// Cyrillic.
+    MixedScript { name: Symbol, span: Span, scripts: Vec<String> },
+}
+
+/// The lookalikes among `identifiers`, each at its first occurrence. Only
+/// identifiers with non-ASCII characters are reported.
+pub(crate) fn lookalikes(identifiers: impl IntoIterator<Item = (Symbol, Span)>) -> Vec<Lookalike> {
+    let mut seen = HashSet::new();
+    let identifiers = identifiers
+        .into_iter()
+        .filter(|(name, _)| seen.insert(*name))
+        .collect::<Vec<_>>();
+    if identifiers.iter().all(|(name, _)| name.as_str().is_ascii()) {
+        return Vec::new();
+    }
+
+    let mut skeletons = HashMap::new();
+    let mut lookalikes = Vec::new();
+    for (name, span) in identifiers {
+        let text = name.as_str();
+        if !text.is_ascii() && !text.is_single_script() {
+            lookalikes.push(Lookalike::MixedScript { name, span, scripts: scripts(text) });
+        }
+
+        match skeletons.entry(skeleton(text).collect::<String>()) {
+            Entry::Occupied(entry) => {
+                let (other, other_span): (Symbol, Span) = *entry.get();
+                if !text.is_ascii() || !other.as_str().is_ascii() {
+                    lookalikes.push(Lookalike::Confusable { name, span, other, other_span });
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((name, span));
+            }
+        }
+    }
+
+    lookalikes
+}
+
+/// The scripts of the characters of `text`, in order, leaving out those
+/// shared by all scripts, such as digits.
+fn scripts(text: &str) -> Vec<String> {
+    let mut scripts = Vec::new();
+    for c in text.chars() {
+        let set = AugmentedScriptSet::for_char(c);
+        let script = set.to_string();
+        if !set.is_all() && !scripts.contains(&script) {
+            scripts.push(script);
+        }
+    }
+
+    scripts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_normalized() {
+        for spelling in ["café", "caf\\u00E9", "caf\\U000000e9", "cafe\u{301}", "cafe\\u0301"] {
+            assert_eq!(decode(spelling, Standard::C23), (Symbol::intern("café"), vec![]));
+        }
+    }
+
+    #[test]
+    fn characters_depend_on_standard() {
+        // U+00B2 SUPERSCRIPT TWO is in Annex D, but isn't `XID_Continue`.
+        assert!(is_identifier_continue('²', Standard::C11));
+        assert!(!is_identifier_continue('²', Standard::C23));
+        // Combining marks may continue an identifier, but not start it.
+        assert!(!is_identifier_start('\u{301}', Standard::C11));
+        assert!(is_identifier_continue('\u{301}', Standard::C11));
+
+        let (_, problems) = decode("x£\\u0041\\uD800", Standard::C23);
+        assert_eq!(problems, [
+            Problem::NotAllowed { range: 1..3, c: '£', at_start: false },
+            Problem::InvalidUcn { range: 3..9, value: 0x41 },
+            Problem::InvalidUcn { range: 9..15, value: 0xD800 },
+        ]);
+    }
+
+    #[test]
+    fn finds_lookalikes() {
+        let span = Span::new(0, 0);
+        let latin = Symbol::intern("paypal");
+        let mixed = Symbol::intern("pаypal");
+        let cyrillic = Symbol::intern("рорка");
+
+        assert_eq!(lookalikes([(latin, span), (latin, span)]), []);
+        assert_eq!(lookalikes([(latin, span), (mixed, span), (cyrillic, span)]), [
+            Lookalike::MixedScript {
+                name: mixed,
+                span,
+                scripts: vec!["Latin".to_string(), "Cyrillic".to_string()],
+            },
+            Lookalike::Confusable { name: mixed, span, other: latin, other_span: span },
+        ]);
+    }
+}
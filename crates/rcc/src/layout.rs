@@ -0,0 +1,858 @@
+//! Type layout: the size and alignment of types, and where the members of
+//! structs and unions are, as answered to `sizeof`, `_Alignof` and
+//! `offsetof`.
+//!
+//! The sizes of the basic types come from a [`DataModel`]. Structs and
+//! unions are laid out as by the System V psABIs, which GCC and Clang follow
+//! on every target but Windows:
+//!
+//! - members are placed in order, each at the next multiple of its
+//!   alignment, and a struct is padded to a multiple of the largest one,
+//! - a bit-field is placed right after the previous member, unless it would
+//!   then straddle a boundary of its type's alignment, and a zero-width one
+//!   starts the next such unit,
+//! - unnamed bit-fields don't add to the alignment of the struct,
+//! - a flexible array member adds nothing to the size of the struct but
+//!   trailing padding, and
+//! - `#pragma pack(N)` caps the alignment of every member at `N`, and lets
+//!   bit-fields straddle.
+//!
+//! The Microsoft layout of bit-fields, used with [`DataModel::Llp64`] on
+//! Windows, isn't supported.
+
+use crate::ast::{
+    DataType,
+    Struct,
+    Symbol,
+};
+use derive_more::Display;
+use strum_macros::{
+    Display as StrumDisplay,
+    EnumString,
+};
+
+/// The widths of the basic types, as chosen by a target.
+///
+/// Besides the widths that name them, each data model has the alignments of
+/// the ABI it is best known from.
+#[derive(Debug, Default, StrumDisplay, EnumString, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataModel {
+    /// 32-bit `int`, `long` and pointers, as on i386, where `long long`
+    /// and `double` are only 4-byte aligned, and `long double` is the
+    /// 12-byte x87 format.
+    #[strum(serialize = "ilp32")]
+    Ilp32,
+    /// 64-bit `long` and pointers, as on x86-64, where `long double` is the
+    /// x87 format padded to 16 bytes.
+    #[default]
+    #[strum(serialize = "lp64")]
+    Lp64,
+    /// 64-bit pointers but 32-bit `long`, as on 64-bit Windows, where
+    /// `long double` is the same as `double`.
+    #[strum(serialize = "llp64")]
+    Llp64,
+}
+
+impl DataModel {
+    /// The layout of a scalar type, or `None` if `ty` isn't one.
+    pub fn scalar(self, ty: &DataType) -> Option<Layout> {
+        let word = match self {
+            DataModel::Ilp32 => 4,
+            DataModel::Lp64 | DataModel::Llp64 => 8,
+        };
+
+        let layout = match (ty, self) {
+            (DataType::Bool | DataType::Char, _) => Layout::new(1, 1),
+            (DataType::Short, _) => Layout::new(2, 2),
+            (DataType::Int | DataType::Enum(_) | DataType::Float, _) => Layout::new(4, 4),
+            (DataType::Long, DataModel::Lp64) => Layout::new(8, 8),
+            (DataType::Long, _) => Layout::new(4, 4),
+            (DataType::LongLong | DataType::Double, _) => Layout::new(8, word.min(8)),
+            (DataType::LongDouble, DataModel::Ilp32) => Layout::new(12, 4),
+            (DataType::LongDouble, DataModel::Lp64) => Layout::new(16, 16),
+            (DataType::LongDouble, DataModel::Llp64) => Layout::new(8, 8),
+            (DataType::Pointer(_), _) => Layout::new(word, word),
+            _ => return None,
+        };
+
+        Some(layout)
+    }
+}
+
+/// The size and alignment of a type, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Layout {
+    pub size:  u64,
+    pub align: u64,
+}
+
+impl Layout {
+    pub const fn new(size: u64, align: u64) -> Self {
+        Self { size, align }
+    }
+}
+
+/// Where a member of a struct or union is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MemberLayout {
+    pub name:       Option<Symbol>,
+    /// The offset of the member from the start of the struct, in bits.
+    pub bit_offset: u64,
+    /// The width of a bit-field.
+    pub bit_width:  Option<u32>,
+    /// The layout of the member's type.
+    pub layout:     Layout,
+}
+
+impl MemberLayout {
+    /// The offset of the byte the member starts in.
+    pub fn offset(&self) -> u64 {
+        self.bit_offset / 8
+    }
+}
+
+/// The layout of a struct or union, and of each of its members.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordLayout {
+    pub size:    u64,
+    pub align:   u64,
+    pub members: Vec<MemberLayout>,
+}
+
+impl RecordLayout {
+    pub fn layout(&self) -> Layout {
+        Layout::new(self.size, self.align)
+    }
+}
+
+/// Why a type has no layout, or an `offsetof` has no answer.
+#[derive(Debug, Display, Clone, PartialEq, Eq)]
+pub enum LayoutError {
+    #[display(fmt = "`{_0}` is not a complete object type")]
+    Incomplete(String),
+    #[display(fmt = "`{_0}` is too large")]
+    TooLarge(String),
+    #[display(fmt = "flexible array member `{member}` {reason}")]
+    FlexibleArrayMember { member: String, reason: &'static str },
+    #[display(fmt = "bit-field `{member}` has non-integer type `{ty}`")]
+    BitFieldType { member: String, ty: String },
+    #[display(fmt = "width of bit-field `{member}` ({width} bits) exceeds the width of its type \
+                     ({max} bits)")]
+    BitFieldTooWide { member: String, width: u32, max: u64 },
+    #[display(fmt = "named bit-field `{_0}` has zero width")]
+    ZeroWidthBitField(String),
+    #[display(fmt = "requested alignment {_0} is not a positive power of 2")]
+    InvalidAlignment(u64),
+    #[display(fmt = "`_Alignas({alignment})` of `{member}` is less than the alignment of its \
+                     type ({natural})")]
+    AlignmentTooSmall { member: String, alignment: u64, natural: u64 },
+    #[display(fmt = "`{record}` has no member named `{member}`")]
+    NoSuchMember { record: String, member: String },
+    #[display(fmt = "cannot take the offset of bit-field `{_0}`")]
+    OffsetOfBitField(String),
+    #[display(fmt = "`{_0}` is not a struct or union")]
+    NotARecord(String),
+    #[display(fmt = "`{_0}` is not an array")]
+    NotAnArray(String),
+    #[display(fmt = "invalid member designator `{_0}`")]
+    InvalidDesignator(String),
+}
+
+impl std::error::Error for LayoutError {}
+
+/// Lays out types for one [`DataModel`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutEngine {
+    data_model: DataModel,
+}
+
+impl LayoutEngine {
+    pub fn new(data_model: DataModel) -> Self {
+        Self { data_model }
+    }
+
+    pub fn data_model(&self) -> DataModel {
+        self.data_model
+    }
+
+    /// The size of `ty`, as given by `sizeof`.
+    pub fn size_of(&self, ty: &DataType) -> Result<u64, LayoutError> {
+        self.layout_of(ty).map(|layout| layout.size)
+    }
+
+    /// The alignment of `ty`, as given by `_Alignof`.
+    pub fn align_of(&self, ty: &DataType) -> Result<u64, LayoutError> {
+        self.layout_of(ty).map(|layout| layout.align)
+    }
+
+    pub fn layout_of(&self, ty: &DataType) -> Result<Layout, LayoutError> {
+        if let Some(layout) = self.data_model.scalar(ty) {
+            return Ok(layout);
+        }
+
+        match ty {
+            DataType::Array(element, Some(len)) => {
+                let element = self.layout_of(element)?;
+                let size = element
+                    .size
+                    .checked_mul(*len as u64)
+                    // Bit offsets must fit too.
+                    .filter(|size| *size <= u64::MAX / 8)
+                    .ok_or_else(|| LayoutError::TooLarge(ty.to_string()))?;
+
+                Ok(Layout::new(size, element.align))
+            }
+            DataType::Struct(record) => self.record_layout(record).map(|record| record.layout()),
+            _ => Err(LayoutError::Incomplete(ty.to_string())),
+        }
+    }
+
+    /// Lays out the members of `record`. A struct without members, which
+    /// GNU C allows, has size `0`.
+    pub fn record_layout(&self, record: &Struct) -> Result<RecordLayout, LayoutError> {
+        let mut members = Vec::with_capacity(record.members.len());
+        // The end of the last member, and of the largest one of a union.
+        let mut end = 0;
+        let mut align = 1;
+
+        for (index, member) in record.members.iter().enumerate() {
+            let name = || member.name.map_or("<anonymous>".to_string(), |name| name.to_string());
+
+            let layout = match &member.ty {
+                DataType::Array(element, None) => {
+                    let reason = if record.union {
+                        Some("is in a union")
+                    } else if index + 1 < record.members.len() {
+                        Some("is not the last member")
+                    } else if !record.members[..index].iter().any(|member| member.name.is_some()) {
+                        Some("is the only named member")
+                    } else {
+                        None
+                    };
+                    if let Some(reason) = reason {
+                        return Err(LayoutError::FlexibleArrayMember { member: name(), reason });
+                    }
+
+                    Layout::new(0, self.align_of(element)?)
+                }
+                ty => self.layout_of(ty)?,
+            };
+
+            let mut member_align = layout.align;
+            if let Some(alignment) = member.alignment {
+                if !alignment.is_power_of_two() {
+                    return Err(LayoutError::InvalidAlignment(alignment));
+                }
+                if alignment < layout.align {
+                    return Err(LayoutError::AlignmentTooSmall {
+                        member: name(),
+                        alignment,
+                        natural: layout.align,
+                    });
+                }
+                member_align = alignment;
+            }
+            // `#pragma pack` overrides `_Alignas` too.
+            if let Some(pack) = record.pack {
+                member_align = member_align.min(pack);
+            }
+
+            let start = if record.union { 0 } else { end };
+            let unit = member_align * 8;
+            let (bit_offset, bits) = match member.bit_width {
+                Some(width) => {
+                    if !is_integer(&member.ty) {
+                        return Err(LayoutError::BitFieldType {
+                            member: name(),
+                            ty:     member.ty.to_string(),
+                        });
+                    }
+                    if u64::from(width) > layout.size * 8 {
+                        return Err(LayoutError::BitFieldTooWide {
+                            member: name(),
+                            width,
+                            max: layout.size * 8,
+                        });
+                    }
+                    if width == 0 && member.name.is_some() {
+                        return Err(LayoutError::ZeroWidthBitField(name()));
+                    }
+
+                    let straddles = start % unit + u64::from(width) > layout.size * 8;
+                    if width == 0 || (straddles && record.pack.is_none()) {
+                        (align_to(start, unit), u64::from(width))
+                    } else {
+                        (start, u64::from(width))
+                    }
+                }
+                None => (align_to(start, unit), layout.size * 8),
+            };
+
+            if member.bit_width.is_none() || member.name.is_some() {
+                align = align.max(member_align);
+            }
+            end = end.max(bit_offset + bits);
+            members.push(MemberLayout {
+                name: member.name,
+                bit_offset,
+                bit_width: member.bit_width,
+                layout,
+            });
+        }
+
+        Ok(RecordLayout { size: align_to((end + 7) / 8, align), align, members })
+    }
+
+    /// The offset of the member `designator` names in `record`, as given by
+    /// `offsetof`, e.g. `inner.values[2]`. The members of anonymous structs
+    /// and unions are found as if they were members of `record`.
+    pub fn offset_of(&self, record: &Struct, designator: &str) -> Result<u64, LayoutError> {
+        let invalid = || LayoutError::InvalidDesignator(designator.to_string());
+
+        let mut offset = 0;
+        // The type designated so far, or `None` for `record` itself.
+        let mut ty: Option<&DataType> = None;
+        let mut rest = designator.trim();
+        while ty.is_none() || !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix('[') {
+                let (index, tail) = after.split_once(']').ok_or_else(invalid)?;
+                let index = index.trim().parse::<u64>().map_err(|_| invalid())?;
+                let element = match ty {
+                    Some(DataType::Array(element, _)) => element,
+                    Some(other) => return Err(LayoutError::NotAnArray(other.to_string())),
+                    None => return Err(invalid()),
+                };
+
+                offset += index * self.size_of(element)?;
+                ty = Some(&**element);
+                rest = tail.trim_start();
+                continue;
+            }
+
+            let after = match ty {
+                None => rest,
+                Some(_) => rest.strip_prefix('.').ok_or_else(invalid)?.trim_start(),
+            };
+            let len = after
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .unwrap_or(after.len());
+            let (name, tail) = after.split_at(len);
+            if name.is_empty() {
+                return Err(invalid());
+            }
+
+            let record = match ty {
+                None => record,
+                Some(DataType::Struct(record)) => record,
+                Some(other) => return Err(LayoutError::NotARecord(other.to_string())),
+            };
+            let (member_offset, member_ty) =
+                self.member_offset(record, Symbol::intern(name))?.ok_or_else(|| {
+                    LayoutError::NoSuchMember {
+                        record: record.name.clone(),
+                        member: name.to_string(),
+                    }
+                })?;
+
+            offset += member_offset;
+            ty = Some(member_ty);
+            rest = tail.trim_start();
+        }
+
+        Ok(offset)
+    }
+
+    /// The offset and type of the member `name` of `record`, looking into
+    /// anonymous structs and unions.
+    fn member_offset<'a>(
+        &self,
+        record: &'a Struct,
+        name: Symbol,
+    ) -> Result<Option<(u64, &'a DataType)>, LayoutError> {
+        let layout = self.record_layout(record)?;
+
+        for (member, member_layout) in record.members.iter().zip(&layout.members) {
+            match (&member.name, &member.ty) {
+                (Some(member_name), _) if *member_name == name => {
+                    if member.bit_width.is_some() {
+                        return Err(LayoutError::OffsetOfBitField(name.to_string()));
+                    }
+                    return Ok(Some((member_layout.offset(), &member.ty)));
+                }
+                (None, DataType::Struct(anonymous)) => {
+                    if let Some((offset, ty)) = self.member_offset(anonymous, name)? {
+                        return Ok(Some((member_layout.offset() + offset, ty)));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Whether `ty` can be the type of a bit-field.
+fn is_integer(ty: &DataType) -> bool {
+    matches!(
+        ty,
+        DataType::Bool |
+            DataType::Char |
+            DataType::Short |
+            DataType::Int |
+            DataType::Long |
+            DataType::LongLong |
+            DataType::Enum(_)
+    )
+}
+
+/// `offset` rounded up to a multiple of `align`, a power of 2.
+fn align_to(offset: u64, align: u64) -> u64 {
+    (offset + align - 1) & !(align - 1)
+}
+
+/// A `#pragma pack` directive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackPragma {
+    /// `#pragma pack(N)`, or `#pragma pack()` to stop packing.
+    Set(Option<u64>),
+    /// `#pragma pack(push)`, or `#pragma pack(push, N)`.
+    Push(Option<u64>),
+    /// `#pragma pack(pop)`.
+    Pop,
+}
+
+/// Every `#pragma pack` of `text`, with the byte offset it takes effect
+/// from. Identifiers naming pushed values are ignored, as are invalid
+/// alignments.
+pub fn pack_pragmas(text: &str) -> Vec<(usize, PackPragma)> {
+    let mut pragmas = Vec::new();
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+
+        let args = line
+            .trim_start()
+            .strip_prefix('#')
+            .and_then(|directive| directive.trim_start().strip_prefix("pragma"))
+            .and_then(|pragma| pragma.trim_start().strip_prefix("pack"))
+            .and_then(|pack| pack.trim().strip_prefix('('))
+            .and_then(|args| args.strip_suffix(')'));
+        let Some(args) = args else {
+            continue;
+        };
+
+        let args = args.split(',').map(str::trim).collect::<Vec<_>>();
+        let alignment = args
+            .iter()
+            .find_map(|arg| arg.parse::<u64>().ok())
+            .filter(|alignment| alignment.is_power_of_two());
+        let pragma = match args[0] {
+            "push" => PackPragma::Push(alignment),
+            "pop" => PackPragma::Pop,
+            "" => PackPragma::Set(None),
+            _ if alignment.is_some() => PackPragma::Set(alignment),
+            _ => continue,
+        };
+
+        pragmas.push((start, pragma));
+    }
+
+    pragmas
+}
+
+/// The alignment `pragmas` cap members at, at `offset`, if any.
+pub fn pack_at(pragmas: &[(usize, PackPragma)], offset: usize) -> Option<u64> {
+    let mut stack = Vec::new();
+    let mut pack = None;
+
+    for (_, pragma) in pragmas.iter().take_while(|(start, _)| *start <= offset) {
+        match pragma {
+            PackPragma::Set(set) => pack = *set,
+            PackPragma::Push(set) => {
+                stack.push(pack);
+                pack = set.or(pack);
+            }
+            // An unmatched `pop` is ignored.
+            PackPragma::Pop => pack = stack.pop().unwrap_or(pack),
+        }
+    }
+
+    pack
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Member;
+    use rstest::rstest;
+
+    fn member(name: &str, ty: DataType) -> Member {
+        Member { name: Some(name.into()), ty, bit_width: None, alignment: None }
+    }
+
+    fn bits(name: &str, ty: DataType, width: u32) -> Member {
+        let name = (!name.is_empty()).then(|| name.into());
+        Member { name, ty, bit_width: Some(width), alignment: None }
+    }
+
+    fn aligned(alignment: u64, member: Member) -> Member {
+        Member { alignment: Some(alignment), ..member }
+    }
+
+    fn anonymous(record: Struct) -> Member {
+        Member { name: None, ty: DataType::Struct(record), bit_width: None, alignment: None }
+    }
+
+    fn record(members: Vec<Member>) -> Struct {
+        Struct { name: "S".to_string(), union: false, members, pack: None }
+    }
+
+    fn union(members: Vec<Member>) -> Struct {
+        Struct { union: true, ..record(members) }
+    }
+
+    fn packed(pack: u64, record: Struct) -> Struct {
+        Struct { pack: Some(pack), ..record }
+    }
+
+    fn array(ty: DataType, len: Option<usize>) -> DataType {
+        DataType::Array(Box::new(ty), len)
+    }
+
+    fn pointer(ty: DataType) -> DataType {
+        DataType::Pointer(Box::new(ty))
+    }
+
+    fn enumeration() -> DataType {
+        DataType::Enum(crate::ast::Enum { name: "E".into(), constants: vec!["X".into()] })
+    }
+
+    /// Each record, the size and alignment GCC gives it on x86-64 Linux,
+    /// and the bit offset of each of its members.
+    #[rstest]
+    // struct { char c; int i; char d; }
+    #[case::padding(
+        record(vec![
+            member("c", DataType::Char),
+            member("i", DataType::Int),
+            member("d", DataType::Char),
+        ]),
+        Layout::new(12, 4),
+        &[0, 32, 64]
+    )]
+    // struct { char c; double d; }
+    #[case::double(
+        record(vec![member("c", DataType::Char), member("d", DataType::Double)]),
+        Layout::new(16, 8),
+        &[0, 64]
+    )]
+    // struct { char c; long double ld; }
+    #[case::long_double(
+        record(vec![member("c", DataType::Char), member("ld", DataType::LongDouble)]),
+        Layout::new(32, 16),
+        &[0, 128]
+    )]
+    // struct { short s; char c; }
+    #[case::tail_padding(
+        record(vec![member("s", DataType::Short), member("c", DataType::Char)]),
+        Layout::new(4, 2),
+        &[0, 16]
+    )]
+    // struct { char c; void *p; long l; }
+    #[case::pointer_and_long(
+        record(vec![
+            member("c", DataType::Char),
+            member("p", pointer(DataType::Void)),
+            member("l", DataType::Long),
+        ]),
+        Layout::new(24, 8),
+        &[0, 64, 128]
+    )]
+    // struct { _Bool b; short s[3]; }
+    #[case::array(
+        record(vec![member("b", DataType::Bool), member("s", array(DataType::Short, Some(3)))]),
+        Layout::new(8, 2),
+        &[0, 16]
+    )]
+    // struct { char c; enum E e; }
+    #[case::enumeration(
+        record(vec![member("c", DataType::Char), member("e", enumeration())]),
+        Layout::new(8, 4),
+        &[0, 32]
+    )]
+    // union { char c[5]; int i; }
+    #[case::union(
+        union(vec![member("c", array(DataType::Char, Some(5))), member("i", DataType::Int)]),
+        Layout::new(8, 4),
+        &[0, 0]
+    )]
+    // struct { char c; struct { char d; long e; } inner; }
+    #[case::nested(
+        record(vec![
+            member("c", DataType::Char),
+            member(
+                "inner",
+                DataType::Struct(record(vec![
+                    member("d", DataType::Char),
+                    member("e", DataType::Long),
+                ])),
+            ),
+        ]),
+        Layout::new(24, 8),
+        &[0, 64]
+    )]
+    // struct { int k; union { char a; double b; }; }
+    #[case::anonymous_union(
+        record(vec![
+            member("k", DataType::Int),
+            anonymous(union(vec![member("a", DataType::Char), member("b", DataType::Double)])),
+        ]),
+        Layout::new(16, 8),
+        &[0, 64]
+    )]
+    // struct { unsigned a : 3; unsigned b : 5; unsigned c : 25; }
+    #[case::bit_fields(
+        record(vec![
+            bits("a", DataType::Int, 3),
+            bits("b", DataType::Int, 5),
+            bits("c", DataType::Int, 25),
+        ]),
+        Layout::new(8, 4),
+        &[0, 3, 32]
+    )]
+    // struct { char a; int b : 4; }
+    #[case::bit_field_shares_a_unit(
+        record(vec![member("a", DataType::Char), bits("b", DataType::Int, 4)]),
+        Layout::new(4, 4),
+        &[0, 8]
+    )]
+    // struct { char a; short b : 9; short c : 9; }
+    #[case::bit_fields_dont_straddle(
+        record(vec![
+            member("a", DataType::Char),
+            bits("b", DataType::Short, 9),
+            bits("c", DataType::Short, 9),
+        ]),
+        Layout::new(6, 2),
+        &[0, 16, 32]
+    )]
+    // struct { char a; long long b : 40; char c; }
+    #[case::wide_bit_field(
+        record(vec![
+            member("a", DataType::Char),
+            bits("b", DataType::LongLong, 40),
+            member("c", DataType::Char),
+        ]),
+        Layout::new(8, 8),
+        &[0, 8, 48]
+    )]
+    // struct { char a; int : 0; char b; }
+    #[case::zero_width_bit_field(
+        record(vec![
+            member("a", DataType::Char),
+            bits("", DataType::Int, 0),
+            member("b", DataType::Char),
+        ]),
+        Layout::new(5, 1),
+        &[0, 32, 32]
+    )]
+    // struct { char a; int : 3; char b; }
+    #[case::unnamed_bit_field(
+        record(vec![
+            member("a", DataType::Char),
+            bits("", DataType::Int, 3),
+            member("b", DataType::Char),
+        ]),
+        Layout::new(3, 1),
+        &[0, 8, 16]
+    )]
+    // union { char a : 3; int b : 17; }
+    #[case::union_of_bit_fields(
+        union(vec![bits("a", DataType::Char, 3), bits("b", DataType::Int, 17)]),
+        Layout::new(4, 4),
+        &[0, 0]
+    )]
+    // struct { char c; _Alignas(16) int i; }
+    #[case::alignas(
+        record(vec![member("c", DataType::Char), aligned(16, member("i", DataType::Int))]),
+        Layout::new(32, 16),
+        &[0, 128]
+    )]
+    // struct { int n; double d[]; }
+    #[case::flexible_array_member(
+        record(vec![member("n", DataType::Int), member("d", array(DataType::Double, None))]),
+        Layout::new(8, 8),
+        &[0, 64]
+    )]
+    // struct { char c; int a[]; }
+    #[case::flexible_array_member_padding(
+        record(vec![member("c", DataType::Char), member("a", array(DataType::Int, None))]),
+        Layout::new(4, 4),
+        &[0, 32]
+    )]
+    // #pragma pack(1)
+    // struct { char c; int i; }
+    #[case::pack_1(
+        packed(1, record(vec![member("c", DataType::Char), member("i", DataType::Int)])),
+        Layout::new(5, 1),
+        &[0, 8]
+    )]
+    // #pragma pack(2)
+    // struct { char c; double d; }
+    #[case::pack_2(
+        packed(2, record(vec![member("c", DataType::Char), member("d", DataType::Double)])),
+        Layout::new(10, 2),
+        &[0, 16]
+    )]
+    // #pragma pack(1)
+    // struct { char a; int b : 31; char c; }
+    #[case::packed_bit_fields_straddle(
+        packed(
+            1,
+            record(vec![
+                member("a", DataType::Char),
+                bits("b", DataType::Int, 31),
+                member("c", DataType::Char),
+            ])
+        ),
+        Layout::new(6, 1),
+        &[0, 8, 40]
+    )]
+    fn system_v_x86_64(#[case] record: Struct, #[case] layout: Layout, #[case] offsets: &[u64]) {
+        let record = LayoutEngine::new(DataModel::Lp64).record_layout(&record).unwrap();
+
+        assert_eq!(record.layout(), layout);
+        let bit_offsets = record.members.iter().map(|member| member.bit_offset).collect::<Vec<_>>();
+        assert_eq!(bit_offsets, offsets);
+    }
+
+    #[test]
+    fn data_models() {
+        let layout = |data_model, ty: &DataType| LayoutEngine::new(data_model).layout_of(ty);
+        let pointer = pointer(DataType::Char);
+
+        assert_eq!(layout(DataModel::Lp64, &DataType::Long), Ok(Layout::new(8, 8)));
+        assert_eq!(layout(DataModel::Lp64, &pointer), Ok(Layout::new(8, 8)));
+        assert_eq!(layout(DataModel::Llp64, &DataType::Long), Ok(Layout::new(4, 4)));
+        assert_eq!(layout(DataModel::Llp64, &pointer), Ok(Layout::new(8, 8)));
+        assert_eq!(layout(DataModel::Llp64, &DataType::LongDouble), Ok(Layout::new(8, 8)));
+        assert_eq!(layout(DataModel::Ilp32, &DataType::Long), Ok(Layout::new(4, 4)));
+        assert_eq!(layout(DataModel::Ilp32, &pointer), Ok(Layout::new(4, 4)));
+        assert_eq!(layout(DataModel::Ilp32, &DataType::LongDouble), Ok(Layout::new(12, 4)));
+        assert_eq!("llp64".parse(), Ok(DataModel::Llp64));
+
+        // As checked with `gcc -m32`.
+        let i386 = LayoutEngine::new(DataModel::Ilp32);
+        let double = record(vec![member("c", DataType::Char), member("d", DataType::Double)]);
+        let wide_bit_field = record(vec![
+            member("a", DataType::Char),
+            bits("b", DataType::LongLong, 40),
+            member("c", DataType::Char),
+        ]);
+        assert_eq!(i386.record_layout(&double).unwrap().layout(), Layout::new(12, 4));
+        assert_eq!(i386.record_layout(&wide_bit_field).unwrap().layout(), Layout::new(8, 4));
+    }
+
+    #[test]
+    fn offset_of() {
+        let inner = record(vec![
+            member("d", DataType::Char),
+            member("values", array(DataType::Long, Some(4))),
+        ]);
+        let outer = record(vec![
+            member("c", DataType::Char),
+            member("inner", DataType::Struct(inner)),
+            anonymous(union(vec![member("a", DataType::Char), member("b", DataType::Double)])),
+            bits("flags", DataType::Int, 3),
+        ]);
+        let engine = LayoutEngine::default();
+        let offset_of = |designator| engine.offset_of(&outer, designator);
+
+        assert_eq!(offset_of("c"), Ok(0));
+        assert_eq!(offset_of("inner"), Ok(8));
+        assert_eq!(offset_of("inner.values"), Ok(16));
+        assert_eq!(offset_of("inner . values [2]"), Ok(32));
+        assert_eq!(offset_of("b"), Ok(48));
+        assert_eq!(offset_of("flags"), Err(LayoutError::OffsetOfBitField("flags".to_string())));
+        assert_eq!(
+            offset_of("inner.e"),
+            Err(LayoutError::NoSuchMember { record: "S".to_string(), member: "e".to_string() })
+        );
+        assert_eq!(offset_of("c[1]"), Err(LayoutError::NotAnArray("char".to_string())));
+        assert_eq!(offset_of("c.d"), Err(LayoutError::NotARecord("char".to_string())));
+        assert_eq!(offset_of("inner..d"), Err(LayoutError::InvalidDesignator("inner..d".into())));
+        assert_eq!(offset_of(""), Err(LayoutError::InvalidDesignator(String::new())));
+    }
+
+    #[test]
+    fn invalid_layouts() {
+        let engine = LayoutEngine::default();
+        let error = |members| engine.record_layout(&record(members)).unwrap_err().to_string();
+
+        assert_eq!(
+            error(vec![member("a", array(DataType::Int, None)), member("n", DataType::Int)]),
+            "flexible array member `a` is not the last member"
+        );
+        assert_eq!(
+            error(vec![member("a", array(DataType::Int, None))]),
+            "flexible array member `a` is the only named member"
+        );
+        assert_eq!(
+            error(vec![bits("d", DataType::Double, 3)]),
+            "bit-field `d` has non-integer type `double`"
+        );
+        assert_eq!(
+            error(vec![bits("c", DataType::Char, 9)]),
+            "width of bit-field `c` (9 bits) exceeds the width of its type (8 bits)"
+        );
+        assert_eq!(error(vec![bits("z", DataType::Int, 0)]), "named bit-field `z` has zero width");
+        assert_eq!(
+            error(vec![aligned(3, member("i", DataType::Int))]),
+            "requested alignment 3 is not a positive power of 2"
+        );
+        assert_eq!(
+            error(vec![aligned(2, member("i", DataType::Int))]),
+            "`_Alignas(2)` of `i` is less than the alignment of its type (4)"
+        );
+        assert_eq!(
+            error(vec![member("v", DataType::Void)]),
+            "`void` is not a complete object type"
+        );
+        assert_eq!(
+            engine.size_of(&array(array(DataType::Int, Some(usize::MAX)), Some(2))),
+            Err(LayoutError::TooLarge("int[18446744073709551615]".to_string()))
+        );
+    }
+
+    #[test]
+    fn pack_pragmas() {
+        let text = "struct A;\n\
+                    #pragma pack(push, 1)\n\
+                    struct B;\n\
+                    # pragma pack (push, r, 2)\n\
+                    struct C;\n\
+                    #pragma pack(pop)\n\
+                    struct D;\n\
+                    #pragma pack()\n\
+                    struct E;\n\
+                    #pragma pack(pop)\n\
+                    struct F;\n\
+                    #pragma pack(3)\n\
+                    #pragma pack(pop)\n\
+                    struct G;\n";
+        let pragmas = super::pack_pragmas(text);
+        let pack = |name: &str| pack_at(&pragmas, text.find(name).unwrap());
+
+        assert_eq!(pragmas.len(), 6);
+        assert_eq!(pack("struct A"), None);
+        assert_eq!(pack("struct B"), Some(1));
+        assert_eq!(pack("struct C"), Some(2));
+        assert_eq!(pack("struct D"), Some(1));
+        assert_eq!(pack("struct E"), None);
+        assert_eq!(pack("struct F"), None);
+        assert_eq!(pack("struct G"), None);
+    }
+}
@@ -1,14 +1,23 @@
 use crate::{
+    charset,
     cst::SyntaxKind,
     diagnostics::{
         self,
+        Diagnostic,
         DiagnosticsEngine,
         FileId,
+        SourceFile,
     },
-};
-use codespan_reporting::{
-    diagnostic::Diagnostic,
-    files::SimpleFile,
+    identifier::{
+        self,
+        Lookalike,
+        Problem,
+    },
+    macros,
+    source_manager::ExpansionId,
+    standard::Standard,
+    symbol::Symbol,
+    target::Target,
 };
 use derive_more::Display;
 use getset::{
@@ -19,11 +28,12 @@ use getset::{
 use logos::Logos;
 use owo_colors::OwoColorize;
 use std::{
+    borrow::Cow,
     fmt::{
         self,
         Display,
     },
-    fs::read_to_string,
+    fs,
     ops::Range,
     path::PathBuf,
 };
@@ -121,13 +131,19 @@ pub enum TokenKind {
     LPAREN,
     #[token(")")]
     RPAREN,
+    // `<:`, `:>`, `<%` and `%>` are the digraph spellings of `[`, `]`, `{`
+    // and `}`. They keep their spelling as their lexeme.
     #[token("[")]
+    #[token("<:")]
     LBRACKET,
     #[token("]")]
+    #[token(":>")]
     RBRACKET,
     #[token("{")]
+    #[token("<%")]
     LBRACE,
     #[token("}")]
+    #[token("%>")]
     RBRACE,
     #[token("...")]
     ELLIPSIS,
@@ -162,9 +178,13 @@ pub enum TokenKind {
     // {NZ}{D}*{IS}?				{ return I_CONSTANT; }
     // "0"{O}*{IS}?				{ return I_CONSTANT; }
     // {CP}?"'"([^'\\\n]|{ES})+"'"		{ return I_CONSTANT; }
-    #[regex("[xX][0-9a-fA-F]+(((u|U)(l|L|ll|LL)?)|((l|L|ll|LL)(u|U)?))?")]
-    #[regex("[1-9][0-9]*(((u|U)(l|L|ll|LL)?)|((l|L|ll|LL)(u|U)?))?")]
-    #[regex("0[0-7]*(((u|U)(l|L|ll|LL)?)|((l|L|ll|LL)(u|U)?))?")]
+    //
+    // C23 adds binary constants and `'` digit separators, which are accepted
+    // here and rejected in other standards by `lex_with_diagnostics`.
+    #[regex("0[xX][0-9a-fA-F]('?[0-9a-fA-F])*(((u|U)(l|L|ll|LL)?)|((l|L|ll|LL)(u|U)?))?")]
+    #[regex("0[bB][01]('?[01])*(((u|U)(l|L|ll|LL)?)|((l|L|ll|LL)(u|U)?))?")]
+    #[regex("[1-9]('?[0-9])*(((u|U)(l|L|ll|LL)?)|((l|L|ll|LL)(u|U)?))?")]
+    #[regex("0('?[0-7])*(((u|U)(l|L|ll|LL)?)|((l|L|ll|LL)(u|U)?))?")]
     // TODO: may need to come back to this
     // #[regex("(u|U|L)?'([^'\\\n]|(\\(['\"\\?\\abfnrtv]|[0-7]{1,3}|x[a-fA-F0-9]+)))*'")]
     INTEGER_CONSTANT,
@@ -175,12 +195,14 @@ pub enum TokenKind {
     // {HP}{H}+{P}{FS}?			{ return F_CONSTANT; }
     // {HP}{H}*"."{H}+{P}{FS}?			{ return F_CONSTANT; }
     // {HP}{H}+"."{P}{FS}?			{ return F_CONSTANT; }
-    #[regex("[0-9]+[eE][+-]?[0-9]+(f|F|l|L)?")]
-    #[regex("[0-9]*\\.[0-9]+([eE][+-]?[0-9]+)?(f|F|l|L)?")]
-    #[regex("[0-9]+\\.([eE][+-]?[0-9]+)?(f|F|l|L)?")]
-    #[regex("[xX][0-9a-fA-F]+[pP][+-]?[0-9]+(f|F|l|L)?")]
-    #[regex("[xX][0-9a-fA-F]*\\.[0-9a-fA-F]+[pP][+-]?[0-9]+(f|F|l|L)?")]
-    #[regex("[xX][0-9a-fA-F]+\\.[pP][+-]?[0-9]+(f|F|l|L)?")]
+    #[regex("[0-9]('?[0-9])*[eE][+-]?[0-9]('?[0-9])*(f|F|l|L)?")]
+    #[regex("([0-9]('?[0-9])*)?\\.[0-9]('?[0-9])*([eE][+-]?[0-9]('?[0-9])*)?(f|F|l|L)?")]
+    #[regex("[0-9]('?[0-9])*\\.([eE][+-]?[0-9]('?[0-9])*)?(f|F|l|L)?")]
+    #[regex("0[xX][0-9a-fA-F]('?[0-9a-fA-F])*[pP][+-]?[0-9]('?[0-9])*(f|F|l|L)?")]
+    #[regex(
+        "0[xX]([0-9a-fA-F]('?[0-9a-fA-F])*)?\\.[0-9a-fA-F]('?[0-9a-fA-F])*[pP][+-]?[0-9]('?[0-9])*(f|F|l|L)?"
+    )]
+    #[regex("0[xX][0-9a-fA-F]('?[0-9a-fA-F])*\\.[pP][+-]?[0-9]('?[0-9])*(f|F|l|L)?")]
     FLOATING_CONSTANT,
     // Keywords
     // #[token("and")]
@@ -194,6 +216,8 @@ pub enum TokenKind {
     #[token("char")]
     CHAR_KW,
     #[token("const")]
+    #[token("__const")]
+    #[token("__const__")]
     CONST_KW,
     #[token("continue")]
     CONTINUE_KW,
@@ -218,6 +242,8 @@ pub enum TokenKind {
     #[token("if")]
     IF_KW,
     #[token("inline")]
+    #[token("__inline")]
+    #[token("__inline__")]
     INLINE_KW,
     #[token("int")]
     INT_KW,
@@ -226,12 +252,16 @@ pub enum TokenKind {
     #[token("register")]
     REGISTER_KW,
     #[token("restrict")]
+    #[token("__restrict")]
+    #[token("__restrict__")]
     RESTRICT_KW,
     #[token("return")]
     RETURN_KW,
     #[token("short")]
     SHORT_KW,
     #[token("signed")]
+    #[token("__signed")]
+    #[token("__signed__")]
     SIGNED_KW,
     #[token("sizeof")]
     SIZEOF_KW,
@@ -250,16 +280,23 @@ pub enum TokenKind {
     #[token("void")]
     VOID_KW,
     #[token("volatile")]
+    #[token("__volatile")]
+    #[token("__volatile__")]
     VOLATILE_KW,
     #[token("while")]
     WHILE_KW,
     #[token("_Alignas")]
+    #[token("alignas")]
     ALIGNAS_KW,
     #[token("_Alignof")]
+    #[token("alignof")]
+    #[token("__alignof")]
+    #[token("__alignof__")]
     ALIGNOF_KW,
     #[token("_Atomic")]
     ATOMIC_KW,
     #[token("_Bool")]
+    #[token("bool")]
     BOOL_KW,
     #[token("_Complex")]
     COMPLEX_KW,
@@ -270,12 +307,45 @@ pub enum TokenKind {
     #[token("_Noreturn")]
     NORETURN_KW,
     #[token("_Static_assert")]
+    #[token("static_assert")]
     STATIC_ASSERT_KW,
     #[token("_Thread_local")]
+    #[token("thread_local")]
     THREAD_LOCAL_KW,
     #[token("__func__")]
     FUNC_NAME_KW,
 
+    // C23 keywords. Along with the lowercase spellings of `_Bool`,
+    // `_Static_assert` and friends above, these are only keywords with
+    // `--std=c23` (see `TokenKind::is_c23_keyword`).
+    #[token("true")]
+    TRUE_KW,
+    #[token("false")]
+    FALSE_KW,
+    #[token("nullptr")]
+    NULLPTR_KW,
+    #[token("constexpr")]
+    CONSTEXPR_KW,
+    #[token("typeof_unqual")]
+    TYPEOF_UNQUAL_KW,
+
+    // GNU extensions. Along with the `__`-prefixed spellings of the keywords
+    // above, these are only keywords with `--std=gnu11` (see
+    // `TokenKind::is_gnu_keyword`).
+    #[token("__attribute__")]
+    #[token("__attribute")]
+    ATTRIBUTE_KW,
+    #[token("asm")]
+    #[token("__asm__")]
+    #[token("__asm")]
+    ASM_KW,
+    #[token("__extension__")]
+    EXTENSION_KW,
+    #[token("typeof")]
+    #[token("__typeof__")]
+    #[token("__typeof")]
+    TYPEOF_KW,
+
     // 0[xX]{H}+{IS}?		{ count(); return(CONSTANT); }
     // 0{D}+{IS}?		{ count(); return(CONSTANT); }
     // {D}+{IS}?		{ count(); return(CONSTANT); }
@@ -311,7 +381,13 @@ pub enum TokenKind {
     // LAMBDA_KW,
 
     // Identifiers and literals
-    #[regex("[a-zA-Z_][a-zA-Z0-9_]*")]
+    //
+    // Any non-ASCII character, and any universal character name, is taken
+    // to be part of an identifier; those which aren't allowed in one are
+    // reported once it is lexed (see `identifier::decode`).
+    #[regex(
+        r"([a-zA-Z_]|[^\x00-\x7F]|\\u[0-9a-fA-F]{4}|\\U[0-9a-fA-F]{8})([a-zA-Z0-9_]|[^\x00-\x7F]|\\u[0-9a-fA-F]{4}|\\U[0-9a-fA-F]{8})*"
+    )]
     IDENTIFIER,
     // #[regex("\\d+")]
     // INT,
@@ -331,6 +407,10 @@ pub enum TokenKind {
 
     // Whitespace and special tokens
     #[regex("//[^\n]*")]
+    // `#pragma` lines survive preprocessing. The diagnostic ones are read by
+    // the `DiagnosticsEngine`; the others are ignored.
+    #[regex("#[ \t]*pragma[^\n]*")]
+    #[regex("%:[ \t]*pragma[^\n]*")]
     COMMENT,
     #[regex("[ \t]+")]
     WHITESPACE,
@@ -340,6 +420,10 @@ pub enum TokenKind {
     // #[token("    ")]
     // INDENT,
     // OUTDENT,
+    // Like `#` and `##`, their digraphs `%:` and `%:%:` only mean something
+    // to the preprocessor, so they are unknown tokens here.
+    #[token("%:", |_| false)]
+    #[token("%:%:", |_| false)]
     UNKNOWN,
     #[end]
     EOF,
@@ -446,6 +530,15 @@ impl Display for TokenKind {
             TokenKind::STATIC_ASSERT_KW => write!(f, "_Static_assert"),
             TokenKind::THREAD_LOCAL_KW => write!(f, "_Thread_local"),
             TokenKind::FUNC_NAME_KW => write!(f, "__func__"),
+            TokenKind::TRUE_KW => write!(f, "true"),
+            TokenKind::FALSE_KW => write!(f, "false"),
+            TokenKind::NULLPTR_KW => write!(f, "nullptr"),
+            TokenKind::CONSTEXPR_KW => write!(f, "constexpr"),
+            TokenKind::TYPEOF_UNQUAL_KW => write!(f, "typeof_unqual"),
+            TokenKind::ATTRIBUTE_KW => write!(f, "__attribute__"),
+            TokenKind::ASM_KW => write!(f, "__asm__"),
+            TokenKind::EXTENSION_KW => write!(f, "__extension__"),
+            TokenKind::TYPEOF_KW => write!(f, "typeof"),
 
             // Identifier and Literals
             TokenKind::IDENTIFIER => write!(f, "IDENTIFIER"),
@@ -580,9 +673,124 @@ impl TokenKind {
             TokenKind::INTEGER_CONSTANT => SyntaxKind::INTEGER_CONSTANT,
             TokenKind::FLOATING_CONSTANT => SyntaxKind::FLOATING_CONSTANT,
             TokenKind::FUNC_NAME_KW => SyntaxKind::FUNC_NAME_KW,
+            TokenKind::ATTRIBUTE_KW => SyntaxKind::ATTRIBUTE_KW,
+            TokenKind::ASM_KW => SyntaxKind::ASM_KW,
+            TokenKind::EXTENSION_KW => SyntaxKind::EXTENSION_KW,
+            TokenKind::TYPEOF_KW => SyntaxKind::TYPEOF_KW,
+            TokenKind::TRUE_KW => SyntaxKind::TRUE_KW,
+            TokenKind::FALSE_KW => SyntaxKind::FALSE_KW,
+            TokenKind::NULLPTR_KW => SyntaxKind::NULLPTR_KW,
+            TokenKind::CONSTEXPR_KW => SyntaxKind::CONSTEXPR_KW,
+            TokenKind::TYPEOF_UNQUAL_KW => SyntaxKind::TYPEOF_UNQUAL_KW,
+        }
+    }
+
+    /// Whether `lexeme`, lexed as `self`, is only a keyword when GNU
+    /// extensions are enabled: either a GNU keyword such as `__attribute__`,
+    /// or a `__`-prefixed spelling of a standard keyword such as
+    /// `__restrict`.
+    pub(crate) fn is_gnu_keyword(self, lexeme: &str) -> bool {
+        match self {
+            TokenKind::ATTRIBUTE_KW |
+            TokenKind::ASM_KW |
+            TokenKind::EXTENSION_KW |
+            TokenKind::TYPEOF_KW => true,
+            TokenKind::IDENTIFIER | TokenKind::FUNC_NAME_KW => false,
+            _ => lexeme.starts_with("__"),
+        }
+    }
+
+    /// Whether `lexeme`, lexed as `self`, is only a keyword in C23: either a
+    /// new keyword such as `nullptr`, or a lowercase spelling of a C11
+    /// keyword such as `bool`.
+    pub(crate) fn is_c23_keyword(self, lexeme: &str) -> bool {
+        match self {
+            TokenKind::TRUE_KW |
+            TokenKind::FALSE_KW |
+            TokenKind::NULLPTR_KW |
+            TokenKind::CONSTEXPR_KW |
+            TokenKind::TYPEOF_UNQUAL_KW => true,
+            TokenKind::ALIGNAS_KW |
+            TokenKind::ALIGNOF_KW |
+            TokenKind::BOOL_KW |
+            TokenKind::STATIC_ASSERT_KW |
+            TokenKind::THREAD_LOCAL_KW => !lexeme.starts_with('_'),
+            TokenKind::TYPEOF_KW => lexeme == "typeof",
+            _ => false,
         }
     }
 
+    /// Whether `lexeme`, lexed as `self`, is a keyword in `standard` rather
+    /// than a plain identifier. `typeof`, for instance, is a keyword both as
+    /// a GNU extension and in C23.
+    pub(crate) fn is_keyword_in(self, lexeme: &str, standard: Standard) -> bool {
+        let gnu = self.is_gnu_keyword(lexeme);
+        let c23 = self.is_c23_keyword(lexeme);
+
+        (!gnu && !c23) || (gnu && standard.gnu_extensions()) || (c23 && standard.c23())
+    }
+
+    pub(crate) fn is_keyword(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::AUTO_KW |
+                TokenKind::BREAK_KW |
+                TokenKind::CASE_KW |
+                TokenKind::CHAR_KW |
+                TokenKind::CONST_KW |
+                TokenKind::CONTINUE_KW |
+                TokenKind::DEFAULT_KW |
+                TokenKind::DO_KW |
+                TokenKind::DOUBLE_KW |
+                TokenKind::ELSE_KW |
+                TokenKind::ENUM_KW |
+                TokenKind::EXTERN_KW |
+                TokenKind::FLOAT_KW |
+                TokenKind::FOR_KW |
+                TokenKind::GOTO_KW |
+                TokenKind::IF_KW |
+                TokenKind::INLINE_KW |
+                TokenKind::INT_KW |
+                TokenKind::LONG_KW |
+                TokenKind::REGISTER_KW |
+                TokenKind::RESTRICT_KW |
+                TokenKind::RETURN_KW |
+                TokenKind::SHORT_KW |
+                TokenKind::SIGNED_KW |
+                TokenKind::SIZEOF_KW |
+                TokenKind::STATIC_KW |
+                TokenKind::STRUCT_KW |
+                TokenKind::SWITCH_KW |
+                TokenKind::TYPEDEF_KW |
+                TokenKind::UNION_KW |
+                TokenKind::UNSIGNED_KW |
+                TokenKind::VOID_KW |
+                TokenKind::VOLATILE_KW |
+                TokenKind::WHILE_KW |
+                TokenKind::ALIGNAS_KW |
+                TokenKind::ALIGNOF_KW |
+                TokenKind::ATOMIC_KW |
+                TokenKind::BOOL_KW |
+                TokenKind::COMPLEX_KW |
+                TokenKind::GENERIC_KW |
+                TokenKind::IMAGINARY_KW |
+                TokenKind::NORETURN_KW |
+                TokenKind::STATIC_ASSERT_KW |
+                TokenKind::THREAD_LOCAL_KW |
+                TokenKind::FUNC_NAME_KW |
+                TokenKind::ATTRIBUTE_KW |
+                TokenKind::ASM_KW |
+                TokenKind::EXTENSION_KW |
+                TokenKind::TYPEOF_KW |
+                TokenKind::TRUE_KW |
+                TokenKind::FALSE_KW |
+                TokenKind::NULLPTR_KW |
+                TokenKind::CONSTEXPR_KW |
+                TokenKind::TYPEOF_UNQUAL_KW
+        )
+    }
+
+    #[allow(dead_code)]
     pub(crate) fn is_declaration_specifier(&self) -> bool {
         self.is_storage_class_specifier() ||
             self.is_type_specifier() ||
@@ -591,26 +799,51 @@ impl TokenKind {
             self.is_alignment_specifier()
     }
 
+    #[allow(dead_code)]
     pub(crate) fn is_semicolon(&self) -> bool {
         matches!(self, TokenKind::SEMICOLON)
     }
 
+    /// Whether the token is an operator or punctuator, e.g. `+=` or `;`,
+    /// which is always spelled the same way.
+    pub(crate) fn is_punctuator(&self) -> bool {
+        !self.is_keyword() &&
+            !matches!(
+                self,
+                TokenKind::IDENTIFIER |
+                    TokenKind::STRING |
+                    TokenKind::BYTES |
+                    TokenKind::INTEGER_CONSTANT |
+                    TokenKind::FLOATING_CONSTANT |
+                    TokenKind::COMMENT |
+                    TokenKind::WHITESPACE |
+                    TokenKind::NEWLINE |
+                    TokenKind::UNKNOWN |
+                    TokenKind::EOF
+            )
+    }
+
+    #[allow(dead_code)]
     pub(crate) fn is_declarator(&self) -> bool {
         self.is_direct_declarator() || self.is_pointer()
     }
 
+    #[allow(dead_code)]
     pub(crate) fn is_direct_declarator(&self) -> bool {
         matches!(self, TokenKind::IDENTIFIER | TokenKind::LPAREN)
     }
 
+    #[allow(dead_code)]
     pub(crate) fn is_pointer(&self) -> bool {
         matches!(self, TokenKind::STAR)
     }
 
+    #[allow(dead_code)]
     pub(crate) fn is_equal(&self) -> bool {
         matches!(self, TokenKind::EQ)
     }
 
+    #[allow(dead_code)]
     pub(crate) fn is_storage_class_specifier(&self) -> bool {
         matches!(
             self,
@@ -618,7 +851,8 @@ impl TokenKind {
                 TokenKind::EXTERN_KW |
                 TokenKind::REGISTER_KW |
                 TokenKind::STATIC_KW |
-                TokenKind::THREAD_LOCAL_KW
+                TokenKind::THREAD_LOCAL_KW |
+                TokenKind::CONSTEXPR_KW
         )
     }
 
@@ -646,10 +880,13 @@ impl TokenKind {
                 TokenKind::ATOMIC_KW |
                 TokenKind::GENERIC_KW |
                 TokenKind::NORETURN_KW |
-                TokenKind::STATIC_ASSERT_KW
+                TokenKind::STATIC_ASSERT_KW |
+                TokenKind::TYPEOF_KW |
+                TokenKind::TYPEOF_UNQUAL_KW
         )
     }
 
+    #[allow(dead_code)]
     pub(crate) fn is_function_specifier(&self) -> bool {
         matches!(self, TokenKind::INLINE_KW | TokenKind::NORETURN_KW)
     }
@@ -664,10 +901,12 @@ impl TokenKind {
         )
     }
 
+    #[allow(dead_code)]
     pub(crate) fn is_alignment_specifier(&self) -> bool {
         matches!(self, TokenKind::ALIGNAS_KW)
     }
 
+    #[allow(dead_code)]
     pub(crate) fn is_l_brace(&self) -> bool {
         matches!(self, TokenKind::LBRACE)
     }
@@ -726,23 +965,43 @@ impl Span {
     }
 }
 
+/// A token: its kind, its spelling, interned, and where it is in the
+/// source. Tokens are `Copy`, so passing them around never allocates.
+///
+/// A token produced by a macro expansion is spelled where the macro is
+/// defined, and records the expansion, so that diagnostics can show how it
+/// got where it is.
 #[derive(
-    Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Getters, MutGetters, Setters, TypedBuilder,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Ord,
+    PartialOrd,
+    Getters,
+    MutGetters,
+    Setters,
+    TypedBuilder,
 )]
 #[getset(get = "pub", get_mut = "pub", set = "pub")]
 pub struct Token {
-    pub kind:   TokenKind,
-    pub lexeme: String,
-    pub span:   Span,
+    pub kind:      TokenKind,
+    pub lexeme:    Symbol,
+    pub span:      Span,
+    /// The macro expansion the token was produced by, if any.
+    #[builder(default)]
+    pub expansion: Option<ExpansionId>,
 }
 
 impl Token {
-    pub fn new(kind: TokenKind, lexeme: String, span: Span) -> Self {
-        Self { kind, lexeme, span }
+    pub fn new(kind: TokenKind, lexeme: impl Into<Symbol>, span: Span) -> Self {
+        Self { kind, lexeme: lexeme.into(), span, expansion: None }
     }
 
     pub fn pretty_print(&self) -> String {
-        format!("{} {} {}", self.kind.blue(), self.lexeme, self.span.black().italic(),).into()
+        format!("{} {} {}", self.kind.blue(), self.lexeme, self.span.black().italic(),)
     }
 }
 
@@ -781,7 +1040,7 @@ impl TokenStream {
 
     pub fn from_file(file_id: FileId, file: &PathBuf) -> Self {
         Self {
-            text: read_to_string(file).unwrap().into(),
+            text: charset::decode(&fs::read(file).unwrap(), charset::UTF_8).text,
             tokens: Vec::new(),
             cursor: 0,
             file_name: file.clone(),
@@ -789,7 +1048,7 @@ impl TokenStream {
         }
     }
 
-    fn from_db_file(file_id: FileId, db_file: SimpleFile<String, String>) -> Self {
+    fn from_db_file(file_id: FileId, db_file: &SourceFile) -> Self {
         let text = db_file.source().to_string();
 
         Self { tokens: Vec::new(), cursor: 0, file_id, file_name: db_file.name().into(), text }
@@ -800,13 +1059,17 @@ impl TokenStream {
     }
 
     pub fn get(&self, index: usize) -> Option<Token> {
-        self.tokens.get(index).cloned()
+        self.tokens.get(index).copied()
     }
 
     pub fn len(&self) -> usize {
         self.tokens.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
     pub fn pretty_print(&self) -> String {
         self.tokens.iter().map(|token| token.pretty_print()).collect::<Vec<String>>().join("\n")
     }
@@ -816,20 +1079,201 @@ impl Iterator for TokenStream {
     type Item = Token;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let token = self.tokens.get(self.cursor).cloned();
+        let token = self.tokens.get(self.cursor).copied();
         self.cursor += 1;
         token
     }
 }
 
+/// The trigraphs of C before C23: `??` followed by one of these characters
+/// stands for the character paired with it.
+const TRIGRAPHS: [(u8, char); 9] = [
+    (b'=', '#'),
+    (b'(', '['),
+    (b'/', '\\'),
+    (b')', ']'),
+    (b'\'', '^'),
+    (b'<', '{'),
+    (b'!', '|'),
+    (b'>', '}'),
+    (b'-', '~'),
+];
+
+/// A trigraph found in the source, whether or not it was replaced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Trigraph {
+    /// Where the trigraph is in the source.
+    span:        Span,
+    /// Where the trigraph, or what it was replaced with, is in the spliced
+    /// text.
+    offset:      usize,
+    replacement: char,
+    converted:   bool,
+}
+
+/// A piece of the source replaced by translation phases 1 and 2: a
+/// trigraph by the character it stands for, or a backslash-newline by
+/// nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Edit {
+    /// Where the replacement starts in the spliced text.
+    offset: usize,
+    /// The length of the replacement, `1` for a trigraph and `0` for a
+    /// backslash-newline.
+    len:    usize,
+    /// How many bytes shorter the spliced text is than the source, up to
+    /// the end of this edit.
+    shift:  usize,
+}
+
+/// The text the lexer sees, after translation phases 1 and 2: trigraphs
+/// replaced (if enabled) and lines ending in a backslash spliced with the
+/// next one. Offsets into it are mapped back to the source, so that a token
+/// split across lines covers all of its pieces.
+#[derive(Debug, Clone)]
+struct Spliced<'a> {
+    text:      Cow<'a, str>,
+    edits:     Vec<Edit>,
+    trigraphs: Vec<Trigraph>,
+}
+
+impl<'a> Spliced<'a> {
+    fn new(input: &'a str, trigraphs: bool) -> Self {
+        if !input.contains('\\') && !input.contains("??") {
+            return Self { text: Cow::Borrowed(input), edits: vec![], trigraphs: vec![] };
+        }
+
+        let bytes = input.as_bytes();
+        let newline = |at: usize| match bytes.get(at..) {
+            Some([b'\n', ..]) => 1,
+            Some([b'\r', b'\n', ..]) => 2,
+            _ => 0,
+        };
+
+        let mut text = String::with_capacity(input.len());
+        let mut edits: Vec<Edit> = vec![];
+        let mut seen = vec![];
+        // The start of the source not yet copied to `text`.
+        let mut copied = 0;
+        let mut at = 0;
+
+        while at < bytes.len() {
+            let trigraph = match bytes[at..] {
+                [b'?', b'?', third, ..] => TRIGRAPHS.iter().find(|(c, _)| *c == third),
+                _ => None,
+            };
+            if let Some(&(_, replacement)) = trigraph {
+                seen.push(Trigraph {
+                    span:      Span::new(at, at + 3),
+                    offset:    text.len() + at - copied,
+                    replacement,
+                    converted: trigraphs,
+                });
+            }
+
+            // The character at `at` after phase 1, and how many bytes of the
+            // source it takes up.
+            let (c, width) = match trigraph {
+                Some(&(_, replacement)) if trigraphs => (replacement, 3),
+                _ => (char::from(bytes[at]), 1),
+            };
+            let newline = if c == '\\' { newline(at + width) } else { 0 };
+
+            if newline == 0 && width == 1 {
+                at += 1;
+                continue;
+            }
+
+            text.push_str(&input[copied..at]);
+            let len = if newline == 0 {
+                text.push(c);
+                1
+            } else {
+                0
+            };
+            let shift = edits.last().map_or(0, |edit| edit.shift) + width + newline - len;
+            edits.push(Edit { offset: text.len() - len, len, shift });
+
+            at += width + newline;
+            copied = at;
+        }
+
+        text.push_str(&input[copied..]);
+        Self { text: Cow::Owned(text), edits, trigraphs: seen }
+    }
+
+    /// Maps the start of a token in the spliced text back to the source.
+    fn start(&self, offset: usize) -> usize {
+        let edits = self.edits.partition_point(|edit| edit.offset + edit.len <= offset);
+        offset + edits.checked_sub(1).map_or(0, |edit| self.edits[edit].shift)
+    }
+
+    /// Maps the end of a token in the spliced text back to the source. A
+    /// backslash-newline right after a token isn't part of it.
+    fn end(&self, offset: usize) -> usize {
+        let edits = self.edits.partition_point(|edit| edit.offset < offset);
+        offset + edits.checked_sub(1).map_or(0, |edit| self.edits[edit].shift)
+    }
+
+    /// Maps a span of the spliced text back to the source.
+    fn span(&self, range: Range<usize>) -> Span {
+        if range.is_empty() {
+            let start = self.start(range.start);
+            Span::new(start, start)
+        } else {
+            Span::new(self.start(range.start), self.end(range.end))
+        }
+    }
+}
+
+/// Rejects the parts of numeric literal syntax which `standard` doesn't
+/// support: digit separators (`1'000`) before C23, and binary constants
+/// (`0b1010`) outside of C23 and GNU C.
+fn check_literal(token: &Token, standard: Standard, file_id: FileId) -> Option<Diagnostic<FileId>> {
+    if !matches!(token.kind, TokenKind::INTEGER_CONSTANT | TokenKind::FLOATING_CONSTANT) {
+        return None;
+    }
+
+    let lexeme = token.lexeme.as_str();
+    if lexeme.contains('\'') && !standard.c23() {
+        Some(diagnostics::unsupported_literal_diagnostic(
+            file_id,
+            token,
+            "Digit separators",
+            "--std=c23",
+        ))
+    } else if (lexeme.starts_with("0b") || lexeme.starts_with("0B")) &&
+        !standard.c23() &&
+        !standard.gnu_extensions()
+    {
+        Some(diagnostics::unsupported_literal_diagnostic(
+            file_id,
+            token,
+            "Binary constants",
+            "--std=c23 or --std=gnu11",
+        ))
+    } else {
+        None
+    }
+}
+
+/// Lexes `input`, after replacing its trigraphs (with `trigraphs`, before
+/// C23) and splicing lines ending in a backslash with the next one, and
+/// expands its macros, with those predefined on `target`. The spans of the
+/// tokens are those of their spelling in `input`.
 pub fn lex_with_diagnostics(
     input: &str,
+    standard: Standard,
+    target: &Target,
+    trigraphs: bool,
     diagnostics: &mut DiagnosticsEngine,
     file_id: usize,
 ) -> TokenSink {
     let start = std::time::Instant::now();
 
-    let mut lexer = TokenKind::lexer(input);
+    let spliced = Spliced::new(input, trigraphs && !standard.c23());
+    let mut lexer = TokenKind::lexer(&spliced.text);
+    let mut comments = vec![];
     let file = diagnostics.files.get(file_id).expect("Failed to get file from db");
     let mut token_sink = TokenSink::from_db_file(file_id, file);
     let mut current_unknown_token: Option<Token> = None;
@@ -837,15 +1281,23 @@ pub fn lex_with_diagnostics(
     while let Some(token_result) = lexer.next() {
         match token_result {
             Ok(token) => {
-                if let Some(unknown_token) = current_unknown_token.clone() {
-                    token_sink
-                        .lexical_errors
-                        .push(diagnostics::unknown_token_diagnostic(file_id, &unknown_token));
-
+                if let Some(unknown_token) = current_unknown_token {
                     token_sink.tokens.push(unknown_token);
                     current_unknown_token = None;
                 }
 
+                // Keywords of other dialects, such as `__attribute__` in ISO C or
+                // `nullptr` before C23, are ordinary identifiers.
+                let token = if token.is_keyword_in(lexer.slice(), standard) {
+                    token
+                } else {
+                    TokenKind::IDENTIFIER
+                };
+
+                if token == TokenKind::COMMENT {
+                    comments.push(lexer.span());
+                }
+
                 // If token is whitespace (e.g. a newline, comment, etc.), skip it.
                 if token == TokenKind::WHITESPACE ||
                     token == TokenKind::COMMENT ||
@@ -874,30 +1326,57 @@ pub fn lex_with_diagnostics(
 
                     token_sink.tokens.push(Token::new(
                         TokenKind::STAR,
-                        "*".to_string().into(),
-                        (lexer.span().start..lexer.span().start + 1).into(),
+                        "*",
+                        spliced.span(lexer.span().start..lexer.span().start + 1),
                     ));
 
                     token_sink.tokens.push(Token::new(
                         TokenKind::STAR,
-                        "*".to_string().into(),
-                        (lexer.span().start + 1..lexer.span().end).into(),
+                        "*",
+                        spliced.span(lexer.span().start + 1..lexer.span().end),
                     ));
 
                     continue;
                 }
 
-                token_sink.tokens.push(Token::new(
-                    token,
-                    lexer.slice().to_string().into(),
-                    lexer.span().into(),
-                ));
+                let lexeme = if token == TokenKind::IDENTIFIER {
+                    let (name, problems) = identifier::decode(lexer.slice(), standard);
+                    for problem in problems {
+                        let start = lexer.span().start;
+                        token_sink.lexical_errors.push(match problem {
+                            Problem::NotAllowed { range, c, at_start } => {
+                                let span = spliced.span(start + range.start..start + range.end);
+                                diagnostics::character_not_allowed_in_identifier(
+                                    file_id, span, c, at_start,
+                                )
+                            }
+                            Problem::InvalidUcn { range, value } => {
+                                let ucn = &lexer.slice()[range.clone()];
+                                let span = spliced.span(start + range.start..start + range.end);
+                                diagnostics::invalid_universal_character_name(
+                                    file_id, span, ucn, value,
+                                )
+                            }
+                        });
+                    }
+                    name
+                } else {
+                    Symbol::intern(lexer.slice())
+                };
+                let token = Token::new(token, lexeme, spliced.span(lexer.span()));
+
+                if let Some(diagnostic) = check_literal(&token, standard, file_id) {
+                    token_sink.lexical_errors.push(diagnostic);
+                }
+
+                token_sink.tokens.push(token);
             }
             Err(()) => {
-                if let Some(unknown_token) = current_unknown_token.clone() {
-                    let Token { kind: _, span, lexeme } = unknown_token;
+                if let Some(unknown_token) = current_unknown_token {
+                    let Token { span, lexeme, .. } = unknown_token;
 
-                    let span = span.merge(lexer.span());
+                    let next = spliced.span(lexer.span());
+                    let span = span.merge(*next.start()..*next.end());
                     let updated_lexeme = format!("{}{}", lexeme, lexer.slice());
 
                     tracing::debug!(
@@ -909,7 +1388,7 @@ pub fn lex_with_diagnostics(
                     );
 
                     current_unknown_token =
-                        Some(Token::new(TokenKind::UNKNOWN, updated_lexeme.into(), span));
+                        Some(Token::new(TokenKind::UNKNOWN, updated_lexeme, span));
                 } else {
                     tracing::trace!(
                         " {}  Creating unknown token {:?} at {:?}",
@@ -920,14 +1399,18 @@ pub fn lex_with_diagnostics(
 
                     current_unknown_token = Some(Token::new(
                         TokenKind::UNKNOWN,
-                        lexer.slice().to_string().into(),
-                        lexer.span().into(),
+                        lexer.slice().to_string(),
+                        spliced.span(lexer.span()),
                     ));
                 }
             }
         }
     }
 
+    if let Some(unknown_token) = current_unknown_token {
+        token_sink.tokens.push(unknown_token);
+    }
+
     tracing::trace!(
         " {}  Creating token {} at {:?}",
         "LEXER".green(),
@@ -935,16 +1418,67 @@ pub fn lex_with_diagnostics(
         lexer.span().black().italic()
     );
 
-    token_sink.tokens.push(Token::new(TokenKind::EOF, "".to_string(), lexer.span().into()));
+    token_sink.tokens.push(Token::new(TokenKind::EOF, "", spliced.span(lexer.span())));
+
+    let identifiers = token_sink
+        .tokens
+        .tokens
+        .iter()
+        .filter(|token| token.kind == TokenKind::IDENTIFIER)
+        .map(|token| (token.lexeme, token.span));
+    for lookalike in identifier::lookalikes(identifiers) {
+        token_sink.lexical_errors.push(match lookalike {
+            Lookalike::Confusable { name, span, other, other_span } => {
+                diagnostics::confusable_identifier(file_id, name, span, other, other_span)
+            }
+            Lookalike::MixedScript { name, span, scripts } => {
+                diagnostics::mixed_script_identifier(file_id, name, span, &scripts)
+            }
+        });
+    }
 
-    // Collect token information and format it as a tree
-    let mut token_info = vec![];
-    for token in token_sink.tokens().tokens.iter() {
-        token_info.push(token);
+    // Trigraphs are no longer part of C23, so they go unmentioned there. As
+    // with GCC, those in comments aren't worth a warning.
+    if !standard.c23() {
+        for trigraph in &spliced.trigraphs {
+            let in_comment = comments.iter().any(|comment| comment.contains(&trigraph.offset));
+            if !in_comment {
+                token_sink.lexical_errors.push(diagnostics::trigraph(
+                    file_id,
+                    &input[*trigraph.span.start()..*trigraph.span.end()],
+                    trigraph.span,
+                    trigraph.replacement,
+                    trigraph.converted,
+                ));
+            }
+        }
     }
-    let formatted_tokens = format_tokens_as_tree(&token_info, "  ");
 
-    tracing::debug!("\n\n{}{}\n\n{}", "Token Stream".blue(), ":".black(), formatted_tokens);
+    // Unknown tokens are reported once macros are expanded: `#` is one, and
+    // so is anything in a macro which is never invoked.
+    let (tokens, errors) = macros::expand(
+        &token_sink.tokens.tokens,
+        input,
+        file_id,
+        standard,
+        target,
+        &mut diagnostics.files,
+    );
+    token_sink.tokens.tokens = tokens;
+    token_sink.lexical_errors.extend(errors);
+    for token in &token_sink.tokens.tokens {
+        if token.kind == TokenKind::UNKNOWN {
+            token_sink.lexical_errors.push(diagnostics::unknown_token_diagnostic(file_id, token));
+        }
+    }
+
+    // Collect token information and format it as a tree
+    if tracing::enabled!(tracing::Level::DEBUG) {
+        let token_info = token_sink.tokens().tokens.iter().collect::<Vec<_>>();
+        let formatted_tokens = format_tokens_as_tree(&token_info, "  ");
+
+        tracing::debug!("\n\n{}{}\n\n{}", "Token Stream".blue(), ":".black(), formatted_tokens);
+    }
 
     let elapsed = start.elapsed();
     let num_errors = token_sink.num_errors();
@@ -953,7 +1487,6 @@ pub fn lex_with_diagnostics(
 
     // Emit diagnostics
     token_sink.drain_errors(diagnostics);
-    diagnostics.flush();
 
     if num_errors > 0 {
         let error_description = if num_errors > 1 { "errors" } else { "error" };
@@ -1177,9 +1710,9 @@ impl TokenSink {
     //     Self { tokens: TokenStream::empty_stream(), lexical_errors: Vec::new() }
     // }
 
-    pub fn from_db_file(file_id: FileId, db_file: &SimpleFile<String, String>) -> Self {
+    pub fn from_db_file(file_id: FileId, db_file: &SourceFile) -> Self {
         Self {
-            tokens:         TokenStream::from_db_file(file_id, db_file.clone()),
+            tokens:         TokenStream::from_db_file(file_id, db_file),
             lexical_errors: Vec::new(),
         }
     }
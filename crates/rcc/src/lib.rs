@@ -0,0 +1,32 @@
+//! `rcc` is a C compiler front-end: a lexer, a resilient parser producing a
+//! concrete syntax tree, and lowering to an abstract syntax tree.
+//!
+//! The library is driven through a [`Session`]; the `rcc` binary is a thin
+//! command-line interface over it.
+
+pub mod ast;
+pub mod charset;
+pub mod condition;
+pub mod cst;
+pub mod diagnostics;
+pub mod fixtures;
+pub mod flow;
+pub mod identifier;
+pub mod layout;
+pub mod lexer;
+pub mod line_index;
+pub mod macros;
+pub mod parser;
+pub mod preprocessor;
+pub mod session;
+pub mod source_manager;
+pub mod standard;
+pub mod symbol;
+pub mod target;
+pub mod token_set;
+
+pub use session::{
+    Options,
+    Session,
+};
+pub use standard::Standard;
@@ -0,0 +1,229 @@
+//! Line and column positions in a source file.
+//!
+//! A [`Span`] is a byte range. To report it as a line and a column, each
+//! file has a [`LineIndex`], built once when the file is added, which maps
+//! a byte offset to a 0-based [`LineCol`] with a binary search over the
+//! starts of its lines.
+//!
+//! Columns are counted in UTF-8 bytes. Editors speaking the Language Server
+//! Protocol, and SARIF, count them in UTF-16 code units instead, and GCC
+//! style diagnostics in characters, so columns can be converted to and from
+//! any [`Encoding`]. Only lines with non-ASCII characters need converting;
+//! the index keeps the position of every such character to do it.
+
+use crate::lexer::Span;
+use std::{
+    collections::HashMap,
+    ops::Range,
+};
+
+/// What a column counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// UTF-8 bytes, as spans do.
+    Utf8,
+    /// UTF-16 code units, as the Language Server Protocol and SARIF do.
+    Utf16,
+    /// Unicode scalar values, i.e. characters.
+    Utf32,
+}
+
+/// A 0-based line and column. Unless it has been converted with
+/// [`LineIndex::to_encoding`], the column is in UTF-8 bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LineCol {
+    pub line: usize,
+    pub col:  usize,
+}
+
+/// A non-ASCII character, by its byte range within its line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WideChar {
+    start: usize,
+    end:   usize,
+}
+
+impl WideChar {
+    /// Its length in `encoding`.
+    fn len(self, encoding: Encoding) -> usize {
+        match encoding {
+            Encoding::Utf8 => self.end - self.start,
+            Encoding::Utf16 if self.end - self.start == 4 => 2,
+            Encoding::Utf16 | Encoding::Utf32 => 1,
+        }
+    }
+}
+
+/// The starts of the lines of a file, for converting between byte offsets
+/// and line/column positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    /// The byte offset each line starts at; the first is always 0.
+    line_starts: Vec<usize>,
+    /// The non-ASCII characters of each line that has any.
+    wide_chars:  HashMap<usize, Vec<WideChar>>,
+    len:         usize,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut wide_chars = HashMap::new();
+
+        for (offset, c) in text.char_indices() {
+            if c == '\n' {
+                line_starts.push(offset + 1);
+            } else if !c.is_ascii() {
+                let line_start = line_starts[line_starts.len() - 1];
+                let start = offset - line_start;
+                wide_chars
+                    .entry(line_starts.len() - 1)
+                    .or_insert_with(Vec::new)
+                    .push(WideChar { start, end: start + c.len_utf8() });
+            }
+        }
+
+        Self { line_starts, wide_chars, len: text.len() }
+    }
+
+    /// The number of lines. A file ending in a newline has an empty last
+    /// line.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The byte range of `line`, including its newline, if any.
+    pub fn line_range(&self, line: usize) -> Option<Range<usize>> {
+        let start = *self.line_starts.get(line)?;
+        let end = self.line_starts.get(line + 1).copied().unwrap_or(self.len);
+        Some(start..end)
+    }
+
+    /// The line and byte column of `offset`. Offsets past the end of the
+    /// file are taken to be at its end.
+    pub fn line_col(&self, offset: usize) -> LineCol {
+        let offset = offset.min(self.len);
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        LineCol { line, col: offset - self.line_starts[line] }
+    }
+
+    /// The line and column of the start and end of `span`.
+    pub fn span(&self, span: Span) -> (LineCol, LineCol) {
+        (self.line_col(*span.start()), self.line_col(*span.end()))
+    }
+
+    /// The byte offset of `line_col`, if the line exists and the column is
+    /// within it.
+    pub fn offset(&self, line_col: LineCol) -> Option<usize> {
+        let range = self.line_range(line_col.line)?;
+        let offset = range.start + line_col.col;
+        (offset <= range.end).then_some(offset)
+    }
+
+    /// `line_col`, with its byte column converted to count `encoding`.
+    pub fn to_encoding(&self, line_col: LineCol, encoding: Encoding) -> LineCol {
+        let mut col = line_col.col;
+        for c in self.wide_chars_on(line_col.line) {
+            if c.end > line_col.col {
+                break;
+            }
+            col -= c.len(Encoding::Utf8) - c.len(encoding);
+        }
+
+        LineCol { line: line_col.line, col }
+    }
+
+    /// `line_col`, whose column counts `encoding`, with its column converted
+    /// back to bytes.
+    pub fn from_encoding(&self, line_col: LineCol, encoding: Encoding) -> LineCol {
+        let mut col = line_col.col;
+        for c in self.wide_chars_on(line_col.line) {
+            if c.start >= col {
+                break;
+            }
+            col += c.len(Encoding::Utf8) - c.len(encoding);
+        }
+
+        LineCol { line: line_col.line, col }
+    }
+
+    /// The line and column of `offset`, with the column counting
+    /// `encoding`.
+    pub fn position(&self, offset: usize, encoding: Encoding) -> LineCol {
+        self.to_encoding(self.line_col(offset), encoding)
+    }
+
+    /// `offset`, counted in `encoding` from the start of the file rather
+    /// than in bytes.
+    pub fn offset_in(&self, offset: usize, encoding: Encoding) -> usize {
+        let line_col = self.line_col(offset);
+        let before = self
+            .wide_chars
+            .iter()
+            .filter(|(&line, _)| line < line_col.line)
+            .flat_map(|(_, chars)| chars)
+            .map(|c| c.len(Encoding::Utf8) - c.len(encoding))
+            .sum::<usize>();
+
+        self.line_starts[line_col.line] - before + self.to_encoding(line_col, encoding).col
+    }
+
+    fn wide_chars_on(&self, line: usize) -> &[WideChar] {
+        self.wide_chars.get(&line).map_or(&[], Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_lines_and_columns() {
+        let index = LineIndex::new("int x;\r\nint y;\n\nz");
+        assert_eq!(index.line_count(), 4);
+        assert_eq!(index.line_col(0), LineCol { line: 0, col: 0 });
+        assert_eq!(index.line_col(4), LineCol { line: 0, col: 4 });
+        assert_eq!(index.line_col(7), LineCol { line: 0, col: 7 });
+        assert_eq!(index.line_col(8), LineCol { line: 1, col: 0 });
+        assert_eq!(index.line_col(15), LineCol { line: 2, col: 0 });
+        assert_eq!(index.line_col(16), LineCol { line: 3, col: 0 });
+        assert_eq!(index.line_col(17), LineCol { line: 3, col: 1 });
+        assert_eq!(index.line_col(100), LineCol { line: 3, col: 1 });
+
+        assert_eq!(index.line_range(1), Some(8..15));
+        assert_eq!(index.line_range(3), Some(16..17));
+        assert_eq!(index.line_range(4), None);
+
+        for offset in 0..=17 {
+            assert_eq!(index.offset(index.line_col(offset)), Some(offset));
+        }
+        assert_eq!(index.offset(LineCol { line: 2, col: 2 }), None);
+    }
+
+    #[test]
+    fn converts_columns() {
+        // `é` is 2 bytes, 1 UTF-16 unit; `𝔵` is 4 bytes, 2 UTF-16 units.
+        let text = "a\n/* é */ int 𝔵 = 1;";
+        let index = LineIndex::new(text);
+        let x = text.find('𝔵').unwrap();
+        let one = text.find('1').unwrap();
+
+        assert_eq!(index.line_col(x), LineCol { line: 1, col: 13 });
+        assert_eq!(index.position(x, Encoding::Utf16), LineCol { line: 1, col: 12 });
+        assert_eq!(index.position(x, Encoding::Utf32), LineCol { line: 1, col: 12 });
+        assert_eq!(index.position(one, Encoding::Utf16), LineCol { line: 1, col: 17 });
+        assert_eq!(index.position(one, Encoding::Utf32), LineCol { line: 1, col: 16 });
+
+        for encoding in [Encoding::Utf8, Encoding::Utf16, Encoding::Utf32] {
+            for (offset, _) in text.char_indices() {
+                let line_col = index.line_col(offset);
+                let converted = index.to_encoding(line_col, encoding);
+                assert_eq!(index.from_encoding(converted, encoding), line_col);
+            }
+        }
+
+        assert_eq!(index.offset_in(one, Encoding::Utf8), one);
+        assert_eq!(index.offset_in(one, Encoding::Utf16), one - 3);
+        assert_eq!(index.offset_in(one, Encoding::Utf32), one - 4);
+    }
+}
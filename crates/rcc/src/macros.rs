@@ -0,0 +1,648 @@
+//! Macro expansion.
+//!
+//! `#define` and `#undef` lines are taken out of the token stream, and the
+//! macros they define are expanded wherever they are invoked after them.
+//! Arguments are expanded before they are substituted, and each expansion
+//! is rescanned for more macros, but a macro is never expanded again within
+//! its own expansion.
+//!
+//! Every expansion is recorded in the [`SourceManager`], along with the
+//! expansion its invocation came from, if any, and the tokens it produces
+//! point back to it. A diagnostic on such a token can then show where each
+//! macro was invoked and defined, innermost first.
+//!
+//! The macros predefined on the target, such as `__x86_64__`, are defined
+//! from the start. Having no `#define`, they aren't recorded as expansions;
+//! their tokens are spelled at their invocation instead.
+//!
+//! Conditional inclusion is handled here too: the conditional directives
+//! are taken out, along with every line of a skipped group, and `#if`
+//! conditions are evaluated with the macros defined at that point (see
+//! [`condition`]).
+//!
+//! Other directives, such as `#include`, are left in the stream for now,
+//! and `#` and `##` in replacement lists aren't supported yet.
+
+use crate::{
+    condition::{
+        self,
+        Conditional,
+    },
+    diagnostics::{
+        self,
+        Diagnostic,
+        FileId,
+    },
+    lexer::{
+        Span,
+        Token,
+        TokenKind,
+    },
+    source_manager::{
+        Expansion,
+        SourceManager,
+    },
+    standard::Standard,
+    symbol::Symbol,
+    target::Target,
+};
+use logos::Logos;
+use std::{
+    collections::{
+        HashMap,
+        VecDeque,
+    },
+    mem,
+};
+
+/// A macro, as defined by a `#define` or predefined on the target.
+#[derive(Debug, Clone)]
+struct Macro {
+    /// Where its name is in the `#define`.
+    span:       Span,
+    /// The parameters of a function-like macro, not counting `...`.
+    params:     Option<Vec<Symbol>>,
+    variadic:   bool,
+    body:       Vec<Token>,
+    /// Whether the macro is predefined on the target, rather than by a
+    /// `#define`.
+    predefined: bool,
+}
+
+/// Expands the macros of `tokens`, the tokens of the file `file_id`, whose
+/// source is `text`, taking out the `#define`, `#undef` and conditional
+/// directive lines along the way, along with the lines of skipped groups.
+/// Returns the expanded tokens and the errors in invocations and
+/// directives.
+pub(crate) fn expand(
+    tokens: &[Token],
+    text: &str,
+    file_id: FileId,
+    standard: Standard,
+    target: &Target,
+    sources: &mut SourceManager,
+) -> (Vec<Token>, Vec<Diagnostic<FileId>>) {
+    let (tokens, eof) = match tokens.split_last() {
+        Some((eof, tokens)) if eof.kind == TokenKind::EOF => (tokens, Some(*eof)),
+        _ => (tokens, None),
+    };
+
+    let mut expander = Expander {
+        file_id,
+        standard,
+        sources,
+        macros: predefined_macros(target),
+        conditionals: Vec::new(),
+        errors: Vec::new(),
+    };
+    let mut output = Vec::with_capacity(tokens.len());
+    // The lines since the last directive. An invocation may span lines, so
+    // they are expanded together.
+    let mut pending = Vec::new();
+    let mut start = 0;
+    while start < tokens.len() {
+        let end = (start + 1..tokens.len())
+            .find(|&at| starts_line(text, &tokens[at - 1], &tokens[at]))
+            .unwrap_or(tokens.len());
+        let line = &tokens[start..end];
+        start = end;
+
+        if !matches!(line[0].lexeme.as_str(), "#" | "%:") {
+            if expander.active() {
+                pending.extend_from_slice(line);
+            }
+            continue;
+        }
+
+        output.extend(expander.expand_all(mem::take(&mut pending)));
+        if !expander.directive(line) {
+            output.extend_from_slice(line);
+        }
+    }
+    output.extend(expander.expand_all(pending));
+    output.extend(eof);
+
+    if !expander.conditionals.is_empty() {
+        expander.errors.push(diagnostics::unterminated_conditional(file_id, text.len()));
+    }
+
+    (output, expander.errors)
+}
+
+/// The macros predefined on `target`. Their tokens have no spans until they
+/// are invoked.
+fn predefined_macros(target: &Target) -> HashMap<Symbol, Macro> {
+    let mut macros = HashMap::new();
+    for (name, value) in target.predefined_macros() {
+        let mut lexer = TokenKind::lexer(&value);
+        let mut body = Vec::new();
+        while let Some(kind) = lexer.next() {
+            match kind {
+                Ok(TokenKind::WHITESPACE) => {}
+                kind => body.push(Token::new(
+                    kind.unwrap_or(TokenKind::UNKNOWN),
+                    lexer.slice(),
+                    Span::new(0, 0),
+                )),
+            }
+        }
+
+        macros.insert(Symbol::intern(name), Macro {
+            span: Span::new(0, 0),
+            params: None,
+            variadic: false,
+            body,
+            predefined: true,
+        });
+    }
+
+    macros
+}
+
+/// Whether `token` is the first on its line, i.e. whether there is a
+/// newline between it and `previous` that isn't spliced away.
+fn starts_line(text: &str, previous: &Token, token: &Token) -> bool {
+    let Some(gap) = text.get(*previous.span.end()..*token.span.start()) else {
+        return false;
+    };
+
+    gap.match_indices('\n')
+        .any(|(at, _)| !gap[..at].trim_end_matches('\r').ends_with('\\'))
+}
+
+/// Whether `token` can name a macro: an identifier, or a keyword.
+fn is_name(token: &Token) -> bool {
+    token.lexeme.as_str().starts_with(|c: char| c == '_' || c.is_alphabetic())
+}
+
+struct Expander<'a> {
+    file_id:      FileId,
+    /// The dialect of C, which decides whether `#elifdef` and `#elifndef`
+    /// are supported.
+    standard:     Standard,
+    sources:      &'a mut SourceManager,
+    macros:       HashMap<Symbol, Macro>,
+    /// The open `#if`/`#ifdef`/`#ifndef` groups, innermost last.
+    conditionals: Vec<Conditional>,
+    errors:       Vec<Diagnostic<FileId>>,
+}
+
+impl Expander<'_> {
+    /// Whether lines at the current position are kept, i.e. whether every
+    /// enclosing conditional group is in a kept branch.
+    fn active(&self) -> bool {
+        self.conditionals.last().map_or(true, |conditional| conditional.active)
+    }
+
+    /// Handles the directive `line`, from its `#`: conditional inclusion,
+    /// `#define` and `#undef`. Every directive in a skipped group is taken
+    /// out too. Returns `false`, handling nothing, for any other directive,
+    /// which is left in the stream.
+    fn directive(&mut self, line: &[Token]) -> bool {
+        let Some(name) = line.get(1) else {
+            return false;
+        };
+        let directive = name.lexeme.as_str();
+        let rest = &line[2..];
+        let span = Span::new(*line[0].span.start(), *line[line.len() - 1].span.end());
+
+        if matches!(directive, "elifdef" | "elifndef") && !self.standard.c23() {
+            self.errors.push(diagnostics::unsupported_directive(self.file_id, span, directive));
+        }
+
+        match directive {
+            "if" | "ifdef" | "ifndef" => {
+                let outer_active = self.active();
+                let active = outer_active && self.condition(directive, rest, span);
+                self.conditionals.push(Conditional {
+                    active,
+                    taken: active,
+                    outer_active,
+                    seen_else: false,
+                });
+            }
+            "elif" | "elifdef" | "elifndef" | "else" => {
+                let Some(conditional) = self.conditionals.pop() else {
+                    self.errors.push(diagnostics::unmatched_conditional_directive(
+                        self.file_id,
+                        span,
+                        directive,
+                    ));
+                    return true;
+                };
+                if conditional.seen_else {
+                    self.errors.push(diagnostics::directive_after_else(
+                        self.file_id,
+                        span,
+                        directive,
+                    ));
+                }
+
+                let active = conditional.outer_active &&
+                    !conditional.taken &&
+                    (directive == "else" || self.condition(directive, rest, span));
+                self.conditionals.push(Conditional {
+                    active,
+                    taken: conditional.taken || active,
+                    seen_else: conditional.seen_else || directive == "else",
+                    ..conditional
+                });
+            }
+            "endif" => {
+                if self.conditionals.pop().is_none() {
+                    self.errors.push(diagnostics::unmatched_conditional_directive(
+                        self.file_id,
+                        span,
+                        directive,
+                    ));
+                }
+            }
+            _ if !self.active() => {}
+            "define" => return self.define(rest),
+            "undef" => {
+                if let Some(name) = rest.first() {
+                    self.macros.remove(&name.lexeme);
+                }
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Evaluates the condition of an `#if`, `#ifdef`, `#elifdef`, etc., from
+    /// the tokens `rest` after the directive's name. An invalid condition is
+    /// reported, and taken to be false.
+    fn condition(&mut self, directive: &str, rest: &[Token], span: Span) -> bool {
+        let defined = rest.first().is_some_and(|name| self.macros.contains_key(&name.lexeme));
+        match directive {
+            "ifdef" | "elifdef" => return defined,
+            "ifndef" | "elifndef" => return !defined,
+            _ => {}
+        }
+
+        let value = condition::replace_defined(rest, |name| self.macros.contains_key(&name))
+            .and_then(|tokens| condition::evaluate(&self.expand_all(tokens), span));
+        value.unwrap_or_else(|(error, span)| {
+            self.errors.push(diagnostics::invalid_condition(self.file_id, span, directive, &error));
+            false
+        })
+    }
+
+    /// Defines the macro of the `#define` with the tokens `tokens`, e.g.
+    /// `MAX(a, b) ((a) > (b) ? (a) : (b))`. Returns `false`, defining
+    /// nothing, if they don't define a macro.
+    fn define(&mut self, tokens: &[Token]) -> bool {
+        let Some((name, mut body)) = tokens.split_first() else {
+            return false;
+        };
+        if !is_name(name) {
+            return false;
+        }
+
+        // A macro is function-like if its name is followed by `(` right away.
+        let mut params = None;
+        let mut variadic = false;
+        if body.first().is_some_and(|paren| {
+            paren.kind == TokenKind::LPAREN && paren.span.start() == name.span.end()
+        }) {
+            let mut names = Vec::new();
+            let mut at = 1;
+            loop {
+                match body.get(at) {
+                    Some(token) if token.kind == TokenKind::RPAREN && names.is_empty() => break,
+                    Some(token) if token.kind == TokenKind::ELLIPSIS => variadic = true,
+                    Some(token) if is_name(token) => names.push(token.lexeme),
+                    _ => return false,
+                }
+                match body.get(at + 1) {
+                    Some(token) if token.kind == TokenKind::RPAREN => {
+                        at += 1;
+                        break;
+                    }
+                    Some(token) if token.kind == TokenKind::COMMA && !variadic => at += 2,
+                    _ => return false,
+                }
+            }
+            params = Some(names);
+            body = &body[at + 1..];
+        }
+
+        self.macros.insert(name.lexeme, Macro {
+            span: name.span,
+            params,
+            variadic,
+            body: body.to_vec(),
+            predefined: false,
+        });
+        true
+    }
+
+    /// Expands every macro invocation in `tokens`, and in what they expand
+    /// to.
+    fn expand_all(&mut self, tokens: Vec<Token>) -> Vec<Token> {
+        let mut pending = VecDeque::from(tokens);
+        let mut output = Vec::with_capacity(pending.len());
+        while let Some(token) = pending.pop_front() {
+            let Some(definition) = self.macros.get(&token.lexeme).filter(|_| is_name(&token))
+            else {
+                output.push(token);
+                continue;
+            };
+            if self.is_hidden(&token) {
+                output.push(token);
+                continue;
+            }
+            let definition = definition.clone();
+
+            if definition.predefined {
+                for body_token in definition.body.iter().rev() {
+                    pending.push_front(Token {
+                        span: token.span,
+                        expansion: token.expansion,
+                        ..*body_token
+                    });
+                }
+                continue;
+            }
+
+            let Some(params) = &definition.params else {
+                let id = self.sources.add_expansion(Expansion {
+                    name:       token.lexeme,
+                    file_id:    self.file_id,
+                    definition: definition.span,
+                    invocation: token.span,
+                    parent:     token.expansion,
+                });
+                for body_token in definition.body.iter().rev() {
+                    pending.push_front(Token { expansion: Some(id), ..*body_token });
+                }
+                continue;
+            };
+
+            // A function-like macro not followed by `(` isn't invoked.
+            if pending.front().map_or(true, |paren| paren.kind != TokenKind::LPAREN) {
+                output.push(token);
+                continue;
+            }
+            pending.pop_front();
+            let Some((mut args, commas, rparen)) = arguments(&mut pending) else {
+                self.errors.push(diagnostics::unterminated_macro_invocation(self.file_id, &token));
+                break;
+            };
+
+            if params.is_empty() && args.len() == 1 && args[0].is_empty() {
+                args.clear();
+            }
+            let arity_ok = if definition.variadic {
+                args.len() >= params.len()
+            } else {
+                args.len() == params.len()
+            };
+            if !arity_ok {
+                self.errors.push(diagnostics::wrong_number_of_macro_arguments(
+                    self.file_id,
+                    &token,
+                    definition.span,
+                    params.len(),
+                    definition.variadic,
+                    args.len(),
+                ));
+                continue;
+            }
+
+            // The variadic arguments, with the commas between them.
+            let mut variadic_args = Vec::new();
+            for (index, arg) in args.iter().enumerate().skip(params.len()) {
+                if index > params.len() {
+                    variadic_args.push(commas[index - 1]);
+                }
+                variadic_args.extend_from_slice(arg);
+            }
+            args.truncate(params.len());
+            let args = args.into_iter().map(|arg| self.expand_all(arg)).collect::<Vec<_>>();
+            let variadic_args = self.expand_all(variadic_args);
+
+            let invocation = if token.expansion == rparen.expansion {
+                token.span.merge(*rparen.span.start()..*rparen.span.end())
+            } else {
+                token.span
+            };
+            let id = self.sources.add_expansion(Expansion {
+                name: token.lexeme,
+                file_id: self.file_id,
+                definition: definition.span,
+                invocation,
+                parent: token.expansion,
+            });
+
+            let va_args = Symbol::intern("__VA_ARGS__");
+            let mut replacement = Vec::with_capacity(definition.body.len());
+            for body_token in &definition.body {
+                if let Some(index) = params.iter().position(|param| *param == body_token.lexeme) {
+                    replacement.extend_from_slice(&args[index]);
+                } else if definition.variadic && body_token.lexeme == va_args {
+                    replacement.extend_from_slice(&variadic_args);
+                } else {
+                    replacement.push(Token { expansion: Some(id), ..*body_token });
+                }
+            }
+            for replacement_token in replacement.into_iter().rev() {
+                pending.push_front(replacement_token);
+            }
+        }
+
+        output
+    }
+
+    /// Whether `token` names a macro it was itself produced by, and so
+    /// isn't expanded.
+    fn is_hidden(&self, token: &Token) -> bool {
+        token.expansion.is_some_and(|id| {
+            self.sources.backtrace(id).any(|expansion| expansion.name == token.lexeme)
+        })
+    }
+}
+
+/// Takes the arguments of an invocation from `tokens`, up to and including
+/// its `)`, once its `(` has been taken. Returns them with the commas which
+/// separate them and the `)`, or `None` if there is no `)`.
+fn arguments(tokens: &mut VecDeque<Token>) -> Option<(Vec<Vec<Token>>, Vec<Token>, Token)> {
+    let mut args = vec![Vec::new()];
+    let mut commas = Vec::new();
+    let mut depth = 0;
+    while let Some(token) = tokens.pop_front() {
+        match token.kind {
+            TokenKind::RPAREN if depth == 0 => return Some((args, commas, token)),
+            TokenKind::COMMA if depth == 0 => {
+                commas.push(token);
+                args.push(Vec::new());
+                continue;
+            }
+            TokenKind::LPAREN => depth += 1,
+            TokenKind::RPAREN => depth -= 1,
+            _ => {}
+        }
+        args.last_mut().expect("there is always an argument").push(token);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        diagnostics::DiagnosticsEngine,
+        lexer,
+        standard::Standard,
+        target::Target,
+    };
+
+    /// Lexes `text` for `target`, expanding its macros, and returns its
+    /// tokens (but the last) and the names of the macros each was expanded
+    /// from, innermost first.
+    fn expand_for(
+        target: &Target,
+        text: &str,
+    ) -> (Vec<(String, Vec<String>)>, DiagnosticsEngine) {
+        let mut diagnostics = DiagnosticsEngine::new();
+        let file_id = diagnostics.add_file("macros.c", text);
+        let sink = lexer::lex_with_diagnostics(
+            text,
+            Standard::C11,
+            target,
+            false,
+            &mut diagnostics,
+            file_id,
+        );
+        let tokens = sink.tokens.tokens().clone();
+
+        let tokens = tokens[..tokens.len() - 1]
+            .iter()
+            .map(|token| {
+                let backtrace = token.expansion.map_or_else(Vec::new, |id| {
+                    diagnostics
+                        .files
+                        .backtrace(id)
+                        .map(|expansion| expansion.name.to_string())
+                        .collect()
+                });
+                (token.lexeme.to_string(), backtrace)
+            })
+            .collect();
+
+        (tokens, diagnostics)
+    }
+
+    fn expand(text: &str) -> (Vec<(String, Vec<String>)>, DiagnosticsEngine) {
+        expand_for(&Target::default(), text)
+    }
+
+    fn spellings(tokens: &[(String, Vec<String>)]) -> String {
+        tokens.iter().map(|(lexeme, _)| lexeme.as_str()).collect::<Vec<_>>().join(" ")
+    }
+
+    #[test]
+    fn expands_object_and_function_like_macros() {
+        let (tokens, diagnostics) = expand(
+            "#define ONE 1\n\
+             #define ADD(a, b) a + b\n\
+             #define LOG(format, ...) printf(format, __VA_ARGS__)\n\
+             int x = ADD(ONE, (2, 3));\n\
+             #undef ONE\n\
+             int y = ONE; LOG(\"%d %d\", x,\n y);",
+        );
+
+        assert_eq!(
+            spellings(&tokens),
+            "int x = 1 + ( 2 , 3 ) ; int y = ONE ; printf ( \"%d %d\" , x , y ) ;"
+        );
+        assert_eq!(tokens[3], ("1".to_string(), vec!["ONE".to_string()]));
+        assert_eq!(tokens[4], ("+".to_string(), vec!["ADD".to_string()]));
+        assert!(diagnostics.diagnostics.is_empty(), "{:?}", diagnostics.diagnostics);
+    }
+
+    #[test]
+    fn records_nested_expansions() {
+        let (tokens, _) = expand(
+            "#define INNER(x) (x)\n\
+             #define OUTER(x) INNER(x) + OUTER\n\
+             int x = OUTER(1);",
+        );
+
+        assert_eq!(spellings(&tokens), "int x = ( 1 ) + OUTER ;");
+        assert_eq!(tokens[3].1, ["INNER", "OUTER"]);
+        assert_eq!(tokens[4], ("1".to_string(), vec![]));
+        assert_eq!(tokens[7], ("OUTER".to_string(), vec!["OUTER".to_string()]));
+    }
+
+    #[test]
+    fn reports_bad_invocations() {
+        let (tokens, diagnostics) = expand("#define F(a, b) a\nint x = F(1) + F;\nint y = F(1,");
+
+        assert_eq!(spellings(&tokens), "int x = + F ; int y =");
+        let codes = diagnostics
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.code.as_deref().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(codes, ["E0013", "E0014"]);
+    }
+
+    #[test]
+    fn expands_predefined_macros() {
+        let text = "int x = __SIZEOF_LONG__; __SIZE_TYPE__ n; int e = __BYTE_ORDER__;";
+        let (tokens, _) = expand_for(&"i686-linux-gnu".parse().unwrap(), text);
+
+        assert_eq!(spellings(&tokens), "int x = 4 ; unsigned int n ; int e = 1234 ;");
+        assert_eq!(tokens[3].1, Vec::<String>::new());
+        let (tokens, _) = expand("#undef __x86_64__
+int x = __x86_64__ + __LP64__;");
+        assert_eq!(spellings(&tokens), "int x = __x86_64__ + 1 ;");
+    }
+
+    #[test]
+    fn includes_conditional_groups() {
+        let (tokens, diagnostics) = expand(
+            "#define N 4\n\
+             #define TWICE(x) ((x) * 2)\n\
+             #if TWICE(N) == 8 && !defined(M)\n\
+             int a;\n\
+             #ifdef M\n\
+             int b;\n\
+             #endif\n\
+             #elif 1\n\
+             int c;\n\
+             #else\n\
+             int d;\n\
+             #endif",
+        );
+
+        assert_eq!(spellings(&tokens), "int a ;");
+        assert!(diagnostics.diagnostics.is_empty(), "{:?}", diagnostics.diagnostics);
+    }
+
+    #[test]
+    fn reports_bad_conditionals() {
+        let (tokens, diagnostics) = expand(
+            "#define A\n\
+             #if 1 +\n\
+             int a;\n\
+             #elifdef A\n\
+             int b;\n\
+             #else\n\
+             int c;\n\
+             #endif\n\
+             #endif\n\
+             #if 1",
+        );
+
+        assert_eq!(spellings(&tokens), "int b ;");
+        let codes = diagnostics
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.code.as_deref().unwrap_or_default())
+            .collect::<Vec<_>>();
+        assert_eq!(codes, ["E0025", "E0019", "E0021", "E0020"]);
+    }
+}
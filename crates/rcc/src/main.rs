@@ -1,15 +1,27 @@
-mod ast;
-mod cst;
-mod diagnostics;
-mod lexer;
-mod parser;
-mod preprocessor;
-mod token_set;
-
 use anyhow::Result;
-use codespan_reporting::files::SimpleFiles;
+use rcc::{
+    charset,
+    diagnostics::{
+        ColorChoice,
+        DiagnosticCode,
+        DiagnosticFormat,
+        WarningOptions,
+        DEFAULT_ERROR_LIMIT,
+        DEFAULT_MACRO_BACKTRACE_LIMIT,
+    },
+    fixtures::{
+        self,
+        Outcome,
+    },
+    target::Target,
+    Options,
+    Session,
+    Standard,
+};
 use std::{
+    env,
     fs,
+    path::PathBuf,
     process::ExitCode,
 };
 use tracing_subscriber::{
@@ -41,51 +53,252 @@ fn main() -> Result<ExitCode> {
         .finish();
 
     // Set the subscriber as the default.
-    tracing::subscriber::set_global_default(subscriber).expect(
-        "failed to set
-    subscriber",
-    );
+    tracing::subscriber::set_global_default(subscriber).expect("failed to set subscriber");
+
+    let mut standard = Standard::default();
+    let mut target = Target::default();
+    let mut diagnostic_format = DiagnosticFormat::default();
+    let mut color = ColorChoice::Auto;
+    let mut warnings = WarningOptions::default();
+    let mut error_limit = DEFAULT_ERROR_LIMIT;
+    let mut macro_backtrace_limit = DEFAULT_MACRO_BACKTRACE_LIMIT;
+    let mut show_caret = false;
+    let mut trigraphs = false;
+    let mut input_charset = charset::UTF_8;
+    let mut paths = Vec::new();
+    let mut args = env::args().skip(1).peekable();
+    if args.next_if(|arg| arg == "test").is_some() {
+        return test(args);
+    }
+    let fix = args.next_if(|arg| arg == "fix").is_some();
+    while let Some(arg) = args.next() {
+        if arg == "--explain" {
+            return Ok(explain(args.next()));
+        }
+
+        if let Some(name) = arg.strip_prefix("--std=") {
+            match name.parse() {
+                Ok(std) => standard = std,
+                Err(_) => {
+                    eprintln!("rcc: unknown standard `{name}` (expected `c11`, `gnu11` or `c23`)");
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+        } else if let Some(triple) = arg.strip_prefix("--target=") {
+            match triple.parse() {
+                Ok(triple) => target = triple,
+                Err(error) => {
+                    eprintln!("rcc: {error}");
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+        } else if arg == "-trigraphs" {
+            trigraphs = true;
+        } else if let Some(name) = arg.strip_prefix("-finput-charset=") {
+            match charset::charset(name) {
+                Some(charset) => input_charset = charset,
+                None => {
+                    eprintln!("rcc: unknown input charset `{name}`");
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+        } else if arg.starts_with("-W") {
+            if let Err(error) = warnings.apply_flag(&arg) {
+                eprintln!("rcc: {error}");
+                return Ok(ExitCode::FAILURE);
+            }
+        } else if let Some(limit) = arg.strip_prefix("-ferror-limit=") {
+            match limit.parse() {
+                Ok(limit) => error_limit = limit,
+                Err(_) => {
+                    eprintln!("rcc: invalid error limit `{limit}` (expected a number)");
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+        } else if let Some(limit) = arg.strip_prefix("-fmacro-backtrace-limit=") {
+            match limit.parse() {
+                Ok(limit) => macro_backtrace_limit = limit,
+                Err(_) => {
+                    eprintln!("rcc: invalid macro backtrace limit `{limit}` (expected a number)");
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+        } else if arg == "-fdiagnostics-show-caret" {
+            show_caret = true;
+        } else if arg == "-fno-diagnostics-show-caret" {
+            show_caret = false;
+        } else if let Some(name) = arg.strip_prefix("--color=") {
+            match name {
+                "auto" => color = ColorChoice::Auto,
+                "always" => color = ColorChoice::Always,
+                "never" => color = ColorChoice::Never,
+                _ => {
+                    eprintln!(
+                        "rcc: unknown color choice `{name}` (expected `auto`, `always` or `never`)"
+                    );
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+        } else if let Some(name) = arg.strip_prefix("--diagnostic-format=") {
+            match name.parse() {
+                Ok(format) => diagnostic_format = format,
+                Err(_) => {
+                    eprintln!(
+                        "rcc: unknown diagnostic format `{name}` (expected `human`, `json`, \
+                         `sarif` or `gcc`)"
+                    );
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+        } else {
+            paths.push(arg);
+        }
+    }
+
+    if paths.is_empty() {
+        eprintln!(
+            "usage: rcc [--std=<standard>] [--target=<triple>] [-trigraphs] \
+             [-finput-charset=<charset>] [-W<warning>...] [-ferror-limit=<n>] \
+             [-fmacro-backtrace-limit=<n>] [--color=<when>] [--diagnostic-format=<format>] \
+             [-fdiagnostics-show-caret] <file>..."
+        );
+        eprintln!("       rcc fix [<option>...] <file>...");
+        eprintln!("       rcc test [--bless] [<directory>]");
+        eprintln!("       rcc --explain <code>");
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let options = Options::builder()
+        .color(color)
+        .standard(standard)
+        .target(target)
+        .trigraphs(trigraphs)
+        .input_charset(input_charset)
+        .diagnostic_format(diagnostic_format)
+        .warnings(warnings)
+        .error_limit(error_limit)
+        .macro_backtrace_limit(macro_backtrace_limit)
+        .show_caret(show_caret)
+        .build();
+
+    if fix {
+        for path in &paths {
+            apply_fixes(path, &options)?;
+        }
+    }
 
-    // preprocessor::main();
+    // After `rcc fix`, this checks what is left of the fixed files.
+    let mut session = Session::builder().options(options).build();
 
-    let mut diagnostics = diagnostics::DiagnosticsEngine::new();
+    for path in &paths {
+        let file_id = session.add_file(path)?;
+        session.lower(file_id);
+    }
 
-    let file_path = "testdata/parse/b.c";
-    let text = fs::read_to_string(file_path)?;
-    let file_id = diagnostics.add_file(file_path, text);
+    session.flush_diagnostics();
 
-    // // Parse the file into a CST
-    let mut cst = parser::parse_file_with_diagnotics(file_path, &mut diagnostics)?;
-    // let mut cst =
-    // parser::parse_file("testdata/parse/b.c")?; // // Reduce the CST to an AST
-    // // let ast = ast::lower_with_diagnostics(file_id, cst.clone(), &mut
-    // // diagnostics); println!("{:#?}", ast);
+    Ok(if session.has_errors() { ExitCode::FAILURE } else { ExitCode::SUCCESS })
+}
+
+/// How many times `rcc fix` re-checks a file for new fixes, e.g. once a
+/// missing `;` lets the parser get further.
+const MAX_FIX_PASSES: usize = 8;
+
+/// Applies the machine-applicable suggestions for `path` in place, until
+/// there are none left. The file is written back in the encoding it was
+/// read in, and left alone if any of it didn't decode.
+fn apply_fixes(path: &str, options: &Options) -> Result<()> {
+    let decoded = charset::decode(&fs::read(path)?, options.input_charset());
+    if !decoded.malformed.is_empty() {
+        eprintln!("rcc: not fixing {path}: it isn't valid {}", decoded.charset.name());
+        return Ok(());
+    }
+
+    let mut text = decoded.text.clone();
+    let mut total = 0;
+    for _ in 0..MAX_FIX_PASSES {
+        let mut session = Session::builder().options(options.clone()).build();
+        let file_id = session.add_source(path, text.as_str());
+        let (fixed, applied) = session.fix(file_id);
+        if applied == 0 {
+            break;
+        }
 
-    let mut ast_sink = ast::reduce_with_diagnostics(&mut cst, &mut diagnostics);
+        let Some(bytes) = decoded.encode(&fixed) else {
+            let charset = decoded.charset.name();
+            eprintln!("rcc: can't write the fixes of {path} in {charset}");
+            break;
+        };
+        fs::write(path, bytes)?;
+        text = fixed;
+        total += applied;
+    }
 
-    ast_sink.drain_errors(&mut diagnostics);
-    diagnostics.flush();
-    // let file_id = SimpleFiles::new().add("testdata/parse/b.c", cst.source());
+    if total > 0 {
+        eprintln!("rcc: applied {total} fix{} to {path}", if total == 1 { "" } else { "es" });
+    }
 
-    // let ast_sink = ast::lower_with_diagnostics(cst, file_id, &mut
-    // diagnostics_engine);
+    Ok(())
+}
+
+/// Runs the snapshot tests of `testdata`, or of the given directory,
+/// rewriting out-of-date snapshots with `--bless`.
+fn test(args: impl Iterator<Item = String>) -> Result<ExitCode> {
+    let mut bless = false;
+    let mut root = PathBuf::from("testdata");
+    for arg in args {
+        if arg == "--bless" {
+            bless = true;
+        } else {
+            root = PathBuf::from(arg);
+        }
+    }
 
-    //
-    // println!("{ast:#?}");
+    let (mut passed, mut failed, mut known_bugs, mut blessed) = (0, 0, 0, 0);
+    for result in fixtures::run(&root, bless)? {
+        match result.outcome {
+            Outcome::Passed => passed += 1,
+            Outcome::KnownBug => known_bugs += 1,
+            Outcome::Blessed => {
+                println!("blessed {}", result.name);
+                blessed += 1;
+            }
+            Outcome::Failed(failures) => {
+                println!("FAILED {}", result.name);
+                for failure in failures {
+                    println!("    {}", failure.replace('\n', "\n    "));
+                }
+                failed += 1;
+            }
+        }
+    }
 
-    // Recursively parse all files in a directory
-    // if let Ok(results) = parser::parse_directory("testdata/parse") {
-    //     for result in results {
-    //         // println!("{}", result);
-    //     }
-    // }
+    println!(
+        "test result: {passed} passed; {failed} failed; {known_bugs} known bugs; {blessed} blessed"
+    );
+    Ok(if failed == 0 { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
 
-    // // Parse the current working directory
-    // if let Ok(results) = parser::parse_cwd() {
-    //     for result in results {
-    //         println!("{}", result);
-    //     }
-    // }
+/// Prints the long-form explanation of a diagnostic code, e.g. `E0005`, like
+/// `rustc --explain` does.
+fn explain(code: Option<String>) -> ExitCode {
+    let Some(code) = code else {
+        eprintln!("rcc: `--explain` expects a diagnostic code, e.g. `rcc --explain E0005`");
+        return ExitCode::FAILURE;
+    };
 
-    Ok(ExitCode::SUCCESS)
+    match code.parse::<DiagnosticCode>() {
+        Ok(code) => {
+            println!("{code}: {}\n\n{}", code.title(), code.explanation());
+            ExitCode::SUCCESS
+        }
+        Err(_) => {
+            if let Some(explanation) = DiagnosticCode::retired(&code) {
+                println!("{code}: retired\n\n{explanation}");
+                return ExitCode::SUCCESS;
+            }
+            eprintln!("rcc: `{code}` is not a valid diagnostic code");
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -1,16 +1,20 @@
 use crate::{
+    charset,
     cst::{
         Child,
+        SyntaxKind,
         Tree,
         TreeKind,
         TreeSink,
     },
     diagnostics::{
         self,
+        Applicability,
         DiagnosticsEngine,
         ErrorKind,
         FileId,
         SemanticError,
+        Suggestion,
         SyntaxError,
         UnexpectedToken,
     },
@@ -22,37 +26,72 @@ use crate::{
         TokenSink,
         TokenStream,
     },
+    standard::Standard,
+    symbol::Symbol,
+    target::Target,
     token_set::TokenSet,
 };
 use anyhow::Result;
+use logos::Logos;
 use owo_colors::OwoColorize;
-use smartstring::alias::String;
-use std::{
-    cell::Cell,
-    fs,
+use resilient_ll::{
+    Language,
+    MarkClosed,
+    MarkOpened,
 };
+use smartstring::alias::String;
+use std::collections::HashSet;
 
+#[allow(dead_code)]
 pub(crate) fn parse_file_with_diagnotics(
     file_path: &str,
     diagnostics: &mut DiagnosticsEngine,
 ) -> Result<Tree> {
-    let text = fs::read_to_string(file_path)?;
-    let file_id = diagnostics.add_file(file_path, text.to_string());
+    let file_id = diagnostics.load_file(file_path, charset::UTF_8)?;
+    let text = diagnostics.files.get(file_id)?.source().to_string();
 
-    Ok(parse_with_diagnostics(&text, diagnostics, file_id))
+    Ok(parse_with_diagnostics(&text, Standard::default(), diagnostics, file_id))
 }
 
 pub fn parse_with_diagnostics(
     text: &str,
+    standard: Standard,
     diagnostics_engine: &mut DiagnosticsEngine,
     file_id: usize,
 ) -> Tree {
-    parse_tree_with_diagnostics(text, TreeKind::TranslationUnit, diagnostics_engine, file_id)
+    parse_tree_with_diagnostics(
+        text,
+        TreeKind::TranslationUnit,
+        standard,
+        diagnostics_engine,
+        file_id,
+    )
 }
 
 pub fn parse_tree_with_diagnostics(
     text: &str,
     tree_kind: TreeKind,
+    standard: Standard,
+    diagnostics: &mut DiagnosticsEngine,
+    file_id: usize,
+) -> Tree {
+    let token_sink: TokenSink = lexer::lex_with_diagnostics(
+        text,
+        standard,
+        &Target::default(),
+        false,
+        diagnostics,
+        file_id,
+    );
+
+    parse_tokens_with_diagnostics(token_sink.tokens, tree_kind, standard, diagnostics, file_id)
+}
+
+/// Parses already lexed tokens into a `tree_kind` tree.
+pub fn parse_tokens_with_diagnostics(
+    token_stream: TokenStream,
+    tree_kind: TreeKind,
+    standard: Standard,
     diagnostics: &mut DiagnosticsEngine,
     file_id: usize,
 ) -> Tree {
@@ -67,11 +106,8 @@ pub fn parse_tree_with_diagnostics(
         "...".black()
     );
 
-    let token_sink: TokenSink = lexer::lex_with_diagnostics(text, diagnostics, file_id);
-    let token_stream = token_sink.tokens;
-
     let start = std::time::Instant::now();
-    let mut p = Parser::new(token_stream, file_id);
+    let mut p = Parser::new(token_stream, file_id, standard);
 
     match tree_kind {
         TreeKind::TranslationUnit => translation_unit(&mut p),
@@ -167,18 +203,27 @@ pub fn parse_tree_with_diagnostics(
         TreeKind::Designation => todo!(),
         TreeKind::DesignatorList => todo!(),
         TreeKind::Designator => todo!(),
+        TreeKind::AttributeSpecifier => attribute_specifier(&mut p),
+        TreeKind::Attribute => attribute(&mut p),
+        TreeKind::AsmLabel => asm_label(&mut p),
+        TreeKind::AsmStatement => asm_statement(&mut p),
+        TreeKind::AsmOperandList => asm_operand_list(&mut p),
+        TreeKind::AsmOperand => asm_operand(&mut p),
+        TreeKind::TypeofSpecifier => typeof_specifier(&mut p),
+        TreeKind::StatementExpression => statement_expression(&mut p),
+        TreeKind::StandardAttributeSpecifier => standard_attribute_specifier(&mut p),
     }
 
-    let tree = p.clone().build_tree();
-    let elapsed = start.elapsed();
-    tracing::debug!(" {}\n\n  {}{}\n\n{}", "PARSER".green(), "CST".blue(), ":".black(), tree);
-
     let num_errors = p.num_errors();
 
     // Emit diagnostics
     p.drain_errors(diagnostics);
     // diagnostics.flush();
 
+    let tree = p.build_tree();
+    let elapsed = start.elapsed();
+    tracing::debug!(" {}\n\n  {}{}\n\n{}", "PARSER".green(), "CST".blue(), ":".black(), tree);
+
     if num_errors == 0 {
         tracing::info!(
             " {}  {} {} constructed{}{}{}",
@@ -206,6 +251,35 @@ pub fn parse_tree_with_diagnostics(
     tree
 }
 
+/// Whether any of `tokens` comes from a macro expansion. Such a token is
+/// spanned at the macro's call site, so a fix-it around it would edit the
+/// invocation rather than the definition it came from, e.g. turn `DECL(1);`
+/// into `DECL(1;);`. No fix-it is suggested then.
+fn from_expansion<'a>(tokens: impl IntoIterator<Item = Option<&'a Token>>) -> bool {
+    tokens.into_iter().flatten().any(|token| token.expansion.is_some())
+}
+
+/// Whether a declaration or statement can end with a token of `kind`, so a
+/// `;` missing right after it is known to belong there: a name, a constant,
+/// a closing bracket, a postfix `++`/`--` or a keyword such as `break`.
+fn ends_construct(kind: TokenKind) -> bool {
+    kind.is_keyword() ||
+        matches!(
+            kind,
+            TokenKind::IDENTIFIER |
+                TokenKind::STRING |
+                TokenKind::BYTES |
+                TokenKind::INTEGER_CONSTANT |
+                TokenKind::FLOATING_CONSTANT |
+                TokenKind::RPAREN |
+                TokenKind::RBRACKET |
+                TokenKind::RBRACE |
+                TokenKind::INC_OP |
+                TokenKind::DEC_OP
+        )
+}
+
+#[allow(dead_code)]
 fn large_parser_prefix() -> String {
     format!(
         "\n\n{}\n{}\n{}{}{}\n{}\n{}",
@@ -220,10 +294,12 @@ fn large_parser_prefix() -> String {
     .into()
 }
 
+#[allow(dead_code)]
 fn small_parser_prefix() -> String {
     format!("\t{}{}{}", "[".black(), " PARSER ".yellow(), "]".black(),).into()
 }
 
+#[allow(dead_code)]
 fn format_call_stack(call_stack: &[String]) -> String {
     let mut result = String::new();
     // let mut indentation = 0;
@@ -243,73 +319,89 @@ fn format_call_stack(call_stack: &[String]) -> String {
         }
 
         result.push_str(&format!("+-> {call}\n"));
-        format!("{call}");
     }
 
     result
 }
 
+/// The C language, as far as the [`resilient_ll`] parser core is concerned.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Event {
-    Open { kind: TreeKind, range: Span },
-    Close,
-    Advance,
-}
+pub enum CLanguage {}
 
-impl Event {
-    fn get_range(&self) -> Span {
-        match self {
-            Event::Open { range, .. } => *range,
-            Event::Close => panic!("Attempted to get range from a Close event."),
-            Event::Advance => panic!("Attempted to get range from an Advance event."),
-        }
-    }
-}
+impl Language for CLanguage {
+    type TokenKind = TokenKind;
+    type TreeKind = TreeKind;
+    type Token = Token;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct MarkOpened {
-    index: usize,
-}
+    const EOF: TokenKind = TokenKind::EOF;
+    const ERROR_TREE: TreeKind = TreeKind::ErrorTree;
 
-impl MarkOpened {
-    fn new(index: usize) -> MarkOpened {
-        MarkOpened { index }
+    fn token_kind(token: &Token) -> TokenKind {
+        token.kind
     }
 }
 
-struct MarkClosed {
-    index: usize,
+impl resilient_ll::Tree<CLanguage> for Tree {
+    fn push_token(&mut self, token: Token) {
+        self.children.push(Child::Token(token));
+    }
+
+    fn push_tree(&mut self, tree: Tree) {
+        self.children.push(Child::Tree(tree));
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Parser {
-    tokens:        TokenStream,
-    pos:           usize,
-    fuel:          Cell<u32>,
-    events:        Vec<Event>,
+    core:          resilient_ll::Parser<CLanguage>,
     call_stack:    Vec<ParserCall>,
     tree_sink:     TreeSink,
     file_id:       FileId,
+    standard:      Standard,
+    /// Whether an error has been reported since the parser last recovered,
+    /// i.e. consumed a `;`, `{` or `}`. Errors reported in between are
+    /// follow-on errors of the first one, in the tokens skipped into an
+    /// `ErrorTree`, and are suppressed.
     error_emitted: bool,
+    /// The names declared with `typedef` so far, which are type specifiers
+    /// rather than identifiers from then on.
+    typedef_names: HashSet<Symbol>,
+    /// Whether the declarators being parsed belong to a `typedef`.
+    in_typedef:    bool,
+    /// The position of a misspelled keyword or type name, and the token the
+    /// grammar takes it for (see [`Parser::recover_misspelled_keyword`]).
+    /// The token stream, and so the CST, keeps the token as written.
+    recovered:     Option<(usize, Token)>,
 }
 
 #[derive(Debug, Clone)]
 pub struct ParserCall {
+    #[allow(dead_code)]
     pub(crate) name: String,
     // pub(crate) body: Tree,
 }
 
 impl Parser {
-    pub fn new(tokens: TokenStream, file_id: usize) -> Parser {
+    pub fn new(mut tokens: TokenStream, file_id: usize, standard: Standard) -> Parser {
         Parser {
-            tokens,
-            pos: 0,
-            fuel: Cell::new(256),
-            events: Vec::new(),
+            core: resilient_ll::Parser::new(std::mem::take(tokens.tokens_mut())),
             call_stack: Vec::new(),
             tree_sink: TreeSink::new(),
             file_id,
+            standard,
             error_emitted: false,
+            typedef_names: Self::builtin_typedef_names(standard),
+            in_typedef: false,
+            recovered: None,
+        }
+    }
+
+    /// The type names GCC predefines, which system headers rely on.
+    fn builtin_typedef_names(standard: Standard) -> HashSet<Symbol> {
+        if standard.gnu_extensions() {
+            HashSet::from([Symbol::intern("__builtin_va_list")])
+        } else {
+            HashSet::new()
         }
     }
 
@@ -334,43 +426,9 @@ impl Parser {
         // e.g. [PARSER] (TYPEDEF_KW, 'typdef', 0..7) - Current Call Stack (<node> ->
         // <node> -> <node>)
 
-        let call_stack_nodes = self
-            .call_stack
-            .iter()
-            .map(|call| {
-                let call_name = call.name.clone().to_string();
-
-                format!("{}{}{}", "<".black(), call_name.cyan(), ">".black()).into()
-            })
-            .collect::<Vec<String>>();
-        // .join(&" -> ".yellow().to_string());
-
-        let current_token = &format!(
-            " {}{}{} {}{}{}{}{}{}",
-            "(".black().italic(),
-            self.current().blue(),
-            ",".black(),
-            "`".red(),
-            self.current_token().lexeme(),
-            "`".red(),
-            ",".black(),
-            self.current_token().span().italic(),
-            ")".black().italic(),
-        );
-
-        // debug the call stack (e.g. <node> -> <node> -> <node>)
-        // let call_stack_lines: String = call_stack
-        //   .lines()
-        //   .collect::<Vec<&str>>()
-        //   .join("\n\t\t")
-        //   .into();
-
-        let fmt_call_stack = false; // TODO: Make this a debug flag via cli and configuration
-
-        let call_stack = match fmt_call_stack {
-            true => format_call_stack(&call_stack_nodes),
-            false => node.to_string().into(),
-        };
+        // TODO: Format the call stack and the current token for the logging
+        // below once it is enabled again. Doing so eagerly on every call made
+        // parsing quadratic, as the call stack is never fully unwound.
 
         // tracing::debug!(
         //   "{call_stack} {current_token}",
@@ -428,7 +486,7 @@ impl Parser {
 
     pub fn trace_exit(&mut self) {
         // Removes the node from the parser call stack
-        let ast_node = self.call_stack.pop();
+        let _ast_node = self.call_stack.pop();
 
         // // Prints the node to the console
         // tracing::debug!(
@@ -466,7 +524,88 @@ impl Parser {
             TokenKind::STRUCT_KW,
             TokenKind::UNION_KW,
             TokenKind::ENUM_KW,
-        ])
+            TokenKind::ATTRIBUTE_KW,
+            TokenKind::EXTENSION_KW,
+            TokenKind::TYPEOF_KW,
+            TokenKind::BOOL_KW,
+            TokenKind::THREAD_LOCAL_KW,
+            TokenKind::STATIC_ASSERT_KW,
+            TokenKind::CONSTEXPR_KW,
+            TokenKind::TYPEOF_UNQUAL_KW,
+        ]) || self.at_typedef_name() ||
+            self.at_standard_attribute()
+    }
+
+    /// Whether the external declaration starting at the current token is a
+    /// function definition, i.e. whether its declarator is followed by the
+    /// `{` of a body rather than by `;`, `=` or `,`, or by the declaration
+    /// list of an old-style definition as in `int f(a) int a; { ... }`.
+    ///
+    /// The declaration specifiers and declarator can be arbitrarily long
+    /// (e.g. with struct bodies or GNU attributes), so this scans the tokens
+    /// directly rather than through [`Parser::nth`], which would run out of
+    /// fuel.
+    fn at_function_def(&self) -> bool {
+        let mut kinds = self.tokens_from(self.core.pos()).map(|token| token.kind);
+        let mut depth = 0usize;
+        // After `struct`, `union` or `enum` (and its tag, if `Some(true)`), a
+        // `{` opens the body of the type rather than that of a function.
+        let mut tag_named = None;
+        // The previous token, and whether the outermost parentheses being
+        // (or just) scanned follow a name or a `)`, i.e. hold parameters
+        // rather than e.g. the operand of `typeof` or `__attribute__`.
+        let mut previous = TokenKind::EOF;
+        let mut in_params = false;
+        let mut after_params = false;
+
+        while let Some(kind) = kinds.next() {
+            // Nothing but a declaration may follow the parameters of a
+            // declarator when its declaration has no `;`, `=` or `,` yet.
+            if after_params && (kind == TokenKind::IDENTIFIER || kind.is_keyword()) &&
+                !matches!(kind, TokenKind::ATTRIBUTE_KW | TokenKind::ASM_KW)
+            {
+                return true;
+            }
+            after_params = false;
+
+            match kind {
+                TokenKind::LPAREN if depth == 0 => {
+                    in_params = matches!(previous, TokenKind::IDENTIFIER | TokenKind::RPAREN);
+                    depth += 1;
+                }
+                TokenKind::RPAREN if depth == 1 => {
+                    after_params = in_params;
+                    depth = 0;
+                }
+                TokenKind::LPAREN | TokenKind::LBRACKET => depth += 1,
+                TokenKind::RPAREN | TokenKind::RBRACKET => depth = depth.saturating_sub(1),
+                TokenKind::EOF => return false,
+                _ if depth > 0 => {}
+                TokenKind::STRUCT_KW | TokenKind::UNION_KW | TokenKind::ENUM_KW => {
+                    tag_named = Some(false)
+                }
+                TokenKind::IDENTIFIER if tag_named == Some(false) => tag_named = Some(true),
+                TokenKind::ATTRIBUTE_KW if tag_named.is_some() => {}
+                TokenKind::LBRACE if tag_named.is_some() => {
+                    let mut braces = 1;
+                    for kind in kinds.by_ref() {
+                        match kind {
+                            TokenKind::LBRACE => braces += 1,
+                            TokenKind::RBRACE if braces == 1 => break,
+                            TokenKind::RBRACE => braces -= 1,
+                            _ => {}
+                        }
+                    }
+                    tag_named = None;
+                }
+                TokenKind::LBRACE => return true,
+                TokenKind::SEMICOLON | TokenKind::EQ | TokenKind::COMMA => return false,
+                _ => tag_named = None,
+            }
+            previous = kind;
+        }
+
+        false
     }
 
     fn at_designator(&self) -> bool {
@@ -478,6 +617,9 @@ impl Parser {
             TokenKind::INTEGER_CONSTANT,
             TokenKind::FLOATING_CONSTANT,
             TokenKind::IDENTIFIER,
+            TokenKind::TRUE_KW,
+            TokenKind::FALSE_KW,
+            TokenKind::NULLPTR_KW,
         ])
     }
 
@@ -534,56 +676,8 @@ impl Parser {
     }
 
     pub fn build_tree(self) -> Tree {
-        let mut tokens = self.tokens;
-        let mut events = self.events;
-        let mut stack = Vec::new();
-
-        // Special case: pop the last `Close` event to ensure
-        // that the stack is non-empty inside the loop.
-        assert!(matches!(events.pop(), Some(Event::Close)));
-
-        // println!("events: {events:#?}");
-
-        for event in events {
-            // println!("event: {:?}", event);
-            match event {
-                // Starting a new node; just push an empty tree to the stack.
-                Event::Open { range, kind } => {
-                    stack.push(Tree {
-                        kind,
-                        children: Vec::new(),
-                        range,
-                        file_id: self.file_id,
-                        pos: 0,
-                    });
-                }
-
-                // A tree is done.
-                // Pop it off the stack and append to a new current tree.
-                Event::Close => {
-                    let tree = stack.pop().unwrap();
-                    stack.last_mut().unwrap().children.push(Child::Tree(tree));
-                }
-
-                // Consume a token and append it to the current tree.
-                Event::Advance => {
-                    let token = tokens.next().unwrap();
-                    stack.last_mut().unwrap().children.push(Child::Token(token));
-                }
-            }
-        }
-
-        let mut tree = stack.pop().unwrap();
-
-        // Bump over the EOF token if it exists (it should) in the token stream.
-        let eof_token = tokens.next();
-        assert!(eof_token.is_some(), "EOF not found");
-        // println!("{tokens:#?}");
-        // assert!(
-        //   eof_token.is_some()
-        //     && eof_token.unwrap().kind() == &TokenKind::EOF,
-        //   "EOF not found"
-        // );
+        let file_id = self.file_id;
+        let mut tree = self.core.build_tree(|kind| Tree { kind, file_id, ..Tree::default() });
 
         // Traverse the tree and update the ranges for interior nodes to merge
         // the ranges of their children's tokens.
@@ -621,42 +715,49 @@ impl Parser {
 
         update_ranges(&mut tree);
 
-        // Our parser will guarantee that all the trees are closed
-        // and cover the entirety of tokens.
-        assert!(stack.is_empty());
-        assert!(tokens.next().is_none());
         tree
     }
 
     fn open(&mut self) -> MarkOpened {
-        let mark = MarkOpened { index: self.events.len() };
-        self.events.push(Event::Open { kind: TreeKind::ErrorTree, range: Span::default() });
-        mark
+        self.core.open()
     }
 
+    #[allow(dead_code)]
     fn open_before(&mut self, m: MarkClosed) -> MarkOpened {
-        let mark = MarkOpened { index: m.index };
-        self.events
-            .insert(m.index, Event::Open { kind: TreeKind::ErrorTree, range: Span::default() });
-        mark
+        self.core.open_before(m)
     }
 
     fn close(&mut self, m: MarkOpened, kind: TreeKind) -> MarkClosed {
-        let range = self.events[m.index].get_range(); // Get the range from the opened event.
-        self.events[m.index] = Event::Open { kind, range }; // Replace the opened event with a closed event.
-        self.events.push(Event::Close);
-        MarkClosed { index: m.index }
+        self.core.close(m, kind)
     }
 
     fn advance(&mut self) {
-        assert!(!self.eof());
-        self.fuel.set(256);
-        self.events.push(Event::Advance);
-        self.pos += 1;
+        if self.at_any(&[TokenKind::SEMICOLON, TokenKind::LBRACE, TokenKind::RBRACE]) {
+            self.error_emitted = false;
+        }
+        self.core.advance();
+    }
+
+    /// Reports a syntax error, unless it is a follow-on error of one
+    /// reported before the parser recovered.
+    fn push_error(&mut self, diagnostic: diagnostics::Diagnostic<FileId>) {
+        if self.error_emitted {
+            tracing::debug!("Suppressing follow-on error: {}", diagnostic.message);
+        } else {
+            self.tree_sink.push_error(diagnostic);
+        }
+    }
+
+    /// Reports a syntax error the parser has to recover from, suppressing
+    /// any error until it has.
+    fn push_error_and_recover(&mut self, diagnostic: diagnostics::Diagnostic<FileId>) {
+        self.push_error(diagnostic);
+        self.error_emitted = true;
     }
 
+    #[allow(dead_code)]
     fn advance_with_diagnostic(&mut self, diagnostic: diagnostics::Diagnostic<FileId>) {
-        self.tree_sink.push_error(diagnostic);
+        self.push_error(diagnostic);
         self.advance();
     }
 
@@ -676,7 +777,7 @@ impl Parser {
         self.enter(TreeKind::ErrorTree);
         let m = self.open();
 
-        let expected = self.current();
+        let _expected = self.current();
 
         // Create a custom diagnostic based on the error kind and push it to the
         // tree_sink
@@ -685,14 +786,19 @@ impl Parser {
                 unexpected_token,
                 expected,
             })) => {
-                self.tree_sink.push_error(diagnostics::unexpected_token_diagnostic(
+                let mut diagnostic = diagnostics::unexpected_token_diagnostic(
                     self.file_id,
                     &unexpected_token,
                     &expected,
-                ));
+                );
+                if let Some(suggestion) = self.missing_token_suggestion(&unexpected_token, expected)
+                {
+                    diagnostic = diagnostic.with_suggestion(suggestion);
+                }
+                self.push_error_and_recover(diagnostic);
             }
             ErrorKind::Semantic(SemanticError::ExpectedFunctionOrDecl) => {
-                self.tree_sink.push_error(diagnostics::expected_function_or_decl(
+                self.push_error(diagnostics::expected_function_or_decl(
                     self.file_id,
                     &self.final_token(),
                 ));
@@ -727,10 +833,96 @@ impl Parser {
         self.close(m, TreeKind::ErrorTree);
     }
 
+    /// A fix-it inserting the `expected` punctuator, which is missing before
+    /// `unexpected`. A missing `;` belongs right after the previous token when
+    /// that token can end a declaration or statement, so that one is safe to
+    /// apply. After an operator, as in `x = 1 +`, or after another `;`, the
+    /// construct itself is broken and the `;` is only a guess, as are other
+    /// punctuators.
+    fn missing_token_suggestion(
+        &self,
+        unexpected: &Token,
+        expected: TokenKind,
+    ) -> Option<Suggestion<FileId>> {
+        let previous = self.core.tokens()[..self.core.pos()].last();
+        if !expected.is_punctuator() || from_expansion([Some(unexpected), previous]) {
+            return None;
+        }
+
+        Some(match previous {
+            Some(previous) if expected == TokenKind::SEMICOLON => {
+                let applicability = if ends_construct(previous.kind) {
+                    Applicability::MachineApplicable
+                } else {
+                    Applicability::MaybeIncorrect
+                };
+                Suggestion::insert(self.file_id, *previous.span.end(), ";", applicability)
+            }
+            _ => Suggestion::insert(
+                self.file_id,
+                *unexpected.span.start(),
+                expected.to_string(),
+                Applicability::MaybeIncorrect,
+            ),
+        })
+    }
+
+    /// Recovers from a misspelled keyword or type name at the start of a
+    /// declaration or statement, as in `retrun 0;` or `unsinged int x;`: the
+    /// typo is reported along with the closest keyword or type name, which
+    /// the grammar then takes the token for. The token itself is left as
+    /// written, so the CST still spells out the source.
+    ///
+    /// A name can't be followed by another name or a constant, so only such
+    /// a name is taken for a typo.
+    fn recover_misspelled_keyword(&mut self) {
+        let next = self.nth(1);
+        let follows_name = next.is_keyword() ||
+            matches!(
+                next,
+                TokenKind::IDENTIFIER |
+                    TokenKind::INTEGER_CONSTANT |
+                    TokenKind::FLOATING_CONSTANT |
+                    TokenKind::STRING
+            );
+        if !self.at(TokenKind::IDENTIFIER) || self.at_typedef_name() || !follows_name {
+            return;
+        }
+
+        let token = self.current_token();
+        let mut candidates: Vec<&str> = SyntaxKind::keywords(self.standard).collect();
+        candidates.extend(self.typedef_names.iter().map(|name| name.as_str()));
+        let Some(name) = diagnostics::similar_name(token.lexeme.as_str(), candidates) else {
+            return;
+        };
+
+        // A keyword is lexed as itself, anything else as an identifier.
+        let kind = TokenKind::lexer(name)
+            .next()
+            .and_then(Result::ok)
+            .filter(|kind| kind.is_keyword())
+            .unwrap_or(TokenKind::IDENTIFIER);
+        let mut diagnostic = diagnostics::misspelled_keyword(self.file_id, &token);
+        if !from_expansion([Some(&token)]) {
+            let suggestion = Suggestion::new(
+                self.file_id,
+                *token.span.start()..*token.span.end(),
+                name,
+                Applicability::MaybeIncorrect,
+            )
+            .with_message(format!("did you mean `{name}`?"));
+            diagnostic = diagnostic.with_suggestion(suggestion);
+        }
+        self.push_error(diagnostic);
+        let recovered = Token { kind, lexeme: Symbol::from(name), ..token };
+        self.recovered = Some((self.core.pos(), recovered));
+    }
+
     fn final_token(&self) -> Token {
-        self.tokens.get(self.tokens.len() - 1).unwrap()
+        self.core.tokens().last().copied().unwrap()
     }
 
+    #[allow(dead_code)]
     fn final_token_span(&self) -> Span {
         *self.final_token().span()
     }
@@ -746,36 +938,19 @@ impl Parser {
     }
 
     fn eof(&self) -> bool {
-        self.at(TokenKind::EOF) || self.pos == self.tokens.len()
+        self.core.eof()
     }
 
     fn nth(&self, lookahead: usize) -> TokenKind {
-        if self.fuel.get() == 0 {
-            panic!(
-                "Parser ran out of fuel at {:?}. This is likely a bug in the parser related to \
-                 either error recovery or left recursion. Please report this issue to \
-                 https://github.com/pulanski/rcc/issues/new",
-                self.current_token()
-            );
-        }
-        self.fuel.set(self.fuel.get() - 1);
-        self.tokens.get(self.pos + lookahead).map_or(TokenKind::EOF, |it| it.kind)
+        self.nth_token(lookahead).kind
     }
 
     fn nth_token(&self, lookahead: usize) -> Token {
-        if self.fuel.get() == 0 {
-            panic!(
-                "Parser ran out of fuel at {:?}. This is likely a bug in the parser related to \
-                 either error recovery or left recursion. Please report this issue to \
-                 https://github.com/pulanski/rcc/issues/new",
-                self.current_token()
-            );
+        let token = self.core.nth_token(lookahead).copied();
+        match self.recovered {
+            Some((pos, recovered)) if pos == self.core.pos() + lookahead => recovered,
+            _ => token.unwrap_or_else(|| Token::new(TokenKind::EOF, "", Span::default())),
         }
-
-        self.fuel.set(self.fuel.get() - 1);
-        self.tokens
-            .get(self.pos + lookahead)
-            .map_or(Token::new(TokenKind::EOF, "".into(), Span::default()), |it| it)
     }
 
     fn at(&self, kind: TokenKind) -> bool {
@@ -784,6 +959,7 @@ impl Parser {
 
     /// Checks if the current token is in contained within the
     /// given [`TokenSet`], `kinds`.
+    #[allow(dead_code)]
     pub(crate) fn at_ts(&self, kinds: TokenSet) -> bool {
         kinds.contains(self.current())
     }
@@ -795,7 +971,7 @@ impl Parser {
     }
 
     pub fn current_token(&self) -> Token {
-        self.tokens.get(self.pos).unwrap()
+        self.peek().unwrap()
     }
 
     fn at_any(&self, kinds: &[TokenKind]) -> bool {
@@ -813,7 +989,7 @@ impl Parser {
 
     fn expect(&mut self, kind: TokenKind) {
         let curr_tok = self.current_token();
-        let found = curr_tok.lexeme();
+        let _found = curr_tok.lexeme();
 
         if self.eat(kind) {
             return;
@@ -882,7 +1058,15 @@ impl Parser {
     }
 
     fn at_unary_expression(&self) -> bool {
-        self.at_postfix_expression() || self.at_unary_operator()
+        self.at_postfix_expression() ||
+            self.at_unary_operator() ||
+            self.at_any(&[
+                TokenKind::INC_OP,
+                TokenKind::DEC_OP,
+                TokenKind::SIZEOF_KW,
+                TokenKind::ALIGNOF_KW,
+                TokenKind::EXTENSION_KW,
+            ])
     }
 
     fn at_postfix_expression(&self) -> bool {
@@ -894,13 +1078,16 @@ impl Parser {
             self.at_type_specifier() ||
             self.at_type_qualifier() ||
             self.at_function_specifier() ||
-            self.at_alignment_specifier()
+            self.at_alignment_specifier() ||
+            self.at_any(&[TokenKind::ATTRIBUTE_KW, TokenKind::EXTENSION_KW]) ||
+            self.at_standard_attribute()
     }
 
     fn at_alignment_specifier(&self) -> bool {
         self.at(TokenKind::ALIGNAS_KW)
     }
 
+    #[allow(dead_code)]
     fn at_declaration_list(&self) -> bool {
         self.at_declaration()
     }
@@ -923,9 +1110,29 @@ impl Parser {
             TokenKind::ATOMIC_KW,
             TokenKind::STRUCT_KW,
             TokenKind::UNION_KW,
-            // TODO: figure out what these are and how to handle them
-            // TokenKind::TYPEDEF_NAME,
-        ])
+            TokenKind::TYPEOF_KW,
+            TokenKind::TYPEOF_UNQUAL_KW,
+        ]) || self.at_typedef_name()
+    }
+
+    fn at_typedef_name(&self) -> bool {
+        self.nth_is_typedef_name(0)
+    }
+
+    /// Whether the token `n` tokens ahead is an identifier previously
+    /// declared with `typedef`.
+    ///
+    /// Typedef names aren't scoped, so a variable which shadows a typedef
+    /// name within a block is still taken for a type.
+    fn nth_is_typedef_name(&self, n: usize) -> bool {
+        let token = self.nth_token(n);
+        token.kind == TokenKind::IDENTIFIER && self.typedef_names.contains(&token.lexeme)
+    }
+
+    /// Whether the parser is at a C23 `[[attribute]]`. No declaration or
+    /// statement can otherwise start with `[[`.
+    fn at_standard_attribute(&self) -> bool {
+        self.standard.c23() && self.at(TokenKind::LBRACKET) && self.nth(1) == TokenKind::LBRACKET
     }
 
     fn at_generic_selection(&self) -> bool {
@@ -940,9 +1147,11 @@ impl Parser {
             TokenKind::THREAD_LOCAL_KW,
             TokenKind::AUTO_KW,
             TokenKind::REGISTER_KW,
+            TokenKind::CONSTEXPR_KW,
         ])
     }
 
+    #[allow(dead_code)]
     fn at_compound_statement(&self) -> bool {
         self.at(TokenKind::LBRACE)
     }
@@ -951,6 +1160,7 @@ impl Parser {
         self.at_any(&[TokenKind::INLINE_KW, TokenKind::NORETURN_KW])
     }
 
+    #[allow(dead_code)]
     fn add_leaf(&mut self, kind: TreeKind) {
         let m = self.open();
         self.advance();
@@ -1011,12 +1221,16 @@ impl Parser {
     fn at_primary_expression(&self) -> bool {
         self.at_any(&[
             TokenKind::IDENTIFIER,
-            // TokenKind::CONSTANT,
+            TokenKind::INTEGER_CONSTANT,
+            TokenKind::FLOATING_CONSTANT,
             TokenKind::STRING,
+            TokenKind::FUNC_NAME_KW,
             TokenKind::LPAREN,
+            TokenKind::GENERIC_KW,
+            TokenKind::TRUE_KW,
+            TokenKind::FALSE_KW,
+            TokenKind::NULLPTR_KW,
         ])
-        // TODO: update to 2011 standard
-        // || self.at_constant()
     }
 
     fn at_unary_operator(&self) -> bool {
@@ -1030,10 +1244,23 @@ impl Parser {
         ])
     }
 
-    fn peek(&mut self) -> Option<Token> {
-        self.tokens.get(self.pos)
+    /// The tokens from position `start` on, as the grammar sees them, i.e.
+    /// with a recovered typo taken for what it was corrected to.
+    fn tokens_from(&self, start: usize) -> impl Iterator<Item = Token> + '_ {
+        self.core.tokens()[start..].iter().enumerate().map(move |(i, token)| match self.recovered {
+            Some((pos, recovered)) if pos == start + i => recovered,
+            _ => *token,
+        })
     }
 
+    fn peek(&self) -> Option<Token> {
+        match self.recovered {
+            Some((pos, recovered)) if pos == self.core.pos() => Some(recovered),
+            _ => self.core.tokens().get(self.core.pos()).copied(),
+        }
+    }
+
+    #[allow(dead_code)]
     fn at_declarator(&self) -> bool {
         self.at_any(&[TokenKind::IDENTIFIER, TokenKind::STAR, TokenKind::LPAREN])
     }
@@ -1070,8 +1297,8 @@ pub fn translation_unit(p: &mut Parser) {
             };
 
             // TODO: make more robust here...
+            p.push_error_and_recover(err);
             p.advance();
-            p.tree_sink.push_error(err);
         }
 
         if !seen_extern {
@@ -1185,88 +1412,18 @@ fn extern_decl(p: &mut Parser) -> Result<(), ErrorKind> {
     // 	| init_declarator_list ',' init_declarator
     // 	;
 
-    // init_declarator | parser here
-    // 	: declarator   |  '=' initializer
-    // 	| declarator   |
-    // 	;
-    //
-    // // function_definition               | parser here
-    // 	: declaration_specifiers declarator | declaration_list compound_statement
-    // 	| declaration_specifiers declarator | compound_statement
-    // 	;
-    //
-    // declaration_list
-    // 	: declaration
-    // 	| declaration_list declaration
-    // 	;
-    //
-    // TODO: FIXME: currently we are matching "int x;" to a function definition
-    // when it should be a declaration.
-
     p.enter(TreeKind::ExternDecl);
     let m = p.open();
 
+    p.recover_misspelled_keyword();
     if p.at_declaration_specifier() {
-        // println!("parsing declaration_specifiers in extern_decl {:?}",
-        // p.current_token()); // TODO: distinguish between function definition
-        // and declaration
-
-        let mut declaration_specifer_count = 0;
-        while p.nth(declaration_specifer_count).is_declaration_specifier() {
-            declaration_specifer_count += 1;
-        }
-
-        if p.nth(declaration_specifer_count).is_semicolon() {
-            // declaration
-            // 	: declaration_specifiers ';'
-            // 	| declaration_specifiers init_declarator_list ';'
-            // 	| static_assert_declaration
-            // 	;
-
-            declaration(p);
-        } else if !p.nth(declaration_specifer_count).is_declarator() {
-            // TODO: error reporting and recovery
-            p.advance_with_error(&format!(
-                "Unexpected token {}{}{}{} Expected one of{} {}\n",
-                "'".cyan(),
-                p.nth_token(declaration_specifer_count).lexeme().red(),
-                "'".cyan(),
-                ".".black(),
-                ":".black(),
-                display(FN_DEF_DECLARATION_SPECIFIERS_FIRST)
-            ));
+        // A function definition and a declaration share a prefix of
+        // declaration specifiers and a declarator, so look ahead for the `{`
+        // of a function body to tell them apart.
+        if p.at_function_def() {
+            function_def(p);
         } else {
-            // TODO: continue parsing
-            // function_definition
-            // 	: declaration_specifiers declarator declaration_list compound_statement
-            // 	| declaration_specifiers declarator compound_statement
-            // 	;
-            if p.nth(declaration_specifer_count + 1).is_declarator() ||
-                p.nth(declaration_specifer_count + 1).is_l_brace()
-            {
-                // if p.at_declaration_specifier() || p.at_compound_statement() {
-                // function_definition
-                // : declaration_specifiers declarator declaration_list
-                // compound_statement
-                function_def(p);
-            } else {
-                declaration(p);
-            }
-            // init_declarator | parser here
-            // 	: declarator   |  '=' initializer
-            // 	| declarator   |
-            // 	;
-            // else if p.nth(declaration_specifer_count + 1).is_equal() {
-            //     // init_declarator
-            //     // : declarator '=' initializer
-            //     // | declarator
-            //     // 	;
-            //     // We know we're at a declaration now, so parse it
-            //     declaration(p);
-            // } else {
-            //     // Declaration
-            //     declaration(p);
-            // }
+            declaration(p);
         }
     } else if p.at_static_assert_declaration() {
         // TODO: need to figure out how to open intermediary nodes to build the tree
@@ -1274,13 +1431,29 @@ fn extern_decl(p: &mut Parser) -> Result<(), ErrorKind> {
 
         // If we have a static assert declaration, parse it (this is a declaration)
         static_assert_declaration(p);
-    } else {
-        // TODO: do not emit subsequent errors if we have already emitted this error
-        // (i.e. no need to emit the same error twice, only emit first occurrence)
-
-        // Set the error_emitted flag to true
-        p.error_emitted = true;
+    } else if p.at(TokenKind::IDENTIFIER) {
+        // A declaration without a type specifier, e.g. `main(void) { ... }`,
+        // whose type defaults to `int`. Report it, then parse the rest of the
+        // declaration as if the `int` were there.
+        let token = p.current_token();
+        let mut diagnostic = diagnostics::type_specifier_missing(p.file_id, &token);
+        if !from_expansion([Some(&token)]) {
+            let int = Suggestion::insert(
+                p.file_id,
+                *token.span.start(),
+                "int ",
+                Applicability::MachineApplicable,
+            );
+            diagnostic = diagnostic.with_suggestion(int);
+        }
+        p.push_error(diagnostic);
 
+        if p.at_function_def() {
+            function_def(p);
+        } else {
+            declaration(p);
+        }
+    } else {
         p.close(m, TreeKind::ExternDecl);
         p.trace_exit();
 
@@ -1302,9 +1475,9 @@ fn extern_decl(p: &mut Parser) -> Result<(), ErrorKind> {
 
 ///```yacc
 /// function_definition
-/// 	: declaration_specifiers declarator declaration_list compound_statement
-/// 	| declaration_specifiers declarator compound_statement
-/// 	;
+///     : declaration_specifiers declarator declaration_list compound_statement
+///     | declaration_specifiers declarator compound_statement
+///     ;
 /// ```
 ///
 /// FunctionDef = DeclarationSpecifiers Declarator (DeclarationList)?
@@ -1315,7 +1488,7 @@ pub(crate) fn function_def(p: &mut Parser) {
     let m = p.open();
 
     // Parse declaration specifiers
-    declaration_specifiers(p);
+    declaration_specifiers_or_implicit_int(p);
 
     // println!("parsing declarator in function_def {:?}", p.current_token());
 
@@ -1341,12 +1514,18 @@ pub(crate) fn function_def(p: &mut Parser) {
 const STATEMENT_LIST_FIRST: &[TokenKind] = &[
     TokenKind::IDENTIFIER,
     TokenKind::IF_KW,
+    TokenKind::SWITCH_KW,
+    TokenKind::CASE_KW,
+    TokenKind::DEFAULT_KW,
     TokenKind::WHILE_KW,
+    TokenKind::DO_KW,
     TokenKind::FOR_KW,
     TokenKind::RETURN_KW,
     TokenKind::BREAK_KW,
     TokenKind::CONTINUE_KW,
+    TokenKind::GOTO_KW,
     TokenKind::LBRACE,
+    TokenKind::ASM_KW,
 ];
 
 const DECLARATION_LIST_FIRST: &[TokenKind] = &[
@@ -1378,6 +1557,7 @@ const DECLARATION_LIST_FIRST: &[TokenKind] = &[
 // ;
 //
 // StatementList = (Statement)* Statement
+#[allow(dead_code)]
 fn statement_list(p: &mut Parser) {
     p.enter(TreeKind::StatementList);
     let m = p.open();
@@ -1461,6 +1641,7 @@ pub(crate) fn statement(p: &mut Parser) {
     // println!("parsing statement: {:?}", p.current_token());
 
     let m = p.open();
+    p.recover_misspelled_keyword();
 
     if p.at_any(&[TokenKind::CASE_KW, TokenKind::DEFAULT_KW]) ||
         (p.at(TokenKind::IDENTIFIER) && p.nth(1) == TokenKind::COLON)
@@ -1468,7 +1649,7 @@ pub(crate) fn statement(p: &mut Parser) {
         labeled_statement(p);
     } else if p.at(TokenKind::LBRACE) {
         compound_statement(p);
-    } else if p.at(TokenKind::IF_KW) {
+    } else if p.at_any(&[TokenKind::IF_KW, TokenKind::SWITCH_KW]) {
         selection_statement(p);
     } else if p.at_any(&[TokenKind::WHILE_KW, TokenKind::FOR_KW, TokenKind::DO_KW]) {
         iteration_statement(p);
@@ -1479,6 +1660,8 @@ pub(crate) fn statement(p: &mut Parser) {
         TokenKind::GOTO_KW,
     ]) {
         jump_statement(p);
+    } else if p.at(TokenKind::ASM_KW) {
+        asm_statement(p);
     } else if p.at_any(&[TokenKind::IDENTIFIER, TokenKind::SEMICOLON]) {
         expression_statement(p);
     } else {
@@ -1658,6 +1841,7 @@ pub(crate) fn block_item(p: &mut Parser) {
     p.enter(TreeKind::BlockItem);
     let m = p.open();
 
+    p.recover_misspelled_keyword();
     if p.at_declaration() {
         declaration(p);
     } else if p.at_statement() {
@@ -1865,6 +2049,7 @@ pub(crate) fn iteration_statement(p: &mut Parser) {
 // ;
 //
 // AtomicTypeSpecifier = ATOMIC_KW '(' TypeName ')'
+#[allow(dead_code)]
 fn atomic_type_specifier(p: &mut Parser) {
     p.enter(TreeKind::AtomicTypeSpecifier);
     let m = p.open();
@@ -1945,12 +2130,20 @@ pub(crate) fn declaration(p: &mut Parser) {
         static_assert_declaration(p);
     } else {
         // Parse declaration specifiers
-        declaration_specifiers(p);
+        let start = p.core.pos();
+        declaration_specifiers_or_implicit_int(p);
+        let is_typedef = p
+            .tokens_from(start)
+            .take(p.core.pos() - start)
+            .any(|token| token.kind == TokenKind::TYPEDEF_KW);
+
         if p.at(TokenKind::SEMICOLON) {
             // Consume the semicolon
             p.expect(TokenKind::SEMICOLON);
         } else {
+            let outer = std::mem::replace(&mut p.in_typedef, is_typedef);
             init_declarator_list(p);
+            p.in_typedef = outer;
             p.expect(TokenKind::SEMICOLON);
         }
     }
@@ -1963,8 +2156,10 @@ pub(crate) fn declaration(p: &mut Parser) {
 // 	: STATIC_ASSERT '(' constant_expression ',' STRING_LITERAL ')' ';'
 // 	;
 //
-// StaticAssertDeclaration = STATIC_ASSERT '(' ConstantExpression ','
-// STRING_LITERAL ')' ';'
+// StaticAssertDeclaration = STATIC_ASSERT '(' ConstantExpression (','
+// STRING_LITERAL)? ')' ';'
+//
+// The message is optional as of C23.
 fn static_assert_declaration(p: &mut Parser) {
     p.enter(TreeKind::StaticAssertDeclaration);
     let m = p.open();
@@ -1972,8 +2167,10 @@ fn static_assert_declaration(p: &mut Parser) {
     p.expect(TokenKind::STATIC_ASSERT_KW);
     p.expect(TokenKind::LPAREN);
     constant_expression(p);
-    p.expect(TokenKind::COMMA);
-    p.expect(TokenKind::STRING);
+    if !p.standard.c23() || p.at(TokenKind::COMMA) {
+        p.expect(TokenKind::COMMA);
+        p.expect(TokenKind::STRING);
+    }
     p.expect(TokenKind::RPAREN);
     p.expect(TokenKind::SEMICOLON);
 
@@ -2007,13 +2204,20 @@ const PARAMETER_TYPE_LIST_FIRST: &[TokenKind] = &[
     TokenKind::SHORT_KW,
     TokenKind::INT_KW,
     TokenKind::LONG_KW,
-    // TokenKind::FLOAT,
+    TokenKind::FLOAT_KW,
     TokenKind::DOUBLE_KW,
     TokenKind::SIGNED_KW,
     TokenKind::UNSIGNED_KW,
+    TokenKind::BOOL_KW,
     TokenKind::STRUCT_KW,
     TokenKind::UNION_KW,
     TokenKind::ENUM_KW,
+    TokenKind::CONST_KW,
+    TokenKind::VOLATILE_KW,
+    TokenKind::RESTRICT_KW,
+    TokenKind::REGISTER_KW,
+    TokenKind::ATTRIBUTE_KW,
+    TokenKind::TYPEOF_KW,
     TokenKind::IDENTIFIER,
 ];
 
@@ -2057,8 +2261,9 @@ pub(crate) fn direct_declarator(p: &mut Parser) {
         p.advance(); // Consume IDENTIFIER
     }
 
-    // Now, check for arrays and function calls
-    while p.at(TokenKind::LBRACKET) || p.at(TokenKind::LPAREN) {
+    // Now, check for arrays and function calls. A `[[` after the name starts
+    // a C23 attribute of the declarator instead.
+    while (p.at(TokenKind::LBRACKET) && !p.at_standard_attribute()) || p.at(TokenKind::LPAREN) {
         if p.at(TokenKind::LBRACKET) {
             p.advance(); // Consume '['
 
@@ -2104,11 +2309,16 @@ pub(crate) fn direct_declarator(p: &mut Parser) {
         } else if p.at(TokenKind::LPAREN) {
             p.advance(); // Consume '('
 
-            // Check for function calls with parameters or identifier lists
-            if p.at_any(PARAMETER_TYPE_LIST_FIRST) {
-                parameter_type_list(p);
-            } else if p.at(TokenKind::IDENTIFIER) {
+            // Check for function calls with parameters or identifier lists.
+            // A lone name which isn't a type, as in `int f(a, b)`, starts the
+            // identifier list of an old-style definition.
+            if p.at(TokenKind::IDENTIFIER) &&
+                !p.at_typedef_name() &&
+                matches!(p.nth(1), TokenKind::COMMA | TokenKind::RPAREN)
+            {
                 identifier_list(p);
+            } else if p.at_any(PARAMETER_TYPE_LIST_FIRST) {
+                parameter_type_list(p);
             }
 
             p.expect(TokenKind::RPAREN); // Consume ')'
@@ -2285,8 +2495,18 @@ fn identifier_list(p: &mut Parser) {
     p.enter(TreeKind::IdentifierList);
     let m = p.open();
 
-    while !p.eof() && !p.at(TokenKind::RPAREN) {
-        // TODO: Error recovery.
+    // C23 removed old-style definitions, and `int f()` now means
+    // `int f(void)`.
+    if p.standard.c23() {
+        let diagnostic =
+            diagnostics::old_style_parameters_unsupported(p.file_id, &p.current_token());
+        p.push_error(diagnostic);
+    }
+
+    p.expect(TokenKind::IDENTIFIER);
+    while p.at(TokenKind::COMMA) {
+        p.advance(); // Consume ','
+        p.expect(TokenKind::IDENTIFIER);
     }
 
     p.close(m, TreeKind::IdentifierList);
@@ -2398,7 +2618,8 @@ fn assignment_expression(p: &mut Parser) {
 // | AND_ASSIGN
 // | XOR_ASSIGN
 // | OR_ASSIGN
-fn assignment_operator(p: &mut Parser) {
+#[allow(dead_code)]
+fn assignment_operator(_p: &mut Parser) {
     // p.expect_assignment_operator();
 }
 
@@ -2642,7 +2863,9 @@ fn cast_expression(p: &mut Parser) {
     p.enter(TreeKind::CastExpression);
     let m = p.open();
 
-    if p.at(TokenKind::LPAREN) {
+    if p.at(TokenKind::LPAREN) &&
+        (p.nth(1).is_type_specifier() || p.nth(1).is_type_qualifier() || p.nth_is_typedef_name(1))
+    {
         p.advance();
         type_name(p);
         p.expect(TokenKind::RPAREN);
@@ -2663,7 +2886,7 @@ fn cast_expression(p: &mut Parser) {
 fn type_name(p: &mut Parser) {
     let m = p.open();
     specifier_qualifier_list(p);
-    if p.at(TokenKind::IDENTIFIER) {
+    if p.at_any(&[TokenKind::STAR, TokenKind::LPAREN]) {
         abstract_declarator(p);
     }
 
@@ -2679,18 +2902,27 @@ fn type_name(p: &mut Parser) {
 // | TypeQualifier SpecifierQualifierList?
 fn specifier_qualifier_list(p: &mut Parser) {
     let m = p.open();
-    if p.at(TokenKind::CONST_KW) || p.at(TokenKind::VOLATILE_KW)
-    // || p.at(TokenKind::RESTRICT_KW)
-    {
+    if p.at_type_qualifier() {
         type_qualifier(p);
-        specifier_qualifier_list(p);
+    } else if p.at_alignment_specifier() {
+        alignment_specifier(p);
+    } else if p.at(TokenKind::ATTRIBUTE_KW) {
+        attribute_specifier(p);
+    } else if p.at_standard_attribute() {
+        standard_attribute_specifier(p);
+    } else if p.at(TokenKind::EXTENSION_KW) {
+        p.advance();
     } else {
         type_specifier(p);
-        if p.at(TokenKind::CONST_KW) || p.at(TokenKind::VOLATILE_KW)
-        // || p.at(TokenKind::RESTRICT_KW)
-        {
-            specifier_qualifier_list(p);
-        }
+    }
+
+    if p.at_type_qualifier() ||
+        p.at_type_specifier() ||
+        p.at_alignment_specifier() ||
+        p.at_any(&[TokenKind::ATTRIBUTE_KW, TokenKind::EXTENSION_KW]) ||
+        p.at_standard_attribute()
+    {
+        specifier_qualifier_list(p);
     }
 
     p.close(m, TreeKind::SpecifierQualifierList);
@@ -2746,6 +2978,9 @@ pub(crate) fn unary_expression(p: &mut Parser) {
     } else if p.at_unary_operator() {
         unary_operator(p);
         cast_expression(p);
+    } else if p.at(TokenKind::EXTENSION_KW) {
+        p.advance();
+        cast_expression(p);
     } else if p.at(TokenKind::SIZEOF_KW) || p.at(TokenKind::ALIGNOF_KW) {
         p.advance();
         if p.at(TokenKind::LPAREN) {
@@ -2890,6 +3125,11 @@ pub(crate) fn primary_expression(p: &mut Parser) {
         constant(p);
     } else if p.at_string() {
         string(p);
+    } else if p.at(TokenKind::LPAREN) &&
+        p.nth(1) == TokenKind::LBRACE &&
+        p.standard.gnu_extensions()
+    {
+        statement_expression(p);
     } else if p.at(TokenKind::LPAREN) {
         p.advance();
         expression(p);
@@ -2968,20 +3208,18 @@ pub(crate) fn generic_association(p: &mut Parser) {
 // : INTEGER_CONSTANT
 // | FLOATING_CONSTANT
 // | ENUMERATION_CONSTANT
+// | predefined_constant
 // ;
 //
 // Constant = INTEGER_CONSTANT
 // | FLOATING_CONSTANT
 // | ENUMERATION_CONSTANT
+// | TRUE | FALSE | NULLPTR
 pub(crate) fn constant(p: &mut Parser) {
     p.enter(TreeKind::Constant);
     let m = p.open();
 
-    if p.at(TokenKind::INTEGER_CONSTANT) ||
-        p.at(TokenKind::FLOATING_CONSTANT) ||
-        p.at(TokenKind::IDENTIFIER)
-    // ENUMERATION_CONSTANT
-    {
+    if p.at_constant() {
         p.advance();
     } else {
         p.advance_with_error(
@@ -3048,7 +3286,8 @@ fn parameter_list(p: &mut Parser) {
     let m = p.open();
 
     parameter_declaration(p);
-    while p.at(TokenKind::COMMA) {
+    // A trailing `, ...` belongs to the enclosing ParamTypeList.
+    while p.at(TokenKind::COMMA) && p.nth(1) != TokenKind::ELLIPSIS {
         p.advance();
         parameter_declaration(p);
     }
@@ -3108,6 +3347,10 @@ fn parameter_declaration(p: &mut Parser) {
         direct_declarator(p);
     }
 
+    while p.at(TokenKind::ATTRIBUTE_KW) {
+        attribute_specifier(p);
+    }
+
     p.close(m, TreeKind::ParameterDeclaration);
     p.trace_exit();
 }
@@ -3352,7 +3595,30 @@ fn init_declarator(p: &mut Parser) {
     p.enter(TreeKind::InitDeclarator);
     let m = p.open();
 
+    let start = p.core.pos();
     declarator(p);
+    if p.in_typedef {
+        // The name of a declarator is the first identifier in it, e.g. `f`
+        // in `(*f)(int x)`.
+        let name = p
+            .tokens_from(start)
+            .take(p.core.pos() - start)
+            .find(|token| token.kind == TokenKind::IDENTIFIER)
+            .map(|token| token.lexeme);
+        p.typedef_names.extend(name);
+    }
+    if p.at(TokenKind::ASM_KW) {
+        asm_label(p);
+    }
+    loop {
+        if p.at(TokenKind::ATTRIBUTE_KW) {
+            attribute_specifier(p);
+        } else if p.at_standard_attribute() {
+            standard_attribute_specifier(p);
+        } else {
+            break;
+        }
+    }
     if p.eat(TokenKind::EQ) {
         initializer(p);
     }
@@ -3363,22 +3629,29 @@ fn init_declarator(p: &mut Parser) {
 
 // initializer
 // : assignment_expression
+// | '{' '}'
 // | '{' initializer_list '}'
 // | '{' initializer_list ',' '}'
 // ;
 //
 // Initializer = AssignmentExpression
+// | '{' '}'
 // | '{' InitializerList '}'
 // | '{' InitializerList ',' '}'
+//
+// The empty initializer `{}` is new in C23, and a GNU extension before it.
 fn initializer(p: &mut Parser) {
     p.enter(TreeKind::Initializer);
     let m = p.open();
 
     if p.at(TokenKind::LBRACE) {
         p.advance();
-        initializer_list(p);
-        if p.at(TokenKind::COMMA) {
-            p.advance();
+        let empty_allowed = p.standard.c23() || p.standard.gnu_extensions();
+        if !(empty_allowed && p.at(TokenKind::RBRACE)) {
+            initializer_list(p);
+            if p.at(TokenKind::COMMA) {
+                p.advance();
+            }
         }
         p.expect(TokenKind::RBRACE);
     } else {
@@ -3413,7 +3686,9 @@ fn initializer_list(p: &mut Parser) {
         initializer(p);
     }
 
-    while p.eat(TokenKind::COMMA) {
+    // A trailing `,` belongs to the enclosing Initializer.
+    while p.at(TokenKind::COMMA) && p.nth(1) != TokenKind::RBRACE {
+        p.advance();
         if p.at_designator() {
             designation(p);
             initializer(p);
@@ -3573,6 +3848,21 @@ fn declaration_specifiers(p: &mut Parser) {
         if p.at_declaration_specifier() {
             declaration_specifiers(p);
         }
+    } else if p.at(TokenKind::ATTRIBUTE_KW) {
+        attribute_specifier(p);
+        if p.at_declaration_specifier() {
+            declaration_specifiers(p);
+        }
+    } else if p.at_standard_attribute() {
+        standard_attribute_specifier(p);
+        if p.at_declaration_specifier() {
+            declaration_specifiers(p);
+        }
+    } else if p.at(TokenKind::EXTENSION_KW) {
+        p.advance();
+        if p.at_declaration_specifier() {
+            declaration_specifiers(p);
+        }
     } else {
         // TODO: error reporting
         p.advance_with_error(&format!(
@@ -3591,6 +3881,20 @@ fn declaration_specifiers(p: &mut Parser) {
     p.trace_exit();
 }
 
+/// Parses the declaration specifiers of a declaration, or none at all if it
+/// starts with its declarator, as in `main(void)`. The missing specifiers
+/// default to `int` (which has been reported by then).
+fn declaration_specifiers_or_implicit_int(p: &mut Parser) {
+    if p.at(TokenKind::IDENTIFIER) && !p.at_declaration_specifier() {
+        p.enter(TreeKind::DeclarationSpecifiers);
+        let m = p.open();
+        p.close(m, TreeKind::DeclarationSpecifiers);
+        p.trace_exit();
+    } else {
+        declaration_specifiers(p);
+    }
+}
+
 // function_specifier
 // 	: INLINE
 // 	| NORETURN
@@ -3647,20 +3951,17 @@ fn alignment_specifier(p: &mut Parser) {
 // | STATIC_KW
 // | EXTERN_KW
 // | TYPEDEF_KW
+// | THREAD_LOCAL_KW
+// | CONSTEXPR_KW
 // ;
 //
-// StorageClassSpecifier = Auto | Register | Static | Extern | Typedef
+// StorageClassSpecifier = Auto | Register | Static | Extern | Typedef |
+// ThreadLocal | Constexpr
 fn storage_class_specifier(p: &mut Parser) {
     p.enter(TreeKind::StorageClassSpecifier);
     let m = p.open();
 
-    if p.at_any(&[
-        TokenKind::AUTO_KW,
-        TokenKind::REGISTER_KW,
-        TokenKind::STATIC_KW,
-        TokenKind::EXTERN_KW,
-        TokenKind::TYPEDEF_KW,
-    ]) {
+    if p.at_storage_class_specifier() {
         p.advance();
     } else {
         // TODO: error reporting
@@ -3703,12 +4004,17 @@ fn type_specifier(p: &mut Parser) {
         TokenKind::DOUBLE_KW,
         TokenKind::SIGNED_KW,
         TokenKind::UNSIGNED_KW,
+        TokenKind::BOOL_KW,
+        TokenKind::COMPLEX_KW,
+        TokenKind::IMAGINARY_KW,
     ]) {
         p.advance();
     } else if p.at_any(&[TokenKind::STRUCT_KW, TokenKind::UNION_KW]) {
         struct_or_union_specifier(p);
     } else if p.at(TokenKind::ENUM_KW) {
         enum_specifier(p);
+    } else if p.at_any(&[TokenKind::TYPEOF_KW, TokenKind::TYPEOF_UNQUAL_KW]) {
+        typeof_specifier(p);
     } else if p.at(TokenKind::IDENTIFIER) {
         p.advance();
     } else {
@@ -3806,15 +4112,27 @@ fn struct_or_union_specifier(p: &mut Parser) {
 
     struct_or_union(p);
 
-    if p.at(TokenKind::IDENTIFIER) {
-        p.advance();
+    loop {
+        if p.at(TokenKind::ATTRIBUTE_KW) {
+            attribute_specifier(p);
+        } else if p.at_standard_attribute() {
+            standard_attribute_specifier(p);
+        } else {
+            break;
+        }
     }
 
+    let named = p.eat(TokenKind::IDENTIFIER);
+
     if p.at(TokenKind::LBRACE) {
         p.advance();
         struct_declaration_list(p);
         p.expect(TokenKind::RBRACE);
-    } else {
+
+        while p.at(TokenKind::ATTRIBUTE_KW) {
+            attribute_specifier(p);
+        }
+    } else if !named {
         p.advance_with_error(&format!(
             "expected struct or union specifier, but instead found {}",
             p.nth(0),
@@ -3882,22 +4200,12 @@ fn struct_declaration_list(p: &mut Parser) {
 
     struct_declaration(p);
 
-    while p.at_any(&[
-        TokenKind::CHAR_KW,
-        TokenKind::SHORT_KW,
-        TokenKind::INT_KW,
-        TokenKind::LONG_KW,
-        TokenKind::FLOAT_KW,
-        TokenKind::DOUBLE_KW,
-        TokenKind::SIGNED_KW,
-        TokenKind::UNSIGNED_KW,
-        TokenKind::STRUCT_KW,
-        TokenKind::UNION_KW,
-        TokenKind::ENUM_KW,
-        TokenKind::CONST_KW,
-        TokenKind::VOLATILE_KW,
-        TokenKind::IDENTIFIER,
-    ]) {
+    while p.at_type_specifier() ||
+        p.at_type_qualifier() ||
+        p.at_alignment_specifier() ||
+        p.at_any(&[TokenKind::IDENTIFIER, TokenKind::ATTRIBUTE_KW, TokenKind::EXTENSION_KW]) ||
+        p.at_standard_attribute()
+    {
         struct_declaration(p);
     }
 
@@ -3915,7 +4223,8 @@ fn struct_declaration(p: &mut Parser) {
 
     specifier_qualifier_list(p);
 
-    if p.at_any(&[TokenKind::IDENTIFIER, TokenKind::STAR, TokenKind::LPAREN]) {
+    // An unnamed bit-field, as in `int : 0;`, has no declarator.
+    if p.at_any(&[TokenKind::IDENTIFIER, TokenKind::STAR, TokenKind::LPAREN, TokenKind::COLON]) {
         struct_declarator_list(p);
     }
 
@@ -3969,6 +4278,10 @@ fn struct_declarator(p: &mut Parser) {
         }
     }
 
+    while p.at(TokenKind::ATTRIBUTE_KW) {
+        attribute_specifier(p);
+    }
+
     p.close(m, TreeKind::StructDeclarator);
 }
 
@@ -4006,6 +4319,245 @@ fn type_qualifier(p: &mut Parser) {
     p.close(m, TreeKind::TypeQualifier);
 }
 
+// GNU extensions
+//
+// The keywords introducing these are only lexed as such with `--std=gnu11`,
+// so none of the following are reachable in strict ISO mode.
+
+/// ```text
+/// AttributeSpecifier = '__attribute__' '(' '(' (Attribute (',' Attribute)*)? ')' ')'
+/// ```
+///
+/// e.g. `__attribute__((nonnull(1), format(printf, 1, 2)))`
+fn attribute_specifier(p: &mut Parser) {
+    p.enter(TreeKind::AttributeSpecifier);
+    let m = p.open();
+
+    p.expect(TokenKind::ATTRIBUTE_KW);
+    p.expect(TokenKind::LPAREN);
+    p.expect(TokenKind::LPAREN);
+
+    while !p.eof() && !p.at(TokenKind::RPAREN) {
+        attribute(p);
+        if !p.eat(TokenKind::COMMA) {
+            break;
+        }
+    }
+
+    p.expect(TokenKind::RPAREN);
+    p.expect(TokenKind::RPAREN);
+
+    p.close(m, TreeKind::AttributeSpecifier);
+    p.trace_exit();
+}
+
+/// ```text
+/// Attribute = 'identifier' (':' ':' 'identifier')? ('(' ArgumentExpressionList? ')')?
+/// ```
+///
+/// The name of an attribute may also be a keyword, as in
+/// `__attribute__((__const__))`. Within a C23 [`standard_attribute_specifier`]
+/// it may be prefixed by a namespace, as in `[[gnu::always_inline]]`.
+fn attribute(p: &mut Parser) {
+    p.enter(TreeKind::Attribute);
+    let m = p.open();
+
+    attribute_name(p);
+    if p.at(TokenKind::COLON) && p.nth(1) == TokenKind::COLON {
+        p.advance();
+        p.advance();
+        attribute_name(p);
+    }
+
+    if p.eat(TokenKind::LPAREN) {
+        if !p.at(TokenKind::RPAREN) {
+            argument_expression_list(p);
+        }
+        p.expect(TokenKind::RPAREN);
+    }
+
+    p.close(m, TreeKind::Attribute);
+    p.trace_exit();
+}
+
+fn attribute_name(p: &mut Parser) {
+    if p.at(TokenKind::IDENTIFIER) || p.current().is_keyword() {
+        p.advance();
+    } else {
+        p.advance_with_error(&format!(
+            "expected an attribute name, but instead found {}",
+            p.current()
+        ));
+    }
+}
+
+/// ```text
+/// AsmLabel = '__asm__' '(' String* ')'
+/// ```
+///
+/// e.g. `extern FILE *fopen(const char *, const char *) __asm__("fopen64");`
+fn asm_label(p: &mut Parser) {
+    p.enter(TreeKind::AsmLabel);
+    let m = p.open();
+
+    p.expect(TokenKind::ASM_KW);
+    p.expect(TokenKind::LPAREN);
+    while p.at(TokenKind::STRING) {
+        string(p);
+    }
+    p.expect(TokenKind::RPAREN);
+
+    p.close(m, TreeKind::AsmLabel);
+    p.trace_exit();
+}
+
+/// ```text
+/// AsmStatement = '__asm__' ('volatile' | 'inline' | 'goto')* '(' String* AsmOperandList* ')' ';'
+/// ```
+///
+/// e.g. `__asm__ volatile ("mov %1, %0" : "=r"(dst) : "r"(src));`
+fn asm_statement(p: &mut Parser) {
+    p.enter(TreeKind::AsmStatement);
+    let m = p.open();
+
+    p.expect(TokenKind::ASM_KW);
+    while p.at_any(&[TokenKind::VOLATILE_KW, TokenKind::INLINE_KW, TokenKind::GOTO_KW]) {
+        p.advance();
+    }
+
+    p.expect(TokenKind::LPAREN);
+    while p.at(TokenKind::STRING) {
+        string(p);
+    }
+    while p.at(TokenKind::COLON) {
+        asm_operand_list(p);
+    }
+    p.expect(TokenKind::RPAREN);
+    p.expect(TokenKind::SEMICOLON);
+
+    p.close(m, TreeKind::AsmStatement);
+    p.trace_exit();
+}
+
+/// One `:`-separated section of an [`asm_statement`]: the outputs, inputs,
+/// clobbers or goto labels.
+///
+/// ```text
+/// AsmOperandList = ':' (AsmOperand (',' AsmOperand)*)?
+/// ```
+fn asm_operand_list(p: &mut Parser) {
+    p.enter(TreeKind::AsmOperandList);
+    let m = p.open();
+
+    p.expect(TokenKind::COLON);
+    if p.at_any(&[TokenKind::LBRACKET, TokenKind::STRING, TokenKind::IDENTIFIER]) {
+        asm_operand(p);
+        while p.eat(TokenKind::COMMA) {
+            asm_operand(p);
+        }
+    }
+
+    p.close(m, TreeKind::AsmOperandList);
+    p.trace_exit();
+}
+
+/// ```text
+/// AsmOperand = ('[' 'identifier' ']')? (String ('(' Expression ')')? | 'identifier')
+/// ```
+fn asm_operand(p: &mut Parser) {
+    p.enter(TreeKind::AsmOperand);
+    let m = p.open();
+
+    if p.eat(TokenKind::LBRACKET) {
+        p.expect(TokenKind::IDENTIFIER);
+        p.expect(TokenKind::RBRACKET);
+    }
+
+    if p.at(TokenKind::IDENTIFIER) {
+        p.advance();
+    } else {
+        string(p);
+        if p.eat(TokenKind::LPAREN) {
+            expression(p);
+            p.expect(TokenKind::RPAREN);
+        }
+    }
+
+    p.close(m, TreeKind::AsmOperand);
+    p.trace_exit();
+}
+
+/// ```text
+/// TypeofSpecifier = ('typeof' | 'typeof_unqual') '(' (TypeName | Expression) ')'
+/// ```
+///
+/// `typeof` is also a keyword in C23, which adds `typeof_unqual`.
+fn typeof_specifier(p: &mut Parser) {
+    p.enter(TreeKind::TypeofSpecifier);
+    let m = p.open();
+
+    p.expect_any(&[TokenKind::TYPEOF_KW, TokenKind::TYPEOF_UNQUAL_KW]);
+    p.expect(TokenKind::LPAREN);
+    if p.at_type_specifier() || p.at_type_qualifier() {
+        type_name(p);
+    } else {
+        expression(p);
+    }
+    p.expect(TokenKind::RPAREN);
+
+    p.close(m, TreeKind::TypeofSpecifier);
+    p.trace_exit();
+}
+
+/// ```text
+/// StatementExpression = '(' CompoundStatement ')'
+/// ```
+///
+/// e.g. `({ int y = f(x); y * y; })`, whose value is that of its last
+/// statement.
+fn statement_expression(p: &mut Parser) {
+    p.enter(TreeKind::StatementExpression);
+    let m = p.open();
+
+    p.expect(TokenKind::LPAREN);
+    compound_statement(p);
+    p.expect(TokenKind::RPAREN);
+
+    p.close(m, TreeKind::StatementExpression);
+    p.trace_exit();
+}
+
+// C23
+//
+// `[[` only starts an attribute with `--std=c23`; see
+// `Parser::at_standard_attribute`.
+
+/// ```text
+/// StandardAttributeSpecifier = '[' '[' (Attribute (',' Attribute)*)? ']' ']'
+/// ```
+///
+/// e.g. `[[nodiscard, gnu::always_inline]]` or `[[deprecated("use g")]]`
+fn standard_attribute_specifier(p: &mut Parser) {
+    p.enter(TreeKind::StandardAttributeSpecifier);
+    let m = p.open();
+
+    p.expect(TokenKind::LBRACKET);
+    p.expect(TokenKind::LBRACKET);
+
+    while !p.eof() && !p.at(TokenKind::RBRACKET) {
+        attribute(p);
+        if !p.eat(TokenKind::COMMA) {
+            break;
+        }
+    }
+
+    p.expect(TokenKind::RBRACKET);
+    p.expect(TokenKind::RBRACKET);
+
+    p.close(m, TreeKind::StandardAttributeSpecifier);
+    p.trace_exit();
+}
+
 // Current log behavior:
 // DEBUG rcc::parser: PARSER (TYPEDEF_KW, 'typedef', 0..7) -> translation_unit
 // DEBUG rcc::parser: PARSER (TYPEDEF_KW, 'typedef', 0..7) ->
@@ -4224,6 +4776,7 @@ mod tests {
     #[case::recursive("testdata/parse/ok/medium/recursive.c")]
     #[case::array_and_loop("testdata/parse/ok/medium/array_and_loop.c")]
     #[case::complex_conditional("testdata/parse/ok/medium/complex_conditional.c")]
+    #[case::old_style_definition("testdata/parse/ok/medium/old_style_definition.c")]
     // #[case::advanced_function_decl("testdata/parse/ok/medium/
     // advanced_function_decl.c")] #[case::function_pointers("testdata/parse/ok/
     // medium/function_pointers.c")] #[case::pointer_and_struct("testdata/parse/
@@ -4233,6 +4786,10 @@ mod tests {
     // testdata/parse/ok/struct_definition. c")] # TODO: implement
     // #[case("testdata/parse/ok/typedef.c")]
     fn valid_syntax(#[case] file_path: &str) {
+        // Test data lives at the workspace root, not within the crate.
+        let file_path = format!("{}/../../{file_path}", env!("CARGO_MANIFEST_DIR"));
+        let file_path = file_path.as_str();
+
         // Parse the source code
         let mut diagnostics = DiagnosticsEngine::new();
 
@@ -4265,4 +4822,60 @@ mod tests {
         //     }
         // }
     }
+
+    #[rstest]
+    #[case::attributes("testdata/parse/ok/gnu/attributes.c")]
+    #[case::asm("testdata/parse/ok/gnu/asm.c")]
+    #[case::glibc_stdio_excerpt("testdata/parse/ok/gnu/glibc_stdio_excerpt.c")]
+    #[case::typeof_and_statement_expressions(
+        "testdata/parse/ok/gnu/typeof_and_statement_expressions.c"
+    )]
+    fn valid_gnu_syntax(#[case] file_path: &str) {
+        let file_path = format!("{}/../../{file_path}", env!("CARGO_MANIFEST_DIR"));
+        let text = std::fs::read_to_string(&file_path).unwrap();
+
+        let mut diagnostics = DiagnosticsEngine::new();
+        let file_id = diagnostics.add_file(file_path, text.clone());
+        let tree = parse_with_diagnostics(&text, Standard::Gnu11, &mut diagnostics, file_id);
+
+        assert_no_errors(&tree);
+    }
+
+    #[rstest]
+    #[case::keywords("testdata/parse/ok/c23/keywords.c")]
+    #[case::attributes("testdata/parse/ok/c23/attributes.c")]
+    #[case::literals_and_initializers("testdata/parse/ok/c23/literals_and_initializers.c")]
+    fn valid_c23_syntax(#[case] file_path: &str) {
+        let file_path = format!("{}/../../{file_path}", env!("CARGO_MANIFEST_DIR"));
+        let text = std::fs::read_to_string(&file_path).unwrap();
+
+        let mut diagnostics = DiagnosticsEngine::new();
+        let file_id = diagnostics.add_file(file_path, text.clone());
+        let tree = parse_with_diagnostics(&text, Standard::C23, &mut diagnostics, file_id);
+
+        assert_no_errors(&tree);
+        assert!(!diagnostics.has_errors(), "{:#?}", diagnostics.diagnostics);
+    }
+
+    #[test]
+    fn gnu_keywords_are_identifiers_in_iso_mode() {
+        let text = "int __attribute__;";
+
+        let mut diagnostics = DiagnosticsEngine::new();
+        let file_id = diagnostics.add_file("iso.c", text.to_string());
+        let tree = parse_with_diagnostics(text, Standard::C11, &mut diagnostics, file_id);
+
+        assert_no_errors(&tree);
+        assert!(!tree.to_string().contains("AttributeSpecifier"), "{tree}");
+    }
+    #[test]
+    fn c23_keywords_are_identifiers_before_c23() {
+        let text = "int bool, true, nullptr, constexpr, typeof_unqual;";
+
+        let mut diagnostics = DiagnosticsEngine::new();
+        let file_id = diagnostics.add_file("c11.c", text.to_string());
+        let tree = parse_with_diagnostics(text, Standard::C11, &mut diagnostics, file_id);
+
+        assert_no_errors(&tree);
+    }
 }
@@ -378,8 +378,8 @@ fn replace_next_macro(line: &str, macros: &HashMap<String, String>) -> Option<St
         let before = parts.next().unwrap();
         let after = parts.next()?;
 
-        if before.chars().next_back().map_or(false, is_word_char)
-            || after.chars().next().map_or(false, is_word_char)
+        if before.chars().next_back().is_some_and(is_word_char)
+            || after.chars().next().is_some_and(is_word_char)
         {
             return None;
         }
@@ -1,14 +1,33 @@
-use crate::diagnostics::FileId;
+use crate::{
+    condition::{
+        self,
+        ConditionError,
+        Conditional,
+    },
+    diagnostics::{
+        self,
+        Diagnostic,
+        FileId,
+    },
+    lexer::{
+        self,
+        Span,
+        Token,
+    },
+    line_index::LineIndex,
+    source_manager::SourceManager,
+    standard::Standard,
+    symbol::Symbol,
+    target::Target,
+};
 use chrono::Datelike;
 use chrono::Local;
 use chrono::Timelike;
-use codespan_reporting::diagnostic::Diagnostic;
-use logos::{
-    Logos,
-    Span,
-};
+use logos::Logos;
 use owo_colors::OwoColorize;
 use regex::Regex;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
 use std::io::{
@@ -17,49 +36,341 @@ use std::io::{
     BufReader,
     Write,
 };
+use std::ops::Range;
 use std::path::Path;
-use std::process::Command;
 use std::time::Instant;
 use walkdir::WalkDir;
 
-pub struct Preprocessor {
-    file_id:    FileId,
-    input_text: String,
-    output:     TextSink,
+pub struct Preprocessor<'a> {
+    file_id:      FileId,
+    input_text:   String,
+    /// Where the files named by `#embed` are resolved and read from.
+    sources:      Cow<'a, SourceManager>,
+    output:       TextSink,
+    /// The dialect of C, which decides whether the C23 directives are
+    /// supported.
+    standard:     Standard,
+    /// The macros defined so far, starting with those predefined on the
+    /// target, with the replacement list of each object-like one. Macros
+    /// aren't expanded in the text, but `#ifdef` and friends need their
+    /// names, and `#if` the values of object-like ones.
+    defines:      HashMap<String, Option<String>>,
+    /// The open `#if`/`#ifdef`/`#ifndef` groups, innermost last.
+    conditionals: Vec<Conditional>,
 }
 
-impl Preprocessor {
+impl<'a> Preprocessor<'a> {
+    /// A preprocessor for `input_text`, reading the files it names from
+    /// disk.
     pub fn new(file_id: FileId, input_text: String) -> Self {
-        Self { file_id, input_text, output: TextSink::new() }
+        Self::with_cow_sources(file_id, input_text, Cow::Owned(SourceManager::new()))
+    }
+
+    /// A preprocessor for `input_text`, the source of `file_id` in
+    /// `sources`, resolving and reading the files it names through
+    /// `sources`.
+    pub fn with_sources(file_id: FileId, input_text: String, sources: &'a SourceManager) -> Self {
+        Self::with_cow_sources(file_id, input_text, Cow::Borrowed(sources))
+    }
+
+    fn with_cow_sources(
+        file_id: FileId,
+        input_text: String,
+        sources: Cow<'a, SourceManager>,
+    ) -> Self {
+        Self {
+            file_id,
+            input_text,
+            sources,
+            output: TextSink::new(),
+            standard: Standard::default(),
+            defines: predefined_defines(&Target::default()),
+            conditionals: Vec::new(),
+        }
+    }
+
+    /// Preprocesses `standard` C, rather than the default dialect.
+    pub fn with_standard(mut self, standard: Standard) -> Self {
+        self.standard = standard;
+        self
+    }
+
+    /// Preprocesses for `target`, rather than the default one, with the
+    /// macros predefined on it.
+    pub fn with_target(mut self, target: &Target) -> Self {
+        self.defines = predefined_defines(target);
+        self
     }
 
-    pub fn preprocess(&mut self) -> Result<(), Diagnostic<FileId>> {
-        let text = &self.input_text;
+    pub fn preprocess(&mut self) -> Result<(), Box<Diagnostic<FileId>>> {
+        let text = self.input_text.clone();
 
         // Remove leading and trailing whitespace
-        let text = text.strip_suffix("\r\n").or_else(|| text.strip_suffix('\n')).unwrap_or(text);
+        let text = text.strip_suffix("\r\n").or_else(|| text.strip_suffix('\n')).unwrap_or(&text);
 
         // Perform global transformations on text (https://www.math.utah.edu/docs/info/cpp_1.html#SEC2)
         // let text = global_transform(text, self.file_id);
 
-        let include_directories =
-            vec![String::from("/usr/include"), String::from("/usr/local/include")];
-
-        for line in text.lines() {
-            let line = line;
-            let processed_line = process_line(line, include_directories.as_slice());
+        let include_directories = self
+            .sources
+            .include_dirs()
+            .iter()
+            .map(|dir| dir.display().to_string())
+            .collect::<Vec<_>>();
+
+        let mut offset = 0;
+        for line in text.split('\n') {
+            let span = offset..offset + line.len();
+            offset += line.len() + 1;
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            // Lines replaced by directives are kept as empty lines, so that
+            // line numbers are preserved.
+            let processed_line = match self.process_directive(line, span) {
+                Some(processed_line) => processed_line,
+                None => process_line(line, include_directories.as_slice()),
+            };
             self.output.transformed_text.push_str(&processed_line);
             self.output.transformed_text.push('\n');
         }
 
+        if !self.conditionals.is_empty() {
+            let end = self.input_text.len();
+            self.output.push_error(diagnostics::unterminated_conditional(self.file_id, end));
+        }
+
         Ok(())
     }
 
+    /// Whether lines at the current position are kept, i.e. whether every
+    /// enclosing conditional group is in a kept branch.
+    fn active(&self) -> bool {
+        self.conditionals.last().map_or(true, |conditional| conditional.active)
+    }
+
+    /// Handles the directives which need the preprocessor's state:
+    /// conditional inclusion, `#define`/`#undef` (as far as which names are
+    /// defined; both are kept for the lexer, which expands macros) and
+    /// `#embed`. Also drops every line of a skipped branch.
+    ///
+    /// Returns the text replacing `line`, or `None` if the line should be
+    /// processed by [`process_line`].
+    fn process_directive(&mut self, line: &str, span: Range<usize>) -> Option<String> {
+        let Some(directive) = line.trim_start().strip_prefix('#') else {
+            return (!self.active()).then(String::new);
+        };
+        let directive = directive.trim_start();
+        let (name, rest) = directive.split_once(char::is_whitespace).unwrap_or((directive, ""));
+        let rest = rest.trim();
+
+        if matches!(name, "elifdef" | "elifndef" | "embed") && !self.standard.c23() {
+            self.output.push_error(diagnostics::unsupported_directive(
+                self.file_id,
+                span.clone().into(),
+                name,
+            ));
+        }
+
+        match name {
+            "if" | "ifdef" | "ifndef" => {
+                let outer_active = self.active();
+                let active = outer_active && self.condition(name, rest, span.clone());
+                self.conditionals.push(Conditional {
+                    active,
+                    taken: active,
+                    outer_active,
+                    seen_else: false,
+                });
+            }
+            "elif" | "elifdef" | "elifndef" | "else" => {
+                let Some(conditional) = self.conditionals.pop() else {
+                    self.unmatched_directive(name, span);
+                    return Some(String::new());
+                };
+                if conditional.seen_else {
+                    self.output.push_error(diagnostics::directive_after_else(
+                        self.file_id,
+                        span.clone().into(),
+                        name,
+                    ));
+                }
+
+                let active = conditional.outer_active &&
+                    !conditional.taken &&
+                    (name == "else" || self.condition(name, rest, span));
+                self.conditionals.push(Conditional {
+                    active,
+                    taken: conditional.taken || active,
+                    seen_else: conditional.seen_else || name == "else",
+                    ..conditional
+                });
+            }
+            "endif" => {
+                if self.conditionals.pop().is_none() {
+                    self.unmatched_directive(name, span);
+                }
+            }
+            _ if !self.active() => {}
+            "define" => {
+                let end = rest.find(|c: char| c == '(' || c.is_whitespace()).unwrap_or(rest.len());
+                let (name, body) = rest.split_at(end);
+                if !name.is_empty() {
+                    let value = (!body.starts_with('(')).then(|| body.trim().to_string());
+                    self.defines.insert(name.to_string(), value);
+                }
+                return None;
+            }
+            "undef" => {
+                self.defines.remove(rest);
+                return None;
+            }
+            "embed" => return Some(self.embed(rest, span)),
+            _ => return None,
+        }
+
+        Some(String::new())
+    }
+
+    /// Evaluates the condition of an `#if`, `#ifdef`, `#elifdef`, etc., on
+    /// the line at `span`. An invalid condition is reported, and taken to
+    /// be false.
+    fn condition(&mut self, directive: &str, expression: &str, span: Range<usize>) -> bool {
+        match directive {
+            "ifdef" | "elifdef" => return self.defines.contains_key(expression),
+            "ifndef" | "elifndef" => return !self.defines.contains_key(expression),
+            _ => {}
+        }
+
+        let span = Span::from(span);
+        let tokens = condition_tokens(expression, span);
+        let value = condition::replace_defined(&tokens, |name| {
+            self.defines.contains_key(name.as_str())
+        })
+        .and_then(|tokens| self.expand(tokens, &mut Vec::new()))
+        .and_then(|tokens| condition::evaluate(&tokens, span));
+        value.unwrap_or_else(|(error, _)| {
+            self.output.push_error(diagnostics::invalid_condition(
+                self.file_id,
+                span,
+                directive,
+                &error,
+            ));
+            false
+        })
+    }
+
+    /// Expands the object-like macros of the condition `tokens`, and those
+    /// in their replacement lists, except the macros in `expanding`, which
+    /// are being expanded already. Function-like macros can't be expanded
+    /// here.
+    fn expand(
+        &self,
+        tokens: Vec<Token>,
+        expanding: &mut Vec<Symbol>,
+    ) -> Result<Vec<Token>, (ConditionError, Span)> {
+        let mut output = Vec::with_capacity(tokens.len());
+        for token in tokens {
+            match self.defines.get(token.lexeme.as_str()) {
+                Some(_) if expanding.contains(&token.lexeme) => output.push(token),
+                Some(Some(value)) => {
+                    expanding.push(token.lexeme);
+                    output.extend(self.expand(condition_tokens(value, token.span), expanding)?);
+                    expanding.pop();
+                }
+                Some(None) => {
+                    return Err((ConditionError::FunctionLikeMacro(token.lexeme), token.span));
+                }
+                None => output.push(token),
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Expands `#embed "file"` or `#embed <file>` to the bytes of the file,
+    /// as a comma-separated list of integer constants. Of the embed
+    /// parameters, only `limit(N)` is supported; others are ignored.
+    ///
+    /// The file is found with [`SourceManager::resolve`]: `"file"` next to
+    /// the file being preprocessed and then in the include directories,
+    /// `<file>` only in the latter.
+    fn embed(&mut self, rest: &str, span: Range<usize>) -> String {
+        let (filename, parameters, quoted) = if let Some(rest) = rest.strip_prefix('"') {
+            let (filename, parameters) = rest.split_once('"').unwrap_or((rest, ""));
+            (filename, parameters, true)
+        } else if let Some(rest) = rest.strip_prefix('<') {
+            let (filename, parameters) = rest.split_once('>').unwrap_or((rest, ""));
+            (filename, parameters, false)
+        } else {
+            self.output.push_error(diagnostics::expected_embed_filename(self.file_id, span.into()));
+            return String::new();
+        };
+
+        let limit = parameters
+            .split_once("limit(")
+            .and_then(|(_, limit)| limit.split_once(')'))
+            .and_then(|(limit, _)| limit.trim().parse::<usize>().ok());
+
+        let bytes = self
+            .sources
+            .resolve(filename, quoted, Some(self.file_id))
+            .and_then(|path| self.sources.read(path).ok());
+        let Some(bytes) = bytes else {
+            self.output.push_error(diagnostics::embed_file_not_found(
+                self.file_id,
+                span.into(),
+                filename,
+            ));
+            return String::new();
+        };
+
+        bytes
+            .iter()
+            .take(limit.unwrap_or(usize::MAX))
+            .map(|byte| byte.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn unmatched_directive(&mut self, directive: &str, span: Range<usize>) {
+        self.output.push_error(diagnostics::unmatched_conditional_directive(
+            self.file_id,
+            span.into(),
+            directive,
+        ));
+    }
+
     pub fn into_output(self) -> TextSink {
         self.output
     }
 }
 
+/// The macros predefined on `target`, which are all object-like.
+fn predefined_defines(target: &Target) -> HashMap<String, Option<String>> {
+    target
+        .predefined_macros()
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), Some(value)))
+        .collect()
+}
+
+/// The tokens of the text `text` of a condition, all at `span`: the text
+/// preprocessor only knows which line a condition is on.
+fn condition_tokens(text: &str, span: Span) -> Vec<Token> {
+    use lexer::TokenKind::*;
+
+    let mut lexer = lexer::TokenKind::lexer(text);
+    let mut tokens = Vec::new();
+    while let Some(kind) = lexer.next() {
+        match kind.unwrap_or(UNKNOWN) {
+            WHITESPACE | NEWLINE | COMMENT => {}
+            kind => tokens.push(Token::new(kind, lexer.slice(), span)),
+        }
+    }
+    tokens
+}
+
+#[derive(Default)]
 pub struct TextSink {
     transformed_text: String,
     errors:           Vec<Diagnostic<FileId>>,
@@ -67,7 +378,7 @@ pub struct TextSink {
 
 impl TextSink {
     pub fn new() -> Self {
-        Self { transformed_text: String::new(), errors: Vec::new() }
+        Self::default()
     }
 
     pub fn num_errors(&self) -> usize {
@@ -93,6 +404,7 @@ fn preprocess_file(
     input_path: &str,
     output_path: &str,
     include_directories: &[String],
+    target: &Target,
 ) -> io::Result<()> {
     let input_file = File::open(input_path)?;
     let output_file = File::create(output_path)?;
@@ -106,10 +418,9 @@ fn preprocess_file(
     let text = remove_leading_and_trailing_whitespace(text, input_path);
 
     // Perform global transformations on text (https://www.math.utah.edu/docs/info/cpp_1.html#SEC2)
-    let text = global_transform(&text, Path::new(input_path));
+    let text = global_transform(&text, Path::new(input_path), target);
 
     for line in text.lines() {
-        let line = line;
         let processed_line = process_line(line, include_directories);
         writer.write_all(processed_line.as_bytes())?;
         writer.write_all(b"\n")?;
@@ -147,7 +458,7 @@ fn remove_leading_and_trailing_whitespace(text: String, input_path: &str) -> Str
 /// meaning. Predefined macro names are replaced with their expansions (see
 /// section Predefined Macros). The first two transformations are done before
 /// nearly all other parsing and before preprocessing directives are recognized.
-fn global_transform(text: &str, input_path: &Path) -> String {
+fn global_transform(text: &str, input_path: &Path, target: &Target) -> String {
     // Global transform: Remove comments.
     let text = remove_comments(text);
 
@@ -155,7 +466,7 @@ fn global_transform(text: &str, input_path: &Path) -> String {
     let text = remove_backslash_newline(&text);
 
     // Replace predefined macro names with their expansions.
-    let text = replace_predefined_macros(&text, input_path);
+    let text = replace_predefined_macros(&text, input_path, target);
 
     // TODO: Replace non-standard macros with their expansions.
     // https://www.math.utah.edu/docs/info/cpp_1.html#SEC15
@@ -503,19 +814,6 @@ pub enum TokenKind {
     EOF,
 }
 
-fn line_number(span: Span, input: &str) -> usize {
-    let mut line = 1;
-    for (i, c) in input.char_indices() {
-        if i >= span.start {
-            break;
-        }
-        if c == '\n' {
-            line += 1;
-        }
-    }
-    line
-}
-
 fn current_date() -> String {
     let current_date = Local::now();
     let month = current_date.format("%b").to_string();
@@ -534,37 +832,6 @@ fn current_time() -> String {
     format!("{hour}:{minute}:{second}")
 }
 
-fn create_temp_c_file(c_code: &str) -> io::Result<()> {
-    let mut file = File::create("temp.c")?;
-    file.write_all(c_code.as_bytes())?;
-    Ok(())
-}
-
-fn preprocess_stdc_version_with_clang() -> io::Result<String> {
-    create_temp_c_file("__STDC_VERSION__")?;
-
-    let output = Command::new("clang").arg("-E").arg("temp.c").arg("-o").arg("temp.out").output();
-
-    let output = match output {
-        Ok(output) => output,
-        Err(e) => {
-            println!("Error: {e}");
-            return Err(e);
-        }
-    };
-
-    // Remove any lines that start with a hash sign
-    let output = std::fs::read_to_string("temp.out")?;
-    let output =
-        output.lines().filter(|line| !line.starts_with('#')).collect::<Vec<_>>().join("\n");
-
-    // Cleanup
-    std::fs::remove_file("temp.c")?;
-    std::fs::remove_file("temp.out")?;
-
-    Ok(output)
-}
-
 // Define a struct to hold the macro and its value.
 #[derive(Debug)]
 pub struct MacroValue {
@@ -582,112 +849,6 @@ impl MacroValue {
     }
 }
 
-// Preprocess C code and retrieve macro values.
-pub fn get_macro_values(macros: &[&str]) -> io::Result<Vec<MacroValue>> {
-    // Create C code which is simply a line for each macro.
-    // e.g.
-    // __STDC_VERSION__
-    // __GNUC__
-    // __GNUC_MINOR__
-    let mut c_code = String::new();
-    for macro_name in macros {
-        c_code.push_str(macro_name);
-        c_code.push('\n');
-    }
-
-    // Try to use Clang for preprocessing.
-    let clang_result = preprocess_with_compiler("clang", &c_code, macros);
-
-    match clang_result {
-        Ok(result) => Ok(result),
-        Err(_) => {
-            // If Clang is not found, try GCC.
-            let gcc_result = preprocess_with_compiler("gcc", &c_code, macros);
-
-            match gcc_result {
-                Ok(result) => Ok(result),
-                Err(_) => {
-                    // If neither Clang nor GCC is found, use default values.
-                    // let mut default_values = Vec::new();
-                    // for macro_name in macros {
-                    //     default_values.push(MacroValue::new(macro_name, "default_value"));
-                    // }
-                    Ok(Vec::new())
-                }
-            }
-        }
-    }
-}
-
-// Helper function to preprocess C code using a given compiler.
-fn preprocess_with_compiler(
-    compiler: &str,
-    c_code: &str,
-    macros: &[&str],
-) -> io::Result<Vec<MacroValue>> {
-    // Create a temporary C file.
-    create_temp_c_file(c_code)?;
-
-    // Build the command to preprocess the file.
-    let mut cmd = Command::new(compiler);
-    cmd.arg("-E").arg("temp.c").arg("-o").arg("temp.out");
-
-    // Execute the command.
-    let output = cmd.output();
-    let out = std::fs::read_to_string("temp.out")?;
-
-    // Remove any lines starting with a hash sign
-    let compiler_out = out.lines().filter(|line| !line.starts_with('#')).collect::<Vec<&str>>();
-
-    // Parse the macro values from the preprocessed output.
-    let mut macro_values = Vec::new();
-    for (out_idx, macro_name) in macros.iter().enumerate() {
-        if let Some(value) = extract_macro_value(compiler_out[out_idx], macro_name) {
-            macro_values.push(MacroValue::new(macro_name, &value));
-
-            // println!("{macro_name} = {value}");
-        }
-    }
-
-    // Cleanup
-    std::fs::remove_file("temp.c")?;
-    std::fs::remove_file("temp.out")?;
-
-    Ok(macro_values)
-}
-
-// Helper function to extract the value of a macro from preprocessed output.
-fn extract_macro_value(output: &str, macro_name: &str) -> Option<String> {
-    for line in output.lines() {
-        if line.starts_with("#define") && line.contains(macro_name) {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 3 {
-                return Some(parts[2].to_string());
-            }
-        }
-    }
-
-    // Predefined macros are not defined by the preprocessor, so we need to
-    // extract their values from the compiler.
-    match macro_name {
-        __GNUC__ | __GNUC_MINOR__ | __STDC_VERSION__ => Some(output.to_string()),
-        _ => None,
-    }
-}
-
-// This macro expands to the C Standard's version number, a long integer
-// constant of the form `yyyymmL' where yyyy and mm are the year and month of
-// the Standard version. This signifies which version of the C Standard the
-// preprocessor conforms to. Like `__STDC__', whether this version number is
-// accurate for the entire implementation depends on what C compiler will
-// operate on the output from the preprocessor.
-fn current_stdc_version() -> Option<String> {
-    match preprocess_stdc_version_with_clang() {
-        Ok(output) => Some(output),
-        _ => None,
-    }
-}
-
 const __FILE__: &str = "__FILE__";
 const __LINE__: &str = "__LINE__";
 const __DATE__: &str = "__DATE__";
@@ -704,7 +865,7 @@ const DEFAULT_STDC_VERSION: &str = "201710L";
 const DEFAULT_GNUC: &str = "4";
 const DEFAULT_GNUC_MINOR: &str = "2";
 
-fn replace_predefined_macros(input: &str, input_path: &Path) -> String {
+fn replace_predefined_macros(input: &str, input_path: &Path, target: &Target) -> String {
     let mut include_level = 0; // Initialize the include level counter
 
     tracing::debug!(
@@ -720,17 +881,15 @@ fn replace_predefined_macros(input: &str, input_path: &Path) -> String {
     );
     let mut output = String::new();
     let mut lexer = TokenKind::lexer(input);
+    let line_index = LineIndex::new(input);
 
-    // Extract the values of certain predefined macros from
-    // other host C compilers. This is meant for both testing purposes
-    // as well as to drive the implementation of the preprocessor over
-    // certain non-standard features, or non-trivial features to implement.
-    let macros = vec![__GNUC__, __GNUC_MINOR__, __STDC_VERSION__];
-
-    let macro_values = match get_macro_values(&macros) {
-        Ok(values) => values,
-        Err(_) => Vec::new(),
-    };
+    // The macros predefined on the target. Those which aren't, such as
+    // `__STDC_VERSION__`, have the default values below.
+    let macro_values = target
+        .predefined_macros()
+        .into_iter()
+        .map(|(name, value)| MacroValue::new(name, &value))
+        .collect::<Vec<_>>();
 
     while let Some(token_result) = lexer.next() {
         match token_result {
@@ -785,7 +944,7 @@ fn replace_predefined_macros(input: &str, input_path: &Path) -> String {
                 // `__LINE__' are altered if a `#line' directive is used. See section Combining
                 // Source Files.
 
-                let line_number = line_number(lexer.span(), input);
+                let line_number = line_index.line_col(lexer.span().start).line + 1;
 
                 tracing::debug!(
                     "{}",
@@ -1031,7 +1190,7 @@ fn replace_predefined_macros(input: &str, input_path: &Path) -> String {
     output
 }
 
-fn process_line(line: &str, include_directories: &[String]) -> String {
+fn process_line(line: &str, _include_directories: &[String]) -> String {
     // Ignore empty lines
     if line.trim().is_empty() {
         return line.to_string();
@@ -1145,10 +1304,6 @@ fn parse_include_directive(line: &str) -> String {
         );
 
         return include_location[1..include_location.len() - 1].to_string();
-    } else {
-        // Invalid include
-        println!("Invalid include 1: {line}");
-        return format!("/* Invalid include: {line} */");
     }
 
     // println!("{:#?}", parts.nth(1));
@@ -1292,6 +1447,7 @@ fn parse_include_directive(line: &str) -> String {
 //     }
 // }
 
+#[allow(dead_code)]
 fn read_included_file(filename: &str, include_directories: &[String]) -> Result<String, String> {
     // Iterate through each include directory to find the file
     for include_dir in include_directories {
@@ -1316,7 +1472,7 @@ fn read_included_file(filename: &str, include_directories: &[String]) -> Result<
             Ok(mut file) => {
                 // Read the content of the file into a string
                 let mut content = String::new();
-                if let Err(_) = file.read_to_string(&mut content) {
+                if file.read_to_string(&mut content).is_err() {
                     return Err(format!("Failed to read file: {}", full_path));
                 }
                 return Ok(content);
@@ -1332,6 +1488,7 @@ fn read_included_file(filename: &str, include_directories: &[String]) -> Result<
     Err(format!("File not found: {}", filename))
 }
 
+#[allow(dead_code)]
 fn process_code(code: &str, include_directories: &[String]) -> String {
     // Split the code into lines and process each line
     let lines: Vec<&str> = code.lines().collect();
@@ -1395,6 +1552,7 @@ fn preprocess_files_recursively(root_dir: &str) -> io::Result<()> {
                         input_path.to_str().unwrap(),
                         output_path.to_str().unwrap(),
                         &include_directories,
+                        &Target::default(),
                     )?;
 
                     let elapsed = start.elapsed();
@@ -1422,3 +1580,107 @@ pub fn main() {
         println!("Preprocessing completed successfully!");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Preprocesses `text` as C23, which has all the directives.
+    fn preprocess(text: &str) -> TextSink {
+        let mut preprocessor = Preprocessor::new(0, text.to_string()).with_standard(Standard::C23);
+        preprocessor.preprocess().unwrap();
+        preprocessor.into_output()
+    }
+
+    #[test]
+    fn conditional_inclusion() {
+        let output = preprocess(
+            "#define B\n#ifdef A\na\n#elifdef B\nb\n#ifndef B\nnested\n#endif\n#else\nc\n#endif",
+        );
+
+        assert_eq!(output.num_errors(), 0);
+        assert_eq!(output.into_transformed_text(), "#define B\n\n\n\nb\n\n\n\n\n\n\n");
+    }
+
+    #[test]
+    fn undef_and_defined() {
+        let output = preprocess("#define A\n#undef A\n#if !defined(A)\nx\n#elifndef A\ny\n#endif");
+
+        assert_eq!(output.num_errors(), 0);
+        assert_eq!(output.into_transformed_text(), "#define A\n#undef A\n\nx\n\n\n\n");
+    }
+
+    #[test]
+    fn integer_constant_expressions() {
+        let output = preprocess(
+            "#define N 4\n#define M (N * 2)\n#if M == 8 && N % 3\na\n#elif 1\nb\n#endif",
+        );
+
+        assert_eq!(output.num_errors(), 0);
+        assert_eq!(output.into_transformed_text(), "#define N 4\n#define M (N * 2)\n\na\n\n\n\n");
+    }
+
+    #[test]
+    fn invalid_conditions() {
+        let output = preprocess("#define F(x) x\n#if F(1)\na\n#elif 1 +\nb\n#else\nc\n#endif");
+
+        assert_eq!(output.num_errors(), 2);
+        assert_eq!(output.into_transformed_text(), "#define F(x) x\n\n\n\n\n\nc\n\n");
+    }
+
+    #[test]
+    fn predefined_macros() {
+        let text = "#ifdef __x86_64__\nx86_64\n#elifdef __aarch64__\naarch64\n#endif";
+        let output = preprocess(text);
+        assert_eq!(output.into_transformed_text(), "\nx86_64\n\n\n\n");
+
+        let target = "aarch64-linux-gnu".parse().unwrap();
+        let mut preprocessor = Preprocessor::new(0, text.to_string())
+            .with_standard(Standard::C23)
+            .with_target(&target);
+        preprocessor.preprocess().unwrap();
+        assert_eq!(preprocessor.into_output().into_transformed_text(), "\n\n\naarch64\n\n");
+    }
+
+    #[test]
+    fn c23_directives_before_c23() {
+        let text = "#ifdef A\na\n#elifndef A\nb\n#endif";
+        let mut preprocessor = Preprocessor::new(0, text.to_string());
+        preprocessor.preprocess().unwrap();
+        let output = preprocessor.into_output();
+
+        assert_eq!(output.num_errors(), 1);
+        assert_eq!(output.into_transformed_text(), "\n\n\nb\n\n");
+    }
+
+    #[test]
+    fn unbalanced_conditionals() {
+        assert_eq!(preprocess("#endif").num_errors(), 1);
+        assert_eq!(preprocess("#ifdef A\n#else\n#else").num_errors(), 2);
+    }
+
+    #[test]
+    fn embed_with_limit() {
+        let path = format!("{}/../../testdata/preprocessor/ifdef/in.c", env!("CARGO_MANIFEST_DIR"));
+        let output = preprocess(&format!("char s[] = {{\n#embed \"{path}\" limit(4)\n}};"));
+
+        assert_eq!(output.num_errors(), 0);
+        assert_eq!(output.into_transformed_text(), "char s[] = {\n35, 100, 101, 102\n};\n");
+    }
+
+    #[test]
+    fn embed_from_overlay() {
+        let mut sources = SourceManager::new();
+        sources.set_overlay("/virtual/data.bin", [1, 2, 3]);
+        let text = "#embed \"data.bin\"\n#embed <data.bin>";
+        let file_id = sources.add_path("/virtual/main.c", text);
+
+        let mut preprocessor = Preprocessor::with_sources(file_id, text.to_string(), &sources)
+            .with_standard(Standard::C23);
+        preprocessor.preprocess().unwrap();
+        let output = preprocessor.into_output();
+
+        assert_eq!(output.num_errors(), 1);
+        assert_eq!(output.into_transformed_text(), "1, 2, 3\n\n");
+    }
+}
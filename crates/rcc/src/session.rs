@@ -0,0 +1,263 @@
+//! The embedding API of `rcc`.
+//!
+//! A [`Session`] owns the sources being compiled and the diagnostics they
+//! produce, and runs the phases of the compiler on demand:
+//!
+//! ```no_run
+//! use rcc::Session;
+//!
+//! let mut session = Session::builder().build();
+//! let file_id = session.add_source("main.c", "int main(void) { return 0; }");
+//!
+//! let tokens = session.lex(file_id);
+//! let cst = session.parse(file_id);
+//! let ast = session.lower(file_id);
+//!
+//! session.flush_diagnostics();
+//! ```
+
+use crate::{
+    ast::{
+        self,
+        TranslationUnit,
+    },
+    charset::{
+        Charset,
+        UTF_8,
+    },
+    cst::{
+        Tree,
+        TreeKind,
+    },
+    diagnostics::{
+        self,
+        stderr_color_choice,
+        ColorChoice,
+        DiagnosticFormat,
+        DiagnosticsEngine,
+        FileId,
+        StandardStream,
+        WarningOptions,
+        WriteColor,
+        DEFAULT_ERROR_LIMIT,
+        DEFAULT_MACRO_BACKTRACE_LIMIT,
+    },
+    lexer::{
+        self,
+        TokenStream,
+    },
+    line_index::LineIndex,
+    parser,
+    source_manager::SourceManager,
+    standard::Standard,
+    target::Target,
+};
+use anyhow::Result;
+use getset::{
+    Getters,
+    MutGetters,
+};
+use std::{
+    fmt,
+    path::Path,
+};
+use typed_builder::TypedBuilder;
+
+/// Options controlling a [`Session`].
+#[derive(Debug, Clone, Getters, TypedBuilder)]
+#[getset(get = "pub")]
+pub struct Options {
+    /// Whether diagnostics written to the default sink (stderr) are colored.
+    /// With [`ColorChoice::Auto`], they are only colored if stderr is a
+    /// terminal and `NO_COLOR` isn't set.
+    #[builder(default = ColorChoice::Auto)]
+    color:             ColorChoice,
+    /// The dialect of C sources are parsed as.
+    #[builder(default)]
+    standard:          Standard,
+    /// What sources are compiled for, as set by `--target=`.
+    #[builder(default)]
+    target:            Target,
+    /// Whether trigraphs such as `??=` are replaced before sources are
+    /// lexed, as set by `-trigraphs`. They never are with C23.
+    #[builder(default)]
+    trigraphs:         bool,
+    /// The charset of sources without a byte order mark, as set by
+    /// `-finput-charset=`.
+    #[builder(default = UTF_8)]
+    input_charset:     &'static Charset,
+    /// How diagnostics are rendered by [`Session::flush_diagnostics`].
+    #[builder(default)]
+    diagnostic_format: DiagnosticFormat,
+    /// Which warnings are reported, and which are promoted to errors, as
+    /// set by `-W` flags.
+    #[builder(default)]
+    warnings:          WarningOptions,
+    /// How many errors are reported before the rest are dropped, or `0` for
+    /// no limit, as set by `-ferror-limit=N`.
+    #[builder(default = DEFAULT_ERROR_LIMIT)]
+    error_limit:       usize,
+    /// How many macro expansions a diagnostic shows the backtrace of, or
+    /// `0` for no limit, as set by `-fmacro-backtrace-limit=N`.
+    #[builder(default = DEFAULT_MACRO_BACKTRACE_LIMIT)]
+    macro_backtrace_limit: usize,
+    /// Whether the `gcc` diagnostic format shows source excerpts, as set by
+    /// `-fdiagnostics-show-caret`.
+    #[builder(default)]
+    show_caret:        bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// A single compilation: a set of sources, the options they are compiled
+/// with, and the diagnostics produced along the way.
+#[derive(Getters, MutGetters, TypedBuilder)]
+pub struct Session {
+    #[builder(default)]
+    #[getset(get = "pub")]
+    options:     Options,
+    #[builder(
+        default = DiagnosticsEngine::with_warnings(options.warnings.clone())
+            .with_error_limit(options.error_limit)
+            .with_macro_backtrace_limit(options.macro_backtrace_limit)
+            .with_caret(options.show_caret),
+        setter(skip)
+    )]
+    #[getset(get = "pub", get_mut = "pub")]
+    diagnostics: DiagnosticsEngine,
+    /// Where rendered diagnostics are written by
+    /// [`Session::flush_diagnostics`]. Defaults to stderr.
+    #[builder(
+        default,
+        setter(transform = |sink: impl WriteColor + 'static| Some(Box::new(sink) as Box<dyn WriteColor>))
+    )]
+    sink:        Option<Box<dyn WriteColor>>,
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Session")
+            .field("options", &self.options)
+            .field("diagnostics", &self.diagnostics)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Session {
+    /// Adds an in-memory source to the session.
+    pub fn add_source(&mut self, name: impl Into<String>, text: impl Into<String>) -> FileId {
+        self.diagnostics.add_file(name, text)
+    }
+
+    /// Reads `path` and adds it to the session, decoding it from the input
+    /// charset unless it starts with a byte order mark.
+    ///
+    /// The file is read through the session's [`SourceManager`], so an
+    /// overlay of `path` is read instead of the file on disk, and a file
+    /// which has already been added, by this path or any other, keeps its
+    /// [`FileId`].
+    pub fn add_file(&mut self, path: impl AsRef<Path>) -> Result<FileId> {
+        Ok(self.diagnostics.load_file(path, self.options.input_charset)?)
+    }
+
+    /// Adds an in-memory source to the session, decoding it as
+    /// [`Session::add_file`] does.
+    pub fn add_bytes(&mut self, name: impl Into<String>, bytes: &[u8]) -> FileId {
+        self.diagnostics.add_bytes(name, bytes, self.options.input_charset)
+    }
+
+    /// Lexes the source `file_id` into a [`TokenStream`].
+    pub fn lex(&mut self, file_id: FileId) -> TokenStream {
+        let text = self.source(file_id);
+        lexer::lex_with_diagnostics(
+            &text,
+            self.options.standard,
+            &self.options.target,
+            self.options.trigraphs,
+            &mut self.diagnostics,
+            file_id,
+        )
+        .tokens
+    }
+
+    /// Parses the source `file_id` into a concrete syntax [`Tree`].
+    pub fn parse(&mut self, file_id: FileId) -> Tree {
+        let tokens = self.lex(file_id);
+        parser::parse_tokens_with_diagnostics(
+            tokens,
+            TreeKind::TranslationUnit,
+            self.options.standard,
+            &mut self.diagnostics,
+            file_id,
+        )
+    }
+
+    /// Parses the source `file_id` and lowers it to a [`TranslationUnit`].
+    pub fn lower(&mut self, file_id: FileId) -> TranslationUnit {
+        let mut cst = self.parse(file_id);
+        let mut ast_sink = ast::reduce_with_diagnostics(&mut cst, &mut self.diagnostics);
+        ast_sink.drain_errors(&mut self.diagnostics);
+
+        ast_sink.translation_unit
+    }
+
+    /// Lowers the source `file_id` and applies the machine-applicable
+    /// suggestions of the diagnostics produced along the way, returning the
+    /// fixed source and how many suggestions were applied. The source of the
+    /// session itself is left as is, and the diagnostics remain pending.
+    pub fn fix(&mut self, file_id: FileId) -> (String, usize) {
+        self.lower(file_id);
+
+        let text = self.source(file_id);
+        diagnostics::apply_suggestions(
+            &text,
+            self.diagnostics.machine_applicable_suggestions(file_id),
+        )
+    }
+
+    /// The files of the session, and where they were read from.
+    pub fn source_manager(&self) -> &SourceManager {
+        &self.diagnostics.files
+    }
+
+    /// The files of the session, e.g. to overlay a file with the unsaved
+    /// contents of an editor before adding it.
+    pub fn source_manager_mut(&mut self) -> &mut SourceManager {
+        &mut self.diagnostics.files
+    }
+
+    /// The [`LineIndex`] of the source `file_id`, for finding the line and
+    /// column of a token or a syntax tree node from its span.
+    pub fn line_index(&self, file_id: FileId) -> &LineIndex {
+        let file = self.diagnostics.files.get(file_id).expect("Failed to get file from db");
+        file.line_index()
+    }
+
+    /// Whether any error has been reported so far.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.has_errors()
+    }
+
+    /// Renders all pending diagnostics to the session's sink, in the
+    /// session's [`DiagnosticFormat`].
+    pub fn flush_diagnostics(&mut self) {
+        match &mut self.sink {
+            Some(sink) => {
+                self.diagnostics.flush_as(self.options.diagnostic_format, sink.as_mut());
+            }
+            None => {
+                let mut writer = StandardStream::stderr(stderr_color_choice(self.options.color));
+                self.diagnostics.flush_as(self.options.diagnostic_format, &mut writer);
+            }
+        }
+    }
+
+    fn source(&self, file_id: FileId) -> String {
+        let file = self.diagnostics.files.get(file_id).expect("Failed to get file from db");
+        file.source().to_string()
+    }
+}
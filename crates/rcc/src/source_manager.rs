@@ -0,0 +1,292 @@
+//! Reading source files.
+//!
+//! Every file a compilation reads goes through a [`SourceManager`] rather
+//! than straight to disk, so that:
+//!
+//! - a file can be overlaid with contents that aren't on disk, such as an
+//!   editor's unsaved buffer or a header made up by a test,
+//! - a file reached by several paths, e.g. `a.h`, `./a.h` or a symlink to
+//!   it, is loaded once, under its canonical path, and
+//! - the names in `#include` and `#embed` directives are resolved the same
+//!   way by whatever processes them.
+//!
+//! The manager also owns the loaded files themselves, as [`SourceFiles`],
+//! which is what diagnostics are rendered from, and a record of every macro
+//! expansion, which tokens produced by one point back to with an
+//! [`ExpansionId`].
+
+use crate::{
+    diagnostics::{
+        FileId,
+        SourceFiles,
+    },
+    lexer::Span,
+    symbol::Symbol,
+};
+use getset::{
+    Getters,
+    MutGetters,
+};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    env,
+    fs,
+    io,
+    ops::Deref,
+    path::{
+        Component,
+        Path,
+        PathBuf,
+    },
+};
+
+/// A macro expansion: which macro was expanded, and where.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Expansion {
+    pub name:       Symbol,
+    pub file_id:    FileId,
+    /// The name of the macro in its `#define`.
+    pub definition: Span,
+    /// The invocation of the macro, e.g. `MAX(a, b)`.
+    pub invocation: Span,
+    /// The expansion the invocation itself was produced by, if any.
+    pub parent:     Option<ExpansionId>,
+}
+
+/// An [`Expansion`] recorded by a [`SourceManager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ExpansionId(u32);
+
+/// The files of a compilation, and where they were read from.
+#[derive(Debug, Clone, Getters, MutGetters)]
+pub struct SourceManager {
+    files:        SourceFiles,
+    /// Every macro expansion, by [`ExpansionId`].
+    expansions:   Vec<Expansion>,
+    /// The canonical path each file was read from, if it was read from one.
+    paths:        HashMap<FileId, PathBuf>,
+    /// The file read from each canonical path.
+    loaded:       HashMap<PathBuf, FileId>,
+    /// The contents overlaid on the file at each canonical path.
+    overlays:     HashMap<PathBuf, Vec<u8>>,
+    /// Where `<name>` is looked up, and `"name"` if it isn't found next to
+    /// the file naming it.
+    #[getset(get = "pub", get_mut = "pub")]
+    include_dirs: Vec<PathBuf>,
+}
+
+impl Default for SourceManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SourceManager {
+    pub fn new() -> Self {
+        Self {
+            files:        SourceFiles::new(),
+            expansions:   Vec::new(),
+            paths:        HashMap::new(),
+            loaded:       HashMap::new(),
+            overlays:     HashMap::new(),
+            include_dirs: vec![PathBuf::from("/usr/include"), PathBuf::from("/usr/local/include")],
+        }
+    }
+
+    /// Sets where `<name>` is looked up, in order.
+    pub fn with_include_dirs(mut self, include_dirs: Vec<PathBuf>) -> Self {
+        self.include_dirs = include_dirs;
+        self
+    }
+
+    /// The loaded files.
+    pub fn files(&self) -> &SourceFiles {
+        &self.files
+    }
+
+    /// Adds a file which wasn't read from a path, such as a string handed to
+    /// [`Session::add_source`](crate::Session::add_source).
+    pub fn add(&mut self, name: impl Into<String>, source: impl Into<String>) -> FileId {
+        self.files.add(name, source)
+    }
+
+    /// Adds the file read from `path`, named as `path` is spelled. If the
+    /// file has already been loaded, by this path or any other, it is left
+    /// as it is, and its id is returned.
+    pub fn add_path(&mut self, path: impl AsRef<Path>, source: impl Into<String>) -> FileId {
+        let path = path.as_ref();
+        let canonical = self.canonicalize(path);
+        if let Some(&file_id) = self.loaded.get(&canonical) {
+            return file_id;
+        }
+
+        let file_id = self.files.add(path.display().to_string(), source);
+        self.paths.insert(file_id, canonical.clone());
+        self.loaded.insert(canonical, file_id);
+        file_id
+    }
+
+    /// The file loaded from `path`, if any.
+    pub fn file_id(&self, path: impl AsRef<Path>) -> Option<FileId> {
+        self.loaded.get(&self.canonicalize(path.as_ref())).copied()
+    }
+
+    /// The canonical path the file `file_id` was read from, if it was read
+    /// from one.
+    pub fn path(&self, file_id: FileId) -> Option<&Path> {
+        self.paths.get(&file_id).map(PathBuf::as_path)
+    }
+
+    /// Overlays `contents` on the file at `path`, which needn't exist on
+    /// disk. Only files loaded afterwards see the overlay; a file already
+    /// loaded keeps the contents it was loaded with.
+    pub fn set_overlay(&mut self, path: impl AsRef<Path>, contents: impl Into<Vec<u8>>) {
+        self.overlays.insert(self.canonicalize(path.as_ref()), contents.into());
+    }
+
+    /// Removes the overlay of the file at `path`, returning its contents.
+    pub fn remove_overlay(&mut self, path: impl AsRef<Path>) -> Option<Vec<u8>> {
+        self.overlays.remove(&self.canonicalize(path.as_ref()))
+    }
+
+    /// Whether there is a file at `path`, overlaid or on disk.
+    pub fn exists(&self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+        self.overlays.contains_key(&self.canonicalize(path)) || path.is_file()
+    }
+
+    /// The bytes of the file at `path`: its overlay, if it has one, or else
+    /// what is on disk.
+    pub fn read(&self, path: impl AsRef<Path>) -> io::Result<Cow<'_, [u8]>> {
+        let path = path.as_ref();
+        match self.overlays.get(&self.canonicalize(path)) {
+            Some(contents) => Ok(Cow::Borrowed(contents)),
+            None => fs::read(path).map(Cow::Owned),
+        }
+    }
+
+    /// Finds the file named `name` by an `#include` or `#embed` in the file
+    /// `includer`, returning its canonical path.
+    ///
+    /// A `quoted` name, i.e. `"name"`, is first looked up in the directory
+    /// of the file naming it, or in the current directory if that file
+    /// wasn't read from a path. Then, as for `<name>`, it is looked up in
+    /// each of the include directories.
+    pub fn resolve(&self, name: &str, quoted: bool, includer: Option<FileId>) -> Option<PathBuf> {
+        let local = quoted.then(|| {
+            match includer.and_then(|file_id| self.path(file_id)).and_then(Path::parent) {
+                Some(dir) => dir.join(name),
+                None => PathBuf::from(name),
+            }
+        });
+
+        local
+            .into_iter()
+            .chain(self.include_dirs.iter().map(|dir| dir.join(name)))
+            .find(|path| self.exists(path))
+            .map(|path| self.canonicalize(&path))
+    }
+
+    /// Records an expansion, returning its id.
+    pub fn add_expansion(&mut self, expansion: Expansion) -> ExpansionId {
+        self.expansions.push(expansion);
+        ExpansionId((self.expansions.len() - 1) as u32)
+    }
+
+    pub fn expansion(&self, id: ExpansionId) -> &Expansion {
+        &self.expansions[id.0 as usize]
+    }
+
+    /// The expansion `id` and those it was produced by, innermost first.
+    pub fn backtrace(&self, id: ExpansionId) -> impl Iterator<Item = &Expansion> {
+        std::iter::successors(Some(self.expansion(id)), |expansion| {
+            expansion.parent.map(|parent| self.expansion(parent))
+        })
+    }
+
+    /// The canonical form of `path`, with symlinks resolved. A path which
+    /// doesn't exist on disk, e.g. that of an overlay, is only made absolute
+    /// and rid of its `.` and `..` components.
+    pub fn canonicalize(&self, path: &Path) -> PathBuf {
+        fs::canonicalize(path).unwrap_or_else(|_| {
+            let path = match env::current_dir() {
+                Ok(dir) => dir.join(path),
+                Err(_) => path.to_path_buf(),
+            };
+
+            let mut normalized = PathBuf::new();
+            for component in path.components() {
+                match component {
+                    Component::CurDir => {}
+                    Component::ParentDir => {
+                        normalized.pop();
+                    }
+                    component => normalized.push(component),
+                }
+            }
+            normalized
+        })
+    }
+}
+
+impl Deref for SourceManager {
+    type Target = SourceFiles;
+
+    fn deref(&self) -> &Self::Target {
+        &self.files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlays_shadow_the_disk() {
+        let mut sources = SourceManager::new();
+        let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+        assert!(sources.read(&manifest).unwrap().starts_with(b"[package]"));
+
+        sources.set_overlay(&manifest, "overlaid");
+        sources.set_overlay("/virtual/../virtual/./a.h", "int a;");
+        assert_eq!(sources.read(&manifest).unwrap(), &b"overlaid"[..]);
+        assert_eq!(sources.read("/virtual/a.h").unwrap(), &b"int a;"[..]);
+        assert!(sources.exists("/virtual/a.h"));
+
+        assert_eq!(sources.remove_overlay("/virtual/a.h").as_deref(), Some(&b"int a;"[..]));
+        assert!(!sources.exists("/virtual/a.h"));
+        assert!(sources.read("/virtual/a.h").is_err());
+    }
+
+    #[test]
+    fn files_are_loaded_once() {
+        let mut sources = SourceManager::new();
+        let a = sources.add_path("/virtual/a.h", "int a;");
+        assert_eq!(sources.add_path("/virtual/dir/../a.h", "int b;"), a);
+        assert_eq!(sources.file_id("/virtual/./a.h"), Some(a));
+        assert_eq!(sources.path(a), Some(Path::new("/virtual/a.h")));
+        assert_eq!(sources.get(a).unwrap().source(), "int a;");
+
+        let unnamed = sources.add("a.h", "int c;");
+        assert_ne!(unnamed, a);
+        assert_eq!(sources.path(unnamed), None);
+    }
+
+    #[test]
+    fn resolves_includes() {
+        let mut sources =
+            SourceManager::new().with_include_dirs(vec![PathBuf::from("/virtual/include")]);
+        sources.set_overlay("/virtual/src/local.h", "");
+        sources.set_overlay("/virtual/include/local.h", "");
+        sources.set_overlay("/virtual/include/system.h", "");
+        let main = sources.add_path("/virtual/src/main.c", "");
+
+        let resolve = |name, quoted, includer| sources.resolve(name, quoted, includer);
+        assert_eq!(resolve("local.h", true, Some(main)), Some("/virtual/src/local.h".into()));
+        assert_eq!(resolve("local.h", false, Some(main)), Some("/virtual/include/local.h".into()));
+        assert_eq!(resolve("system.h", true, Some(main)), Some("/virtual/include/system.h".into()));
+        assert_eq!(resolve("missing.h", true, Some(main)), None);
+        assert_eq!(resolve("../src/local.h", false, None), Some("/virtual/src/local.h".into()));
+    }
+}
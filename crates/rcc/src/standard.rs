@@ -0,0 +1,39 @@
+//! The dialects of C accepted by `rcc`, as selected with `--std=`.
+
+use strum_macros::{
+    Display,
+    EnumString,
+};
+
+#[derive(Debug, Default, Display, EnumString, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Standard {
+    /// ISO C11.
+    #[default]
+    #[strum(serialize = "c11")]
+    C11,
+    /// C11 with the GNU extensions found throughout system headers:
+    /// `__attribute__`, `__asm__`, `__extension__`, `typeof`, statement
+    /// expressions and the `__restrict`/`__inline` family of alternate
+    /// keywords.
+    #[strum(serialize = "gnu11")]
+    Gnu11,
+    /// ISO C23: `bool`, `true`, `false`, `nullptr`, `static_assert` and
+    /// friends as keywords, `[[attribute]]` syntax, `constexpr` objects,
+    /// binary literals, digit separators and empty initializers.
+    #[strum(serialize = "c23")]
+    C23,
+}
+
+impl Standard {
+    /// Whether GNU extensions are enabled. Without them, GNU keywords such as
+    /// `__attribute__` are lexed as plain identifiers.
+    pub fn gnu_extensions(self) -> bool {
+        matches!(self, Standard::Gnu11)
+    }
+
+    /// Whether C23 features are enabled. Before C23, keywords it introduced
+    /// such as `bool` or `nullptr` are lexed as plain identifiers.
+    pub fn c23(self) -> bool {
+        matches!(self, Standard::C23)
+    }
+}
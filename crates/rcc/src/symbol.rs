@@ -0,0 +1,194 @@
+//! Interned strings.
+//!
+//! The spelling of every token, and every name in the AST, is a [`Symbol`]:
+//! a `Copy` handle into a global table of strings. Each distinct string is
+//! stored once, for the rest of the process, so tokens and names are cheap
+//! to copy and compare.
+//!
+//! The table is shared by all threads behind a lock, but each thread keeps a
+//! cache of it: the table only ever grows, so a string once looked up never
+//! changes, and only a string new to the thread takes the lock.
+
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    sync::Mutex,
+};
+
+static INTERNER: Mutex<Option<Interner>> = Mutex::new(None);
+
+thread_local! {
+    /// The part of the table this thread has seen so far.
+    static CACHE: RefCell<Interner> = RefCell::new(Interner::default());
+}
+
+#[derive(Debug, Default)]
+struct Interner {
+    symbols: HashMap<&'static str, Symbol>,
+    strings: Vec<&'static str>,
+}
+
+impl Interner {
+    /// An interner holding only the empty string, as [`Symbol::default`].
+    fn new() -> Self {
+        Self { symbols: HashMap::from([("", Symbol(0))]), strings: vec![""] }
+    }
+
+    fn intern(&mut self, string: &str) -> Symbol {
+        if let Some(&symbol) = self.symbols.get(string) {
+            return symbol;
+        }
+
+        let string: &'static str = Box::leak(string.into());
+        let symbol = Symbol(u32::try_from(self.strings.len()).expect("too many symbols"));
+        self.strings.push(string);
+        self.symbols.insert(string, symbol);
+        symbol
+    }
+}
+
+fn with_interner<T>(f: impl FnOnce(&mut Interner) -> T) -> T {
+    let mut interner = INTERNER.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(interner.get_or_insert_with(Interner::new))
+}
+
+/// Brings the cache of this thread up to date with the table, after `f`
+/// updates the table.
+fn refresh<T>(cache: &mut Interner, f: impl FnOnce(&mut Interner) -> T) -> T {
+    with_interner(|interner| {
+        let result = f(interner);
+        for &string in &interner.strings[cache.strings.len()..] {
+            cache.symbols.insert(string, interner.symbols[string]);
+            cache.strings.push(string);
+        }
+        result
+    })
+}
+
+/// An interned string, such as the spelling of a token or a name.
+///
+/// Symbols are equal exactly when their strings are, and are ordered by
+/// their strings. Comparing two symbols for equality, or hashing one, never
+/// looks at the strings.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// The symbol for `string`, adding it to the table if it's new.
+    pub fn intern(string: &str) -> Self {
+        CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            match cache.symbols.get(string) {
+                Some(&symbol) => symbol,
+                None => refresh(&mut cache, |interner| interner.intern(string)),
+            }
+        })
+    }
+
+    /// The string of this symbol.
+    pub fn as_str(self) -> &'static str {
+        let index = self.0 as usize;
+        CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if index >= cache.strings.len() {
+                refresh(&mut cache, |_| ());
+            }
+            cache.strings[index]
+        })
+    }
+}
+
+/// The empty string.
+impl Default for Symbol {
+    fn default() -> Self {
+        Self(0)
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(string: &str) -> Self {
+        Self::intern(string)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(string: String) -> Self {
+        Self::intern(&string)
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialOrd for Symbol {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Symbol {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self == other {
+            Ordering::Equal
+        } else {
+            self.as_str().cmp(other.as_str())
+        }
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interns_each_string_once() {
+        let count = Symbol::intern("count");
+        assert_eq!(Symbol::intern("count"), count);
+        assert_eq!(Symbol::from(String::from("count")), count);
+        assert_ne!(Symbol::intern("counter"), count);
+
+        assert_eq!(count.as_str(), "count");
+        assert_eq!(count, "count");
+        assert_eq!(count.to_string(), "count");
+        assert_eq!(format!("{count:?}"), "\"count\"");
+
+        assert_eq!(Symbol::intern(""), Symbol::default());
+    }
+
+    #[test]
+    fn shares_symbols_between_threads() {
+        let symbol = std::thread::spawn(|| Symbol::intern("elsewhere")).join().unwrap();
+        assert_eq!(symbol.as_str(), "elsewhere");
+        assert_eq!(Symbol::intern("elsewhere"), symbol);
+    }
+
+    #[test]
+    fn orders_by_string() {
+        let mut symbols = ["main", "argc", "argv"].map(Symbol::intern);
+        symbols.sort();
+        assert_eq!(symbols.map(Symbol::as_str), ["argc", "argv", "main"]);
+    }
+}
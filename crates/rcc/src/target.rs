@@ -0,0 +1,320 @@
+//! The targets `rcc` compiles for, as chosen with `--target=`.
+//!
+//! A [`Target`] describes what C leaves to the implementation and the rest
+//! of the compiler needs to know: the widths and alignments of types, as a
+//! [`DataModel`], whether plain `char` is signed, the byte order, the types
+//! behind `size_t`, `ptrdiff_t` and `wchar_t`, and the macros predefined on
+//! the target, such as `__x86_64__` and `__SIZEOF_LONG__`.
+
+use crate::{
+    ast::DataType,
+    layout::{
+        DataModel,
+        LayoutEngine,
+    },
+};
+use std::{
+    fmt,
+    str::FromStr,
+};
+use strum::IntoEnumIterator;
+use strum_macros::{
+    Display,
+    EnumIter,
+    EnumString,
+};
+
+/// The architectures `rcc` can target, as spelled in target triples.
+#[derive(Debug, Default, Display, EnumIter, EnumString, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Arch {
+    #[default]
+    #[strum(serialize = "x86_64")]
+    X86_64,
+    #[strum(serialize = "aarch64")]
+    Aarch64,
+    #[strum(serialize = "riscv64")]
+    Riscv64,
+    #[strum(serialize = "i686")]
+    I686,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+/// An integer type a target defines a standard type as, e.g. `size_t` as
+/// `unsigned long`. Displayed as GCC spells it in `__SIZE_TYPE__` and
+/// friends.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntType {
+    #[strum(serialize = "int")]
+    Int,
+    #[strum(serialize = "unsigned int")]
+    UnsignedInt,
+    #[strum(serialize = "long int")]
+    Long,
+    #[strum(serialize = "long unsigned int")]
+    UnsignedLong,
+}
+
+impl IntType {
+    pub fn signed(self) -> bool {
+        matches!(self, IntType::Int | IntType::Long)
+    }
+
+    /// The type, without its signedness.
+    pub fn data_type(self) -> DataType {
+        match self {
+            IntType::Int | IntType::UnsignedInt => DataType::Int,
+            IntType::Long | IntType::UnsignedLong => DataType::Long,
+        }
+    }
+
+    /// The largest value of the type, `size` bytes wide, as a hexadecimal
+    /// constant of the type, e.g. `0x7fffffffL`.
+    fn max(self, size: u64) -> String {
+        let bits = size * 8 - u64::from(self.signed());
+        let suffix = match self {
+            IntType::Int => "",
+            IntType::UnsignedInt => "U",
+            IntType::Long => "L",
+            IntType::UnsignedLong => "UL",
+        };
+
+        format!("{:#x}{suffix}", u64::MAX >> (64 - bits))
+    }
+}
+
+/// What `rcc` compiles for: an architecture running Linux with glibc.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Target {
+    pub arch:         Arch,
+    pub data_model:   DataModel,
+    pub endianness:   Endianness,
+    /// Whether plain `char` is signed.
+    pub char_signed:  bool,
+    pub size_type:    IntType,
+    pub ptrdiff_type: IntType,
+    pub wchar_type:   IntType,
+}
+
+impl Default for Target {
+    fn default() -> Self {
+        Self::new(Arch::default())
+    }
+}
+
+impl Target {
+    /// The Linux target of `arch`, as described by its psABI.
+    pub fn new(arch: Arch) -> Self {
+        match arch {
+            Arch::X86_64 | Arch::Aarch64 | Arch::Riscv64 => Self {
+                arch,
+                data_model: DataModel::Lp64,
+                endianness: Endianness::Little,
+                char_signed: arch == Arch::X86_64,
+                size_type: IntType::UnsignedLong,
+                ptrdiff_type: IntType::Long,
+                wchar_type: if arch == Arch::Aarch64 {
+                    IntType::UnsignedInt
+                } else {
+                    IntType::Int
+                },
+            },
+            Arch::I686 => Self {
+                arch,
+                data_model: DataModel::Ilp32,
+                endianness: Endianness::Little,
+                char_signed: true,
+                size_type: IntType::UnsignedInt,
+                ptrdiff_type: IntType::Int,
+                wchar_type: IntType::Long,
+            },
+        }
+    }
+
+    /// The triples of the supported targets.
+    pub fn triples() -> impl Iterator<Item = String> {
+        Arch::iter().map(|arch| Target::new(arch).to_string())
+    }
+
+    /// Lays out types as the target does.
+    pub fn layout_engine(&self) -> LayoutEngine {
+        LayoutEngine::new(self.data_model)
+    }
+
+    /// The macros predefined on the target, with their replacement lists.
+    pub fn predefined_macros(&self) -> Vec<(&'static str, String)> {
+        let engine = self.layout_engine();
+        let size_of = |ty: DataType| {
+            engine.size_of(&ty).expect("the scalar types have a size on every target")
+        };
+        let pointer_size = size_of(DataType::Pointer(Box::new(DataType::Void)));
+        let size_type_size = size_of(self.size_type.data_type());
+        let ptrdiff_type_size = size_of(self.ptrdiff_type.data_type());
+        let wchar_type_size = size_of(self.wchar_type.data_type());
+
+        let mut macros = vec![
+            ("__linux__", "1".to_string()),
+            ("__linux", "1".to_string()),
+            ("__gnu_linux__", "1".to_string()),
+            ("__unix__", "1".to_string()),
+            ("__unix", "1".to_string()),
+            ("__ELF__", "1".to_string()),
+            ("__CHAR_BIT__", "8".to_string()),
+            ("__SIZEOF_SHORT__", size_of(DataType::Short).to_string()),
+            ("__SIZEOF_INT__", size_of(DataType::Int).to_string()),
+            ("__SIZEOF_LONG__", size_of(DataType::Long).to_string()),
+            ("__SIZEOF_LONG_LONG__", size_of(DataType::LongLong).to_string()),
+            ("__SIZEOF_POINTER__", pointer_size.to_string()),
+            ("__SIZEOF_FLOAT__", size_of(DataType::Float).to_string()),
+            ("__SIZEOF_DOUBLE__", size_of(DataType::Double).to_string()),
+            ("__SIZEOF_LONG_DOUBLE__", size_of(DataType::LongDouble).to_string()),
+            ("__SIZEOF_SIZE_T__", size_type_size.to_string()),
+            ("__SIZEOF_PTRDIFF_T__", ptrdiff_type_size.to_string()),
+            ("__SIZEOF_WCHAR_T__", wchar_type_size.to_string()),
+            ("__SIZE_TYPE__", self.size_type.to_string()),
+            ("__PTRDIFF_TYPE__", self.ptrdiff_type.to_string()),
+            ("__WCHAR_TYPE__", self.wchar_type.to_string()),
+            ("__SCHAR_MAX__", IntType::Int.max(1)),
+            ("__SHRT_MAX__", IntType::Int.max(2)),
+            ("__INT_MAX__", IntType::Int.max(4)),
+            ("__LONG_MAX__", IntType::Long.max(size_of(DataType::Long))),
+            ("__LONG_LONG_MAX__", format!("{}L", IntType::Long.max(8))),
+            ("__SIZE_MAX__", self.size_type.max(size_type_size)),
+            ("__PTRDIFF_MAX__", self.ptrdiff_type.max(ptrdiff_type_size)),
+            ("__WCHAR_MAX__", self.wchar_type.max(wchar_type_size)),
+            ("__BIGGEST_ALIGNMENT__", "16".to_string()),
+            ("__ORDER_LITTLE_ENDIAN__", "1234".to_string()),
+            ("__ORDER_BIG_ENDIAN__", "4321".to_string()),
+            ("__ORDER_PDP_ENDIAN__", "3412".to_string()),
+        ];
+
+        let byte_order = match self.endianness {
+            Endianness::Little => "__ORDER_LITTLE_ENDIAN__",
+            Endianness::Big => "__ORDER_BIG_ENDIAN__",
+        };
+        macros.push(("__BYTE_ORDER__", byte_order.to_string()));
+        macros.push(("__FLOAT_WORD_ORDER__", byte_order.to_string()));
+
+        if !self.char_signed {
+            macros.push(("__CHAR_UNSIGNED__", "1".to_string()));
+        }
+        let data_model_macros: &[&str] = match self.data_model {
+            DataModel::Lp64 => &["_LP64", "__LP64__"],
+            DataModel::Ilp32 => &["_ILP32", "__ILP32__"],
+            DataModel::Llp64 => &[],
+        };
+        let arch_macros: &[&str] = match self.arch {
+            Arch::X86_64 => &["__x86_64__", "__x86_64", "__amd64__", "__amd64"],
+            Arch::Aarch64 => &["__aarch64__", "__AARCH64EL__", "__ARM_64BIT_STATE"],
+            Arch::Riscv64 => &["__riscv"],
+            Arch::I686 => &["__i386__", "__i386", "__i686__", "__i686"],
+        };
+        for name in data_model_macros.iter().chain(arch_macros) {
+            macros.push((name, "1".to_string()));
+        }
+        if self.arch == Arch::Riscv64 {
+            macros.push(("__riscv_xlen", "64".to_string()));
+        }
+
+        macros
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-linux-gnu", self.arch)
+    }
+}
+
+/// Parses a target triple, e.g. `aarch64-linux-gnu` or, with a vendor,
+/// `x86_64-pc-linux-gnu`.
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(triple: &str) -> Result<Self, Self::Err> {
+        let unknown = || {
+            let expected = Target::triples().map(|triple| format!("`{triple}`")).collect::<Vec<_>>();
+            format!("unknown target `{triple}` (expected {})", expected.join(", "))
+        };
+
+        let parts = triple.split('-').collect::<Vec<_>>();
+        let arch = match parts[..] {
+            [arch, "linux", "gnu"] | [arch, _, "linux", "gnu"] => arch,
+            _ => return Err(unknown()),
+        };
+
+        arch.parse().map(Target::new).map_err(|_| unknown())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn predefined(triple: &str, name: &str) -> Option<String> {
+        let target = triple.parse::<Target>().unwrap();
+        target.predefined_macros().into_iter().find(|(macro_name, _)| *macro_name == name).map(
+            |(_, value)| value,
+        )
+    }
+
+    #[test]
+    fn triples() {
+        assert_eq!(
+            Target::triples().collect::<Vec<_>>(),
+            ["x86_64-linux-gnu", "aarch64-linux-gnu", "riscv64-linux-gnu", "i686-linux-gnu"]
+        );
+        assert_eq!("x86_64-pc-linux-gnu".parse(), Ok(Target::default()));
+        assert_eq!("i686-linux-gnu".parse::<Target>().unwrap().data_model, DataModel::Ilp32);
+        assert_eq!(
+            "arm-linux-gnueabi".parse::<Target>(),
+            Err("unknown target `arm-linux-gnueabi` (expected `x86_64-linux-gnu`, \
+                 `aarch64-linux-gnu`, `riscv64-linux-gnu`, `i686-linux-gnu`)"
+                .to_string())
+        );
+    }
+
+    /// The values are those of `gcc -dM -E` for each target.
+    #[test]
+    fn predefined_macros() {
+        assert_eq!(predefined("x86_64-linux-gnu", "__x86_64__").as_deref(), Some("1"));
+        assert_eq!(predefined("x86_64-linux-gnu", "__i386__"), None);
+        assert_eq!(predefined("x86_64-linux-gnu", "__SIZEOF_LONG__").as_deref(), Some("8"));
+        assert_eq!(
+            predefined("x86_64-linux-gnu", "__SIZE_TYPE__").as_deref(),
+            Some("long unsigned int")
+        );
+        assert_eq!(
+            predefined("x86_64-linux-gnu", "__SIZE_MAX__").as_deref(),
+            Some("0xffffffffffffffffUL")
+        );
+        assert_eq!(
+            predefined("x86_64-linux-gnu", "__LONG_LONG_MAX__").as_deref(),
+            Some("0x7fffffffffffffffLL")
+        );
+        assert_eq!(predefined("x86_64-linux-gnu", "__CHAR_UNSIGNED__"), None);
+
+        assert_eq!(predefined("i686-linux-gnu", "__i386__").as_deref(), Some("1"));
+        assert_eq!(predefined("i686-linux-gnu", "_ILP32").as_deref(), Some("1"));
+        assert_eq!(predefined("i686-linux-gnu", "__SIZEOF_LONG_DOUBLE__").as_deref(), Some("12"));
+        assert_eq!(predefined("i686-linux-gnu", "__SIZE_MAX__").as_deref(), Some("0xffffffffU"));
+        assert_eq!(predefined("i686-linux-gnu", "__WCHAR_TYPE__").as_deref(), Some("long int"));
+        assert_eq!(predefined("i686-linux-gnu", "__WCHAR_MAX__").as_deref(), Some("0x7fffffffL"));
+        assert_eq!(predefined("i686-linux-gnu", "__PTRDIFF_MAX__").as_deref(), Some("0x7fffffff"));
+
+        assert_eq!(predefined("aarch64-linux-gnu", "__CHAR_UNSIGNED__").as_deref(), Some("1"));
+        assert_eq!(
+            predefined("aarch64-linux-gnu", "__WCHAR_MAX__").as_deref(),
+            Some("0xffffffffU")
+        );
+        assert_eq!(predefined("riscv64-linux-gnu", "__riscv_xlen").as_deref(), Some("64"));
+        assert_eq!(
+            predefined("riscv64-linux-gnu", "__BYTE_ORDER__").as_deref(),
+            Some("__ORDER_LITTLE_ENDIAN__")
+        );
+    }
+}
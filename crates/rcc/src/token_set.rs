@@ -15,12 +15,11 @@ impl From<Vec<TokenKind>> for TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::TokenKind;
+    /// use rcc::{lexer::TokenKind, token_set::TokenSet};
     ///
-    /// let set = TokenSet::from(&[TokenKind::Identifier, TokenKind::Keyword]);
-    /// assert!(set.contains(TokenKind::Identifier));
-    /// assert!(set.contains(TokenKind::Keyword));
+    /// let set = TokenSet::from(vec![TokenKind::IDENTIFIER, TokenKind::SEMICOLON]);
+    /// assert!(set.contains(TokenKind::IDENTIFIER));
+    /// assert!(set.contains(TokenKind::SEMICOLON));
     /// ```
     fn from(kinds: Vec<TokenKind>) -> Self {
         let mut res = 0u128;
@@ -42,8 +41,10 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, token_set::TokenSet};
+    ///
+    /// const DELIMITERS: TokenSet = TokenSet::new(&[SyntaxKind::COMMA, SyntaxKind::SEMICOLON]);
+    /// assert_eq!(DELIMITERS.len(), 2);
     /// ```
     pub const fn new(kinds: &[SyntaxKind]) -> TokenSet {
         let mut res = 0u128;
@@ -60,16 +61,15 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, lexer::TokenKind, token_set::TokenSet};
     ///
-    /// let set1 = TokenSet::new(&[SyntaxKind::Identifier, SyntaxKind::Keyword]);
-    /// let set2 = TokenSet::new(&[SyntaxKind::Punctuation, SyntaxKind::Keyword]);
+    /// let set1 = TokenSet::new(&[SyntaxKind::IDENTIFIER, SyntaxKind::SEMICOLON]);
+    /// let set2 = TokenSet::new(&[SyntaxKind::COMMA, SyntaxKind::SEMICOLON]);
     ///
     /// let union_set = set1.union(set2);
-    /// assert!(union_set.contains(SyntaxKind::Identifier));
-    /// assert!(union_set.contains(SyntaxKind::Punctuation));
-    /// assert!(union_set.contains(SyntaxKind::Keyword));
+    /// assert!(union_set.contains(TokenKind::IDENTIFIER));
+    /// assert!(union_set.contains(TokenKind::COMMA));
+    /// assert!(union_set.contains(TokenKind::SEMICOLON));
     /// ```
     pub const fn union(self, other: TokenSet) -> TokenSet {
         TokenSet(self.0 | other.0)
@@ -80,12 +80,11 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, lexer::TokenKind, token_set::TokenSet};
     ///
-    /// let set = TokenSet::new(&[SyntaxKind::Identifier, SyntaxKind::Keyword]);
-    /// assert!(set.contains(SyntaxKind::Identifier));
-    /// assert!(!set.contains(SyntaxKind::Punctuation));
+    /// let set = TokenSet::new(&[SyntaxKind::IDENTIFIER, SyntaxKind::SEMICOLON]);
+    /// assert!(set.contains(TokenKind::IDENTIFIER));
+    /// assert!(!set.contains(TokenKind::COMMA));
     /// ```
     pub const fn contains(&self, kind: TokenKind) -> bool {
         self.0 & mask(kind.to_syntax()) != 0
@@ -98,14 +97,13 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, lexer::TokenKind, token_set::TokenSet};
     ///
-    /// let mut set = TokenSet::new(&[SyntaxKind::Identifier]);
-    /// set.merge(SyntaxKind::Keyword);
+    /// let mut set = TokenSet::new(&[SyntaxKind::IDENTIFIER]);
+    /// set.merge(TokenKind::SEMICOLON);
     ///
-    /// assert!(set.contains(SyntaxKind::Identifier));
-    /// assert!(set.contains(SyntaxKind::Keyword));
+    /// assert!(set.contains(TokenKind::IDENTIFIER));
+    /// assert!(set.contains(TokenKind::SEMICOLON));
     /// ```
     pub fn merge(&mut self, kind: TokenKind) {
         self.0 |= mask(kind.to_syntax());
@@ -116,7 +114,7 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
+    /// use rcc::token_set::TokenSet;
     ///
     /// let empty_set = TokenSet::EMPTY;
     /// assert!(empty_set.is_empty());
@@ -158,11 +156,10 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, token_set::TokenSet};
     ///
-    /// let set1 = TokenSet::new(&[SyntaxKind::Identifier]);
-    /// let set2 = TokenSet::new(&[SyntaxKind::Identifier, SyntaxKind::Keyword]);
+    /// let set1 = TokenSet::new(&[SyntaxKind::IDENTIFIER]);
+    /// let set2 = TokenSet::new(&[SyntaxKind::IDENTIFIER, SyntaxKind::SEMICOLON]);
     ///
     /// assert!(set1.is_subset(set2));
     /// assert!(!set2.is_subset(set1));
@@ -176,11 +173,10 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, token_set::TokenSet};
     ///
-    /// let set1 = TokenSet::new(&[SyntaxKind::Identifier]);
-    /// let set2 = TokenSet::new(&[SyntaxKind::Keyword]);
+    /// let set1 = TokenSet::new(&[SyntaxKind::IDENTIFIER]);
+    /// let set2 = TokenSet::new(&[SyntaxKind::SEMICOLON]);
     ///
     /// assert!(set1.is_disjoint(set2));
     /// ```
@@ -194,16 +190,15 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, lexer::TokenKind, token_set::TokenSet};
     ///
-    /// let set1 = TokenSet::new(&[SyntaxKind::Identifier, SyntaxKind::Keyword]);
-    /// let set2 = TokenSet::new(&[SyntaxKind::Keyword, SyntaxKind::Punctuation]);
+    /// let set1 = TokenSet::new(&[SyntaxKind::IDENTIFIER, SyntaxKind::SEMICOLON]);
+    /// let set2 = TokenSet::new(&[SyntaxKind::SEMICOLON, SyntaxKind::COMMA]);
     ///
     /// let intersection_set = set1.intersection(set2);
-    /// assert!(!intersection_set.contains(SyntaxKind::Identifier));
-    /// assert!(intersection_set.contains(SyntaxKind::Keyword));
-    /// assert!(!intersection_set.contains(SyntaxKind::Punctuation));
+    /// assert!(!intersection_set.contains(TokenKind::IDENTIFIER));
+    /// assert!(intersection_set.contains(TokenKind::SEMICOLON));
+    /// assert!(!intersection_set.contains(TokenKind::COMMA));
     /// ```
     pub fn intersection(&self, other: TokenSet) -> TokenSet {
         TokenSet(self.0 & other.0)
@@ -214,15 +209,14 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, lexer::TokenKind, token_set::TokenSet};
     ///
-    /// let mut set1 = TokenSet::new(&[SyntaxKind::Identifier, SyntaxKind::Keyword]);
-    /// let set2 = TokenSet::new(&[SyntaxKind::Keyword]);
+    /// let mut set1 = TokenSet::new(&[SyntaxKind::IDENTIFIER, SyntaxKind::SEMICOLON]);
+    /// let set2 = TokenSet::new(&[SyntaxKind::SEMICOLON]);
     ///
     /// set1.remove(set2);
-    /// assert!(set1.contains(SyntaxKind::Identifier));
-    /// assert!(!set1.contains(SyntaxKind::Keyword));
+    /// assert!(set1.contains(TokenKind::IDENTIFIER));
+    /// assert!(!set1.contains(TokenKind::SEMICOLON));
     /// ```
     pub fn remove(&mut self, other: TokenSet) {
         self.0 &= !other.0;
@@ -233,15 +227,14 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, lexer::TokenKind, token_set::TokenSet};
     ///
-    /// let mut set1 = TokenSet::new(&[SyntaxKind::Identifier]);
-    /// let set2 = TokenSet::new(&[SyntaxKind::Keyword]);
+    /// let mut set1 = TokenSet::new(&[SyntaxKind::IDENTIFIER]);
+    /// let set2 = TokenSet::new(&[SyntaxKind::SEMICOLON]);
     ///
     /// set1.insert(set2);
-    /// assert!(set1.contains(SyntaxKind::Identifier));
-    /// assert!(set1.contains(SyntaxKind::Keyword));
+    /// assert!(set1.contains(TokenKind::IDENTIFIER));
+    /// assert!(set1.contains(TokenKind::SEMICOLON));
     /// ```
     pub fn insert(&mut self, other: TokenSet) {
         self.0 |= other.0;
@@ -253,16 +246,15 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, lexer::TokenKind, token_set::TokenSet};
     ///
-    /// let set1 = TokenSet::new(&[SyntaxKind::Identifier, SyntaxKind::Keyword]);
-    /// let set2 = TokenSet::new(&[SyntaxKind::Keyword, SyntaxKind::Punctuation]);
+    /// let set1 = TokenSet::new(&[SyntaxKind::IDENTIFIER, SyntaxKind::SEMICOLON]);
+    /// let set2 = TokenSet::new(&[SyntaxKind::SEMICOLON, SyntaxKind::COMMA]);
     ///
     /// let difference_set = set1.difference(set2);
-    /// assert!(difference_set.contains(SyntaxKind::Identifier));
-    /// assert!(!difference_set.contains(SyntaxKind::Keyword));
-    /// assert!(!difference_set.contains(SyntaxKind::Punctuation));
+    /// assert!(difference_set.contains(TokenKind::IDENTIFIER));
+    /// assert!(!difference_set.contains(TokenKind::SEMICOLON));
+    /// assert!(!difference_set.contains(TokenKind::COMMA));
     /// ```
     pub fn difference(&self, other: TokenSet) -> TokenSet {
         TokenSet(self.0 & !other.0)
@@ -277,17 +269,16 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, lexer::TokenKind, token_set::TokenSet};
     ///
-    /// let set1 = TokenSet::new(&[SyntaxKind::Identifier, SyntaxKind::Keyword]);
-    /// let set2 = TokenSet::new(&[SyntaxKind::Keyword, SyntaxKind::Punctuation]);
+    /// let set1 = TokenSet::new(&[SyntaxKind::IDENTIFIER, SyntaxKind::SEMICOLON]);
+    /// let set2 = TokenSet::new(&[SyntaxKind::SEMICOLON, SyntaxKind::COMMA]);
     ///
     /// let symmetric_difference_set = set1.symmetric_difference(set2);
     ///
-    /// assert!(symmetric_difference_set.contains(SyntaxKind::Identifier));
-    /// assert!(!symmetric_difference_set.contains(SyntaxKind::Keyword));
-    /// assert!(symmetric_difference_set.contains(SyntaxKind::Punctuation));
+    /// assert!(symmetric_difference_set.contains(TokenKind::IDENTIFIER));
+    /// assert!(!symmetric_difference_set.contains(TokenKind::SEMICOLON));
+    /// assert!(symmetric_difference_set.contains(TokenKind::COMMA));
     /// ```
     pub fn symmetric_difference(&self, other: TokenSet) -> TokenSet {
         TokenSet(self.0 ^ other.0)
@@ -301,16 +292,15 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, lexer::TokenKind, token_set::TokenSet};
     ///
-    /// let mut set1 = TokenSet::new(&[SyntaxKind::Identifier, SyntaxKind::Keyword]);
-    /// let set2 = TokenSet::new(&[SyntaxKind::Keyword, SyntaxKind::Punctuation]);
+    /// let mut set1 = TokenSet::new(&[SyntaxKind::IDENTIFIER, SyntaxKind::SEMICOLON]);
+    /// let set2 = TokenSet::new(&[SyntaxKind::SEMICOLON, SyntaxKind::COMMA]);
     ///
     /// set1.toggle(set2);
-    /// assert!(set1.contains(SyntaxKind::Identifier));
-    /// assert!(!set1.contains(SyntaxKind::Keyword));
-    /// assert!(set1.contains(SyntaxKind::Punctuation));
+    /// assert!(set1.contains(TokenKind::IDENTIFIER));
+    /// assert!(!set1.contains(TokenKind::SEMICOLON));
+    /// assert!(set1.contains(TokenKind::COMMA));
     /// ```
     pub fn toggle(&mut self, other: TokenSet) {
         self.0 ^= other.0;
@@ -324,18 +314,17 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, lexer::TokenKind, token_set::TokenSet};
     ///
-    /// let mut set = TokenSet::new(&[SyntaxKind::Identifier]);
+    /// let mut set = TokenSet::new(&[SyntaxKind::IDENTIFIER]);
     ///
-    /// set.toggle_kind(SyntaxKind::Keyword);
-    /// assert!(set.contains(SyntaxKind::Identifier));
-    /// assert!(set.contains(SyntaxKind::Keyword));
+    /// set.toggle_kind(TokenKind::SEMICOLON);
+    /// assert!(set.contains(TokenKind::IDENTIFIER));
+    /// assert!(set.contains(TokenKind::SEMICOLON));
     ///
-    /// set.toggle_kind(SyntaxKind::Identifier);
-    /// assert!(!set.contains(SyntaxKind::Identifier));
-    /// assert!(set.contains(SyntaxKind::Keyword));
+    /// set.toggle_kind(TokenKind::IDENTIFIER);
+    /// assert!(!set.contains(TokenKind::IDENTIFIER));
+    /// assert!(set.contains(TokenKind::SEMICOLON));
     /// ```
     pub fn toggle_kind(&mut self, kind: TokenKind) {
         self.0 ^= mask(kind.to_syntax());
@@ -346,11 +335,10 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, token_set::TokenSet};
     ///
-    /// let set1 = TokenSet::new(&[SyntaxKind::Identifier, SyntaxKind::Keyword]);
-    /// let set2 = TokenSet::new(&[SyntaxKind::Identifier]);
+    /// let set1 = TokenSet::new(&[SyntaxKind::IDENTIFIER, SyntaxKind::SEMICOLON]);
+    /// let set2 = TokenSet::new(&[SyntaxKind::IDENTIFIER]);
     ///
     /// assert!(set1.is_superset(set2));
     /// assert!(!set2.is_superset(set1));
@@ -380,14 +368,13 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, lexer::TokenKind, token_set::TokenSet};
     ///
-    /// let set = TokenSet::new(&[SyntaxKind::Identifier]);
+    /// let set = TokenSet::new(&[SyntaxKind::IDENTIFIER]);
     /// let complement_set = set.complement();
     ///
-    /// assert!(!complement_set.contains(SyntaxKind::Identifier));
-    /// assert!(complement_set.contains(SyntaxKind::Keyword));
+    /// assert!(!complement_set.contains(TokenKind::IDENTIFIER));
+    /// assert!(complement_set.contains(TokenKind::SEMICOLON));
     /// ```
     pub fn complement(self) -> TokenSet {
         TokenSet(!self.0)
@@ -398,13 +385,12 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, token_set::TokenSet};
     ///
-    /// let singleton_set = TokenSet::singleton(SyntaxKind::Identifier);
+    /// let singleton_set = TokenSet::new(&[SyntaxKind::IDENTIFIER]);
     /// assert!(singleton_set.is_singleton());
     ///
-    /// let set = TokenSet::new(&[SyntaxKind::Identifier, SyntaxKind::Keyword]);
+    /// let set = TokenSet::new(&[SyntaxKind::IDENTIFIER, SyntaxKind::SEMICOLON]);
     /// assert!(!set.is_singleton());
     /// ```
     ///
@@ -418,11 +404,9 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, token_set::TokenSet};
     ///
-    /// let set = TokenSet::new
-    /// (&[SyntaxKind::Identifier, SyntaxKind::Keyword]);
+    /// let set = TokenSet::new(&[SyntaxKind::IDENTIFIER, SyntaxKind::SEMICOLON]);
     /// assert_eq!(set.count(), 2);
     ///
     /// let empty_set = TokenSet::EMPTY;
@@ -439,14 +423,13 @@ impl TokenSet {
     /// # Example
     ///
     /// ```
-    /// use crate::TokenSet;
-    /// use crate::SyntaxKind;
+    /// use rcc::{cst::SyntaxKind, lexer::TokenKind, token_set::TokenSet};
     ///
-    /// let mut set = TokenSet::new(&[SyntaxKind::Identifier, SyntaxKind::Keyword]);
+    /// let mut set = TokenSet::new(&[SyntaxKind::IDENTIFIER, SyntaxKind::SEMICOLON]);
     /// let taken_set = set.take();
     ///
-    /// assert!(taken_set.contains(SyntaxKind::Identifier));
-    /// assert!(taken_set.contains(SyntaxKind::Keyword));
+    /// assert!(taken_set.contains(TokenKind::IDENTIFIER));
+    /// assert!(taken_set.contains(TokenKind::SEMICOLON));
     /// assert!(set.is_empty());
     /// ```
     ///
@@ -1,169 +1,1000 @@
-#[cfg(test)]
-mod preprocessor_test_suite {
-    use pretty_assertions_sorted::assert_eq;
-    // use rcc;
-    //   use rcc::preprocessor::Preprocessor;
-
-    // TODO: Get tests working again within Buck
-    //   #[test]
-    //   fn test_syntax() {
-    //     let source = r"def foo():
-    //     pass
-    // ";
-
-    //   #[test]
-    //   fn test_preprocessor() {
-    //     let source = r"def foo():
-    //     pass
-    // ";
-
-    //     let preprocessor =
-    //       rcc::preprocessor::Preprocessor::new(source);
-    //     let tokens = preprocessor.tokenize();
-    //     println!("tokens: {:#?}", tokens);
-    //     assert_eq!(
-    //       tokens,
-    //       vec!["def", "foo", "(", ")", ":", "pass"]
-    //     );
-    //   }
-}
-
-// v1
-// #[cfg(test)]
-// mod preprocessor_test_suite {
-//   use pretty_assertions_sorted::assert_eq;
-//   //   use rcc::preprocessor::Preprocessor;
-
-//   //   #[test]
-//   //   fn test_syntax() {
-//   //     let source = r"def foo():
-//   //     pass
-//   // ";
-
-//   #[test]
-//   fn test_preprocessor() {
-//     let source = r"def foo():
-//     pass
-// ";
-
-//     // let preprocessor =
-//     //   rcc::preprocessor::Preprocessor::new(source);
-//     let tokens = preprocessor.tokenize();
-//     println!("tokens: {:#?}", tokens);
-//     assert_eq!(
-//       tokens,
-//       vec!["def", "foo", "(", ")", ":", "pass"]
-//     );
-//   }
-
-//   //     // let expected
-//   //     let ast = File::parse(source);
-//   //     println!("ast: {ast:#?}");
-//   //     assert_eq!(ast.debug_dump(), source);
-//   //   }
-// }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    // TODO: Refactor to test all trees
-    // #[rstest]
-    // #[case("x", "Identifier")]
-    // #[case("42", "Constant")]
-    // #[case("\"hello\"", "String Literal")]
-    // #[case("(a + b)", "Expression in parentheses")]
-    // fn primary_expr_tests(input: &str, expected_result: &str) {
-    //     let cst = parse_tree(input, TreeKind::PrimaryExpression);
-    //     assert_eq!(cst, format!("Parsed PrimaryExpression:\n\n{}",
-    // expected_result)); }
-}
-
-// // preprocessor::preprocessor();
-
-// let primary_expr_test_cases = vec![
-//     "x",         // Identifier
-//     "42",        // Constant
-//     "\"hello\"", // String Literal
-//     "(a + b)",   // Expression in parentheses
-// ];
-
-// // for input in primary_expr_test_cases {
-// //   let cst = parse_tree(input, TreeKind::PrimaryExpression);
-// //   println!("Parsing:\n\n{input}");
-// //   eprintln!("\nTree:\n\n{cst}");
-// // }
-
-// let decl_test_cases = vec![
-//     // Declaration tests
-//     "int x;",                           // Declaration with a single variable
-//     "int x, y, z;",                     // Declaration with multiple
-// variables     "extern int x = 42;",               // Extern declaration with
-// initialization     "typedef int* IntPtr;",             // Typedef declaration
-//     "struct Point { int x; int y; };",  // Struct declaration
-//     "enum Color { RED, GREEN, BLUE };", // Enum declaration
-//     // Initializer tests
-//     "int x = 42;", // Declaration with initialization
-//     "int x[] = {1, 2, 3};", /* Array initialization
-//                     * "struct Point p = {.x = 10, .y = 20};", // Struct
-//                       initialization */
-// ];
-
-// for input in decl_test_cases {
-//   let cst = parse_tree(input, TreeKind::Declaration);
-//   println!("Parsing:\n\n{input}");
-//   println!("\nTree:\n\n{cst}");
-// }
-
-// fire emoji 🔥
-
-// let statement_test_cases = [
-//     // Statement tests
-//     "goto label;",      // Goto statement
-//     "label: return 0;", // Labeled statement
-//     "x = 42;",          // Expression statement
-//     // Selection statement tests
-//     "if (x > 0) { return x; }",                     // If statement
-//     "if (x > 0) { return x; } else { return -x; }", // If-Else statement
-//     // Iteration statement tests
-//     "while (x > 0) { x--; }", // While loop
-//     "for (int i = 0; i < 10; i++) { printf(\"%d\\n\", i); }", // For loop
-//     "do { x--; } while (x > 0);", // Do-While loop
-//     // Jump statement tests
-//     "goto label;", /* Goto statement */
-//     "continue;",   /* Continue statement */
-//     "break;",      /* Break statement */
-//     "return 0;",   /* Return statement */
-//     "return;",     /* Return statement without expression */
-//     // Compound statement tests
-//     "{ int x = 42; return x; }", // Compound statement
-// ];
-
-// for input in statement_test_cases {
-//     let cst = parse_tree(input, TreeKind::Statement);
-//     println!("Parsing:\n\n{input}");
-//     println!("\nTree:\n\n{cst}");
-// }
-
-// parse the input file
-// let input =
-// &read_to_string("testdata/parse/translation_unit.c").unwrap();
-// let tree = parse(input);
-// println!("{tree:?}");
-
-// Direct declarator tests
-// let fn_def_test_cases = [
-//     "int main() {
-// int x;
-// return 0;
-//     }",
-//     "int main(int argc, char** argv) { return 0; }",
-//     "double add(double x, double y) { return x + y; }",
-// ];
-
-// for input in fn_def_test_cases {
-//     let cst = parse_tree(input, TreeKind::TranslationUnit);
-
-//     // println!("Parsing:\n\n{input}");
-//     // println!("\nTree:\n\n{cst}");
-// }
+use pretty_assertions_sorted::assert_eq;
+use rcc::{
+    ast::{
+        DataType,
+        DeclarationSpecifier,
+        ExternDecl,
+        StorageClass,
+    },
+    charset,
+    cst::TreeKind,
+    diagnostics::{
+        Buffer,
+        Diagnostic,
+        DiagnosticFormat,
+        DiagnosticsEngine,
+        Label,
+        WarningOptions,
+    },
+    fixtures::{
+        self,
+        Outcome,
+    },
+    layout::{
+        DataModel,
+        LayoutEngine,
+    },
+    lexer::TokenKind,
+    line_index::{
+        Encoding,
+        LineCol,
+    },
+    parser,
+    Options,
+    Session,
+    Standard,
+};
+use rstest::rstest;
+use std::path::Path;
+
+#[test]
+fn session_runs_each_phase() {
+    let mut session = Session::builder().build();
+    let file_id = session.add_source("add.c", "int add(int x, int *y) { return x; }");
+
+    let tokens = session.lex(file_id);
+    assert_eq!(tokens.tokens().first().map(|token| token.kind), Some(TokenKind::INT_KW));
+    assert_eq!(tokens.tokens().last().map(|token| token.kind), Some(TokenKind::EOF));
+
+    let cst = session.parse(file_id);
+    assert_eq!(cst.kind(), TreeKind::TranslationUnit);
+    assert!(!cst.contains_errors());
+    assert!(!session.has_errors());
+
+    let ast = session.lower(file_id);
+    let [ExternDecl::Function(function)] = ast.functions.as_slice() else {
+        panic!("expected a single function, found {:#?}", ast.functions);
+    };
+    assert_eq!(function.name.to_string(), "add");
+    assert_eq!(
+        function.params.iter().map(|param| param.ty.clone()).collect::<Vec<_>>(),
+        vec![DataType::Int, DataType::Pointer(Box::new(DataType::Int))]
+    );
+}
+
+#[test]
+fn session_collects_diagnostics() {
+    let mut session = Session::builder().build();
+    let file_id = session.add_source("unknown.c", "int x = @;");
+
+    session.lex(file_id);
+    assert!(session.has_errors());
+
+    let mut buffer = Buffer::no_color();
+    session.diagnostics_mut().flush_to(&mut buffer);
+    let rendered = String::from_utf8(buffer.into_inner()).unwrap();
+
+    assert!(rendered.contains("unknown.c"), "{rendered}");
+    assert!(session.has_errors());
+}
+
+#[test]
+fn session_reads_overlays_once() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../testdata/ui");
+    let mut session = Session::builder().build();
+    session.source_manager_mut().set_overlay(dir.join("missing_semicolon.c"), "int unsaved;");
+
+    let file_id = session.add_file(dir.join("missing_semicolon.c")).unwrap();
+    let again = session.add_file(dir.join("../ui/./missing_semicolon.c")).unwrap();
+    assert_eq!(again, file_id);
+    assert_eq!(session.source_manager().get(file_id).unwrap().source(), "int unsaved;");
+
+    session.parse(file_id);
+    assert!(!session.has_errors());
+
+    let missing = session.add_file(dir.join("missing.c"));
+    assert!(missing.is_err());
+}
+
+/// Renders the diagnostics of `input` in `format`.
+fn render(input: &str, format: DiagnosticFormat) -> String {
+    let mut session = Session::builder().build();
+    let file_id = session.add_source("unknown.c", input);
+    session.lex(file_id);
+
+    let mut buffer = Buffer::no_color();
+    session.diagnostics_mut().flush_as(format, &mut buffer);
+    String::from_utf8(buffer.into_inner()).unwrap()
+}
+
+#[test]
+fn diagnostics_as_json() {
+    let rendered = render("int x;\nint y = @;", DiagnosticFormat::Json);
+    let [line] = rendered.lines().collect::<Vec<_>>()[..] else {
+        panic!("expected a single line, found {rendered}");
+    };
+
+    let diagnostic: serde_json::Value = serde_json::from_str(line).unwrap();
+    assert_eq!(diagnostic["code"], "E0000");
+    assert_eq!(diagnostic["message"], "Unknown token encountered: '@'");
+    assert_eq!(diagnostic["severity"], "error");
+    assert_eq!(diagnostic["labels"][0]["style"], "primary");
+    assert_eq!(diagnostic["labels"][0]["file"], "unknown.c");
+    assert_eq!(diagnostic["labels"][0]["byte_start"], 15);
+    assert_eq!(diagnostic["labels"][0]["byte_end"], 16);
+    assert_eq!(diagnostic["labels"][0]["line_start"], 2);
+    assert_eq!(diagnostic["labels"][0]["column_start"], 9);
+    assert!(!diagnostic["notes"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn diagnostics_as_sarif() {
+    let rendered = render("int x = @;", DiagnosticFormat::Sarif);
+    let log: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "rcc");
+    assert!(run["tool"]["driver"]["rules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|rule| rule["id"] == "E0000"));
+
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], "E0000");
+    assert_eq!(result["level"], "error");
+    let region = &result["locations"][0]["physicalLocation"]["region"];
+    assert_eq!(region["startLine"], 1);
+    assert_eq!(region["startColumn"], 9);
+}
+
+#[test]
+fn diagnostics_as_gcc() {
+    let rendered = render("int x;\nint y = @;", DiagnosticFormat::Gcc);
+    let mut lines = rendered.lines();
+
+    let error = lines.next().unwrap();
+    assert!(error.starts_with("unknown.c:2:9: error: "), "{rendered}");
+    assert!(error.ends_with("[E0000]"), "{rendered}");
+    assert!(lines.all(|line| line.starts_with("unknown.c:2:9: note: ")), "{rendered}");
+}
+
+#[test]
+fn diagnostics_as_gcc_with_carets() {
+    let options = Options::builder().show_caret(true).build();
+    let mut session = Session::builder().options(options).build();
+    let file_id = session.add_source("caret.c", "int x = 1\nint y = 2;\n");
+    session.parse(file_id);
+
+    let mut buffer = Buffer::no_color();
+    session.diagnostics_mut().flush_as(DiagnosticFormat::Gcc, &mut buffer);
+    let rendered = String::from_utf8(buffer.into_inner()).unwrap();
+    assert!(
+        rendered.starts_with(
+            "caret.c:2:1: error: Unexpected token encountered: 'int'. [E0001]\n\
+             \x20   2 | int y = 2;\n\
+             \x20     | ^~~\n\
+             caret.c:2:1: note: Expected token: ';'\n"
+        ),
+        "{rendered}"
+    );
+    assert!(
+        rendered.ends_with(
+            "caret.c:1:10: note: insert `;`\n\
+             \x20   1 | int x = 1\n\
+             \x20     |          ^\n\
+             \x20     |          ;\n"
+        ),
+        "{rendered}"
+    );
+}
+
+#[test]
+fn columns_count_per_format() {
+    // `𝔵` is 4 bytes and 2 UTF-16 code units, `é` 2 bytes and 1 unit.
+    let input = "char *s = \"𝔵é\", x = @;";
+
+    let json: serde_json::Value =
+        serde_json::from_str(&render(input, DiagnosticFormat::Json)).unwrap();
+    assert_eq!(json["labels"][0]["byte_start"], 24);
+    assert_eq!(json["labels"][0]["column_start"], 21);
+
+    let sarif: serde_json::Value =
+        serde_json::from_str(&render(input, DiagnosticFormat::Sarif)).unwrap();
+    assert_eq!(sarif["runs"][0]["columnKind"], "utf16CodeUnits");
+    let region = &sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"];
+    assert_eq!(region["startColumn"], 22);
+    assert_eq!(region["charOffset"], 21);
+    assert_eq!(region["charLength"], 1);
+
+    let gcc = render(input, DiagnosticFormat::Gcc);
+    assert!(gcc.starts_with("unknown.c:1:21: error: "), "{gcc}");
+}
+
+#[test]
+fn tokens_have_lines_and_columns() {
+    let mut session = Session::builder().build();
+    let file_id = session.add_source("lines.c", "int x;
+	char *é = \"é\";
+");
+    let tokens = session.lex(file_id);
+    let line_index = session.line_index(file_id);
+
+    let string = tokens.tokens().iter().find(|token| token.kind == TokenKind::STRING).unwrap();
+    let (start, end) = line_index.span(string.span);
+    assert_eq!((start, end), (LineCol { line: 1, col: 12 }, LineCol { line: 1, col: 16 }));
+    assert_eq!(line_index.to_encoding(start, Encoding::Utf16), LineCol { line: 1, col: 11 });
+
+    let cst = session.parse(file_id);
+    let (start, end) = session.line_index(file_id).span(cst.range());
+    assert_eq!((start.line, end.line), (0, 1));
+}
+
+const NEEDS_FIXES: &str = "main(void) {\n    int x = 1\n    return x;\n}\n";
+
+#[test]
+fn fix_its_are_rendered() {
+    let mut session = Session::builder().build();
+    let file_id = session.add_source("fix.c", NEEDS_FIXES);
+    session.lower(file_id);
+
+    let mut buffer = Buffer::no_color();
+    session.diagnostics_mut().flush_to(&mut buffer);
+    let rendered = String::from_utf8(buffer.into_inner()).unwrap();
+    assert!(rendered.contains("= help: insert `int`\n    1 | int main(void) {"), "{rendered}");
+    assert!(rendered.contains("= help: insert `;`\n    2 |     int x = 1;\n"), "{rendered}");
+
+    session.lower(file_id);
+    let mut buffer = Buffer::no_color();
+    session.diagnostics_mut().flush_as(DiagnosticFormat::Json, &mut buffer);
+    let rendered = String::from_utf8(buffer.into_inner()).unwrap();
+    let suggestion = rendered
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .find(|diagnostic| diagnostic["code"] == "E0001")
+        .map(|diagnostic| diagnostic["suggestions"][0].clone())
+        .unwrap();
+    assert_eq!(suggestion["replacement"], ";");
+    assert_eq!(suggestion["byte_start"], 26);
+    assert_eq!(suggestion["applicability"], "machine-applicable");
+}
+
+#[test]
+fn session_fixes_suggestions() {
+    let mut session = Session::builder().build();
+    let file_id = session.add_source("fix.c", NEEDS_FIXES);
+
+    let (fixed, applied) = session.fix(file_id);
+    assert_eq!(applied, 2);
+    assert_eq!(fixed, "int main(void) {\n    int x = 1;\n    return x;\n}\n");
+
+    let mut session = Session::builder().build();
+    let file_id = session.add_source("fix.c", fixed);
+    assert!(!session.parse(file_id).contains_errors());
+}
+
+#[test]
+fn guessed_fixes_are_not_applied() {
+    let mut session = Session::builder().build();
+    let file_id = session.add_source("guess.c", "int f(void) { return (1; }");
+
+    let (fixed, applied) = session.fix(file_id);
+    assert_eq!(applied, 0);
+    assert_eq!(fixed, "int f(void) { return (1; }");
+}
+
+#[test]
+fn semicolon_after_broken_expression_is_not_applied() {
+    let mut session = Session::builder().build();
+    let file_id = session.add_source("operand.c", "int x = 1 +;\n");
+
+    let (fixed, applied) = session.fix(file_id);
+    assert_eq!(applied, 0);
+    assert_eq!(fixed, "int x = 1 +;\n");
+}
+
+#[rstest]
+#[case::specifier("unsinged int x;", "unsigned")]
+#[case::tag("stuct point { int x; } origin;", "struct")]
+#[case::statement("int f(void) { retrun 0; }", "return")]
+#[case::typedef_name("typedef int length; lenght x;", "length")]
+fn misspelled_keywords(#[case] input: &str, #[case] expected: &str) {
+    let mut session = Session::builder().build();
+    let file_id = session.add_source("typo.c", input);
+    let tree = session.parse(file_id);
+
+    // The typo is only taken for the keyword by the grammar; the tree keeps
+    // the source as written.
+    assert_eq!(tree.text(), input);
+
+    let [diagnostic] = session.diagnostics().diagnostics.as_slice() else {
+        panic!("expected a single diagnostic, found {:#?}", session.diagnostics().diagnostics);
+    };
+    assert_eq!(diagnostic.code.as_deref(), Some("E0010"));
+
+    let [suggestion] = diagnostic.suggestions.as_slice() else {
+        panic!("expected a single suggestion, found {:#?}", diagnostic.suggestions);
+    };
+    assert_eq!(suggestion.replacement, expected);
+    assert_eq!(suggestion.message.as_deref(), Some(format!("did you mean `{expected}`?").as_str()));
+}
+
+/// Parses `input` with `options`, and renders its diagnostics one per line,
+/// leaving out the notes attached to them.
+fn render_gcc(options: Options, input: &str) -> Vec<String> {
+    let mut session = Session::builder().options(options).build();
+    let file_id = session.add_source("errors.c", input);
+    session.parse(file_id);
+
+    let mut buffer = Buffer::no_color();
+    session.diagnostics_mut().flush_as(DiagnosticFormat::Gcc, &mut buffer);
+    String::from_utf8(buffer.into_inner())
+        .unwrap()
+        .lines()
+        .filter(|line| !line.starts_with("errors.c") || !line.contains(": note: "))
+        .map(str::to_string)
+        .collect()
+}
+
+#[rstest]
+#[case::limited(3, 5)]
+#[case::unlimited(0, 30)]
+fn error_limit(#[case] limit: usize, #[case] expected: usize) {
+    let input = (0..30).map(|i| format!("int f{i}(void) {{ return @; }}\n")).collect::<String>();
+    let rendered = render_gcc(Options::builder().error_limit(limit).build(), &input);
+
+    assert_eq!(rendered.len(), expected, "{rendered:#?}");
+    if limit > 0 {
+        assert_eq!(
+            rendered[limit - 1],
+            format!("errors.c:{limit}:23: error: Unknown token encountered: '@' [E0000]")
+        );
+        assert_eq!(rendered[limit], "rcc: note: too many errors emitted (3), stopping now");
+        assert_eq!(rendered[limit + 1], "rcc: note: Use `-ferror-limit=0` to report every error.");
+    }
+}
+
+#[rstest]
+#[case::limited(6, [0, 1, 2, 7, 8, 9], Some(4))]
+#[case::unlimited(0, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9], None)]
+fn macro_backtrace_limit<const N: usize>(
+    #[case] limit: usize,
+    #[case] shown: [usize; N],
+    #[case] skipped: Option<usize>,
+) {
+    let input = (1..10).map(|i| format!("#define M{i} M{}\n", i - 1)).collect::<String>();
+    let input = format!("#define M0 @\n{input}int x = M9;\n");
+    let mut session =
+        Session::builder().options(Options::builder().macro_backtrace_limit(limit).build()).build();
+    let file_id = session.add_source("macros.c", &input);
+    session.parse(file_id);
+
+    let mut buffer = Buffer::no_color();
+    session.diagnostics_mut().flush_as(DiagnosticFormat::Gcc, &mut buffer);
+    let rendered = String::from_utf8(buffer.into_inner()).unwrap();
+    let expansions = rendered
+        .lines()
+        .filter_map(|line| line.split_once(": note: in expansion of macro `M"))
+        .map(|(_, name)| name.trim_end_matches('`').parse::<usize>().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(expansions, shown, "{rendered}");
+    let note = skipped.map(|skipped| {
+        format!(
+            "macros.c:1:12: note: skipping {skipped} expansions in the backtrace; use \
+             -fmacro-backtrace-limit=0 to see all"
+        )
+    });
+    assert_eq!(rendered.lines().find(|line| line.contains("skipping")), note.as_deref());
+}
+
+#[test]
+fn follow_on_errors_are_suppressed() {
+    let rendered = render_gcc(
+        Options::builder().build(),
+        "int main(void) {\n\
+         \x20   int x = 1 2 3 4;\n\
+         \x20   int y = ) ) );\n\
+         \x20   return x;\n\
+         }\n",
+    );
+
+    // One error per statement, rather than one per token skipped.
+    assert_eq!(
+        rendered,
+        [
+            "errors.c:2:15: error: Unexpected token encountered: '2'. [E0001]",
+            "errors.c:3:15: error: Unexpected token encountered: ')'. [E0001]",
+        ],
+    );
+}
+
+#[test]
+fn diagnostics_are_deduplicated_and_sorted() {
+    let mut diagnostics = DiagnosticsEngine::new();
+    let file_id = diagnostics.add_file("sorted.c", "int x;\nint y;\n");
+    let error = |range| {
+        Diagnostic::error()
+            .with_code("E0001")
+            .with_message("unexpected token")
+            .with_labels(vec![Label::primary(file_id, range)])
+    };
+
+    diagnostics.emit(error(7..10));
+    diagnostics.emit(error(0..3));
+    diagnostics.emit(error(7..10));
+
+    let mut buffer = Buffer::no_color();
+    diagnostics.flush_as(DiagnosticFormat::Gcc, &mut buffer);
+    assert_eq!(
+        String::from_utf8(buffer.into_inner()).unwrap(),
+        "sorted.c:1:1: error: unexpected token [E0001]\n\
+         sorted.c:2:1: error: unexpected token [E0001]\n"
+    );
+}
+
+#[test]
+fn session_lowers_gnu_attributes() {
+    let options = Options::builder().standard(Standard::Gnu11).build();
+    let mut session = Session::builder().options(options).build();
+    let file_id = session.add_source(
+        "gnu.c",
+        "extern int scanf(const char *__restrict, ...) __asm__(\"__isoc99_scanf\");\n\
+         __attribute__((noinline)) void run(void) { return; }\n\
+         extern int printf(const char *__restrict, ...) __attribute__((format(printf, 1, 2)));",
+    );
+
+    assert!(!session.parse(file_id).contains_errors());
+    assert!(!session.has_errors());
+
+    let ast = session.lower(file_id);
+    let [ExternDecl::Declaration(scanf), ExternDecl::Function(run), ExternDecl::Declaration(printf)] =
+        ast.functions.as_slice()
+    else {
+        panic!("expected a declaration, a function and a declaration, found {:#?}", ast.functions);
+    };
+
+    assert_eq!(scanf.var.to_string(), "scanf");
+    assert_eq!(scanf.asm_label.as_deref(), Some("__isoc99_scanf"));
+
+    assert_eq!(
+        run.attributes.iter().map(|attribute| attribute.name.to_string()).collect::<Vec<_>>(),
+        vec!["noinline"]
+    );
+
+    let [format] = printf.attributes.as_slice() else {
+        panic!("expected a single attribute, found {:#?}", printf.attributes);
+    };
+    assert_eq!(format.name.to_string(), "format");
+    assert_eq!(format.args, vec!["printf", "1", "2"]);
+}
+
+#[test]
+fn session_lowers_c23_attributes() {
+    let options = Options::builder().standard(Standard::C23).build();
+    let mut session = Session::builder().options(options).build();
+    let file_id = session.add_source(
+        "c23.c",
+        "[[nodiscard, gnu::always_inline]] constexpr int limit [[maybe_unused]] = 1'000;",
+    );
+
+    assert!(!session.parse(file_id).contains_errors());
+    assert!(!session.has_errors());
+
+    let ast = session.lower(file_id);
+    let [ExternDecl::Declaration(limit)] = ast.functions.as_slice() else {
+        panic!("expected a single declaration, found {:#?}", ast.functions);
+    };
+
+    assert_eq!(
+        limit.attributes.iter().map(|attribute| attribute.name.to_string()).collect::<Vec<_>>(),
+        vec!["nodiscard", "gnu::always_inline", "maybe_unused"]
+    );
+    assert!(limit
+        .specifiers
+        .contains(&DeclarationSpecifier::StorageClass(StorageClass::Constexpr)));
+}
+
+#[test]
+fn session_lowers_old_style_definitions() {
+    let mut session = Session::builder().build();
+    let file_id =
+        session.add_source("old.c", "char *skip(s, n, c) char *s; int m; { return s + n; }");
+
+    assert!(!session.parse(file_id).contains_errors());
+
+    let ast = session.lower(file_id);
+    let [ExternDecl::Function(skip)] = ast.functions.as_slice() else {
+        panic!("expected a single function, found {:#?}", ast.functions);
+    };
+    assert_eq!(
+        skip.params
+            .iter()
+            .map(|param| (param.name.to_string(), param.ty.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("s".to_string(), DataType::Pointer(Box::new(DataType::Char))),
+            ("n".to_string(), DataType::Int),
+            ("c".to_string(), DataType::Int),
+        ]
+    );
+
+    let mut buffer = Buffer::no_color();
+    session.diagnostics_mut().flush_to(&mut buffer);
+    let rendered = String::from_utf8(buffer.into_inner()).unwrap();
+
+    assert!(rendered.contains("warning[W0001]"), "{rendered}");
+    assert_eq!(rendered.matches("warning[W0002]").count(), 2, "{rendered}");
+    assert!(
+        rendered.contains("error[E0009]: declaration for parameter 'm' but no such parameter"),
+        "{rendered}"
+    );
+}
+
+/// Lowers `input` with the warning `flags`, and renders its diagnostics.
+#[test]
+fn session_lays_out_structs() {
+    let mut session = Session::builder().build();
+    let file_id = session.add_source(
+        "layout.c",
+        "#pragma pack(push, 2)\n\
+         struct packed { char c; long l; } p;\n\
+         #pragma pack(pop)\n\
+         struct s {\n\
+             _Alignas(8) char c;\n\
+             unsigned long long f : 3, : 0;\n\
+             long double d;\n\
+             int a[2][3];\n\
+             struct { short x; } in;\n\
+         } v;",
+    );
+
+    assert!(!session.parse(file_id).contains_errors());
+
+    let ast = session.lower(file_id);
+    let [ExternDecl::Declaration(p), ExternDecl::Declaration(v)] = ast.functions.as_slice() else {
+        panic!("expected two declarations, found {:#?}", ast.functions);
+    };
+    let (DataType::Struct(packed), DataType::Struct(s)) = (&p.ty, &v.ty) else {
+        panic!("expected two structs, found {:#?} and {:#?}", p.ty, v.ty);
+    };
+
+    let engine = LayoutEngine::new(DataModel::Lp64);
+    assert_eq!(packed.pack, Some(2));
+    assert_eq!(engine.offset_of(packed, "l"), Ok(2));
+    assert_eq!(engine.size_of(&p.ty), Ok(10));
+
+    assert_eq!(s.pack, None);
+    assert_eq!(
+        s.members.iter().map(|member| (member.ty.clone(), member.bit_width)).collect::<Vec<_>>(),
+        vec![
+            (DataType::Char, None),
+            (DataType::LongLong, Some(3)),
+            (DataType::LongLong, Some(0)),
+            (DataType::LongDouble, None),
+            (
+                DataType::Array(
+                    Box::new(DataType::Array(Box::new(DataType::Int), Some(3))),
+                    Some(2)
+                ),
+                None
+            ),
+            (s.members[5].ty.clone(), None),
+        ]
+    );
+    assert_eq!(s.members[0].alignment, Some(8));
+    let DataType::Struct(inner) = &s.members[5].ty else {
+        panic!("expected a struct, found {:#?}", s.members[5].ty);
+    };
+    assert_eq!(s.members[5].name.as_ref().map(ToString::to_string).as_deref(), Some("in"));
+    assert_eq!(inner.members[0].ty, DataType::Short);
+
+    let layout = engine.record_layout(s).unwrap();
+    assert_eq!(layout.members[1].bit_offset, 8);
+    assert_eq!(engine.offset_of(s, "d"), Ok(16));
+    assert_eq!(engine.offset_of(s, "a[1][2]"), Ok(52));
+    assert_eq!(engine.offset_of(s, "in.x"), Ok(56));
+    assert_eq!(engine.size_of(&v.ty), Ok(64));
+}
+
+fn render_warnings(input: &str, flags: &[&str]) -> String {
+    let mut warnings = WarningOptions::default();
+    for flag in flags {
+        warnings.apply_flag(flag).unwrap();
+    }
+    let options = Options::builder().warnings(warnings).build();
+    let mut session = Session::builder().options(options).build();
+    let file_id = session.add_source("old.c", input);
+    session.lower(file_id);
+
+    let mut buffer = Buffer::no_color();
+    session.diagnostics_mut().flush_to(&mut buffer);
+    String::from_utf8(buffer.into_inner()).unwrap()
+}
+
+#[rstest]
+#[case::default(
+    &[],
+    Some("warning[W0002]: type of parameter 'n' defaults to 'int' [-Wimplicit-int]")
+)]
+#[case::disabled(&["-Wno-implicit-int"], None)]
+#[case::promoted(
+    &["-Werror=implicit-int"],
+    Some("error[W0002]: type of parameter 'n' defaults to 'int' [-Werror=implicit-int]")
+)]
+#[case::all_promoted(
+    &["-Werror", "-Wno-error=old-style-definition"],
+    Some("error[W0002]: type of parameter 'n' defaults to 'int' [-Werror=implicit-int]")
+)]
+fn warning_flags(#[case] flags: &[&str], #[case] expected: Option<&str>) {
+    let rendered = render_warnings("int twice(n) { return n * 2; }", flags);
+
+    match expected {
+        Some(expected) => assert!(rendered.contains(expected), "{rendered}"),
+        None => assert!(!rendered.contains("W0002"), "{rendered}"),
+    }
+    assert!(
+        rendered.contains("warning[W0001]: old-style definition of 'twice' is obsolescent \
+                           [-Wold-style-definition]"),
+        "{rendered}"
+    );
+}
+
+#[rstest]
+#[case::main("int main(void) { }", &[], "")]
+#[case::returns_in_both_branches("int f(int x) { if (x) return 1; else return 0; }", &[], "")]
+#[case::void("void f(int x) { if (x) return; }", &[], "")]
+#[case::missing_return(
+    "int f(int x) {\n    if (x) return 1;\n}",
+    &[],
+    "warning[W0009]: non-void function `f` does not return a value in all control paths \
+     [-Wreturn-type]\n  ┌─ old.c:3:1"
+)]
+#[case::missing_return_disabled("int f(int x) { if (x) return 1; }", &["-Wno-return-type"], "")]
+#[case::missing_return_promoted(
+    "int f(void) { }",
+    &["-Werror=return-type"],
+    "error[W0009]: non-void function `f` does not return a value in all control paths \
+     [-Werror=return-type]"
+)]
+#[case::stray_continue(
+    "void f(void) {\n    continue;\n}",
+    &[],
+    "error[E0015]: `continue` statement not in a loop statement\n  ┌─ old.c:2:5"
+)]
+#[case::case_outside_switch(
+    "int f(int x) {\n    switch (x) { case 1: break; }\n    case 2: return x;\n}",
+    &[],
+    "error[E0016]: `case` label not in a switch statement\n  ┌─ old.c:3:5"
+)]
+#[case::undefined_label(
+    "void f(void) {\n    goto out;\n}",
+    &[],
+    "error[E0018]: use of undeclared label `out`\n  ┌─ old.c:2:5"
+)]
+fn control_flow(#[case] input: &str, #[case] flags: &[&str], #[case] expected: &str) {
+    let rendered = render_warnings(input, flags);
+
+    if expected.is_empty() {
+        assert_eq!(rendered, "");
+    } else {
+        assert!(rendered.starts_with(expected), "{rendered}");
+    }
+}
+
+#[test]
+fn warning_pragmas() {
+    let rendered = render_warnings(
+        "#pragma GCC diagnostic push\n\
+         #pragma GCC diagnostic ignored \"-Wimplicit-int\"\n\
+         int quiet(n) { return n; }\n\
+         #pragma GCC diagnostic pop\n\
+         int loud(n) { return n; }\n",
+        &[],
+    );
+
+    assert_eq!(rendered.matches("warning[W0002]").count(), 1, "{rendered}");
+    assert!(rendered.contains("old.c:5:10"), "{rendered}");
+}
+
+#[test]
+fn old_style_definitions_are_removed_in_c23() {
+    let options = Options::builder().standard(Standard::C23).build();
+    let mut session = Session::builder().options(options).build();
+    let file_id = session.add_source("old.c", "int twice(x) int x; { return x * 2; }");
+
+    session.parse(file_id);
+    assert!(session.has_errors());
+}
+
+#[rstest]
+#[case::digit_separators(Standard::C11, "int x = 1'000;", true)]
+#[case::digit_separators_c23(Standard::C23, "int x = 1'000;", false)]
+#[case::binary(Standard::C11, "int x = 0b1010;", true)]
+#[case::binary_gnu(Standard::Gnu11, "int x = 0b1010;", false)]
+#[case::binary_c23(Standard::C23, "int x = 0b1010;", false)]
+fn literals_depend_on_standard(
+    #[case] standard: Standard,
+    #[case] input: &str,
+    #[case] has_errors: bool,
+) {
+    let options = Options::builder().standard(standard).build();
+    let mut session = Session::builder().options(options).build();
+    let file_id = session.add_source("literal.c", input);
+
+    session.lex(file_id);
+    assert_eq!(session.has_errors(), has_errors);
+}
+
+/// Lexes `input` with `options`, returning its tokens (but the last) as
+/// `(kind, lexeme, source)` and the codes and messages of its diagnostics.
+fn lex(options: Options, input: &str) -> (Vec<(TokenKind, String, &str)>, Vec<String>) {
+    let mut session = Session::builder().options(options).build();
+    let file_id = session.add_source("lex.c", input);
+
+    let stream = session.lex(file_id);
+    let tokens = stream.tokens()[..stream.tokens().len() - 1]
+        .iter()
+        .map(|token| {
+            (token.kind, token.lexeme.to_string(), &input[*token.span.start()..*token.span.end()])
+        })
+        .collect();
+    let diagnostics = session
+        .diagnostics()
+        .diagnostics
+        .iter()
+        .map(|diagnostic| {
+            format!("{}: {}", diagnostic.code.as_deref().unwrap_or_default(), diagnostic.message)
+        })
+        .collect();
+
+    (tokens, diagnostics)
+}
+
+#[rstest]
+#[case::utf8_bom(None, b"\xEF\xBB\xBFint x;", "int x;", &[])]
+#[case::utf16_bom(None, b"\xFF\xFEi\0n\0t\0 \0\xE9\0;\0", "int \u{E9};", &[])]
+#[case::latin1(Some("latin1"), b"char *s = \"\xE9\";", "char *s = \"\u{E9}\";", &[])]
+#[case::invalid(
+    None,
+    b"char *s = \"\xE9\";",
+    "char *s = \"\u{FFFD}\";",
+    &["W0004: invalid UTF-8 in the source: `\\xE9` [-Winvalid-source-encoding]"]
+)]
+fn sources_are_decoded(
+    #[case] input_charset: Option<&str>,
+    #[case] bytes: &[u8],
+    #[case] text: &str,
+    #[case] expected: &[&str],
+) {
+    let input_charset =
+        input_charset.map_or(charset::UTF_8, |name| charset::charset(name).unwrap());
+    let options = Options::builder().input_charset(input_charset).build();
+    let mut session = Session::builder().options(options).build();
+    let file_id = session.add_bytes("decoded.c", bytes);
+
+    assert_eq!(session.diagnostics().files.get(file_id).unwrap().source(), text);
+    let diagnostics = session
+        .diagnostics()
+        .diagnostics
+        .iter()
+        .map(|diagnostic| {
+            format!("{}: {}", diagnostic.code.as_deref().unwrap_or_default(), diagnostic.message)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(diagnostics, expected);
+    assert!(!session.has_errors());
+}
+
+#[test]
+fn lines_are_spliced() {
+    let (tokens, diagnostics) = lex(Options::default(), "in\\\nt x = 1\\\r\n2;\\\n");
+
+    assert_eq!(
+        tokens,
+        [
+            (TokenKind::INT_KW, "int".to_string(), "in\\\nt"),
+            (TokenKind::IDENTIFIER, "x".to_string(), "x"),
+            (TokenKind::EQ, "=".to_string(), "="),
+            (TokenKind::INTEGER_CONSTANT, "12".to_string(), "1\\\r\n2"),
+            (TokenKind::SEMICOLON, ";".to_string(), ";"),
+        ]
+    );
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
+#[test]
+fn digraphs_are_punctuators() {
+    let (tokens, diagnostics) = lex(Options::default(), "<: :> <% %> %:");
+
+    assert_eq!(
+        tokens.iter().map(|(kind, ..)| *kind).collect::<Vec<_>>(),
+        [
+            TokenKind::LBRACKET,
+            TokenKind::RBRACKET,
+            TokenKind::LBRACE,
+            TokenKind::RBRACE,
+            TokenKind::UNKNOWN,
+        ]
+    );
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+}
+
+#[test]
+fn identifiers_are_normalized() {
+    let input = "int caf\\u00E9 = cafe\u{301} + café;";
+    let (tokens, diagnostics) = lex(Options::default(), input);
+
+    let names = tokens
+        .iter()
+        .filter(|(kind, ..)| *kind == TokenKind::IDENTIFIER)
+        .map(|(_, lexeme, _)| lexeme.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["café", "café", "café"]);
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
+#[rstest]
+#[case::x86_64("x86_64-linux-gnu", &["8", "8", "int", "1"])]
+#[case::aarch64("aarch64-linux-gnu", &["8", "8", "unsigned int", "__x86_64__"])]
+#[case::riscv64("riscv64-linux-gnu", &["8", "8", "int", "__x86_64__"])]
+#[case::i686("i686-linux-gnu", &["4", "4", "long int", "__x86_64__"])]
+fn predefined_macros_follow_the_target(#[case] triple: &str, #[case] expected: &[&str]) {
+    let options = Options::builder().target(triple.parse().unwrap()).build();
+    let (tokens, diagnostics) = lex(
+        options,
+        "__SIZEOF_POINTER__; __SIZEOF_LONG__; __WCHAR_TYPE__; __x86_64__;",
+    );
+
+    let values = tokens
+        .split(|(kind, ..)| *kind == TokenKind::SEMICOLON)
+        .filter(|tokens| !tokens.is_empty())
+        .map(|tokens| tokens.iter().map(|(_, lexeme, _)| lexeme.as_str()).collect::<Vec<_>>())
+        .map(|lexemes| lexemes.join(" "))
+        .collect::<Vec<_>>();
+    assert_eq!(values, expected);
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
+#[rstest]
+#[case::converted(
+    Options::builder().trigraphs(true).build(),
+    &[TokenKind::IDENTIFIER, TokenKind::LBRACKET, TokenKind::RBRACKET, TokenKind::SEMICOLON],
+    &[
+        "W0003: trigraph ??( converted to [ [-Wtrigraphs]",
+        "W0003: trigraph ??) converted to ] [-Wtrigraphs]",
+    ]
+)]
+#[case::ignored(
+    Options::default(),
+    &[
+        TokenKind::IDENTIFIER,
+        TokenKind::QUESTION,
+        TokenKind::QUESTION,
+        TokenKind::LPAREN,
+        TokenKind::QUESTION,
+        TokenKind::QUESTION,
+        TokenKind::RPAREN,
+        TokenKind::SEMICOLON,
+    ],
+    &[
+        "W0003: trigraph ??( ignored, use -trigraphs to enable [-Wtrigraphs]",
+        "W0003: trigraph ??) ignored, use -trigraphs to enable [-Wtrigraphs]",
+    ]
+)]
+#[case::removed_in_c23(
+    Options::builder().standard(Standard::C23).trigraphs(true).build(),
+    &[
+        TokenKind::IDENTIFIER,
+        TokenKind::QUESTION,
+        TokenKind::QUESTION,
+        TokenKind::LPAREN,
+        TokenKind::QUESTION,
+        TokenKind::QUESTION,
+        TokenKind::RPAREN,
+        TokenKind::SEMICOLON,
+    ],
+    &[]
+)]
+fn trigraphs(#[case] options: Options, #[case] kinds: &[TokenKind], #[case] expected: &[&str]) {
+    let (tokens, diagnostics) = lex(options, "a??(??); // ??!");
+
+    assert_eq!(tokens.iter().map(|(kind, ..)| *kind).collect::<Vec<_>>(), kinds);
+    assert_eq!(diagnostics, expected);
+}
+
+#[test]
+fn trigraphs_keep_their_spans() {
+    let options = Options::builder().trigraphs(true).build();
+    let (tokens, _) = lex(options, "a??(1??/\n2??);");
+
+    assert_eq!(
+        tokens,
+        [
+            (TokenKind::IDENTIFIER, "a".to_string(), "a"),
+            (TokenKind::LBRACKET, "[".to_string(), "??("),
+            (TokenKind::INTEGER_CONSTANT, "12".to_string(), "1??/\n2"),
+            (TokenKind::RBRACKET, "]".to_string(), "??)"),
+            (TokenKind::SEMICOLON, ";".to_string(), ";"),
+        ]
+    );
+}
+
+#[rstest]
+#[case::goto("goto label;")]
+#[case::labeled("label: return 0;")]
+#[case::expression("x = 42;")]
+#[case::if_stmt("if (x > 0) { return x; }")]
+#[case::if_else("if (x > 0) { return x; } else { return -x; }")]
+#[case::while_loop("while (x > 0) { x--; }")]
+#[case::do_while("do { x--; } while (x > 0);")]
+#[case::continue_stmt("continue;")]
+#[case::break_stmt("break;")]
+#[case::return_stmt("return 0;")]
+#[case::return_void("return;")]
+#[case::compound("{ int x = 42; return x; }")]
+fn statements(#[case] input: &str) {
+    let mut session = Session::builder().build();
+    let file_id = session.add_source("stmt.c", input);
+
+    let cst = parser::parse_tree_with_diagnostics(
+        input,
+        TreeKind::Statement,
+        Standard::default(),
+        session.diagnostics_mut(),
+        file_id,
+    );
+
+    assert_eq!(cst.kind(), TreeKind::Statement);
+    assert!(!cst.contains_errors(), "{cst}");
+}
+
+#[rstest]
+#[case::attribute(TreeKind::Attribute, "format(printf, 1, 2)")]
+#[case::asm_label(TreeKind::AsmLabel, "__asm__(\"fopen64\")")]
+#[case::asm_operand_list(TreeKind::AsmOperandList, ": \"=r\"(dst), [src] \"r\"(src)")]
+#[case::asm_operand(TreeKind::AsmOperand, "[dst] \"=r\"(dst)")]
+#[case::typeof_specifier(TreeKind::TypeofSpecifier, "typeof(x + 1)")]
+#[case::statement_expression(TreeKind::StatementExpression, "({ int y = x; y * y; })")]
+fn gnu_extensions(#[case] kind: TreeKind, #[case] input: &str) {
+    let mut session = Session::builder().build();
+    let file_id = session.add_source("gnu.c", input);
+
+    let cst = parser::parse_tree_with_diagnostics(
+        input,
+        kind,
+        Standard::Gnu11,
+        session.diagnostics_mut(),
+        file_id,
+    );
+
+    assert_eq!(cst.kind(), kind);
+    assert!(!cst.contains_errors(), "{cst}");
+}
+
+/// Runs the fixtures of `testdata`, as `rcc test` does. Out-of-date
+/// snapshots are rewritten with `RCC_BLESS=1`.
+#[test]
+fn fixtures() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../testdata");
+    let bless = std::env::var_os("RCC_BLESS").is_some();
+
+    let failures = fixtures::run(&root, bless)
+        .unwrap()
+        .into_iter()
+        .filter_map(|result| match result.outcome {
+            Outcome::Failed(failures) => {
+                Some(format!("{}:\n    {}", result.name, failures.join("\n    ")))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert!(
+        failures.is_empty(),
+        "{} fixtures failed (`rcc test --bless` rewrites snapshots):\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
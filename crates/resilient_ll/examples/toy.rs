@@ -1,9 +1,15 @@
-use std::{
-    cell::Cell,
-    fmt::{self, Display},
-};
+//! A toy language parsed with `resilient_ll`, from matklad's tutorial.
+//!
+//! Run with `cargo run -p resilient_ll --example toy`.
+
+use std::fmt::{self, Display};
 
 use owo_colors::OwoColorize;
+use resilient_ll::{
+    Language,
+    MarkClosed,
+    MarkOpened,
+};
 use smartstring::alias::String;
 
 use tracing_subscriber::fmt::Subscriber;
@@ -67,7 +73,7 @@ impl Display for TokenKind {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 enum TreeKind {
     ErrorTree,
     File,
@@ -96,7 +102,7 @@ macro_rules! format_to {
     };
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct Token {
     kind: TokenKind,
     text: String,
@@ -108,12 +114,6 @@ impl Display for Token {
     }
 }
 
-#[derive(Debug)]
-struct Span {
-    start: usize,
-    end: usize,
-}
-
 pub struct Tree {
     kind: TreeKind,
     children: Vec<Child>,
@@ -233,101 +233,60 @@ fn lex(mut text: &str) -> Vec<Token> {
     }
 }
 
+/// The toy language, as far as the parser core is concerned.
 #[derive(Debug)]
-enum Event {
-    Open { kind: TreeKind },
-    Close,
-    Advance,
-}
+enum Toy {}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct MarkOpened {
-    index: usize,
-}
+impl Language for Toy {
+    type TokenKind = TokenKind;
+    type TreeKind = TreeKind;
+    type Token = Token;
+
+    const EOF: TokenKind = TokenKind::Eof;
+    const ERROR_TREE: TreeKind = TreeKind::ErrorTree;
 
-impl MarkOpened {
-    fn new(index: usize) -> MarkOpened {
-        MarkOpened { index }
+    fn token_kind(token: &Token) -> TokenKind {
+        token.kind
     }
 }
 
-struct MarkClosed {
-    index: usize,
+impl resilient_ll::Tree<Toy> for Tree {
+    fn push_token(&mut self, token: Token) {
+        self.children.push(Child::Token(token));
+    }
+
+    fn push_tree(&mut self, tree: Tree) {
+        self.children.push(Child::Tree(tree));
+    }
 }
 
 struct Parser {
-    tokens: Vec<Token>,
-    pos: usize,
-    fuel: Cell<u32>,
-    events: Vec<Event>,
+    core: resilient_ll::Parser<Toy>,
 }
 
 impl Parser {
     fn new(tokens: Vec<Token>) -> Parser {
-        Parser { tokens, pos: 0, fuel: Cell::new(256), events: Vec::new() }
+        Parser { core: resilient_ll::Parser::new(tokens) }
     }
 
     fn build_tree(self) -> Tree {
-        let mut tokens = self.tokens.into_iter();
-        let mut events = self.events;
-        let mut stack = Vec::new();
-
-        // Special case: pop the last `Close` event to ensure
-        // that the stack is non-empty inside the loop.
-        assert!(matches!(events.pop(), Some(Event::Close)));
-
-        for event in events {
-            match event {
-                // Starting a new node; just push an empty tree to the stack.
-                Event::Open { kind } => stack.push(Tree { kind, children: Vec::new() }),
-
-                // A tree is done.
-                // Pop it off the stack and append to a new current tree.
-                Event::Close => {
-                    let tree = stack.pop().unwrap();
-                    stack.last_mut().unwrap().children.push(Child::Tree(tree));
-                }
-
-                // Consume a token and append it to the current tree.
-                Event::Advance => {
-                    let token = tokens.next().unwrap();
-                    stack.last_mut().unwrap().children.push(Child::Token(token));
-                }
-            }
-        }
-
-        let tree = stack.pop().unwrap();
-
-        // Our parser will guarantee that all the trees are closed
-        // and cover the entirety of tokens.
-        assert!(stack.is_empty());
-        assert!(tokens.next().is_none());
-        tree
+        self.core.build_tree(|kind| Tree { kind, children: Vec::new() })
     }
 
     fn open(&mut self) -> MarkOpened {
-        let mark = MarkOpened { index: self.events.len() };
-        self.events.push(Event::Open { kind: TreeKind::ErrorTree });
-        mark
+        self.core.open()
     }
 
     fn open_before(&mut self, m: MarkClosed) -> MarkOpened {
-        let mark = MarkOpened { index: m.index };
-        self.events.insert(m.index, Event::Open { kind: TreeKind::ErrorTree });
-        mark
+        self.core.open_before(m)
     }
 
     fn close(&mut self, m: MarkOpened, kind: TreeKind) -> MarkClosed {
-        self.events[m.index] = Event::Open { kind };
-        self.events.push(Event::Close);
-        MarkClosed { index: m.index }
+        self.core.close(m, kind)
     }
 
     fn advance(&mut self) {
-        assert!(!self.eof());
-        self.fuel.set(256);
-        self.events.push(Event::Advance);
-        self.pos += 1;
+        self.core.advance()
     }
 
     fn advance_with_error(&mut self, error: &str) {
@@ -339,32 +298,23 @@ impl Parser {
     }
 
     fn eof(&self) -> bool {
-        self.pos == self.tokens.len()
+        self.core.eof()
     }
 
     fn nth(&self, lookahead: usize) -> TokenKind {
-        if self.fuel.get() == 0 {
-            panic!("parser is stuck")
-        }
-        self.fuel.set(self.fuel.get() - 1);
-        self.tokens.get(self.pos + lookahead).map_or(TokenKind::Eof, |it| it.kind)
+        self.core.nth(lookahead)
     }
 
     fn at(&self, kind: TokenKind) -> bool {
-        self.nth(0) == kind
+        self.core.at(kind)
     }
 
     fn at_any(&self, kinds: &[TokenKind]) -> bool {
-        kinds.contains(&self.nth(0))
+        self.core.at_any(kinds)
     }
 
     fn eat(&mut self, kind: TokenKind) -> bool {
-        if self.at(kind) {
-            self.advance();
-            true
-        } else {
-            false
-        }
+        self.core.eat(kind)
     }
 
     fn expect(&mut self, kind: TokenKind) {
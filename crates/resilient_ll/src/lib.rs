@@ -0,0 +1,228 @@
+//! The core of a resilient LL parser, as described in matklad's
+//! [Resilient LL Parsing Tutorial](https://matklad.github.io/2023/05/21/resilient-ll-parsing-tutorial.html).
+//!
+//! A [`Parser`] doesn't build a tree directly. Grammar functions record a flat
+//! list of [`Event`]s by calling [`Parser::open`], [`Parser::advance`] and
+//! [`Parser::close`], and the tree is assembled afterwards by
+//! [`Parser::build_tree`]. This makes error recovery cheap (an error is just
+//! another tree), and lets left-recursive constructs such as binary
+//! expressions be wrapped retroactively with [`Parser::open_before`].
+//!
+//! The parser is generic over a [`Language`], which supplies the kinds of
+//! tokens and trees. See `examples/toy.rs` for a complete, small language.
+
+use std::{
+    cell::Cell,
+    fmt,
+};
+
+/// The language-specific types a [`Parser`] works with.
+pub trait Language {
+    type TokenKind: Copy + PartialEq + fmt::Debug;
+    type TreeKind: Copy + fmt::Debug;
+    type Token: Clone + fmt::Debug;
+
+    /// The kind of token reported when looking past the end of the input.
+    const EOF: Self::TokenKind;
+
+    /// The kind of a tree which was opened but not yet closed. Trees produced
+    /// by error recovery are of this kind too.
+    const ERROR_TREE: Self::TreeKind;
+
+    fn token_kind(token: &Self::Token) -> Self::TokenKind;
+}
+
+/// A tree which [`Parser::build_tree`] can attach children to.
+pub trait Tree<L: Language> {
+    fn push_token(&mut self, token: L::Token);
+
+    fn push_tree(&mut self, tree: Self);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event<K> {
+    Open { kind: K },
+    Close,
+    Advance,
+}
+
+/// A tree which was opened with [`Parser::open`] and must be closed with
+/// [`Parser::close`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarkOpened {
+    index: usize,
+}
+
+/// A tree which was closed with [`Parser::close`] and can still be wrapped
+/// in a new tree with [`Parser::open_before`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarkClosed {
+    index: usize,
+}
+
+/// The number of lookahead calls the parser may make without consuming a
+/// token before it is considered stuck.
+const FUEL: u32 = 256;
+
+pub struct Parser<L: Language> {
+    tokens: Vec<L::Token>,
+    pos:    usize,
+    fuel:   Cell<u32>,
+    events: Vec<Event<L::TreeKind>>,
+}
+
+impl<L: Language> Clone for Parser<L> {
+    fn clone(&self) -> Self {
+        Self {
+            tokens: self.tokens.clone(),
+            pos:    self.pos,
+            fuel:   self.fuel.clone(),
+            events: self.events.clone(),
+        }
+    }
+}
+
+impl<L: Language> fmt::Debug for Parser<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Parser")
+            .field("tokens", &self.tokens)
+            .field("pos", &self.pos)
+            .field("fuel", &self.fuel)
+            .field("events", &self.events)
+            .finish()
+    }
+}
+
+impl<L: Language> Parser<L> {
+    pub fn new(tokens: Vec<L::Token>) -> Parser<L> {
+        Parser { tokens, pos: 0, fuel: Cell::new(FUEL), events: Vec::new() }
+    }
+
+    pub fn tokens(&self) -> &[L::Token] {
+        &self.tokens
+    }
+
+    /// The index of the current token.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn events(&self) -> &[Event<L::TreeKind>] {
+        &self.events
+    }
+
+    pub fn open(&mut self) -> MarkOpened {
+        let mark = MarkOpened { index: self.events.len() };
+        self.events.push(Event::Open { kind: L::ERROR_TREE });
+        mark
+    }
+
+    /// Opens a tree which starts before the already closed tree `m`, so that
+    /// `m` becomes its first child.
+    pub fn open_before(&mut self, m: MarkClosed) -> MarkOpened {
+        let mark = MarkOpened { index: m.index };
+        self.events.insert(m.index, Event::Open { kind: L::ERROR_TREE });
+        mark
+    }
+
+    pub fn close(&mut self, m: MarkOpened, kind: L::TreeKind) -> MarkClosed {
+        self.events[m.index] = Event::Open { kind };
+        self.events.push(Event::Close);
+        MarkClosed { index: m.index }
+    }
+
+    pub fn advance(&mut self) {
+        assert!(!self.eof());
+        self.fuel.set(FUEL);
+        self.events.push(Event::Advance);
+        self.pos += 1;
+    }
+
+    /// Whether all tokens (up to a trailing [`Language::EOF`] token, if any)
+    /// have been consumed.
+    pub fn eof(&self) -> bool {
+        self.at(L::EOF)
+    }
+
+    /// The kind of the token `lookahead` tokens ahead of the current one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parser has looked ahead too many times without
+    /// consuming a token, which means a grammar function is stuck in a loop.
+    pub fn nth(&self, lookahead: usize) -> L::TokenKind {
+        self.nth_token(lookahead).map_or(L::EOF, L::token_kind)
+    }
+
+    /// The token `lookahead` tokens ahead of the current one; see
+    /// [`Parser::nth`].
+    pub fn nth_token(&self, lookahead: usize) -> Option<&L::Token> {
+        if self.fuel.get() == 0 {
+            panic!(
+                "Parser ran out of fuel at {:?}. This is likely a bug in the parser related to \
+                 either error recovery or left recursion. Please report this issue to \
+                 https://github.com/pulanski/rcc/issues/new",
+                self.tokens.get(self.pos)
+            );
+        }
+        self.fuel.set(self.fuel.get() - 1);
+        self.tokens.get(self.pos + lookahead)
+    }
+
+    pub fn at(&self, kind: L::TokenKind) -> bool {
+        self.nth(0) == kind
+    }
+
+    pub fn at_any(&self, kinds: &[L::TokenKind]) -> bool {
+        kinds.contains(&self.nth(0))
+    }
+
+    pub fn eat(&mut self, kind: L::TokenKind) -> bool {
+        if self.at(kind) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Replays the recorded events into a tree, creating each node with
+    /// `new_tree`.
+    pub fn build_tree<T: Tree<L>>(self, mut new_tree: impl FnMut(L::TreeKind) -> T) -> T {
+        let mut tokens = self.tokens.into_iter();
+        let mut events = self.events;
+        let mut stack = Vec::new();
+
+        // Special case: pop the last `Close` event to ensure
+        // that the stack is non-empty inside the loop.
+        assert!(matches!(events.pop(), Some(Event::Close)));
+
+        for event in events {
+            match event {
+                // Starting a new node; just push an empty tree to the stack.
+                Event::Open { kind } => stack.push(new_tree(kind)),
+
+                // A tree is done.
+                // Pop it off the stack and append to a new current tree.
+                Event::Close => {
+                    let tree = stack.pop().unwrap();
+                    stack.last_mut().unwrap().push_tree(tree);
+                }
+
+                // Consume a token and append it to the current tree.
+                Event::Advance => {
+                    let token = tokens.next().unwrap();
+                    stack.last_mut().unwrap().push_token(token);
+                }
+            }
+        }
+
+        let tree = stack.pop().unwrap();
+
+        // Our parser will guarantee that all the trees are closed
+        // and cover the entirety of tokens (save for a trailing EOF).
+        assert!(stack.is_empty());
+        assert!(tokens.all(|token| L::token_kind(&token) == L::EOF));
+        tree
+    }
+}
@@ -0,0 +1,184 @@
+#[allow(dead_code)]
+#[path = "../examples/toy.rs"]
+mod toy;
+
+use resilient_ll::{
+    Language,
+    Parser,
+};
+
+#[test]
+fn parses_valid_input() {
+    let tree = toy::parse("fn add(x: i32, y: i32) -> i32 { return x + y * 2; }");
+
+    assert_eq!(
+        format!("{tree:?}"),
+        "\
+File
+  Fn
+    'fn'
+    'add'
+    ParamList
+      '('
+      Param
+        'x'
+        ':'
+        TypeExpr
+          'i32'
+        ','
+      Param
+        'y'
+        ':'
+        TypeExpr
+          'i32'
+      ')'
+    '->'
+    TypeExpr
+      'i32'
+    Block
+      '{'
+      StmtReturn
+        'return'
+        ExprBinary
+          ExprName
+            'x'
+          '+'
+          ExprBinary
+            ExprName
+              'y'
+            '*'
+            ExprLiteral
+              '2'
+        ';'
+      '}'
+"
+    );
+}
+
+#[test]
+fn recovers_from_errors() {
+    let tree = toy::parse("fn f1(x: i32,\nfn f2(x: i32,, z: i32) {}\nfn f3() {\n  let x = ;\n}");
+
+    assert_eq!(
+        format!("{tree:?}"),
+        "\
+File
+  Fn
+    'fn'
+    'f1'
+    ParamList
+      '('
+      Param
+        'x'
+        ':'
+        TypeExpr
+          'i32'
+        ','
+  Fn
+    'fn'
+    'f2'
+    ParamList
+      '('
+      Param
+        'x'
+        ':'
+        TypeExpr
+          'i32'
+        ','
+  ErrorTree
+    ','
+    'z'
+    ':'
+    'i32'
+    ')'
+    '{'
+    '}'
+  Fn
+    'fn'
+    'f3'
+    ParamList
+      '('
+      ')'
+    Block
+      '{'
+      StmtLet
+        'let'
+        'x'
+        '='
+        ';'
+      '}'
+"
+    );
+}
+
+#[derive(Debug)]
+enum Digits {}
+
+impl Language for Digits {
+    type TokenKind = char;
+    type TreeKind = &'static str;
+    type Token = char;
+
+    const EOF: char = '\0';
+    const ERROR_TREE: &'static str = "error";
+
+    fn token_kind(token: &char) -> char {
+        *token
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Node {
+    kind:     &'static str,
+    children: Vec<Result<char, Node>>,
+}
+
+impl resilient_ll::Tree<Digits> for Node {
+    fn push_token(&mut self, token: char) {
+        self.children.push(Ok(token));
+    }
+
+    fn push_tree(&mut self, tree: Node) {
+        self.children.push(Err(tree));
+    }
+}
+
+#[test]
+fn open_before_wraps_closed_tree() {
+    let mut p = Parser::<Digits>::new(vec!['1', '+', '2', '\0']);
+
+    let root = p.open();
+    let lhs = p.open();
+    p.advance();
+    let lhs = p.close(lhs, "lit");
+    let binary = p.open_before(lhs);
+    p.advance();
+    let rhs = p.open();
+    p.advance();
+    p.close(rhs, "lit");
+    p.close(binary, "binary");
+    assert!(p.eof());
+    p.close(root, "root");
+
+    let tree = p.build_tree(|kind| Node { kind, children: Vec::new() });
+    let lit = |c| Err(Node { kind: "lit", children: vec![Ok(c)] });
+    assert_eq!(
+        tree,
+        Node {
+            kind:     "root",
+            children: vec![Err(Node { kind: "binary", children: vec![lit('1'), Ok('+'), lit('2')] })],
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "Parser ran out of fuel")]
+fn stuck_parser_runs_out_of_fuel() {
+    let p = Parser::<Digits>::new(vec!['1', '\0']);
+
+    while !p.eof() {
+        if p.at('2') {
+            break;
+        }
+    }
+}
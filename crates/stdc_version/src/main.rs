@@ -1,13 +1,9 @@
-use std::env;
 use std::fs::File;
 use std::io::{
     self,
     Write,
 };
-use std::process::{
-    Command,
-    Output,
-};
+use std::process::Command;
 
 fn create_temp_c_file(c_code: &str) -> io::Result<()> {
     let mut file = File::create("temp.c")?;
@@ -20,13 +16,10 @@ fn preprocess_stdc_version_with_clang() -> io::Result<String> {
 
     let output = Command::new("clang").arg("-E").arg("temp.c").arg("-o").arg("temp.out").output();
 
-    let output = match output {
-        Ok(output) => output,
-        Err(e) => {
-            println!("Error: {e}");
-            return Err(e);
-        }
-    };
+    if let Err(e) = output {
+        println!("Error: {e}");
+        return Err(e);
+    }
 
     // Remove any lines that start with a hash sign
     let output = std::fs::read_to_string("temp.out")?;
@@ -41,10 +34,7 @@ fn preprocess_stdc_version_with_clang() -> io::Result<String> {
 }
 
 fn current_stdc_version() -> Option<String> {
-    match preprocess_stdc_version_with_clang() {
-        Ok(output) => Some(output),
-        _ => None,
-    }
+    preprocess_stdc_version_with_clang().ok()
 }
 
 fn main() {
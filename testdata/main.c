@@ -1,3 +1,4 @@
+// known-bug: character constants such as `'A'` are not lexed yet
 int main(int argc, char **argv) {
     int integerVar = 42;
     float floatVar = 3.14;
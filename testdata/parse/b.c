@@ -1,3 +1,4 @@
+// known-bug: `#include` is left in the token stream, so it reaches the parser
 // int demo_main(int argc, char **argv) {
 //   int foo = 10;
 //   int bar = 20;
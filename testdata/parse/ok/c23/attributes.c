@@ -0,0 +1,24 @@
+// compile-flags: --std=c23
+[[nodiscard]] int compute(void);
+[[deprecated("use compute")]] int old_compute(void);
+[[gnu::always_inline, maybe_unused]] static int helper(int x) {
+    return x;
+}
+
+struct [[deprecated]] point {
+    [[maybe_unused]] int x;
+    int y;
+};
+
+int values [[maybe_unused]] = 3;
+
+int classify(int x) {
+    switch (x) {
+    case 0:
+        x = 1;
+        [[fallthrough]];
+    case 1:
+        return x;
+    }
+    return 0;
+}
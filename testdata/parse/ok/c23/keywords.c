@@ -0,0 +1,21 @@
+// compile-flags: --std=c23
+static_assert(sizeof(int) >= 2);
+static_assert(1, "message");
+
+thread_local int counter;
+alignas(16) bool flags[4];
+constexpr int limit = 10;
+
+bool is_null(int *p) {
+    bool result = p == nullptr;
+    static_assert(sizeof(bool) == 1);
+    return result;
+}
+
+int main(void) {
+    bool yes = true;
+    bool no = false;
+    typeof(yes) also = no;
+    typeof_unqual(const int) mutable = limit;
+    return (int)(yes && !no);
+}
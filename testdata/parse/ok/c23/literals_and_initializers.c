@@ -0,0 +1,16 @@
+// compile-flags: --std=c23
+int mask = 0b1010;
+unsigned long long big = 0B1111'0000ULL;
+int million = 1'000'000;
+int hex = 0xFF'FF;
+int octal = 0'17;
+double ratio = 3.141'592;
+
+struct pair {
+    int first;
+    int second;
+};
+
+struct pair zero = {};
+int empty[4] = {};
+int listed[2] = {1, 2,};
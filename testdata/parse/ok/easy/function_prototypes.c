@@ -0,0 +1,6 @@
+int square(int x);
+double circle_area(double radius);
+
+int square(int x) {
+    return x * x;
+}
@@ -0,0 +1,12 @@
+// compile-flags: --std=gnu11
+extern int stat(const char *__restrict __file, void *__restrict __buf) __asm__("" "stat64");
+
+int add(int a, int b) {
+    int sum;
+    __asm__ volatile("add %[lhs], %[rhs]"
+                     : [lhs] "=r"(sum)
+                     : [rhs] "r"(a), "0"(b)
+                     : "cc", "memory");
+    asm("nop");
+    return sum;
+}
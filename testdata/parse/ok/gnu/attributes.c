@@ -0,0 +1,16 @@
+// compile-flags: --std=gnu11
+extern int printf(const char *__restrict __format, ...)
+    __attribute__((__format__(__printf__, 1, 2)));
+
+extern void exit(int __status) __attribute__((__nothrow__, __leaf__)) __attribute__((__noreturn__));
+
+struct __attribute__((packed)) header {
+    unsigned char tag;
+    unsigned int len __attribute__((aligned(4)));
+} __attribute__((__aligned__(8)));
+
+__attribute__((unused)) static int counter = 0;
+
+__attribute__((noinline)) int twice(int x __attribute__((unused)), int y) {
+    return y * 2;
+}
@@ -0,0 +1,39 @@
+// compile-flags: --std=gnu11
+// Excerpt of glibc's <stdio.h>, as preprocessed by `gcc -E -std=gnu11`.
+typedef long unsigned int size_t;
+typedef __builtin_va_list __gnuc_va_list;
+typedef long int __off_t;
+__extension__ typedef long long int __off64_t;
+struct _IO_FILE;
+typedef struct _IO_FILE FILE;
+typedef void _IO_lock_t;
+struct _IO_FILE
+{
+  int _flags;
+  char *_IO_read_ptr;
+  struct _IO_FILE *_chain;
+  __off_t _old_offset;
+  unsigned short _cur_column;
+  signed char _vtable_offset;
+  char _shortbuf[1];
+  _IO_lock_t *_lock;
+  __off64_t _offset;
+  void *_freeres_buf;
+  size_t __pad5;
+  char _unused2[15 * sizeof (int) - 4 * sizeof (void *) - sizeof (size_t)];
+};
+extern FILE *stdin;
+extern int fclose (FILE *__stream);
+extern FILE *fopen (const char *__restrict __filename,
+      const char *__restrict __modes)
+  __attribute__ ((__malloc__)) __attribute__ ((__malloc__ (fclose, 1))) ;
+extern void setbuf (FILE *__restrict __stream, char *__restrict __buf) __attribute__ ((__nothrow__ , __leaf__));
+extern int printf (const char *__restrict __format, ...);
+extern int vprintf (const char *__restrict __format, __gnuc_va_list __arg);
+extern int snprintf (char *__restrict __s, size_t __maxlen,
+       const char *__restrict __format, ...)
+     __attribute__ ((__nothrow__)) __attribute__ ((__format__ (__printf__, 3, 4)));
+extern int scanf (const char *__restrict __format, ...) __asm__ ("" "__isoc99_scanf") ;
+extern int sscanf (const char *__restrict __s, const char *__restrict __format, ...) __asm__ ("" "__isoc99_sscanf") __attribute__ ((__nothrow__ , __leaf__));
+extern int getc_unlocked (FILE *__stream);
+extern int __overflow (FILE *, int);
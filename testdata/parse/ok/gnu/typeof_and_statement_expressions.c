@@ -0,0 +1,8 @@
+// compile-flags: --std=gnu11
+__extension__ typedef long long int64;
+
+int square_plus_one(int x) {
+    typeof(x) y = ({ int z = x * x; z + 1; });
+    __typeof__(int *) p = &y;
+    return __extension__ *p;
+}
@@ -1,3 +1,4 @@
+// known-bug: function pointer declarators are reported as unexpected tokens
 int (*function_pointer)(int, int);
 
 int add(int a, int b);
@@ -1,3 +1,4 @@
+// known-bug: function pointer parameters make the parser panic
 // Defining and using function pointers.
 // https://stackoverflow.com/questions/840501/how-do-function-pointers-in-c-work
 
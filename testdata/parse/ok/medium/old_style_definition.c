@@ -0,0 +1,21 @@
+int add(a, b)
+int a, b;
+{
+    return a + b;
+}
+
+char *skip(s, n)
+    char *s;
+    register n;
+{
+    return s + n;
+}
+
+int twice(x)
+{
+    return x * 2;
+}
+
+int main(void) {
+    return add(1, 2) + twice(3);
+}
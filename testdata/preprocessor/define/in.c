@@ -11,3 +11,4 @@
 int a = FOO(1);
 int b = BAR(1, 2);
 int c = BAZ;
+// known-bug: the text preprocessor keeps `#define`s and expands no macros; only the lexer does
@@ -4,3 +4,4 @@
 ne FO\
 O 10\
 20
+// known-bug: line splices and comments are kept, and no line markers are emitted
@@ -9,3 +9,4 @@ foo
 #ifdef Foo
 Bar
 #endif
+// known-bug: directives are kept, and no line markers are emitted
@@ -47,3 +47,4 @@ int main()
 
     // __USER_LABEL_PREFIX__
 }
+// known-bug: neither the text preprocessor nor the lexer predefines `__FILE__`, `__STDC__` and the like
@@ -15,4 +15,5 @@ void test()
     int a = 1;
     int b = FOO(1) + FOO(2);
     int c = BAR(1, FOO(3));
-}
\ No newline at end of file
+}
+// known-bug: the text preprocessor expands no macros, and the lexer supports neither `#` nor `##`
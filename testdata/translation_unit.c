@@ -1,3 +1,4 @@
+// known-bug: character constants such as `'A'` are not lexed yet
 // Minimal C program to test parsing of translation units.
 
 // Function to calculate the factorial of a number using recursion.
@@ -0,0 +1,15 @@
+// compile-flags: --std=c23
+// Conditions are evaluated as integer constant expressions, after their
+// macros are expanded; an invalid one is taken to be false.
+#define WIDTH 4
+#define BITS (WIDTH * 8)
+#if BITS == 32 && !defined(NARROW)
+int wide;
+#elifdef NARROW
+int narrow;
+#endif
+#if BITS / 0 //~ ERROR E0025
+int never;
+#elif 1
+int fallback;
+#endif
@@ -0,0 +1,51 @@
+// compile-flags: -Wall -Wextra
+int main(void) {
+    return 0;
+}
+
+int sign(int x) {
+    if (x < 0)
+        return -1;
+    else
+        return 1;
+}
+
+int forever(void) {
+    while (1) {
+    }
+}
+
+int clamp(int x) {
+    if (x > 9)
+        return 9;
+} //~ WARNING W0009
+
+void stray(int x) {
+    continue; //~ ERROR E0015
+    while (x) { //~ WARNING W0008
+        break;
+    }
+    break; //~ ERROR E0015
+}
+
+int classify(int x) {
+    switch (x) {
+    case 1:
+        return 1;
+        break;
+    case 2 - 1: //~ ERROR E0017
+    default:
+        return 0;
+    }
+    case 3: //~ ERROR E0016
+    default: //~ ERROR E0016
+    return 2;
+    x = 3; //~ WARNING W0008
+}
+
+void jumps(int x) {
+retry: //~ WARNING W0007
+    if (x)
+        goto done; //~ ERROR E0018
+    goto done;
+}
@@ -0,0 +1,3 @@
+// compile-flags: -finput-charset=latin1
+// Caf prices, in .
+char *currency = "";
@@ -0,0 +1,4 @@
+// The byte after `caf` is Latin-1, not UTF-8: caf.
+//~^ WARNING W0004
+char *name = "caf";
+//~^ WARNING W0004
@@ -0,0 +1,4 @@
+#define AT @ //~ ERROR E0000
+#define WRAP(x) (x + AT)
+#define SQUARE(x) WRAP(x) * (x)
+int a = SQUARE(1);
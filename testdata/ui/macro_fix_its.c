@@ -0,0 +1,8 @@
+// Fix-its are not suggested for tokens from a macro expansion: they would
+// edit the invocation, not the definition.
+#define DECL(t) int x = t ) //~ ERROR E0001
+DECL(1);
+#define TYPE unsinged //~ ERROR E0010
+TYPE int y;
+#define NAME z //~ ERROR E0005
+NAME;
@@ -0,0 +1,3 @@
+int x = 1
+int y = 2; //~ ERROR E0001
+int z = 1 2 3; //~ ERROR E0001
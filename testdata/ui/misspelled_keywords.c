@@ -0,0 +1,2 @@
+unsinged int count; //~ ERROR E0010
+stuct point { int x; } origin; //~ ERROR E0010
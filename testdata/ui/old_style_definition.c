@@ -0,0 +1,7 @@
+// compile-flags: -Werror=implicit-int
+int twice(n, m) //~ WARNING W0001
+//~^ ERROR W0002
+    int m;
+{
+    return (n + m) * 2;
+}
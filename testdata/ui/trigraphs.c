@@ -0,0 +1,9 @@
+// compile-flags: -trigraphs
+int values??(2??) = ??< 1, 2 ??>;
+//~^ WARNING W0003
+//~^^ WARNING W0003
+//~^^^ WARNING W0003
+//~^^^^ WARNING W0003
+int spl\
+iced = 1;
+int digraphs<:2:> = <% 3, 4 %>;
@@ -0,0 +1,10 @@
+// compile-flags: --std=c23
+int café = 1;
+int caf\u00E9_total = 2;
+int price£ = 3; //~ ERROR E0011
+int \u0041pple = 4; //~ ERROR E0012
+int paypal = 5;
+int pаypal = 6; //~ WARNING W0005
+//~^ WARNING W0006
+int а = 7;
+int a = 8; //~ WARNING W0005
@@ -0,0 +1 @@
+int at = @; //~ ERROR E0000